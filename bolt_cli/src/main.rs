@@ -1,7 +1,8 @@
-static PORT: u16 = 3344;
+// the core server port, the UI is served on the next one. Both move with --port
+static DEFAULT_PORT: u16 = 3344;
 
 fn main() {
     let args: Vec<String> = std::env::args().collect();
 
-    bolt_core::start(args, PORT);
+    bolt_core::start(args, DEFAULT_PORT);
 }
@@ -0,0 +1,130 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AssertionKind {
+    #[default]
+    STATUS_EQUALS,
+    STATUS_IN_RANGE,
+    HEADER_PRESENT,
+    HEADER_MATCHES,
+    JSON_PATH_EQUALS,
+    JSON_PATH_EXISTS,
+    JSON_PATH_TYPE,
+    BODY_CONTAINS,
+    BODY_MATCHES,
+    RESPONSE_TIME_UNDER,
+}
+
+impl AssertionKind {
+    pub fn count() -> usize {
+        10
+    }
+
+    // what the target column holds, empty when the kind has no target
+    pub fn target_hint(&self) -> &'static str {
+        match self {
+            AssertionKind::HEADER_PRESENT | AssertionKind::HEADER_MATCHES => "header name",
+
+            AssertionKind::JSON_PATH_EQUALS
+            | AssertionKind::JSON_PATH_EXISTS
+            | AssertionKind::JSON_PATH_TYPE => "$.path.to[0].value",
+
+            _ => "",
+        }
+    }
+
+    pub fn value_hint(&self) -> &'static str {
+        match self {
+            AssertionKind::STATUS_EQUALS => "200",
+            AssertionKind::STATUS_IN_RANGE => "200-299",
+            AssertionKind::HEADER_MATCHES | AssertionKind::BODY_MATCHES => "regex",
+            AssertionKind::JSON_PATH_EQUALS => "JSON value, e.g. \"text\" or 42",
+            AssertionKind::JSON_PATH_TYPE => "string, number, boolean, object, array or null",
+            AssertionKind::BODY_CONTAINS => "text",
+            AssertionKind::RESPONSE_TIME_UNDER => "ms",
+            AssertionKind::HEADER_PRESENT | AssertionKind::JSON_PATH_EXISTS => "",
+        }
+    }
+}
+
+impl From<usize> for AssertionKind {
+    fn from(index: usize) -> Self {
+        match index {
+            0 => AssertionKind::STATUS_EQUALS,
+            1 => AssertionKind::STATUS_IN_RANGE,
+            2 => AssertionKind::HEADER_PRESENT,
+            3 => AssertionKind::HEADER_MATCHES,
+            4 => AssertionKind::JSON_PATH_EQUALS,
+            5 => AssertionKind::JSON_PATH_EXISTS,
+            6 => AssertionKind::JSON_PATH_TYPE,
+            7 => AssertionKind::BODY_CONTAINS,
+            8 => AssertionKind::BODY_MATCHES,
+            9 => AssertionKind::RESPONSE_TIME_UNDER,
+            _ => panic!("Invalid index for AssertionKind"),
+        }
+    }
+}
+
+impl fmt::Display for AssertionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssertionKind::STATUS_EQUALS => write!(f, "Status equals"),
+            AssertionKind::STATUS_IN_RANGE => write!(f, "Status in range"),
+            AssertionKind::HEADER_PRESENT => write!(f, "Header present"),
+            AssertionKind::HEADER_MATCHES => write!(f, "Header matches"),
+            AssertionKind::JSON_PATH_EQUALS => write!(f, "JSON path equals"),
+            AssertionKind::JSON_PATH_EXISTS => write!(f, "JSON path exists"),
+            AssertionKind::JSON_PATH_TYPE => write!(f, "JSON path type"),
+            AssertionKind::BODY_CONTAINS => write!(f, "Body contains"),
+            AssertionKind::BODY_MATCHES => write!(f, "Body matches"),
+            AssertionKind::RESPONSE_TIME_UNDER => write!(f, "Response time under"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct Assertion {
+    pub kind: AssertionKind,
+    // header name or JSON path, depending on the kind
+    pub target: String,
+    pub expected: String,
+    pub enabled: bool,
+}
+
+impl Assertion {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn describe(&self) -> String {
+        match (self.target.is_empty(), self.expected.is_empty()) {
+            (true, true) => self.kind.to_string(),
+            (true, false) => format!("{} {}", self.kind, self.expected),
+            (false, true) => format!("{} {}", self.kind, self.target),
+            (false, false) => format!("{} {} {}", self.kind, self.target, self.expected),
+        }
+    }
+}
+
+impl Default for Assertion {
+    fn default() -> Self {
+        Self {
+            kind: AssertionKind::STATUS_EQUALS,
+            target: String::new(),
+            expected: String::new(),
+            enabled: true,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct AssertionResult {
+    pub name: String,
+    pub passed: bool,
+    // why it failed, or what was found when it passed
+    pub message: String,
+}
@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HttpAuthType {
+    #[default]
+    NONE,
+    BASIC,
+    BEARER,
+    API_KEY,
+    DIGEST,
+    OAUTH2,
+}
+
+impl HttpAuthType {
+    pub fn count() -> usize {
+        6
+    }
+}
+
+impl From<usize> for HttpAuthType {
+    fn from(index: usize) -> Self {
+        match index {
+            0 => HttpAuthType::NONE,
+            1 => HttpAuthType::BASIC,
+            2 => HttpAuthType::BEARER,
+            3 => HttpAuthType::API_KEY,
+            4 => HttpAuthType::DIGEST,
+            5 => HttpAuthType::OAUTH2,
+            _ => panic!("Invalid index for HttpAuthType"),
+        }
+    }
+}
+
+impl std::fmt::Display for HttpAuthType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HttpAuthType::NONE => write!(f, "No Auth"),
+            HttpAuthType::BASIC => write!(f, "Basic"),
+            HttpAuthType::BEARER => write!(f, "Bearer Token"),
+            HttpAuthType::API_KEY => write!(f, "API Key"),
+            HttpAuthType::DIGEST => write!(f, "Digest"),
+            HttpAuthType::OAUTH2 => write!(f, "OAuth 2.0"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ApiKeyLocation {
+    #[default]
+    HEADER,
+    QUERY,
+}
+
+// credentials live here instead of in the header list so they are only
+// turned into an Authorization header (or query param) when the request is sent
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct HttpAuth {
+    pub auth_type: HttpAuthType,
+
+    // basic and digest
+    pub username: String,
+    pub password: String,
+
+    // bearer
+    pub token: String,
+
+    // api key
+    pub key: String,
+    pub value: String,
+    pub location: ApiKeyLocation,
+
+    // oauth2, the password grant reuses username and password
+    #[serde(default)]
+    pub oauth2: OAuth2Config,
+}
+
+impl HttpAuth {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OAuth2GrantType {
+    #[default]
+    CLIENT_CREDENTIALS,
+    PASSWORD,
+    AUTHORIZATION_CODE,
+}
+
+impl OAuth2GrantType {
+    pub fn count() -> usize {
+        3
+    }
+}
+
+impl From<usize> for OAuth2GrantType {
+    fn from(index: usize) -> Self {
+        match index {
+            0 => OAuth2GrantType::CLIENT_CREDENTIALS,
+            1 => OAuth2GrantType::PASSWORD,
+            2 => OAuth2GrantType::AUTHORIZATION_CODE,
+            _ => panic!("Invalid index for OAuth2GrantType"),
+        }
+    }
+}
+
+impl std::fmt::Display for OAuth2GrantType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OAuth2GrantType::CLIENT_CREDENTIALS => write!(f, "Client Credentials"),
+            OAuth2GrantType::PASSWORD => write!(f, "Password"),
+            OAuth2GrantType::AUTHORIZATION_CODE => write!(f, "Authorization Code (PKCE)"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct OAuth2Config {
+    pub grant_type: OAuth2GrantType,
+
+    pub token_url: String,
+    pub auth_url: String,
+    pub redirect_url: String,
+
+    pub client_id: String,
+    pub client_secret: String,
+    pub scope: String,
+
+    // send the client credentials as a basic auth header instead of in the form body
+    pub client_auth_header: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct OAuth2Token {
+    pub access_token: String,
+    pub token_type: String,
+    pub refresh_token: String,
+    // unix timestamp in ms, 0 when the server did not send expires_in
+    pub expires_at: u64,
+}
+
+impl OAuth2Token {
+    pub fn is_expired(&self, now: u64) -> bool {
+        // refresh a little early so the token does not expire in flight
+        self.expires_at != 0 && now + 10_000 >= self.expires_at
+    }
+}
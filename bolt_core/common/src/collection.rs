@@ -16,4 +16,115 @@ impl Collection {
             collapsed: false,
         }
     }
+
+    // brings a re-imported collection in without losing edits. requests are matched by
+    // source, matched ones only get the params and headers they are missing and a body
+    // when they have none, the rest is added. returns (updated, added)
+    pub fn update_from(&mut self, imported: Collection) -> (usize, usize) {
+        let mut updated = 0;
+        let mut added = 0;
+
+        for request in imported.requests {
+            let existing = self
+                .requests
+                .iter_mut()
+                .find(|existing| !request.source.is_empty() && existing.source == request.source);
+
+            match existing {
+                Some(existing) => {
+                    add_missing_rows(&mut existing.params, &request.params);
+                    add_missing_rows(&mut existing.headers, &request.headers);
+
+                    if !has_body(existing) {
+                        existing.body_type = request.body_type;
+                        existing.body = request.body;
+                        existing.form = request.form;
+                        existing.multipart = request.multipart;
+                        existing.body_file = request.body_file;
+                        existing.graphql = request.graphql;
+                    }
+
+                    updated += 1;
+                }
+
+                None => {
+                    self.requests.push(request);
+
+                    added += 1;
+                }
+            }
+        }
+
+        (updated, added)
+    }
+}
+
+fn add_missing_rows(rows: &mut Vec<Vec<String>>, imported: &[Vec<String>]) {
+    for row in imported {
+        if row[0].is_empty() || rows.iter().any(|existing| existing[0] == row[0]) {
+            continue;
+        }
+
+        // the empty row the editor keeps at the end is reused
+        match rows.iter_mut().find(|existing| existing.iter().all(|field| field.is_empty())) {
+            Some(empty) => *empty = row.clone(),
+            None => rows.push(row.clone()),
+        }
+    }
+}
+
+fn has_body(request: &HttpRequest) -> bool {
+    !request.body.trim().is_empty()
+        || request.form.iter().any(|row| !row[0].is_empty())
+        || request.multipart.iter().any(|field| !field.name.is_empty())
+        || !request.body_file.is_empty()
+        || !request.graphql.query.trim().is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::HttpBodyType;
+
+    fn graphql_request(query: &str) -> HttpRequest {
+        let mut request = HttpRequest::new();
+
+        request.source = "POST /graphql".to_string();
+        request.body_type = HttpBodyType::GRAPHQL;
+        request.graphql.query = query.to_string();
+
+        request
+    }
+
+    #[test]
+    fn graphql_queries_are_kept() {
+        let mut collection = Collection::new();
+        collection.requests.push(graphql_request("{ me { id } }"));
+
+        let mut imported = Collection::new();
+        let mut request = graphql_request("{ users { id } }");
+        request.body_type = HttpBodyType::JSON;
+        request.body = "{}".to_string();
+        imported.requests.push(request);
+
+        assert_eq!(collection.update_from(imported), (1, 0));
+
+        let request = &collection.requests[0];
+        assert_eq!(request.body_type, HttpBodyType::GRAPHQL);
+        assert_eq!(request.graphql.query, "{ me { id } }");
+        assert!(request.body.is_empty());
+    }
+
+    #[test]
+    fn requests_without_a_body_take_the_imported_query() {
+        let mut collection = Collection::new();
+        collection.requests.push(graphql_request(" "));
+
+        let mut imported = Collection::new();
+        imported.requests.push(graphql_request("{ users { id } }"));
+
+        collection.update_from(imported);
+
+        assert_eq!(collection.requests[0].graphql.query, "{ users { id } }");
+    }
 }
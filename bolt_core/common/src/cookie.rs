@@ -0,0 +1,293 @@
+use crate::prelude::MsgType;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct Cookie {
+    pub name: String,
+    pub value: String,
+
+    // stored without the leading dot
+    pub domain: String,
+    pub path: String,
+
+    // unix timestamp in ms, 0 for session cookies
+    pub expires: u64,
+
+    pub secure: bool,
+    pub http_only: bool,
+
+    // cookies set without a Domain attribute are only sent back to the exact host
+    pub host_only: bool,
+}
+
+impl Cookie {
+    pub fn new() -> Self {
+        Self {
+            path: "/".to_string(),
+            ..Default::default()
+        }
+    }
+
+    // a jar holds one cookie for each name, domain and path
+    pub fn same_as(&self, other: &Cookie) -> bool {
+        self.name == other.name && self.domain == other.domain && self.path == other.path
+    }
+
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires != 0 && self.expires <= now
+    }
+
+    pub fn matches(&self, host: &str, path: &str, secure: bool, now: u64) -> bool {
+        if self.is_expired(now) || (self.secure && !secure) {
+            return false;
+        }
+
+        let host = host.to_lowercase();
+        let domain = self.domain.to_lowercase();
+
+        let domain_match = if self.host_only || domain.is_empty() {
+            host == domain
+        } else {
+            host == domain || host.ends_with(&format!(".{domain}"))
+        };
+
+        if !domain_match {
+            return false;
+        }
+
+        let cookie_path = if self.path.is_empty() { "/" } else { &self.path };
+
+        path == cookie_path
+            || (path.starts_with(cookie_path)
+                && (cookie_path.ends_with('/') || path[cookie_path.len()..].starts_with('/')))
+    }
+}
+
+// adds or replaces a cookie, a cookie that is already expired deletes the stored one
+pub fn store_cookie(cookies: &mut Vec<Cookie>, cookie: Cookie, now: u64) {
+    cookies.retain(|c| !c.same_as(&cookie) && !c.is_expired(now));
+
+    if !cookie.is_expired(now) {
+        cookies.push(cookie);
+    }
+}
+
+// the value of the Cookie header for a request, None when no cookie applies
+pub fn cookie_header(
+    cookies: &[Cookie],
+    host: &str,
+    path: &str,
+    secure: bool,
+    now: u64,
+) -> Option<String> {
+    let mut matching: Vec<&Cookie> = cookies
+        .iter()
+        .filter(|c| !c.name.is_empty() && c.matches(host, path, secure, now))
+        .collect();
+
+    if matching.is_empty() {
+        return None;
+    }
+
+    // more specific paths first (RFC 6265 5.4)
+    matching.sort_by_key(|c| std::cmp::Reverse(c.path.len()));
+
+    let header = matching
+        .iter()
+        .map(|c| format!("{}={}", c.name, c.value))
+        .collect::<Vec<String>>()
+        .join("; ");
+
+    Some(header)
+}
+
+// every domain that has at least one cookie, sorted
+pub fn cookie_domains(cookies: &[Cookie]) -> Vec<String> {
+    let mut domains: Vec<String> = cookies.iter().map(|c| c.domain.clone()).collect();
+
+    domains.sort();
+    domains.dedup();
+
+    domains
+}
+
+// an edit made in the cookie view, core applies it to the jar it owns
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum CookieEdit {
+    // replaces `old` in place, or adds the cookie when there is no old one
+    Set { old: Option<Cookie>, cookie: Cookie },
+    Remove(Cookie),
+    ClearDomain(String),
+    Clear,
+}
+
+impl CookieEdit {
+    pub fn apply(&self, cookies: &mut Vec<Cookie>) {
+        match self {
+            CookieEdit::Set { old, cookie } => {
+                let position = old
+                    .as_ref()
+                    .and_then(|old| cookies.iter().position(|c| c.same_as(old)));
+
+                match position {
+                    Some(index) => {
+                        cookies[index] = cookie.clone();
+
+                        // the edit can turn it into a copy of another cookie
+                        let mut current = 0;
+
+                        cookies.retain(|c| {
+                            let keep = current == index || !c.same_as(cookie);
+                            current += 1;
+
+                            keep
+                        });
+                    }
+
+                    None => {
+                        cookies.retain(|c| !c.same_as(cookie));
+                        cookies.push(cookie.clone());
+                    }
+                }
+            }
+
+            CookieEdit::Remove(cookie) => cookies.retain(|c| !c.same_as(cookie)),
+
+            CookieEdit::ClearDomain(domain) => cookies.retain(|c| &c.domain != domain),
+
+            CookieEdit::Clear => cookies.clear(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct EditCookiesMsg {
+    pub msg_type: MsgType,
+    pub edit: CookieEdit,
+}
+
+// sent to the client whenever a response changed the cookie jar
+#[derive(Serialize, Deserialize)]
+pub struct CookiesChangedMsg {
+    pub msg_type: MsgType,
+    pub cookies: Vec<Cookie>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cookie(name: &str, domain: &str, path: &str) -> Cookie {
+        Cookie {
+            name: name.to_string(),
+            value: format!("{name}-value"),
+            domain: domain.to_string(),
+            path: path.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn domain_cookies_match_subdomains_host_only_ones_do_not() {
+        let domain = cookie("a", "example.com", "/");
+        let mut host_only = cookie("b", "example.com", "/");
+        host_only.host_only = true;
+
+        assert!(domain.matches("example.com", "/", false, 0));
+        assert!(domain.matches("api.Example.com", "/", false, 0));
+        assert!(!domain.matches("badexample.com", "/", false, 0));
+
+        assert!(host_only.matches("example.com", "/", false, 0));
+        assert!(!host_only.matches("api.example.com", "/", false, 0));
+    }
+
+    #[test]
+    fn paths_match_on_segment_boundaries() {
+        let cookie = cookie("a", "example.com", "/api");
+
+        assert!(cookie.matches("example.com", "/api", false, 0));
+        assert!(cookie.matches("example.com", "/api/users", false, 0));
+        assert!(!cookie.matches("example.com", "/apiv2", false, 0));
+        assert!(!cookie.matches("example.com", "/", false, 0));
+    }
+
+    #[test]
+    fn secure_and_expired_cookies_are_not_sent() {
+        let mut secure = cookie("a", "example.com", "/");
+        secure.secure = true;
+
+        assert!(!secure.matches("example.com", "/", false, 0));
+        assert!(secure.matches("example.com", "/", true, 0));
+
+        let mut expired = cookie("b", "example.com", "/");
+        expired.expires = 1_000;
+
+        assert!(expired.matches("example.com", "/", false, 999));
+        assert!(!expired.matches("example.com", "/", false, 1_000));
+    }
+
+    #[test]
+    fn header_lists_longer_paths_first() {
+        let cookies = vec![
+            cookie("root", "example.com", "/"),
+            cookie("api", "example.com", "/api"),
+            cookie("other", "other.com", "/"),
+        ];
+
+        assert_eq!(
+            cookie_header(&cookies, "example.com", "/api/x", false, 0).unwrap(),
+            "api=api-value; root=root-value"
+        );
+        assert_eq!(cookie_header(&cookies, "none.com", "/", false, 0), None);
+    }
+
+    #[test]
+    fn storing_replaces_and_expired_cookies_delete() {
+        let mut cookies = vec![cookie("a", "example.com", "/")];
+
+        let mut updated = cookie("a", "example.com", "/");
+        updated.value = "new".to_string();
+        store_cookie(&mut cookies, updated, 10);
+
+        assert_eq!(cookies.len(), 1);
+        assert_eq!(cookies[0].value, "new");
+
+        let mut deleted = cookie("a", "example.com", "/");
+        deleted.expires = 1;
+        store_cookie(&mut cookies, deleted, 10);
+
+        assert!(cookies.is_empty());
+    }
+
+    #[test]
+    fn edits_keep_the_position_of_changed_cookies() {
+        let mut cookies = vec![
+            cookie("a", "example.com", "/"),
+            cookie("b", "example.com", "/"),
+            cookie("c", "other.com", "/"),
+        ];
+
+        // renaming a to b leaves one b, where a was
+        CookieEdit::Set {
+            old: Some(cookie("a", "example.com", "/")),
+            cookie: cookie("b", "example.com", "/"),
+        }
+        .apply(&mut cookies);
+
+        let names: Vec<&str> = cookies.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["b", "c"]);
+
+        CookieEdit::Set {
+            old: None,
+            cookie: cookie("d", "example.com", "/"),
+        }
+        .apply(&mut cookies);
+        assert_eq!(cookies.len(), 3);
+
+        CookieEdit::ClearDomain("example.com".to_string()).apply(&mut cookies);
+        assert_eq!(cookies, vec![cookie("c", "other.com", "/")]);
+
+        CookieEdit::Remove(cookie("c", "other.com", "/")).apply(&mut cookies);
+        assert!(cookies.is_empty());
+    }
+}
@@ -0,0 +1,1015 @@
+use crate::auth::{ApiKeyLocation, HttpAuthType};
+use crate::http::{HttpBodyType, HttpMethod, HttpRequest, MultipartField, SendHttpRequest};
+use crate::settings::HttpSettings;
+
+// curl options that take a value bolt has no use for, skipped with their value
+const IGNORED_WITH_VALUE: &[&str] = &[
+    "--output",
+    "--write-out",
+    "--cookie-jar",
+    "--dump-header",
+    "--range",
+    "--upload-file",
+    "--proxy-user",
+    "--config",
+    "--speed-limit",
+    "--speed-time",
+    "--retry",
+    "--retry-delay",
+    "--retry-max-time",
+    "--resolve",
+    "--connect-to",
+    "--interface",
+    "--limit-rate",
+    "--trace",
+    "--trace-ascii",
+    "--stderr",
+    "--ciphers",
+    "--cert-type",
+    "--key-type",
+    "--pass",
+    "--capath",
+    "--dns-servers",
+];
+
+const SHORT_WITH_VALUE: &str = "XHdFuAebxmEowcDrTUKYy";
+
+// parses a curl command line, as copied from a browser's dev tools or a docs page,
+// into a request. --compressed is accepted but bolt asks for an uncompressed body.
+pub fn parse_curl(command: &str) -> Result<HttpRequest, String> {
+    let words = split_words(command)?;
+
+    let mut words = words.into_iter();
+
+    match words.next() {
+        Some(word) if word == "curl" || word.ends_with("/curl") || word == "curl.exe" => {}
+        _ => return Err("not a curl command".to_string()),
+    }
+
+    let mut options: Vec<(String, Option<String>)> = vec![];
+    let mut urls: Vec<String> = vec![];
+    let mut only_urls = false;
+
+    while let Some(word) = words.next() {
+        if only_urls || !word.starts_with('-') || word == "-" {
+            urls.push(word);
+        } else if word == "--" {
+            only_urls = true;
+        } else if let Some(long) = word.strip_prefix("--") {
+            let (name, value) = match long.split_once('=') {
+                Some((name, value)) => (format!("--{name}"), Some(value.to_string())),
+                None => (word.clone(), None),
+            };
+
+            if value.is_none() && takes_value(&name) {
+                options.push((name, words.next()));
+            } else {
+                options.push((name, value));
+            }
+        } else {
+            // short options can be grouped (-sSL) and take their value attached (-XPOST)
+            let flags: Vec<char> = word[1..].chars().collect();
+
+            for (i, flag) in flags.iter().enumerate() {
+                let name = format!("-{flag}");
+
+                if SHORT_WITH_VALUE.contains(*flag) {
+                    let rest: String = flags[i + 1..].iter().collect();
+
+                    if rest.is_empty() {
+                        options.push((name, words.next()));
+                    } else {
+                        options.push((name, Some(rest)));
+                    }
+
+                    break;
+                }
+
+                options.push((name, None));
+            }
+        }
+    }
+
+    let mut method: Option<HttpMethod> = None;
+    let mut head = false;
+    let mut get = false;
+    let mut digest = false;
+    let mut headers: Vec<Vec<String>> = vec![];
+    let mut data: Vec<String> = vec![];
+    let mut data_file: Option<String> = None;
+    let mut json = false;
+    let mut multipart: Vec<MultipartField> = vec![];
+    let mut user: Option<String> = None;
+    let mut bearer: Option<String> = None;
+    let mut settings = HttpSettings::new();
+
+    for (name, value) in options {
+        let missing = || format!("{name} needs a value");
+
+        match name.as_str() {
+            "-X" | "--request" => {
+                let value = value.ok_or_else(missing)?;
+
+                method = Some(parse_method(&value)?);
+            }
+
+            "-H" | "--header" => {
+                let value = value.ok_or_else(missing)?;
+
+                // "Name;" sends an empty header in curl
+                if let Some((name, value)) = value.split_once(':') {
+                    headers.push(vec![name.trim().to_string(), value.trim().to_string()]);
+                } else if let Some(name) = value.strip_suffix(';') {
+                    headers.push(vec![name.trim().to_string(), String::new()]);
+                }
+            }
+
+            "-d" | "--data" | "--data-ascii" | "--data-binary" => {
+                let value = value.ok_or_else(missing)?;
+
+                match value.strip_prefix('@') {
+                    Some(path) => data_file = Some(path.to_string()),
+                    None => data.push(value),
+                }
+            }
+
+            "--data-raw" => data.push(value.ok_or_else(missing)?),
+
+            // --data-binary that also sets the JSON content type and accept headers
+            "--json" => {
+                let value = value.ok_or_else(missing)?;
+
+                json = true;
+
+                match value.strip_prefix('@') {
+                    Some(path) => data_file = Some(path.to_string()),
+                    None => data.push(value),
+                }
+            }
+
+            "--data-urlencode" => {
+                let value = value.ok_or_else(missing)?;
+
+                // name=content is sent as name=<encoded content>, =content as the content alone
+                let encoded = match value.split_once('=') {
+                    Some(("", content)) => url_encode(content),
+                    Some((name, content)) => format!("{name}={}", url_encode(content)),
+                    None => url_encode(&value),
+                };
+
+                data.push(encoded);
+            }
+
+            "-F" | "--form" | "--form-string" => {
+                let value = value.ok_or_else(missing)?;
+
+                let (field, content) = value
+                    .split_once('=')
+                    .ok_or_else(|| format!("invalid form field {value}, use name=value"))?;
+
+                let file = if name == "--form-string" {
+                    None
+                } else {
+                    content
+                        .strip_prefix('@')
+                        .or_else(|| content.strip_prefix('<'))
+                };
+
+                multipart.push(match file {
+                    // name=@path;type=text/plain
+                    Some(path) => MultipartField {
+                        name: field.to_string(),
+                        value: path.split(';').next().unwrap_or_default().to_string(),
+                        is_file: true,
+                    },
+                    None => MultipartField {
+                        name: field.to_string(),
+                        value: content.to_string(),
+                        is_file: false,
+                    },
+                });
+            }
+
+            "-u" | "--user" => user = Some(value.ok_or_else(missing)?),
+            "--digest" => digest = true,
+            "--basic" => digest = false,
+            "--oauth2-bearer" => bearer = Some(value.ok_or_else(missing)?),
+
+            "-A" | "--user-agent" => {
+                headers.push(vec!["User-Agent".to_string(), value.ok_or_else(missing)?]);
+            }
+
+            "-e" | "--referer" => {
+                headers.push(vec!["Referer".to_string(), value.ok_or_else(missing)?]);
+            }
+
+            "-b" | "--cookie" => {
+                let value = value.ok_or_else(missing)?;
+
+                // without a = it names a cookie file
+                if value.contains('=') {
+                    headers.push(vec!["Cookie".to_string(), value]);
+                }
+            }
+
+            "-I" | "--head" => head = true,
+            "-G" | "--get" => get = true,
+
+            "-k" | "--insecure" => settings.verify_tls = false,
+            "-x" | "--proxy" => settings.proxy = with_scheme(&value.ok_or_else(missing)?),
+            "-m" | "--max-time" => settings.timeout = millis(&value.ok_or_else(missing)?)?,
+            "--connect-timeout" => {
+                settings.connect_timeout = millis(&value.ok_or_else(missing)?)?;
+            }
+            "--max-redirs" => {
+                let value = value.ok_or_else(missing)?;
+
+                settings.max_redirects = value
+                    .parse()
+                    .map_err(|_| format!("invalid --max-redirs {value}"))?;
+            }
+            "--cacert" => settings.ca_cert = value.ok_or_else(missing)?,
+            "-E" | "--cert" => {
+                let value = value.ok_or_else(missing)?;
+
+                // path:password, the password is not supported
+                settings.client_cert = value.split(':').next().unwrap_or_default().to_string();
+            }
+            "--key" => settings.client_key = value.ok_or_else(missing)?,
+
+            "--url" => urls.push(value.ok_or_else(missing)?),
+
+            _ => {}
+        }
+    }
+
+    let url = urls
+        .into_iter()
+        .next()
+        .ok_or("no URL in the curl command")?;
+
+    let mut request = HttpRequest::new();
+
+    let has_body = !data.is_empty() || data_file.is_some() || !multipart.is_empty();
+
+    request.method = match method {
+        Some(method) => method,
+        None if head => HttpMethod::HEAD,
+        None if has_body && !get => HttpMethod::POST,
+        None => HttpMethod::GET,
+    };
+
+    let mut url = with_scheme(&url);
+
+    if get && !data.is_empty() {
+        url.push(if url.contains('?') { '&' } else { '?' });
+        url.push_str(&data.join("&"));
+
+        data.clear();
+    }
+
+    let (url, params) = split_query(&url);
+
+    request.url = url;
+
+    if !params.is_empty() {
+        request.params = params;
+    }
+
+    if json {
+        for name in ["Content-Type", "Accept"] {
+            if !headers
+                .iter()
+                .any(|header| header[0].eq_ignore_ascii_case(name))
+            {
+                headers.push(vec![name.to_string(), "application/json".to_string()]);
+            }
+        }
+    }
+
+    let content_type = headers
+        .iter()
+        .find(|header| header[0].eq_ignore_ascii_case("content-type"))
+        .map(|header| header[1].to_lowercase());
+
+    if !multipart.is_empty() {
+        request.body_type = HttpBodyType::MULTIPART;
+        request.multipart = multipart;
+    } else if let Some(path) = data_file {
+        request.body_type = HttpBodyType::BINARY;
+        request.body_file = path;
+    } else if json && !data.is_empty() {
+        // curl sends several --json values as one body, without a separator
+        request.body_type = HttpBodyType::JSON;
+        request.body = data.concat();
+    } else if !data.is_empty() {
+        let body = data.join("&");
+        let trimmed = body.trim_start();
+
+        let looks_like_json = trimmed.starts_with('{') || trimmed.starts_with('[');
+
+        let is_json = match &content_type {
+            Some(content_type) => content_type.contains("json"),
+            None => looks_like_json,
+        };
+
+        // is_none_or needs Rust 1.82
+        #[allow(clippy::unnecessary_map_or)]
+        let is_form = content_type.as_ref().map_or(true, |content_type| {
+            content_type.contains("x-www-form-urlencoded")
+        });
+
+        if is_json {
+            request.body_type = HttpBodyType::JSON;
+        } else if is_form {
+            match parse_form(&body) {
+                Some(form) => {
+                    request.body_type = HttpBodyType::FORM_URLENCODED;
+                    request.form = form;
+                }
+
+                // curl sends -d bodies as a form unless told otherwise
+                None if content_type.is_none() => headers.push(vec![
+                    "Content-Type".to_string(),
+                    "application/x-www-form-urlencoded".to_string(),
+                ]),
+                None => {}
+            }
+        }
+
+        request.body = body;
+    }
+
+    if let Some(token) = bearer {
+        request.auth.auth_type = HttpAuthType::BEARER;
+        request.auth.token = token;
+    } else if let Some(user) = user {
+        let (username, password) = user.split_once(':').unwrap_or((&user, ""));
+
+        request.auth.auth_type = if digest {
+            HttpAuthType::DIGEST
+        } else {
+            HttpAuthType::BASIC
+        };
+
+        request.auth.username = username.to_string();
+        request.auth.password = password.to_string();
+    }
+
+    if !headers.is_empty() {
+        request.headers = headers;
+    }
+
+    if settings != HttpSettings::new() {
+        request.settings = Some(settings);
+    }
+
+    request.name = request_name(&request.url);
+
+    Ok(request)
+}
+
+// a curl command that sends the same request, one option per line
+pub fn to_curl(request: &SendHttpRequest) -> String {
+    let mut args: Vec<String> = vec![];
+
+    let mut url = request.url.clone();
+
+    match request.method {
+        HttpMethod::GET => {}
+        HttpMethod::HEAD => args.push("--head".to_string()),
+        method => args.push(format!("-X {method}")),
+    }
+
+    let headers: Vec<&Vec<String>> = request
+        .headers
+        .iter()
+        .filter(|header| !header[0].trim().is_empty())
+        .collect();
+
+    for header in &headers {
+        args.push(format!(
+            "-H {}",
+            shell_quote(&format!("{}: {}", header[0], header[1]))
+        ));
+    }
+
+    let has_header = |name: &str| {
+        headers
+            .iter()
+            .any(|h| h[0].trim().eq_ignore_ascii_case(name))
+    };
+
+    // bolt_http adds the body's content type when there is none, curl needs it spelled out
+    let sends_body = match request.body_type {
+        HttpBodyType::RAW | HttpBodyType::JSON => !request.body.is_empty(),
+        HttpBodyType::BINARY => !request.body_file.is_empty(),
+        HttpBodyType::GRAPHQL => request.graphql.payload().is_ok(),
+        _ => false,
+    };
+
+    if sends_body && !has_header("content-type") {
+        args.push(format!(
+            "-H {}",
+            shell_quote(&format!(
+                "Content-Type: {}",
+                request.body_type.content_type()
+            ))
+        ));
+    }
+
+    let auth = &request.auth;
+
+    match auth.auth_type {
+        HttpAuthType::BASIC => {
+            args.push(format!(
+                "-u {}",
+                shell_quote(&format!("{}:{}", auth.username, auth.password))
+            ));
+        }
+        HttpAuthType::DIGEST => {
+            args.push("--digest".to_string());
+            args.push(format!(
+                "-u {}",
+                shell_quote(&format!("{}:{}", auth.username, auth.password))
+            ));
+        }
+        HttpAuthType::BEARER => {
+            args.push(format!(
+                "-H {}",
+                shell_quote(&format!("Authorization: Bearer {}", auth.token))
+            ));
+        }
+        HttpAuthType::API_KEY if !auth.key.is_empty() => match auth.location {
+            ApiKeyLocation::HEADER => {
+                args.push(format!(
+                    "-H {}",
+                    shell_quote(&format!("{}: {}", auth.key, auth.value))
+                ));
+            }
+            ApiKeyLocation::QUERY => {
+                url.push(if url.contains('?') { '&' } else { '?' });
+                url.push_str(&format!(
+                    "{}={}",
+                    url_encode(&auth.key),
+                    url_encode(&auth.value)
+                ));
+            }
+        },
+        // the token is fetched when the request is sent
+        _ => {}
+    }
+
+    match request.body_type {
+        HttpBodyType::RAW | HttpBodyType::JSON if !request.body.is_empty() => {
+            args.push(format!("--data-raw {}", shell_quote(&request.body)));
+        }
+        HttpBodyType::FORM_URLENCODED => {
+            for row in request.form.iter().filter(|row| !row[0].is_empty()) {
+                args.push(format!(
+                    "--data-urlencode {}",
+                    shell_quote(&format!("{}={}", row[0], row[1]))
+                ));
+            }
+        }
+        HttpBodyType::MULTIPART => {
+            for field in request
+                .multipart
+                .iter()
+                .filter(|field| !field.name.is_empty())
+            {
+                if field.is_file {
+                    args.push(format!(
+                        "-F {}",
+                        shell_quote(&format!("{}=@{}", field.name, field.value))
+                    ));
+                } else {
+                    args.push(format!(
+                        "--form-string {}",
+                        shell_quote(&format!("{}={}", field.name, field.value))
+                    ));
+                }
+            }
+        }
+        HttpBodyType::BINARY if !request.body_file.is_empty() => {
+            args.push(format!(
+                "--data-binary {}",
+                shell_quote(&format!("@{}", request.body_file))
+            ));
+        }
+        HttpBodyType::GRAPHQL => {
+            if let Ok(payload) = request.graphql.payload() {
+                args.push(format!("--data-raw {}", shell_quote(&payload)));
+            }
+        }
+        _ => {}
+    }
+
+    // without its own settings the request uses the workspace ones, which are not known here
+    if let Some(settings) = &request.settings {
+        if settings.follow_redirects {
+            args.push("-L".to_string());
+
+            if settings.max_redirects != HttpSettings::new().max_redirects {
+                args.push(format!("--max-redirs {}", settings.max_redirects));
+            }
+        }
+
+        if !settings.verify_tls {
+            args.push("-k".to_string());
+        }
+
+        if !settings.proxy.is_empty() {
+            args.push(format!("-x {}", shell_quote(&settings.proxy)));
+        }
+
+        if settings.connect_timeout > 0 {
+            args.push(format!(
+                "--connect-timeout {}",
+                seconds(settings.connect_timeout)
+            ));
+        }
+
+        if settings.timeout > 0 {
+            args.push(format!("-m {}", seconds(settings.timeout)));
+        }
+
+        if !settings.ca_cert.is_empty() {
+            args.push(format!("--cacert {}", shell_quote(&settings.ca_cert)));
+        }
+
+        if !settings.client_cert.is_empty() {
+            args.push(format!("--cert {}", shell_quote(&settings.client_cert)));
+        }
+
+        if !settings.client_key.is_empty() {
+            args.push(format!("--key {}", shell_quote(&settings.client_key)));
+        }
+    }
+
+    let mut command = format!("curl {}", shell_quote(&url));
+
+    for arg in args {
+        command.push_str(" \\\n  ");
+        command.push_str(&arg);
+    }
+
+    command
+}
+
+// splits a POSIX shell command line into words, handling '...', "...", $'...'
+// and backslash escapes, including the backslash-newline of multi-line commands
+fn split_words(command: &str) -> Result<Vec<String>, String> {
+    let mut words = vec![];
+    let mut word = String::new();
+    let mut in_word = false;
+
+    let mut chars = command.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+
+            '\\' => match chars.next() {
+                Some('\n') => {}
+                Some('\r') if chars.peek() == Some(&'\n') => {
+                    chars.next();
+                }
+                Some(escaped) => {
+                    word.push(escaped);
+                    in_word = true;
+                }
+                None => {}
+            },
+
+            '\'' => {
+                in_word = true;
+
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => return Err("unterminated ' in the curl command".to_string()),
+                    }
+                }
+            }
+
+            '"' => {
+                in_word = true;
+
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => word.push(c),
+                            Some('\n') => {}
+                            Some(c) => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                            None => return Err("unterminated \" in the curl command".to_string()),
+                        },
+                        Some(c) => word.push(c),
+                        None => return Err("unterminated \" in the curl command".to_string()),
+                    }
+                }
+            }
+
+            '$' if chars.peek() == Some(&'\'') => {
+                chars.next();
+                in_word = true;
+
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some('\\') => match chars.next() {
+                            Some('n') => word.push('\n'),
+                            Some('t') => word.push('\t'),
+                            Some('r') => word.push('\r'),
+                            Some('x') => {
+                                let hex: String = (0..2).filter_map(|_| chars.next()).collect();
+
+                                let code = u8::from_str_radix(&hex, 16)
+                                    .map_err(|_| format!("invalid escape \\x{hex}"))?;
+
+                                word.push(code as char);
+                            }
+                            Some('u') => {
+                                let hex: String = (0..4).filter_map(|_| chars.next()).collect();
+
+                                let c = u32::from_str_radix(&hex, 16)
+                                    .ok()
+                                    .and_then(char::from_u32)
+                                    .ok_or_else(|| format!("invalid escape \\u{hex}"))?;
+
+                                word.push(c);
+                            }
+                            Some(c) => word.push(c),
+                            None => return Err("unterminated $' in the curl command".to_string()),
+                        },
+                        Some(c) => word.push(c),
+                        None => return Err("unterminated $' in the curl command".to_string()),
+                    }
+                }
+            }
+
+            c => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+
+    if in_word {
+        words.push(word);
+    }
+
+    Ok(words)
+}
+
+fn takes_value(name: &str) -> bool {
+    matches!(
+        name,
+        "--request"
+            | "--header"
+            | "--data"
+            | "--data-ascii"
+            | "--data-binary"
+            | "--data-raw"
+            | "--data-urlencode"
+            | "--json"
+            | "--form"
+            | "--form-string"
+            | "--user"
+            | "--oauth2-bearer"
+            | "--user-agent"
+            | "--referer"
+            | "--cookie"
+            | "--proxy"
+            | "--max-time"
+            | "--connect-timeout"
+            | "--max-redirs"
+            | "--cacert"
+            | "--cert"
+            | "--key"
+            | "--url"
+    ) || IGNORED_WITH_VALUE.contains(&name)
+}
+
+fn parse_method(name: &str) -> Result<HttpMethod, String> {
+    (0..HttpMethod::count())
+        .map(HttpMethod::from)
+        .find(|method| method.to_string().eq_ignore_ascii_case(name))
+        .ok_or_else(|| format!("unsupported method {name}"))
+}
+
+// curl assumes http:// when the url has no scheme
+fn with_scheme(url: &str) -> String {
+    if url.contains("://") || url.starts_with("{{") {
+        url.to_string()
+    } else {
+        format!("http://{url}")
+    }
+}
+
+// moves the query into the params table when every pair has a name and a value,
+// parse_url would drop the others
+fn split_query(url: &str) -> (String, Vec<Vec<String>>) {
+    let Some((base, query)) = url.split_once('?') else {
+        return (url.to_string(), vec![]);
+    };
+
+    let params: Option<Vec<Vec<String>>> = query
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((name, value)) if !name.is_empty() && !value.is_empty() => {
+                Some(vec![name.to_string(), value.to_string()])
+            }
+            _ => None,
+        })
+        .collect();
+
+    match params {
+        Some(params) => (base.to_string(), params),
+        None => (url.to_string(), vec![]),
+    }
+}
+
+// name=value&... rows, None when the body is not a form
+fn parse_form(body: &str) -> Option<Vec<Vec<String>>> {
+    body.split('&')
+        .map(|pair| {
+            let (name, value) = pair.split_once('=')?;
+
+            Some(vec![url_decode(name)?, url_decode(value)?])
+        })
+        .collect()
+}
+
+fn request_name(url: &str) -> String {
+    let path = url.split_once("://").map(|(_, rest)| rest).unwrap_or(url);
+
+    path.split(['?', '#'])
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn millis(seconds: &str) -> Result<u64, String> {
+    seconds
+        .parse::<f64>()
+        .map(|seconds| (seconds * 1000.0) as u64)
+        .map_err(|_| format!("invalid number of seconds {seconds}"))
+}
+
+// is_multiple_of needs Rust 1.87
+#[allow(clippy::manual_is_multiple_of)]
+fn seconds(ms: u64) -> String {
+    if ms % 1000 == 0 {
+        (ms / 1000).to_string()
+    } else {
+        format!("{}", ms as f64 / 1000.0)
+    }
+}
+
+// wraps in single quotes unless the word is plain, ' becomes '\''
+pub(crate) fn shell_quote(word: &str) -> String {
+    let plain = !word.is_empty()
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@%+,".contains(c));
+
+    if plain {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', "'\\''"))
+    }
+}
+
+pub(crate) fn url_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+
+    encoded
+}
+
+fn url_decode(text: &str) -> Option<String> {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' => {
+                let hex = std::str::from_utf8(bytes.get(i + 1..i + 3)?).ok()?;
+
+                decoded.push(u8::from_str_radix(hex, 16).ok()?);
+                i += 2;
+            }
+            byte => decoded.push(byte),
+        }
+
+        i += 1;
+    }
+
+    String::from_utf8(decoded).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(request: &HttpRequest, name: &str) -> Option<String> {
+        request
+            .headers
+            .iter()
+            .find(|header| header[0].eq_ignore_ascii_case(name))
+            .map(|header| header[1].clone())
+    }
+
+    fn row(name: &str, value: &str) -> Vec<String> {
+        vec![name.to_string(), value.to_string()]
+    }
+
+    #[test]
+    fn parses_a_copied_browser_command() {
+        let request = parse_curl(
+            "curl 'https://api.test/users?page=2&sort=name' \\\n  -H 'Accept: application/json' \\\n  -H \"X-Token: a\\\"b\" \\\n  --compressed",
+        )
+        .unwrap();
+
+        assert_eq!(request.method, HttpMethod::GET);
+        assert_eq!(request.url, "https://api.test/users");
+        assert_eq!(request.params, vec![row("page", "2"), row("sort", "name")]);
+        assert_eq!(
+            header(&request, "accept").as_deref(),
+            Some("application/json")
+        );
+        assert_eq!(header(&request, "x-token").as_deref(), Some("a\"b"));
+        assert_eq!(request.name, "api.test/users");
+        assert!(request.settings.is_none());
+    }
+
+    #[test]
+    fn data_becomes_a_post_body_of_the_right_type() {
+        let form = parse_curl("curl api.test -d 'name=Ada+L&lang=en%21'").unwrap();
+
+        assert_eq!(form.method, HttpMethod::POST);
+        assert_eq!(form.url, "http://api.test");
+        assert_eq!(form.body_type, HttpBodyType::FORM_URLENCODED);
+        assert_eq!(form.form, vec![row("name", "Ada L"), row("lang", "en!")]);
+
+        let json = parse_curl(r#"curl -XPUT api.test --data-raw '{"id": 1}'"#).unwrap();
+
+        assert_eq!(json.method, HttpMethod::PUT);
+        assert_eq!(json.body_type, HttpBodyType::JSON);
+        assert_eq!(json.body, r#"{"id": 1}"#);
+
+        let file = parse_curl("curl api.test --data-binary @body.bin").unwrap();
+
+        assert_eq!(file.body_type, HttpBodyType::BINARY);
+        assert_eq!(file.body_file, "body.bin");
+
+        let query = parse_curl("curl -G api.test/search -d q=bolt -d n=5").unwrap();
+
+        assert_eq!(query.method, HttpMethod::GET);
+        assert_eq!(query.params, vec![row("q", "bolt"), row("n", "5")]);
+        assert!(query.body.is_empty());
+    }
+
+    #[test]
+    fn json_option_sets_the_body_and_headers() {
+        let request = parse_curl(r#"curl api.test --json '{"a": 1,' --json ' "b": 2}'"#).unwrap();
+
+        assert_eq!(request.method, HttpMethod::POST);
+        assert_eq!(request.body_type, HttpBodyType::JSON);
+        assert_eq!(request.body, r#"{"a": 1, "b": 2}"#);
+        assert_eq!(
+            header(&request, "content-type").as_deref(),
+            Some("application/json")
+        );
+        assert_eq!(
+            header(&request, "accept").as_deref(),
+            Some("application/json")
+        );
+
+        let custom = parse_curl("curl api.test -H 'Accept: */*' --json=@body.json").unwrap();
+
+        assert_eq!(custom.body_file, "body.json");
+        assert_eq!(header(&custom, "accept").as_deref(), Some("*/*"));
+        assert_eq!(custom.headers.len(), 2);
+    }
+
+    #[test]
+    fn parses_multipart_auth_and_settings() {
+        let request = parse_curl(
+            "curl -sSLk -u ada:secret --digest -F 'file=@photo.png;type=image/png' \
+             --form-string 'note=@not a file' -x proxy.test:8080 -m 2.5 --max-redirs 3 api.test",
+        )
+        .unwrap();
+
+        assert_eq!(request.body_type, HttpBodyType::MULTIPART);
+        assert_eq!(request.multipart.len(), 2);
+        assert_eq!(request.multipart[0].value, "photo.png");
+        assert!(request.multipart[0].is_file);
+        assert_eq!(request.multipart[1].value, "@not a file");
+        assert!(!request.multipart[1].is_file);
+
+        assert_eq!(request.auth.auth_type, HttpAuthType::DIGEST);
+        assert_eq!(request.auth.username, "ada");
+        assert_eq!(request.auth.password, "secret");
+
+        let settings = request.settings.unwrap();
+
+        assert!(!settings.verify_tls);
+        assert_eq!(settings.proxy, "http://proxy.test:8080");
+        assert_eq!(settings.timeout, 2500);
+        assert_eq!(settings.max_redirects, 3);
+    }
+
+    #[test]
+    fn rejects_what_is_not_a_curl_command() {
+        assert!(parse_curl("wget api.test").is_err());
+        assert!(parse_curl("curl -H 'Accept: */*'").is_err());
+        assert!(parse_curl("curl 'api.test").is_err());
+        assert!(parse_curl("curl -X BREW api.test").is_err());
+    }
+
+    #[test]
+    fn to_curl_writes_the_body_and_auth() {
+        let mut request = HttpRequest::new();
+        request.method = HttpMethod::POST;
+        request.url = "https://api.test/users".to_string();
+        request.params = vec![row("q", "a b")];
+        request.body_type = HttpBodyType::JSON;
+        request.body = r#"{"name": "O'Neil"}"#.to_string();
+        request.auth.auth_type = HttpAuthType::BEARER;
+        request.auth.token = "t0k".to_string();
+
+        assert_eq!(
+            to_curl(&request.to_send_request(0)),
+            "curl 'https://api.test/users?q=a b' \\\n  \
+             -X POST \\\n  \
+             -H 'Content-Type: application/json' \\\n  \
+             -H 'Authorization: Bearer t0k' \\\n  \
+             --data-raw '{\"name\": \"O'\\''Neil\"}'"
+        );
+    }
+
+    #[test]
+    fn to_curl_only_writes_settings_the_request_has() {
+        let mut request = HttpRequest::new();
+        request.url = "http://api.test".to_string();
+
+        assert_eq!(to_curl(&request.to_send_request(0)), "curl http://api.test");
+
+        let mut settings = HttpSettings::new();
+        settings.follow_redirects = false;
+        settings.timeout = 1500;
+        settings.connect_timeout = 2000;
+        request.settings = Some(settings.clone());
+
+        assert_eq!(
+            to_curl(&request.to_send_request(0)),
+            "curl http://api.test \\\n  --connect-timeout 2 \\\n  -m 1.5"
+        );
+
+        settings.follow_redirects = true;
+        settings.max_redirects = 3;
+        request.settings = Some(settings);
+
+        assert!(to_curl(&request.to_send_request(0)).contains("-L \\\n  --max-redirs 3"));
+    }
+
+    #[test]
+    fn to_curl_output_parses_back() {
+        let original = parse_curl(
+            "curl -X PATCH api.test/items/1 -H 'X-Id: 7' -u ada:pw -d 'a=1&b=two words'",
+        )
+        .unwrap();
+
+        let parsed = parse_curl(&to_curl(&original.to_send_request(0))).unwrap();
+
+        assert_eq!(parsed.method, HttpMethod::PATCH);
+        assert_eq!(parsed.url, original.url);
+        assert_eq!(parsed.form, original.form);
+        assert_eq!(header(&parsed, "x-id").as_deref(), Some("7"));
+        assert_eq!(parsed.auth.username, "ada");
+        assert_eq!(parsed.auth.password, "pw");
+    }
+}
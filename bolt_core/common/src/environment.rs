@@ -0,0 +1,286 @@
+use crate::assertion::Assertion;
+use crate::auth::HttpAuth;
+use crate::graphql::GraphqlBody;
+use crate::http::{HttpRequest, MultipartField, SendHttpRequest};
+use crate::prelude::{MainState, MsgType};
+use crate::settings::HttpSettings;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Environment {
+    pub name: String,
+    pub variables: Vec<Vec<String>>,
+}
+
+impl Environment {
+    pub fn new() -> Self {
+        Self {
+            name: "New Environment ".to_string(),
+            variables: vec![vec![String::new(), String::new()]],
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&String> {
+        self.variables
+            .iter()
+            .find(|var| var.len() > 1 && var[0] == key)
+            .map(|var| &var[1])
+    }
+
+    // updates the variable or adds it, reusing an empty row when there is one
+    pub fn set(&mut self, key: &str, value: &str) {
+        if let Some(var) = self
+            .variables
+            .iter_mut()
+            .find(|var| var.len() > 1 && var[0] == key)
+        {
+            var[1] = value.to_string();
+            return;
+        }
+
+        let empty = self
+            .variables
+            .iter_mut()
+            .find(|var| var.iter().all(|field| field.is_empty()));
+
+        match empty {
+            Some(var) => *var = vec![key.to_string(), value.to_string()],
+            None => self.variables.push(vec![key.to_string(), value.to_string()]),
+        }
+    }
+
+    // replaces every {{name}} placeholder with its value, unknown names are kept as is
+    pub fn resolve(&self, input: &str) -> String {
+        resolve_variables(input, &self.variables)
+    }
+}
+
+// sent to the client with the values a response stored in an environment
+#[derive(Serialize, Deserialize)]
+pub struct VariablesChangedMsg {
+    pub msg_type: MsgType,
+    // index into MainState.environments
+    pub env: usize,
+    // [variable, value] pairs
+    pub variables: Vec<Vec<String>>,
+}
+
+pub fn resolve_variables(input: &str, variables: &[Vec<String>]) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+
+        let after = &rest[start + 2..];
+
+        match after.find("}}") {
+            Some(end) => {
+                let key = after[..end].trim();
+
+                let value = variables
+                    .iter()
+                    .find(|var| var.len() > 1 && !var[0].is_empty() && var[0] == key);
+
+                match value {
+                    Some(var) => output.push_str(&var[1]),
+                    None => output.push_str(&rest[start..start + 2 + end + 2]),
+                }
+
+                rest = &after[end + 2..];
+            }
+
+            None => {
+                output.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+
+    output.push_str(rest);
+
+    output
+}
+
+fn resolve_pairs(pairs: &mut [Vec<String>], env: &Environment) {
+    for pair in pairs.iter_mut() {
+        for value in pair.iter_mut() {
+            *value = env.resolve(value);
+        }
+    }
+}
+
+fn resolve_assertions(assertions: &mut [Assertion], env: &Environment) {
+    for assertion in assertions.iter_mut() {
+        assertion.target = env.resolve(&assertion.target);
+        assertion.expected = env.resolve(&assertion.expected);
+    }
+}
+
+fn resolve_multipart(fields: &mut [MultipartField], env: &Environment) {
+    for field in fields.iter_mut() {
+        field.name = env.resolve(&field.name);
+        field.value = env.resolve(&field.value);
+    }
+}
+
+fn resolve_graphql(graphql: &mut GraphqlBody, env: &Environment) {
+    graphql.query = env.resolve(&graphql.query);
+    graphql.variables = env.resolve(&graphql.variables);
+    graphql.operation_name = env.resolve(&graphql.operation_name);
+}
+
+fn resolve_auth(auth: &mut HttpAuth, env: &Environment) {
+    auth.username = env.resolve(&auth.username);
+    auth.password = env.resolve(&auth.password);
+    auth.token = env.resolve(&auth.token);
+    auth.key = env.resolve(&auth.key);
+    auth.value = env.resolve(&auth.value);
+
+    auth.oauth2.token_url = env.resolve(&auth.oauth2.token_url);
+    auth.oauth2.auth_url = env.resolve(&auth.oauth2.auth_url);
+    auth.oauth2.redirect_url = env.resolve(&auth.oauth2.redirect_url);
+    auth.oauth2.client_id = env.resolve(&auth.oauth2.client_id);
+    auth.oauth2.client_secret = env.resolve(&auth.oauth2.client_secret);
+    auth.oauth2.scope = env.resolve(&auth.oauth2.scope);
+}
+
+fn resolve_settings(settings: &mut HttpSettings, env: &Environment) {
+    settings.proxy = env.resolve(&settings.proxy);
+    settings.ca_cert = env.resolve(&settings.ca_cert);
+    settings.client_cert = env.resolve(&settings.client_cert);
+    settings.client_key = env.resolve(&settings.client_key);
+}
+
+impl MainState {
+    pub fn active_environment(&self) -> Option<&Environment> {
+        match self.env_active {
+            Some(index) => self.environments.get(index),
+            None => None,
+        }
+    }
+
+    pub fn active_environment_mut(&mut self) -> Option<&mut Environment> {
+        match self.env_active {
+            Some(index) => self.environments.get_mut(index),
+            None => None,
+        }
+    }
+
+    pub fn resolve_variables(&self, input: &str) -> String {
+        match self.active_environment() {
+            Some(env) => env.resolve(input),
+            None => input.to_string(),
+        }
+    }
+}
+
+impl HttpRequest {
+    pub fn resolve_variables(&mut self, env: &Environment) {
+        self.url = env.resolve(&self.url);
+        self.body = env.resolve(&self.body);
+
+        resolve_pairs(&mut self.headers, env);
+        resolve_pairs(&mut self.params, env);
+        resolve_pairs(&mut self.form, env);
+        resolve_multipart(&mut self.multipart, env);
+        self.body_file = env.resolve(&self.body_file);
+        resolve_graphql(&mut self.graphql, env);
+        resolve_auth(&mut self.auth, env);
+        resolve_assertions(&mut self.assertions, env);
+
+        if let Some(settings) = self.settings.as_mut() {
+            resolve_settings(settings, env);
+        }
+    }
+}
+
+impl SendHttpRequest {
+    pub fn resolve_variables(&mut self, env: &Environment) {
+        self.url = env.resolve(&self.url);
+        self.body = env.resolve(&self.body);
+
+        resolve_pairs(&mut self.headers, env);
+        resolve_pairs(&mut self.form, env);
+        resolve_multipart(&mut self.multipart, env);
+        self.body_file = env.resolve(&self.body_file);
+        resolve_graphql(&mut self.graphql, env);
+        resolve_auth(&mut self.auth, env);
+        resolve_assertions(&mut self.assertions, env);
+
+        if let Some(settings) = self.settings.as_mut() {
+            resolve_settings(settings, env);
+        }
+    }
+}
+
+impl HttpSettings {
+    pub fn resolve_variables(&mut self, env: &Environment) {
+        resolve_settings(self, env);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(variables: &[(&str, &str)]) -> Environment {
+        Environment {
+            name: "test".to_string(),
+            variables: variables
+                .iter()
+                .map(|(key, value)| vec![key.to_string(), value.to_string()])
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn resolves_known_variables() {
+        let env = env(&[("host", "localhost:3000"), ("id", "42")]);
+
+        assert_eq!(
+            env.resolve("http://{{host}}/users/{{ id }}"),
+            "http://localhost:3000/users/42"
+        );
+    }
+
+    #[test]
+    fn keeps_unknown_and_unclosed_placeholders() {
+        let env = env(&[("host", "localhost")]);
+
+        assert_eq!(env.resolve("{{missing}}/{{host}}"), "{{missing}}/localhost");
+        assert_eq!(env.resolve("{{host}}/{{open"), "localhost/{{open");
+    }
+
+    #[test]
+    fn empty_names_never_match() {
+        let env = env(&[("", "value")]);
+
+        assert_eq!(env.resolve("{{}}"), "{{}}");
+    }
+
+    #[test]
+    fn set_updates_or_fills_an_empty_row() {
+        let mut env = Environment::new();
+
+        env.set("token", "a");
+        assert_eq!(env.variables.len(), 1);
+        assert_eq!(env.get("token"), Some(&"a".to_string()));
+
+        env.set("token", "b");
+        env.set("other", "c");
+        assert_eq!(env.variables.len(), 2);
+        assert_eq!(env.get("token"), Some(&"b".to_string()));
+    }
+
+    #[test]
+    fn main_state_uses_the_active_environment() {
+        let mut state = MainState::new();
+        state.environments = vec![env(&[("name", "dev")]), env(&[("name", "prod")])];
+
+        assert_eq!(state.resolve_variables("{{name}}"), "{{name}}");
+
+        state.env_active = Some(1);
+        assert_eq!(state.resolve_variables("{{name}}"), "prod");
+    }
+}
@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExtractionSource {
+    #[default]
+    JSON_PATH,
+    HEADER,
+    COOKIE,
+    REGEX,
+}
+
+impl ExtractionSource {
+    pub fn count() -> usize {
+        4
+    }
+
+    pub fn hint(&self) -> &'static str {
+        match self {
+            ExtractionSource::JSON_PATH => "$.data.token",
+            ExtractionSource::HEADER => "header name",
+            ExtractionSource::COOKIE => "cookie name",
+            ExtractionSource::REGEX => "regex, the first group is used when there is one",
+        }
+    }
+}
+
+impl From<usize> for ExtractionSource {
+    fn from(index: usize) -> Self {
+        match index {
+            0 => ExtractionSource::JSON_PATH,
+            1 => ExtractionSource::HEADER,
+            2 => ExtractionSource::COOKIE,
+            3 => ExtractionSource::REGEX,
+            _ => panic!("Invalid index for ExtractionSource"),
+        }
+    }
+}
+
+impl fmt::Display for ExtractionSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExtractionSource::JSON_PATH => write!(f, "JSON path"),
+            ExtractionSource::HEADER => write!(f, "Header"),
+            ExtractionSource::COOKIE => write!(f, "Cookie"),
+            ExtractionSource::REGEX => write!(f, "Regex"),
+        }
+    }
+}
+
+// after a response arrives the extracted value is written to `variable`
+// in the active environment
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct ExtractionRule {
+    pub source: ExtractionSource,
+    pub expression: String,
+    pub variable: String,
+    pub enabled: bool,
+}
+
+impl ExtractionRule {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Default for ExtractionRule {
+    fn default() -> Self {
+        Self {
+            source: ExtractionSource::JSON_PATH,
+            expression: String::new(),
+            variable: String::new(),
+            enabled: true,
+        }
+    }
+}
@@ -0,0 +1,733 @@
+use crate::http::SendHttpRequest;
+use crate::prelude::{MainState, MsgType};
+use crate::ws::GraphqlWsProtocol;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+// the parts of a GraphQL request, bolt_http sends them as one JSON document
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct GraphqlBody {
+    pub query: String,
+    // a JSON object, empty sends none
+    pub variables: String,
+    // picks the operation to run when the query has several
+    pub operation_name: String,
+}
+
+impl GraphqlBody {
+    // {"query": ..., "variables": ..., "operationName": ...} as servers expect it over HTTP
+    pub fn payload(&self) -> Result<String, String> {
+        Ok(self.payload_value()?.to_string())
+    }
+
+    pub fn payload_value(&self) -> Result<Value, String> {
+        let mut payload = Map::new();
+
+        payload.insert("query".to_string(), json!(self.query));
+
+        if !self.variables.trim().is_empty() {
+            let variables: Value = serde_json::from_str(&self.variables)
+                .map_err(|err| format!("The GraphQL variables are not valid JSON: {err}"))?;
+
+            if !variables.is_object() {
+                return Err("The GraphQL variables must be a JSON object".to_string());
+            }
+
+            payload.insert("variables".to_string(), variables);
+        }
+
+        if !self.operation_name.trim().is_empty() {
+            payload.insert(
+                "operationName".to_string(),
+                json!(self.operation_name.trim()),
+            );
+        }
+
+        Ok(Value::Object(payload))
+    }
+}
+
+// the "errors" of a GraphQL response, which servers send with a 200
+pub fn graphql_errors(body: &str) -> Vec<String> {
+    let Ok(json) = serde_json::from_str::<Value>(body) else {
+        return vec![];
+    };
+
+    let Some(errors) = json["errors"].as_array() else {
+        return vec![];
+    };
+
+    errors
+        .iter()
+        .map(|error| {
+            let message = match error["message"].as_str() {
+                Some(message) => message.to_string(),
+                None => error.to_string(),
+            };
+
+            let mut context = vec![];
+
+            if let Some(path) = error["path"].as_array() {
+                let path: Vec<String> = path
+                    .iter()
+                    .map(|segment| match segment {
+                        Value::String(name) => name.clone(),
+                        segment => segment.to_string(),
+                    })
+                    .collect();
+
+                context.push(format!("at {}", path.join(".")));
+            }
+
+            if let Some(location) = error["locations"].as_array().and_then(|l| l.first()) {
+                context.push(format!(
+                    "line {}:{}",
+                    location["line"].as_u64().unwrap_or_default(),
+                    location["column"].as_u64().unwrap_or_default()
+                ));
+            }
+
+            if context.is_empty() {
+                message
+            } else {
+                format!("{message} ({})", context.join(", "))
+            }
+        })
+        .collect()
+}
+
+// what the editor needs to know about a server's schema, taken from an introspection query
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct GraphqlSchema {
+    // the url without its query, schemas are cached per endpoint
+    pub endpoint: String,
+    // unix time in ms
+    pub fetched: u64,
+    pub query_type: String,
+    pub mutation_type: String,
+    pub subscription_type: String,
+    pub types: Vec<GraphqlType>,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GraphqlTypeKind {
+    #[default]
+    SCALAR,
+    OBJECT,
+    INTERFACE,
+    UNION,
+    ENUM,
+    INPUT_OBJECT,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct GraphqlType {
+    pub name: String,
+    pub kind: GraphqlTypeKind,
+    pub fields: Vec<GraphqlField>,
+    pub input_fields: Vec<GraphqlInputValue>,
+    pub enum_values: Vec<String>,
+    // the object types of a union or interface
+    pub possible_types: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct GraphqlField {
+    pub name: String,
+    pub args: Vec<GraphqlInputValue>,
+    // in GraphQL notation, e.g. [User!]!
+    pub type_ref: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct GraphqlInputValue {
+    pub name: String,
+    pub type_ref: String,
+    pub has_default: bool,
+}
+
+impl GraphqlInputValue {
+    pub fn is_required(&self) -> bool {
+        self.type_ref.ends_with('!') && !self.has_default
+    }
+}
+
+impl GraphqlSchema {
+    pub fn get_type(&self, name: &str) -> Option<&GraphqlType> {
+        self.types.iter().find(|t| t.name == name)
+    }
+
+    pub fn root_type(&self, operation: &str) -> Option<&GraphqlType> {
+        let name = match operation {
+            "mutation" => &self.mutation_type,
+            "subscription" => &self.subscription_type,
+            _ => &self.query_type,
+        };
+
+        if name.is_empty() {
+            return None;
+        }
+
+        self.get_type(name)
+    }
+}
+
+impl MainState {
+    // the cached schema for a request url, variables resolved with the active environment
+    pub fn graphql_schema(&self, url: &str) -> Option<&GraphqlSchema> {
+        let endpoint = graphql_endpoint(&self.resolve_variables(url));
+
+        self.graphql_schemas.iter().find(|s| s.endpoint == endpoint)
+    }
+}
+
+// [User!]! -> User
+pub fn named_type(type_ref: &str) -> &str {
+    type_ref.trim_matches(|c| c == '[' || c == ']' || c == '!')
+}
+
+// the url a schema is cached for, subscriptions over ws:// share the schema of http://
+pub fn graphql_endpoint(url: &str) -> String {
+    let url = url.split(['?', '#']).next().unwrap_or_default().trim();
+    let url = url.trim_end_matches('/');
+
+    if let Some(rest) = url.strip_prefix("ws://") {
+        format!("http://{rest}")
+    } else if let Some(rest) = url.strip_prefix("wss://") {
+        format!("https://{rest}")
+    } else {
+        url.to_string()
+    }
+}
+
+pub static INTROSPECTION_QUERY: &str = "query IntrospectionQuery {
+  __schema {
+    queryType { name }
+    mutationType { name }
+    subscriptionType { name }
+    types {
+      kind
+      name
+      fields(includeDeprecated: true) {
+        name
+        args { name defaultValue type { ...TypeRef } }
+        type { ...TypeRef }
+      }
+      inputFields { name defaultValue type { ...TypeRef } }
+      enumValues(includeDeprecated: true) { name }
+      possibleTypes { name }
+    }
+  }
+}
+
+fragment TypeRef on __Type {
+  kind
+  name
+  ofType {
+    kind
+    name
+    ofType {
+      kind
+      name
+      ofType {
+        kind
+        name
+        ofType {
+          kind
+          name
+          ofType {
+            kind
+            name
+            ofType {
+              kind
+              name
+              ofType { kind name }
+            }
+          }
+        }
+      }
+    }
+  }
+}";
+
+pub fn parse_introspection(endpoint: &str, body: &str) -> Result<GraphqlSchema, String> {
+    let json: Value = serde_json::from_str(body)
+        .map_err(|_| "The introspection response is not JSON".to_string())?;
+
+    let schema = &json["data"]["__schema"];
+
+    if !schema.is_object() {
+        let errors = graphql_errors(body);
+
+        if errors.is_empty() {
+            return Err("The response has no schema, introspection may be disabled".to_string());
+        }
+
+        return Err(format!("Introspection failed: {}", errors.join("; ")));
+    }
+
+    let root_name = |key: &str| schema[key]["name"].as_str().unwrap_or_default().to_string();
+
+    let types = schema["types"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|t| {
+            t["name"]
+                .as_str()
+                .is_some_and(|name| !name.starts_with("__"))
+        })
+        .map(|t| GraphqlType {
+            name: t["name"].as_str().unwrap_or_default().to_string(),
+            kind: match t["kind"].as_str().unwrap_or_default() {
+                "OBJECT" => GraphqlTypeKind::OBJECT,
+                "INTERFACE" => GraphqlTypeKind::INTERFACE,
+                "UNION" => GraphqlTypeKind::UNION,
+                "ENUM" => GraphqlTypeKind::ENUM,
+                "INPUT_OBJECT" => GraphqlTypeKind::INPUT_OBJECT,
+                _ => GraphqlTypeKind::SCALAR,
+            },
+            fields: t["fields"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|field| GraphqlField {
+                    name: field["name"].as_str().unwrap_or_default().to_string(),
+                    args: input_values(&field["args"]),
+                    type_ref: type_ref(&field["type"]),
+                })
+                .collect(),
+            input_fields: input_values(&t["inputFields"]),
+            enum_values: t["enumValues"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|value| value["name"].as_str().map(str::to_string))
+                .collect(),
+            possible_types: t["possibleTypes"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|value| value["name"].as_str().map(str::to_string))
+                .collect(),
+        })
+        .collect();
+
+    Ok(GraphqlSchema {
+        endpoint: endpoint.to_string(),
+        fetched: 0,
+        query_type: root_name("queryType"),
+        mutation_type: root_name("mutationType"),
+        subscription_type: root_name("subscriptionType"),
+        types,
+    })
+}
+
+fn input_values(values: &Value) -> Vec<GraphqlInputValue> {
+    values
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|value| GraphqlInputValue {
+            name: value["name"].as_str().unwrap_or_default().to_string(),
+            type_ref: type_ref(&value["type"]),
+            has_default: !value["defaultValue"].is_null(),
+        })
+        .collect()
+}
+
+fn type_ref(value: &Value) -> String {
+    match value["kind"].as_str() {
+        Some("NON_NULL") => format!("{}!", type_ref(&value["ofType"])),
+        Some("LIST") => format!("[{}]", type_ref(&value["ofType"])),
+        _ => value["name"].as_str().unwrap_or_default().to_string(),
+    }
+}
+
+// the schema request of the editor, sent like the request itself
+#[derive(Serialize, Deserialize)]
+pub struct FetchGraphqlSchemaMsg {
+    pub msg_type: MsgType,
+    pub endpoint: String,
+    pub request: SendHttpRequest,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GraphqlSchemaMsg {
+    pub msg_type: MsgType,
+    pub endpoint: String,
+    pub schema: Option<GraphqlSchema>,
+    pub error: String,
+}
+
+// the last schema fetch, shown above the query editor
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct GraphqlSchemaStatus {
+    pub endpoint: String,
+    pub loading: bool,
+    pub error: String,
+}
+
+// a message of graphql-transport-ws or graphql-ws, the type of ping and pong included
+#[derive(Debug, Clone)]
+pub struct GraphqlWsFrame {
+    pub kind: String,
+    pub id: String,
+    pub payload: Value,
+}
+
+pub fn parse_graphql_ws_frame(txt: &str) -> Option<GraphqlWsFrame> {
+    let json: Value = serde_json::from_str(txt).ok()?;
+
+    Some(GraphqlWsFrame {
+        kind: json["type"].as_str()?.to_string(),
+        id: json["id"].as_str().unwrap_or_default().to_string(),
+        payload: json["payload"].clone(),
+    })
+}
+
+pub fn graphql_ws_init(payload: &str) -> Result<String, String> {
+    let mut frame = json!({ "type": "connection_init" });
+
+    if !payload.trim().is_empty() {
+        let payload: Value = serde_json::from_str(payload)
+            .map_err(|err| format!("The connection_init payload is not valid JSON: {err}"))?;
+
+        frame["payload"] = payload;
+    }
+
+    Ok(frame.to_string())
+}
+
+pub fn graphql_ws_subscribe(
+    protocol: GraphqlWsProtocol,
+    id: &str,
+    body: &GraphqlBody,
+) -> Result<String, String> {
+    if body.query.trim().is_empty() {
+        return Err("The GraphQL query is empty".to_string());
+    }
+
+    let kind = match protocol {
+        GraphqlWsProtocol::GRAPHQL_TRANSPORT_WS => "subscribe",
+        GraphqlWsProtocol::GRAPHQL_WS => "start",
+    };
+
+    Ok(json!({ "id": id, "type": kind, "payload": body.payload_value()? }).to_string())
+}
+
+pub fn graphql_ws_stop(protocol: GraphqlWsProtocol, id: &str) -> String {
+    let kind = match protocol {
+        GraphqlWsProtocol::GRAPHQL_TRANSPORT_WS => "complete",
+        GraphqlWsProtocol::GRAPHQL_WS => "stop",
+    };
+
+    json!({ "id": id, "type": kind }).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTROSPECTION: &str = r#"{
+        "data": {
+            "__schema": {
+                "queryType": { "name": "Query" },
+                "mutationType": null,
+                "subscriptionType": { "name": "Subscription" },
+                "types": [
+                    {
+                        "kind": "OBJECT",
+                        "name": "Query",
+                        "fields": [
+                            {
+                                "name": "users",
+                                "args": [
+                                    { "name": "first", "defaultValue": "10", "type": { "kind": "NON_NULL", "name": null, "ofType": { "kind": "SCALAR", "name": "Int" } } },
+                                    { "name": "role", "defaultValue": null, "type": { "kind": "NON_NULL", "name": null, "ofType": { "kind": "ENUM", "name": "Role" } } }
+                                ],
+                                "type": {
+                                    "kind": "NON_NULL", "name": null,
+                                    "ofType": { "kind": "LIST", "name": null, "ofType": { "kind": "NON_NULL", "name": null, "ofType": { "kind": "OBJECT", "name": "User" } } }
+                                }
+                            }
+                        ],
+                        "inputFields": null,
+                        "enumValues": null,
+                        "possibleTypes": null
+                    },
+                    { "kind": "ENUM", "name": "Role", "fields": null, "enumValues": [{ "name": "ADMIN" }, { "name": "GUEST" }] },
+                    { "kind": "UNION", "name": "Result", "possibleTypes": [{ "name": "User" }, { "name": "Error" }] },
+                    {
+                        "kind": "INPUT_OBJECT",
+                        "name": "UserInput",
+                        "inputFields": [{ "name": "name", "defaultValue": null, "type": { "kind": "SCALAR", "name": "String" } }]
+                    },
+                    { "kind": "SCALAR", "name": "Int" },
+                    { "kind": "OBJECT", "name": "__Type", "fields": [] }
+                ]
+            }
+        }
+    }"#;
+
+    #[test]
+    fn introspection_types() {
+        let schema = parse_introspection("http://api.test/graphql", INTROSPECTION).unwrap();
+
+        assert_eq!(schema.endpoint, "http://api.test/graphql");
+        assert_eq!(schema.query_type, "Query");
+        assert_eq!(schema.mutation_type, "");
+        assert_eq!(schema.subscription_type, "Subscription");
+
+        // the introspection types are left out
+        let names: Vec<&str> = schema.types.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["Query", "Role", "Result", "UserInput", "Int"]);
+
+        let kinds: Vec<GraphqlTypeKind> = schema.types.iter().map(|t| t.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                GraphqlTypeKind::OBJECT,
+                GraphqlTypeKind::ENUM,
+                GraphqlTypeKind::UNION,
+                GraphqlTypeKind::INPUT_OBJECT,
+                GraphqlTypeKind::SCALAR,
+            ]
+        );
+
+        assert_eq!(
+            schema.get_type("Role").unwrap().enum_values,
+            vec!["ADMIN", "GUEST"]
+        );
+        assert_eq!(
+            schema.get_type("Result").unwrap().possible_types,
+            vec!["User", "Error"]
+        );
+
+        let input = &schema.get_type("UserInput").unwrap().input_fields[0];
+        assert_eq!(
+            (input.name.as_str(), input.type_ref.as_str()),
+            ("name", "String")
+        );
+        assert!(!input.is_required());
+
+        assert!(schema.root_type("mutation").is_none());
+        assert_eq!(schema.root_type("query").unwrap().name, "Query");
+    }
+
+    #[test]
+    fn introspection_type_refs() {
+        let schema = parse_introspection("", INTROSPECTION).unwrap();
+        let users = &schema.root_type("query").unwrap().fields[0];
+
+        assert_eq!(users.name, "users");
+        assert_eq!(users.type_ref, "[User!]!");
+        assert_eq!(named_type(&users.type_ref), "User");
+
+        let args: Vec<(&str, &str, bool)> = users
+            .args
+            .iter()
+            .map(|arg| (arg.name.as_str(), arg.type_ref.as_str(), arg.is_required()))
+            .collect();
+        assert_eq!(
+            args,
+            vec![("first", "Int!", false), ("role", "Role!", true)]
+        );
+    }
+
+    #[test]
+    fn introspection_failures() {
+        assert_eq!(
+            parse_introspection("", "<html>").unwrap_err(),
+            "The introspection response is not JSON"
+        );
+        assert_eq!(
+            parse_introspection("", r#"{ "data": null }"#).unwrap_err(),
+            "The response has no schema, introspection may be disabled"
+        );
+        assert_eq!(
+            parse_introspection(
+                "",
+                r#"{ "errors": [{ "message": "introspection is disabled", "locations": [{ "line": 2, "column": 3 }] }] }"#
+            )
+            .unwrap_err(),
+            "Introspection failed: introspection is disabled (line 2:3)"
+        );
+    }
+
+    #[test]
+    fn errors_with_their_path() {
+        let body = r#"{ "data": null, "errors": [
+            { "message": "not allowed", "path": ["users", 0, "email"] },
+            { "extensions": { "code": "X" } }
+        ] }"#;
+
+        assert_eq!(
+            graphql_errors(body),
+            vec![
+                "not allowed (at users.0.email)".to_string(),
+                r#"{"extensions":{"code":"X"}}"#.to_string(),
+            ]
+        );
+        assert!(graphql_errors(r#"{ "data": {} }"#).is_empty());
+    }
+
+    #[test]
+    fn payloads_and_endpoints() {
+        let mut body = GraphqlBody {
+            query: "query Users { users { id } }".to_string(),
+            variables: String::new(),
+            operation_name: " Users ".to_string(),
+        };
+
+        assert_eq!(
+            body.payload_value().unwrap(),
+            json!({ "query": "query Users { users { id } }", "operationName": "Users" })
+        );
+
+        body.variables = "[1]".to_string();
+        assert!(body.payload().is_err());
+
+        body.variables = r#"{ "first": 2 }"#.to_string();
+        assert_eq!(
+            body.payload_value().unwrap()["variables"],
+            json!({ "first": 2 })
+        );
+
+        assert_eq!(
+            graphql_endpoint("wss://api.test/graphql/?token=1"),
+            "https://api.test/graphql"
+        );
+        assert_eq!(
+            graphql_endpoint("http://api.test/graphql#x"),
+            "http://api.test/graphql"
+        );
+    }
+
+    fn frame(txt: &str) -> (String, String, Value) {
+        let frame = parse_graphql_ws_frame(txt).unwrap();
+
+        (frame.kind, frame.id, frame.payload)
+    }
+
+    #[test]
+    fn frames_of_graphql_transport_ws() {
+        assert_eq!(
+            frame(r#"{"type":"connection_ack"}"#),
+            ("connection_ack".to_string(), String::new(), Value::Null)
+        );
+        assert_eq!(
+            frame(r#"{"type":"ping","payload":{"at":1}}"#),
+            ("ping".to_string(), String::new(), json!({ "at": 1 }))
+        );
+        assert_eq!(
+            frame(r#"{"id":"s1","type":"next","payload":{"data":{"n":1}}}"#),
+            (
+                "next".to_string(),
+                "s1".to_string(),
+                json!({ "data": { "n": 1 } })
+            )
+        );
+        assert_eq!(
+            frame(r#"{"id":"s1","type":"error","payload":[{"message":"bad"}]}"#),
+            (
+                "error".to_string(),
+                "s1".to_string(),
+                json!([{ "message": "bad" }])
+            )
+        );
+        assert_eq!(
+            frame(r#"{"id":"s1","type":"complete"}"#),
+            ("complete".to_string(), "s1".to_string(), Value::Null)
+        );
+    }
+
+    #[test]
+    fn frames_of_graphql_ws() {
+        assert_eq!(
+            frame(r#"{"type":"ka"}"#),
+            ("ka".to_string(), String::new(), Value::Null)
+        );
+        assert_eq!(
+            frame(r#"{"id":"s1","type":"data","payload":{"data":{"n":1}}}"#),
+            (
+                "data".to_string(),
+                "s1".to_string(),
+                json!({ "data": { "n": 1 } })
+            )
+        );
+        assert_eq!(
+            frame(r#"{"type":"connection_error","payload":{"message":"no"}}"#),
+            (
+                "connection_error".to_string(),
+                String::new(),
+                json!({ "message": "no" })
+            )
+        );
+
+        // not frames, shown as they came
+        assert!(parse_graphql_ws_frame("hello").is_none());
+        assert!(parse_graphql_ws_frame(r#"{"id":"s1"}"#).is_none());
+        assert!(parse_graphql_ws_frame(r#"{"type":1}"#).is_none());
+    }
+
+    #[test]
+    fn connection_init_payloads() {
+        assert_eq!(
+            graphql_ws_init("").unwrap(),
+            r#"{"type":"connection_init"}"#
+        );
+        assert_eq!(
+            graphql_ws_init(r#" { "token": "t" } "#).unwrap(),
+            r#"{"payload":{"token":"t"},"type":"connection_init"}"#
+        );
+        assert!(graphql_ws_init("{ token")
+            .unwrap_err()
+            .starts_with("The connection_init payload is not valid JSON"));
+    }
+
+    #[test]
+    fn subscribe_and_stop_frames() {
+        let body = GraphqlBody {
+            query: "subscription { n }".to_string(),
+            variables: r#"{ "first": 2 }"#.to_string(),
+            operation_name: String::new(),
+        };
+
+        let subscribe = |protocol| -> Value {
+            serde_json::from_str(&graphql_ws_subscribe(protocol, "s1", &body).unwrap()).unwrap()
+        };
+
+        let payload = json!({ "query": "subscription { n }", "variables": { "first": 2 } });
+
+        assert_eq!(
+            subscribe(GraphqlWsProtocol::GRAPHQL_TRANSPORT_WS),
+            json!({ "id": "s1", "type": "subscribe", "payload": payload })
+        );
+        assert_eq!(
+            subscribe(GraphqlWsProtocol::GRAPHQL_WS),
+            json!({ "id": "s1", "type": "start", "payload": payload })
+        );
+
+        assert_eq!(
+            graphql_ws_stop(GraphqlWsProtocol::GRAPHQL_TRANSPORT_WS, "s1"),
+            r#"{"id":"s1","type":"complete"}"#
+        );
+        assert_eq!(
+            graphql_ws_stop(GraphqlWsProtocol::GRAPHQL_WS, "s1"),
+            r#"{"id":"s1","type":"stop"}"#
+        );
+
+        let empty = GraphqlBody::default();
+        assert_eq!(
+            graphql_ws_subscribe(GraphqlWsProtocol::GRAPHQL_WS, "s1", &empty).unwrap_err(),
+            "The GraphQL query is empty"
+        );
+
+        let mut invalid = body.clone();
+        invalid.variables = "[1]".to_string();
+        assert!(graphql_ws_subscribe(GraphqlWsProtocol::GRAPHQL_WS, "s1", &invalid).is_err());
+    }
+}
@@ -0,0 +1,1604 @@
+use crate::graphql::{
+    named_type, GraphqlBody, GraphqlField, GraphqlInputValue, GraphqlSchema, GraphqlType,
+    GraphqlTypeKind,
+};
+use serde_json::Value;
+use std::fmt;
+
+// a problem the editor shows under the query, line 0 is not about a place in the query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphqlDiagnostic {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for GraphqlDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line == 0 {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}:{} {}", self.line, self.column, self.message)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphqlCompletion {
+    pub label: String,
+    // the type, and the arguments of a field
+    pub detail: String,
+}
+
+// the suggestions for the word at the cursor, which begins at start
+#[derive(Debug, Clone, Default)]
+pub struct GraphqlCompletions {
+    pub start: usize,
+    pub items: Vec<GraphqlCompletion>,
+}
+
+// syntax, the variables and, with a schema, the fields and arguments of the query
+pub fn check_graphql(body: &GraphqlBody, schema: Option<&GraphqlSchema>) -> Vec<GraphqlDiagnostic> {
+    let mut diagnostics = vec![];
+
+    if body.query.trim().is_empty() {
+        return diagnostics;
+    }
+
+    let mut variables = None;
+
+    if !body.variables.trim().is_empty() {
+        match serde_json::from_str::<Value>(&body.variables) {
+            Ok(Value::Object(object)) => variables = Some(object),
+            Ok(_) => diagnostics.push(note("the variables must be a JSON object")),
+            Err(err) => diagnostics.push(note(&format!("the variables are not valid JSON: {err}"))),
+        }
+    }
+
+    let document = match tokenize(&body.query).and_then(|tokens| Parser::new(tokens).document()) {
+        Ok(document) => document,
+        Err(diagnostic) => {
+            diagnostics.push(diagnostic);
+
+            return diagnostics;
+        }
+    };
+
+    let operation_name = body.operation_name.trim();
+
+    let operation = if operation_name.is_empty() {
+        if document.operations.len() > 1 {
+            diagnostics.push(note(
+                "the query has several operations, set the operation name",
+            ));
+        }
+
+        document.operations.first()
+    } else {
+        let operation = document.operations.iter().find(|operation| {
+            operation
+                .name
+                .is_some_and(|name| name.text == operation_name)
+        });
+
+        if operation.is_none() {
+            diagnostics.push(note(&format!(
+                "the query has no operation named {operation_name}"
+            )));
+        }
+
+        operation
+    };
+
+    // the server refuses the request without them
+    if let Some(operation) = operation {
+        for definition in &operation.variables {
+            let name = definition.name.text;
+            let is_set = variables
+                .as_ref()
+                .is_some_and(|variables| variables.get(name).is_some_and(|v| !v.is_null()));
+
+            if definition.type_ref.ends_with('!') && !definition.has_default && !is_set {
+                diagnostics.push(at(
+                    &definition.name,
+                    &format!("${name} is required, add it to the variables"),
+                ));
+            }
+        }
+    }
+
+    if let Some(schema) = schema {
+        check_document(&document, schema, &mut diagnostics);
+    }
+
+    diagnostics
+}
+
+// fields, arguments and enum values that fit at the cursor, a byte offset into the query
+pub fn complete_graphql(query: &str, cursor: usize, schema: &GraphqlSchema) -> GraphqlCompletions {
+    let mut completions = GraphqlCompletions {
+        start: cursor,
+        items: vec![],
+    };
+
+    if cursor > query.len() || !query.is_char_boundary(cursor) {
+        return completions;
+    }
+
+    let before = &query[..cursor];
+
+    let start = before
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| c.is_ascii_alphanumeric() || *c == '_')
+        .last()
+        .map(|(index, _)| index)
+        .unwrap_or(cursor);
+
+    let prefix = &query[start..cursor];
+
+    completions.start = start;
+
+    // variables, directives, fragment names and comments
+    let previous = query[..start].chars().next_back();
+    let line = query[..start].rsplit('\n').next().unwrap_or_default();
+
+    if prefix.starts_with(|c: char| c.is_ascii_digit())
+        || matches!(previous, Some('$') | Some('@') | Some('.'))
+        || line.contains('#')
+    {
+        return completions;
+    }
+
+    let Ok(tokens) = tokenize(&query[..start]) else {
+        return completions;
+    };
+
+    let items = match context_at(&tokens, schema) {
+        Context::Document => ["query", "mutation", "subscription", "fragment"]
+            .iter()
+            .map(|keyword| GraphqlCompletion {
+                label: keyword.to_string(),
+                detail: String::new(),
+            })
+            .collect(),
+
+        Context::Selection(parent) => {
+            let mut items: Vec<GraphqlCompletion> = parent
+                .fields
+                .iter()
+                .map(|field| GraphqlCompletion {
+                    label: field.name.clone(),
+                    detail: field_signature(field),
+                })
+                .collect();
+
+            items.push(GraphqlCompletion {
+                label: "__typename".to_string(),
+                detail: "String!".to_string(),
+            });
+
+            // the object types of a union or interface are selected with fragments
+            if prefix.is_empty() {
+                for name in &parent.possible_types {
+                    items.push(GraphqlCompletion {
+                        label: format!("... on {name}"),
+                        detail: String::new(),
+                    });
+                }
+            }
+
+            items
+        }
+
+        Context::Arguments(field) => field
+            .args
+            .iter()
+            .map(|arg| GraphqlCompletion {
+                label: arg.name.clone(),
+                detail: arg.type_ref.clone(),
+            })
+            .collect(),
+
+        Context::Value(arg) => match schema.get_type(named_type(&arg.type_ref)) {
+            Some(t) if t.kind == GraphqlTypeKind::ENUM => t
+                .enum_values
+                .iter()
+                .map(|value| GraphqlCompletion {
+                    label: value.clone(),
+                    detail: t.name.clone(),
+                })
+                .collect(),
+            _ => vec![],
+        },
+
+        Context::Unknown => vec![],
+    };
+
+    let lowercase = prefix.to_lowercase();
+
+    completions.items = items
+        .into_iter()
+        .filter(|item| item.label != prefix && item.label.to_lowercase().starts_with(&lowercase))
+        .collect();
+
+    completions
+}
+
+fn field_signature(field: &GraphqlField) -> String {
+    if field.args.is_empty() {
+        return field.type_ref.clone();
+    }
+
+    let args: Vec<String> = field
+        .args
+        .iter()
+        .map(|arg| format!("{}: {}", arg.name, arg.type_ref))
+        .collect();
+
+    format!("({}): {}", args.join(", "), field.type_ref)
+}
+
+enum Context<'s> {
+    Document,
+    Selection(&'s GraphqlType),
+    Arguments(&'s GraphqlField),
+    Value(&'s GraphqlInputValue),
+    Unknown,
+}
+
+enum Frame<'s> {
+    Selection(Option<&'s GraphqlType>),
+    Arguments {
+        field: Option<&'s GraphqlField>,
+        argument: Option<&'s GraphqlInputValue>,
+        in_value: bool,
+    },
+    // lists and input objects inside an argument
+    Value,
+}
+
+// follows the selection sets and argument lists that are open at the end of the tokens
+fn context_at<'s>(tokens: &[Token], schema: &'s GraphqlSchema) -> Context<'s> {
+    let mut stack: Vec<Frame> = vec![];
+
+    // the type the next { selects from
+    let mut pending: Option<&GraphqlType> = None;
+    let mut last_field: Option<&GraphqlField> = None;
+    let mut directive = false;
+
+    let mut i = 0;
+
+    while i < tokens.len() {
+        let token = &tokens[i];
+        let punct = token.kind == TokenKind::Punct;
+        let next = tokens.get(i + 1).map(|next| next.text);
+
+        match stack.last_mut() {
+            None => match token.text {
+                "query" | "mutation" | "subscription" if !punct => {
+                    pending = schema.root_type(token.text);
+                }
+
+                // fragment Name on Type
+                "fragment" if !punct => {
+                    pending = tokens.get(i + 3).and_then(|t| schema.get_type(t.text));
+                }
+
+                "{" if punct => {
+                    let selected = pending.take().or_else(|| schema.root_type("query"));
+
+                    stack.push(Frame::Selection(selected));
+                }
+
+                // variable definitions
+                "(" if punct => stack.push(Frame::Arguments {
+                    field: None,
+                    argument: None,
+                    in_value: false,
+                }),
+
+                _ => {}
+            },
+
+            Some(Frame::Selection(parent)) => {
+                let parent = *parent;
+
+                match token.text {
+                    "{" if punct => {
+                        let selected = pending.take().or_else(|| {
+                            last_field
+                                .and_then(|field| schema.get_type(named_type(&field.type_ref)))
+                        });
+
+                        stack.push(Frame::Selection(selected));
+                        last_field = None;
+                    }
+
+                    "}" if punct => {
+                        stack.pop();
+                        last_field = None;
+                        pending = None;
+                    }
+
+                    "(" if punct => {
+                        let field = if directive { None } else { last_field };
+
+                        stack.push(Frame::Arguments {
+                            field,
+                            argument: None,
+                            in_value: false,
+                        });
+
+                        directive = false;
+                    }
+
+                    "..." if punct => {
+                        last_field = None;
+
+                        if next == Some("on") {
+                            pending = tokens.get(i + 2).and_then(|t| schema.get_type(t.text));
+                            i += 2;
+                        } else if tokens.get(i + 1).is_some_and(|t| t.kind == TokenKind::Name) {
+                            i += 1;
+                        }
+                    }
+
+                    "@" if punct => {
+                        directive = true;
+                        i += 1;
+                    }
+
+                    name if token.kind == TokenKind::Name => {
+                        // an alias comes before the field name
+                        if next != Some(":") {
+                            last_field = parent
+                                .and_then(|parent| parent.fields.iter().find(|f| f.name == name));
+                        }
+
+                        directive = false;
+                    }
+
+                    _ => {}
+                }
+            }
+
+            Some(Frame::Arguments {
+                field,
+                argument,
+                in_value,
+            }) => match token.text {
+                ")" if punct => {
+                    stack.pop();
+                }
+
+                "[" | "{" if punct => stack.push(Frame::Value),
+
+                ":" if punct => *in_value = true,
+
+                name if token.kind == TokenKind::Name && !*in_value => {
+                    *argument = field.and_then(|field| field.args.iter().find(|a| a.name == name));
+                }
+
+                _ if token.kind != TokenKind::Punct => *in_value = false,
+
+                _ => {}
+            },
+
+            Some(Frame::Value) => match token.text {
+                "[" | "{" if punct => stack.push(Frame::Value),
+
+                "]" | "}" if punct => {
+                    stack.pop();
+
+                    if let Some(Frame::Arguments { in_value, .. }) = stack.last_mut() {
+                        *in_value = false;
+                    }
+                }
+
+                _ => {}
+            },
+        }
+
+        i += 1;
+    }
+
+    match stack.last() {
+        None => Context::Document,
+        Some(Frame::Selection(Some(parent))) => Context::Selection(parent),
+        Some(Frame::Arguments {
+            field: Some(field),
+            in_value: false,
+            ..
+        }) => Context::Arguments(field),
+        Some(Frame::Arguments {
+            argument: Some(argument),
+            in_value: true,
+            ..
+        }) => Context::Value(argument),
+        _ => Context::Unknown,
+    }
+}
+
+fn check_document(
+    document: &Document,
+    schema: &GraphqlSchema,
+    diagnostics: &mut Vec<GraphqlDiagnostic>,
+) {
+    let fragment_names: Vec<&str> = document
+        .fragments
+        .iter()
+        .map(|fragment| fragment.name.text)
+        .collect();
+
+    let mut checker = Checker {
+        schema,
+        fragment_names: &fragment_names,
+        diagnostics,
+    };
+
+    let mut names: Vec<&str> = vec![];
+
+    for operation in &document.operations {
+        match operation.name {
+            Some(name) if names.contains(&name.text) => {
+                checker.error(
+                    &name,
+                    &format!("there is more than one operation named {}", name.text),
+                );
+            }
+
+            Some(name) => names.push(name.text),
+
+            None if document.operations.len() > 1 => checker.error(
+                &operation.token,
+                "an operation without a name must be the only one in the query",
+            ),
+
+            None => {}
+        }
+
+        for definition in &operation.variables {
+            let type_name = named_type(&definition.type_ref);
+
+            if schema.get_type(type_name).is_none() {
+                checker.error(&definition.type_token, &format!("unknown type {type_name}"));
+            }
+        }
+
+        for used in &operation.used_variables {
+            if !operation.variables.iter().any(|v| v.name.text == used.text) {
+                checker.error(used, &format!("variable ${} is not defined", used.text));
+            }
+        }
+
+        match schema.root_type(operation.kind) {
+            Some(root) => checker.selections(root, &operation.selections),
+            None => checker.error(
+                &operation.token,
+                &format!("the schema has no {} type", operation.kind),
+            ),
+        }
+    }
+
+    for (index, fragment) in document.fragments.iter().enumerate() {
+        if fragment_names[..index].contains(&fragment.name.text) {
+            checker.error(
+                &fragment.name,
+                &format!(
+                    "there is more than one fragment named {}",
+                    fragment.name.text
+                ),
+            );
+        }
+
+        match schema.get_type(fragment.type_condition.text) {
+            Some(parent) => checker.selections(parent, &fragment.selections),
+            None => checker.error(
+                &fragment.type_condition,
+                &format!("unknown type {}", fragment.type_condition.text),
+            ),
+        }
+    }
+}
+
+struct Checker<'a, 's> {
+    schema: &'s GraphqlSchema,
+    fragment_names: &'a [&'a str],
+    diagnostics: &'a mut Vec<GraphqlDiagnostic>,
+}
+
+impl Checker<'_, '_> {
+    fn selections(&mut self, parent: &GraphqlType, selections: &[Selection]) {
+        for selection in selections {
+            match selection {
+                Selection::Field(field) => self.field(parent, field),
+
+                Selection::Spread(name) => {
+                    if !self.fragment_names.contains(&name.text) {
+                        self.error(name, &format!("fragment {} is not defined", name.text));
+                    }
+                }
+
+                Selection::Inline(type_condition, selections) => match type_condition {
+                    Some(type_condition) => match self.schema.get_type(type_condition.text) {
+                        Some(selected) => self.selections(selected, selections),
+                        None => self.error(
+                            type_condition,
+                            &format!("unknown type {}", type_condition.text),
+                        ),
+                    },
+
+                    None => self.selections(parent, selections),
+                },
+            }
+        }
+    }
+
+    fn field(&mut self, parent: &GraphqlType, field: &Field) {
+        let name = field.name.text;
+
+        if name == "__typename" {
+            if field.selections.is_some() {
+                self.error(
+                    &field.name,
+                    "__typename is a String!, it has no fields to select",
+                );
+            }
+
+            return;
+        }
+
+        // introspection is answered by every server
+        if (name == "__schema" || name == "__type") && parent.name == self.schema.query_type {
+            return;
+        }
+
+        let Some(definition) = parent.fields.iter().find(|f| f.name == name) else {
+            self.error(&field.name, &format!("{} has no field {name}", parent.name));
+
+            return;
+        };
+
+        for argument in &field.arguments {
+            if !definition.args.iter().any(|a| a.name == argument.text) {
+                self.error(
+                    argument,
+                    &format!("{}.{name} has no argument {}", parent.name, argument.text),
+                );
+            }
+        }
+
+        for arg in definition.args.iter().filter(|arg| arg.is_required()) {
+            if !field.arguments.iter().any(|a| a.text == arg.name) {
+                self.error(
+                    &field.name,
+                    &format!(
+                        "{}.{name} needs the argument {}: {}",
+                        parent.name, arg.name, arg.type_ref
+                    ),
+                );
+            }
+        }
+
+        let Some(selected) = self.schema.get_type(named_type(&definition.type_ref)) else {
+            return;
+        };
+
+        let is_leaf = matches!(
+            selected.kind,
+            GraphqlTypeKind::SCALAR | GraphqlTypeKind::ENUM
+        );
+
+        match &field.selections {
+            Some(_) if is_leaf => self.error(
+                &field.name,
+                &format!(
+                    "{name} is a {}, it has no fields to select",
+                    definition.type_ref
+                ),
+            ),
+
+            Some(selections) => self.selections(selected, selections),
+
+            None if !is_leaf => self.error(
+                &field.name,
+                &format!(
+                    "{name} is a {}, select some of its fields",
+                    definition.type_ref
+                ),
+            ),
+
+            None => {}
+        }
+    }
+
+    fn error(&mut self, token: &Token, message: &str) {
+        self.diagnostics.push(at(token, message));
+    }
+}
+
+fn at(token: &Token, message: &str) -> GraphqlDiagnostic {
+    GraphqlDiagnostic {
+        line: token.line,
+        column: token.column,
+        message: message.to_string(),
+    }
+}
+
+fn note(message: &str) -> GraphqlDiagnostic {
+    GraphqlDiagnostic {
+        line: 0,
+        column: 0,
+        message: message.to_string(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenKind {
+    Name,
+    Punct,
+    // strings, numbers and {{variables}}
+    Value,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Token<'q> {
+    kind: TokenKind,
+    text: &'q str,
+    line: usize,
+    column: usize,
+}
+
+fn tokenize(query: &str) -> Result<Vec<Token<'_>>, GraphqlDiagnostic> {
+    let bytes = query.as_bytes();
+    let mut tokens = vec![];
+
+    let mut i = 0;
+    let mut line = 1;
+    let mut line_start = 0;
+
+    while i < bytes.len() {
+        let column = query[line_start..i].chars().count() + 1;
+        let rest = &query[i..];
+
+        let length = match bytes[i] {
+            b'\n' => {
+                line += 1;
+                line_start = i + 1;
+
+                i += 1;
+                continue;
+            }
+
+            b' ' | b'\t' | b'\r' | b',' => {
+                i += 1;
+                continue;
+            }
+
+            b'#' => {
+                i += rest.find('\n').unwrap_or(rest.len());
+                continue;
+            }
+
+            // bolt variables are filled in before the query is sent
+            b'{' if rest.starts_with("{{")
+                && rest[2..]
+                    .find("}}")
+                    .is_some_and(|end| !rest[2..2 + end].contains('\n')) =>
+            {
+                let length = rest[2..].find("}}").unwrap() + 4;
+
+                tokens.push(Token {
+                    kind: TokenKind::Value,
+                    text: &rest[..length],
+                    line,
+                    column,
+                });
+
+                i += length;
+                continue;
+            }
+
+            b'.' if rest.starts_with("...") => 3,
+
+            b'!' | b'$' | b'&' | b'(' | b')' | b':' | b'=' | b'@' | b'[' | b']' | b'{' | b'|'
+            | b'}' => 1,
+
+            b'"' => {
+                let (length, newlines, last_newline) =
+                    string_length(rest).ok_or(GraphqlDiagnostic {
+                        line,
+                        column,
+                        message: "the string is not closed".to_string(),
+                    })?;
+
+                tokens.push(Token {
+                    kind: TokenKind::Value,
+                    text: &rest[..length],
+                    line,
+                    column,
+                });
+
+                if newlines > 0 {
+                    line += newlines;
+                    line_start = i + last_newline + 1;
+                }
+
+                i += length;
+                continue;
+            }
+
+            b'-' | b'0'..=b'9' => {
+                let length = rest
+                    .char_indices()
+                    .skip(1)
+                    .find(|(_, c)| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '+' | '-')))
+                    .map(|(index, _)| index)
+                    .unwrap_or(rest.len());
+
+                tokens.push(Token {
+                    kind: TokenKind::Value,
+                    text: &rest[..length],
+                    line,
+                    column,
+                });
+
+                i += length;
+                continue;
+            }
+
+            b'_' | b'a'..=b'z' | b'A'..=b'Z' => {
+                let length = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+
+                tokens.push(Token {
+                    kind: TokenKind::Name,
+                    text: &rest[..length],
+                    line,
+                    column,
+                });
+
+                i += length;
+                continue;
+            }
+
+            _ => {
+                let c = rest.chars().next().unwrap_or_default();
+
+                // the byte order mark some editors write
+                if c == '\u{feff}' {
+                    i += c.len_utf8();
+                    continue;
+                }
+
+                return Err(GraphqlDiagnostic {
+                    line,
+                    column,
+                    message: format!("unexpected character {c}"),
+                });
+            }
+        };
+
+        tokens.push(Token {
+            kind: TokenKind::Punct,
+            text: &rest[..length],
+            line,
+            column,
+        });
+
+        i += length;
+    }
+
+    Ok(tokens)
+}
+
+// (length, newlines inside, offset of the last newline) of a "string" or """block string"""
+fn string_length(rest: &str) -> Option<(usize, usize, usize)> {
+    let newlines = |text: &str| {
+        (
+            text.matches('\n').count(),
+            text.rfind('\n').unwrap_or_default(),
+        )
+    };
+
+    if let Some(block) = rest.strip_prefix("\"\"\"") {
+        let end = block.find("\"\"\"")? + 6;
+        let (count, last) = newlines(&rest[..end]);
+
+        return Some((end, count, last));
+    }
+
+    let mut escaped = false;
+
+    for (index, c) in rest.char_indices().skip(1) {
+        match c {
+            '\n' => return None,
+            '\\' if !escaped => escaped = true,
+            '"' if !escaped => return Some((index + 1, 0, 0)),
+            _ => escaped = false,
+        }
+    }
+
+    None
+}
+
+struct Document<'q> {
+    operations: Vec<Operation<'q>>,
+    fragments: Vec<Fragment<'q>>,
+}
+
+struct Operation<'q> {
+    // query, mutation or subscription
+    kind: &'q str,
+    token: Token<'q>,
+    name: Option<Token<'q>>,
+    variables: Vec<VariableDefinition<'q>>,
+    used_variables: Vec<Token<'q>>,
+    selections: Vec<Selection<'q>>,
+}
+
+struct VariableDefinition<'q> {
+    name: Token<'q>,
+    type_ref: String,
+    type_token: Token<'q>,
+    has_default: bool,
+}
+
+struct Fragment<'q> {
+    name: Token<'q>,
+    type_condition: Token<'q>,
+    selections: Vec<Selection<'q>>,
+}
+
+enum Selection<'q> {
+    Field(Field<'q>),
+    Spread(Token<'q>),
+    Inline(Option<Token<'q>>, Vec<Selection<'q>>),
+}
+
+struct Field<'q> {
+    name: Token<'q>,
+    arguments: Vec<Token<'q>>,
+    selections: Option<Vec<Selection<'q>>>,
+}
+
+struct Parser<'q> {
+    tokens: Vec<Token<'q>>,
+    position: usize,
+    // $variables of the definition being parsed
+    used_variables: Vec<Token<'q>>,
+}
+
+type Parsed<T> = Result<T, GraphqlDiagnostic>;
+
+impl<'q> Parser<'q> {
+    fn new(tokens: Vec<Token<'q>>) -> Self {
+        Parser {
+            tokens,
+            position: 0,
+            used_variables: vec![],
+        }
+    }
+
+    fn document(mut self) -> Parsed<Document<'q>> {
+        let mut document = Document {
+            operations: vec![],
+            fragments: vec![],
+        };
+
+        while let Some(token) = self.peek() {
+            match token.text {
+                "{" => {
+                    let selections = self.selection_set()?;
+
+                    document.operations.push(Operation {
+                        kind: "query",
+                        token,
+                        name: None,
+                        variables: vec![],
+                        used_variables: std::mem::take(&mut self.used_variables),
+                        selections,
+                    });
+                }
+
+                "query" | "mutation" | "subscription" if token.kind == TokenKind::Name => {
+                    self.position += 1;
+
+                    let name = match self.peek() {
+                        Some(name) if name.kind == TokenKind::Name => {
+                            self.position += 1;
+                            Some(name)
+                        }
+                        _ => None,
+                    };
+
+                    let variables = self.variable_definitions()?;
+                    self.directives()?;
+                    let selections = self.selection_set()?;
+
+                    document.operations.push(Operation {
+                        kind: token.text,
+                        token,
+                        name,
+                        variables,
+                        used_variables: std::mem::take(&mut self.used_variables),
+                        selections,
+                    });
+                }
+
+                "fragment" if token.kind == TokenKind::Name => {
+                    self.position += 1;
+
+                    let name = self.name()?;
+                    self.expect("on")?;
+                    let type_condition = self.name()?;
+                    self.directives()?;
+                    let selections = self.selection_set()?;
+
+                    self.used_variables.clear();
+
+                    document.fragments.push(Fragment {
+                        name,
+                        type_condition,
+                        selections,
+                    });
+                }
+
+                _ => {
+                    return Err(at(
+                        &token,
+                        &format!(
+                            "expected query, mutation, subscription or fragment, found {}",
+                            token.text
+                        ),
+                    ))
+                }
+            }
+        }
+
+        if document.operations.is_empty() {
+            return Err(note("the query has no operation"));
+        }
+
+        Ok(document)
+    }
+
+    fn variable_definitions(&mut self) -> Parsed<Vec<VariableDefinition<'q>>> {
+        let mut definitions = vec![];
+
+        if !self.peek_is("(") {
+            return Ok(definitions);
+        }
+
+        self.position += 1;
+
+        while !self.peek_is(")") {
+            self.expect("$")?;
+            let name = self.name()?;
+            self.expect(":")?;
+
+            let type_token = self.peek().ok_or_else(|| self.end())?;
+            let type_ref = self.type_ref()?;
+
+            let has_default = self.peek_is("=");
+
+            if has_default {
+                self.position += 1;
+                self.value()?;
+            }
+
+            self.directives()?;
+
+            definitions.push(VariableDefinition {
+                name,
+                type_ref,
+                type_token,
+                has_default,
+            });
+        }
+
+        self.position += 1;
+
+        // the definitions are not uses
+        self.used_variables.clear();
+
+        Ok(definitions)
+    }
+
+    fn type_ref(&mut self) -> Parsed<String> {
+        let mut type_ref = if self.peek_is("[") {
+            self.position += 1;
+            let inner = self.type_ref()?;
+            self.expect("]")?;
+
+            format!("[{inner}]")
+        } else {
+            self.name()?.text.to_string()
+        };
+
+        if self.peek_is("!") {
+            self.position += 1;
+            type_ref.push('!');
+        }
+
+        Ok(type_ref)
+    }
+
+    fn selection_set(&mut self) -> Parsed<Vec<Selection<'q>>> {
+        let open = self.expect("{")?;
+
+        let mut selections = vec![];
+
+        while !self.peek_is("}") {
+            if self.peek().is_none() {
+                return Err(at(&open, "the selection set is not closed"));
+            }
+
+            selections.push(self.selection()?);
+        }
+
+        let close = self.expect("}")?;
+
+        if selections.is_empty() {
+            return Err(at(&close, "the selection set is empty"));
+        }
+
+        Ok(selections)
+    }
+
+    fn selection(&mut self) -> Parsed<Selection<'q>> {
+        if self.peek_is("...") {
+            self.position += 1;
+
+            match self.peek() {
+                Some(token) if token.kind == TokenKind::Name && token.text != "on" => {
+                    self.position += 1;
+                    self.directives()?;
+
+                    return Ok(Selection::Spread(token));
+                }
+
+                Some(token) if token.text == "on" => {
+                    self.position += 1;
+
+                    let type_condition = self.name()?;
+                    self.directives()?;
+
+                    return Ok(Selection::Inline(
+                        Some(type_condition),
+                        self.selection_set()?,
+                    ));
+                }
+
+                _ => {
+                    self.directives()?;
+
+                    return Ok(Selection::Inline(None, self.selection_set()?));
+                }
+            }
+        }
+
+        let mut name = self.name()?;
+
+        // alias: name
+        if self.peek_is(":") {
+            self.position += 1;
+            name = self.name()?;
+        }
+
+        let arguments = self.arguments()?;
+        self.directives()?;
+
+        let selections = if self.peek_is("{") {
+            Some(self.selection_set()?)
+        } else {
+            None
+        };
+
+        Ok(Selection::Field(Field {
+            name,
+            arguments,
+            selections,
+        }))
+    }
+
+    fn arguments(&mut self) -> Parsed<Vec<Token<'q>>> {
+        let mut arguments = vec![];
+
+        if !self.peek_is("(") {
+            return Ok(arguments);
+        }
+
+        self.position += 1;
+
+        while !self.peek_is(")") {
+            arguments.push(self.name()?);
+            self.expect(":")?;
+            self.value()?;
+        }
+
+        self.position += 1;
+
+        Ok(arguments)
+    }
+
+    fn directives(&mut self) -> Parsed<()> {
+        while self.peek_is("@") {
+            self.position += 1;
+            self.name()?;
+            self.arguments()?;
+        }
+
+        Ok(())
+    }
+
+    fn value(&mut self) -> Parsed<()> {
+        let token = self.next()?;
+
+        match (token.kind, token.text) {
+            (TokenKind::Punct, "$") => {
+                let name = self.name()?;
+                self.used_variables.push(name);
+            }
+
+            (TokenKind::Punct, "[") => {
+                while !self.peek_is("]") {
+                    self.value()?;
+                }
+
+                self.position += 1;
+            }
+
+            (TokenKind::Punct, "{") => {
+                while !self.peek_is("}") {
+                    self.name()?;
+                    self.expect(":")?;
+                    self.value()?;
+                }
+
+                self.position += 1;
+            }
+
+            (TokenKind::Punct, text) => {
+                return Err(at(&token, &format!("expected a value, found {text}")))
+            }
+
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn peek(&self) -> Option<Token<'q>> {
+        self.tokens.get(self.position).copied()
+    }
+
+    fn peek_is(&self, text: &str) -> bool {
+        self.peek().is_some_and(|token| token.text == text)
+    }
+
+    fn next(&mut self) -> Parsed<Token<'q>> {
+        let token = self.peek().ok_or_else(|| self.end())?;
+        self.position += 1;
+
+        Ok(token)
+    }
+
+    fn expect(&mut self, text: &str) -> Parsed<Token<'q>> {
+        let token = self.next()?;
+
+        if token.text != text {
+            return Err(at(
+                &token,
+                &format!("expected {text}, found {}", token.text),
+            ));
+        }
+
+        Ok(token)
+    }
+
+    fn name(&mut self) -> Parsed<Token<'q>> {
+        let token = self.next()?;
+
+        if token.kind != TokenKind::Name {
+            return Err(at(
+                &token,
+                &format!("expected a name, found {}", token.text),
+            ));
+        }
+
+        Ok(token)
+    }
+
+    fn end(&self) -> GraphqlDiagnostic {
+        match self.tokens.last() {
+            Some(last) => at(last, &format!("the query ends after {}", last.text)),
+            None => note("the query is empty"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(name: &str, type_ref: &str) -> GraphqlInputValue {
+        GraphqlInputValue {
+            name: name.to_string(),
+            type_ref: type_ref.to_string(),
+            has_default: false,
+        }
+    }
+
+    fn field(name: &str, type_ref: &str, args: Vec<GraphqlInputValue>) -> GraphqlField {
+        GraphqlField {
+            name: name.to_string(),
+            args,
+            type_ref: type_ref.to_string(),
+        }
+    }
+
+    fn object(name: &str, kind: GraphqlTypeKind, fields: Vec<GraphqlField>) -> GraphqlType {
+        GraphqlType {
+            name: name.to_string(),
+            kind,
+            fields,
+            ..Default::default()
+        }
+    }
+
+    fn schema() -> GraphqlSchema {
+        let mut first = input("first", "Int!");
+        first.has_default = true;
+
+        let mut role = object("Role", GraphqlTypeKind::ENUM, vec![]);
+        role.enum_values = vec!["ADMIN".to_string(), "GUEST".to_string()];
+
+        let mut result = object("Result", GraphqlTypeKind::UNION, vec![]);
+        result.possible_types = vec!["User".to_string(), "Error".to_string()];
+
+        GraphqlSchema {
+            query_type: "Query".to_string(),
+            mutation_type: "Mutation".to_string(),
+            types: vec![
+                object(
+                    "Query",
+                    GraphqlTypeKind::OBJECT,
+                    vec![
+                        field("users", "[User!]!", vec![first, input("role", "Role!")]),
+                        field("user", "User", vec![input("id", "ID!")]),
+                        field("search", "Result", vec![input("term", "String!")]),
+                    ],
+                ),
+                object(
+                    "Mutation",
+                    GraphqlTypeKind::OBJECT,
+                    vec![field(
+                        "rename",
+                        "User",
+                        vec![input("id", "ID!"), input("name", "String!")],
+                    )],
+                ),
+                object(
+                    "User",
+                    GraphqlTypeKind::OBJECT,
+                    vec![
+                        field("id", "ID!", vec![]),
+                        field("name", "String", vec![]),
+                        field("role", "Role", vec![]),
+                        field("friends", "[User!]!", vec![input("first", "Int")]),
+                    ],
+                ),
+                object(
+                    "Error",
+                    GraphqlTypeKind::OBJECT,
+                    vec![field("message", "String!", vec![])],
+                ),
+                role,
+                result,
+                object("ID", GraphqlTypeKind::SCALAR, vec![]),
+                object("Int", GraphqlTypeKind::SCALAR, vec![]),
+                object("String", GraphqlTypeKind::SCALAR, vec![]),
+            ],
+            ..Default::default()
+        }
+    }
+
+    // the labels offered at the |, and where the completed word begins
+    fn complete(query: &str) -> (usize, Vec<String>) {
+        let cursor = query.find('|').unwrap();
+        let query = query.replace('|', "");
+
+        let completions = complete_graphql(&query, cursor, &schema());
+        let labels = completions
+            .items
+            .into_iter()
+            .map(|item| item.label)
+            .collect();
+
+        (completions.start, labels)
+    }
+
+    fn labels(query: &str) -> Vec<String> {
+        complete(query).1
+    }
+
+    fn check(query: &str, variables: &str) -> Vec<String> {
+        let body = GraphqlBody {
+            query: query.to_string(),
+            variables: variables.to_string(),
+            operation_name: String::new(),
+        };
+
+        check_graphql(&body, Some(&schema()))
+            .iter()
+            .map(|diagnostic| diagnostic.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn keywords_outside_of_operations() {
+        assert_eq!(
+            labels("|"),
+            vec!["query", "mutation", "subscription", "fragment"]
+        );
+        assert_eq!(labels("{ id }\nfr|"), vec!["fragment"]);
+    }
+
+    #[test]
+    fn fields_of_the_selected_type() {
+        assert_eq!(labels("{ |"), vec!["users", "user", "search", "__typename"]);
+        assert_eq!(
+            labels("{ users(role: ADMIN) { | } }"),
+            vec!["id", "name", "role", "friends", "__typename"]
+        );
+        assert_eq!(labels("mutation { re|"), vec!["rename"]);
+
+        // the word at the cursor is replaced from its start, case does not matter
+        assert_eq!(
+            complete("{ user(id: 1) { NA|"),
+            (16, vec!["name".to_string()])
+        );
+
+        // aliases and nested selections
+        assert_eq!(
+            labels("{ people: users { friends { na| } } }"),
+            vec!["name"]
+        );
+
+        let completions = complete_graphql("{ user(id: 1) { fr", 18, &schema());
+        assert_eq!(
+            completions.items,
+            vec![GraphqlCompletion {
+                label: "friends".to_string(),
+                detail: "(first: Int): [User!]!".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn unions_offer_inline_fragments() {
+        assert_eq!(
+            labels("{ search(term: \"x\") { | } }"),
+            vec!["__typename", "... on User", "... on Error"]
+        );
+        assert_eq!(
+            labels("{ search(term: \"x\") { ... on Error { m| } } }"),
+            vec!["message"]
+        );
+    }
+
+    #[test]
+    fn arguments_and_enum_values() {
+        assert_eq!(labels("{ users(|"), vec!["first", "role"]);
+        assert_eq!(labels("{ users(first: 2, r|"), vec!["role"]);
+        assert_eq!(labels("{ users(role: |"), vec!["ADMIN", "GUEST"]);
+        assert_eq!(labels("{ users(role: g|"), vec!["GUEST"]);
+
+        // a finished value goes back to the argument names
+        assert_eq!(labels("{ users(role: GUEST |"), vec!["first", "role"]);
+        assert_eq!(labels("{ users(first: [1, 2] |"), vec!["first", "role"]);
+
+        // directive arguments are not the field's
+        assert!(labels("{ users @include(|").is_empty());
+    }
+
+    #[test]
+    fn fragments_select_from_their_type() {
+        assert_eq!(labels("fragment Person on User { f| }"), vec!["friends"]);
+        assert_eq!(
+            labels("fragment Person on User { id }\n{ user(id: 1) { ...Person n|"),
+            vec!["name"]
+        );
+    }
+
+    #[test]
+    fn variables_and_comments_are_not_completed() {
+        assert!(labels("query ($id: ID!) { user(id: $|").is_empty());
+        assert!(labels("query ($id: ID!) { user(id: $i|").is_empty());
+        assert!(labels("{ users(role: ADMIN) { ...P|").is_empty());
+        assert!(labels("{ users # u|").is_empty());
+        assert!(labels("{ users(first: 1|").is_empty());
+        assert!(labels("{ \"unclosed |").is_empty());
+
+        // the cursor has to be in the query
+        assert!(complete_graphql("{ ", 9, &schema()).items.is_empty());
+    }
+
+    #[test]
+    fn unknown_fields_and_arguments() {
+        assert_eq!(
+            check("{\n  users(role: ADMIN) {\n    nme\n  }\n}", ""),
+            vec!["3:5 User has no field nme"]
+        );
+        assert_eq!(
+            check("{ user(id: 1, bogus: 2) { id } }", ""),
+            vec!["1:15 Query.user has no argument bogus"]
+        );
+        assert_eq!(
+            check("{ users { id } }", ""),
+            vec!["1:3 Query.users needs the argument role: Role!"]
+        );
+        assert_eq!(
+            check("mutation { rename(id: 1) { id } }", ""),
+            vec!["1:12 Mutation.rename needs the argument name: String!"]
+        );
+    }
+
+    #[test]
+    fn leaves_and_objects() {
+        assert_eq!(
+            check("{ user(id: 1) { id { x } } }", ""),
+            vec!["1:17 id is a ID!, it has no fields to select"]
+        );
+        assert_eq!(
+            check("{ user(id: 1) }", ""),
+            vec!["1:3 user is a User, select some of its fields"]
+        );
+        assert_eq!(
+            check("{ __typename { x } }", ""),
+            vec!["1:3 __typename is a String!, it has no fields to select"]
+        );
+
+        // introspection is left to the server
+        assert!(check("{ __schema { types { name } } __typename }", "").is_empty());
+    }
+
+    #[test]
+    fn fragments_are_checked() {
+        assert!(check(
+            "query { user(id: 1) { ...Person } }\nfragment Person on User { name }",
+            ""
+        )
+        .is_empty());
+
+        assert_eq!(
+            check("{ user(id: 1) { ...Missing } }", ""),
+            vec!["1:20 fragment Missing is not defined"]
+        );
+        assert_eq!(
+            check(
+                "{ user(id: 1) { id } }\nfragment A on User { id }\nfragment A on Nope { id }",
+                ""
+            ),
+            vec![
+                "3:10 there is more than one fragment named A",
+                "3:15 unknown type Nope",
+            ]
+        );
+        assert_eq!(
+            check(
+                "{ search(term: \"x\") { ... on Error { name } ... on Missing { id } } }",
+                ""
+            ),
+            vec!["1:38 Error has no field name", "1:52 unknown type Missing",]
+        );
+    }
+
+    #[test]
+    fn variables_are_checked() {
+        let query = "query Find($id: ID!, $first: Int! = 2) {\n  user(id: $id) { id }\n}";
+
+        assert_eq!(
+            check(query, ""),
+            vec!["1:13 $id is required, add it to the variables"]
+        );
+        assert_eq!(
+            check(query, r#"{ "id": null }"#),
+            vec!["1:13 $id is required, add it to the variables"]
+        );
+        assert!(check(query, r#"{ "id": "1" }"#).is_empty());
+
+        assert_eq!(
+            check("{ user(id: $id) { id } }", ""),
+            vec!["1:13 variable $id is not defined"]
+        );
+        assert_eq!(
+            check("query ($id: Id) { user(id: $id) { id } }", ""),
+            vec!["1:13 unknown type Id"]
+        );
+        assert_eq!(
+            check("{ user(id: 1) { id } }", "[1]"),
+            vec!["the variables must be a JSON object"]
+        );
+        assert!(
+            check("{ user(id: 1) { id } }", "{")[0].starts_with("the variables are not valid JSON")
+        );
+    }
+
+    #[test]
+    fn operations_are_checked() {
+        assert_eq!(
+            check(
+                "query A { user(id: 1) { id } }\nquery A { user(id: 2) { id } }",
+                ""
+            ),
+            vec![
+                "the query has several operations, set the operation name",
+                "2:7 there is more than one operation named A",
+            ]
+        );
+        assert_eq!(
+            check("{ user(id: 1) { id } }\n{ user(id: 2) { id } }", "")[1..],
+            [
+                "1:1 an operation without a name must be the only one in the query",
+                "2:1 an operation without a name must be the only one in the query",
+            ]
+        );
+        assert_eq!(
+            check("subscription { users }", ""),
+            vec!["1:1 the schema has no subscription type"]
+        );
+
+        let body = GraphqlBody {
+            query: "query A { user(id: 1) { id } }".to_string(),
+            variables: String::new(),
+            operation_name: "B".to_string(),
+        };
+        assert_eq!(
+            check_graphql(&body, None),
+            vec![note("the query has no operation named B")]
+        );
+    }
+
+    #[test]
+    fn syntax_errors_point_at_the_token() {
+        assert_eq!(
+            check("{ user(id: \"caf\u{e9}\") { id } ", ""),
+            vec!["1:1 the selection set is not closed"]
+        );
+        assert_eq!(
+            check("{ user(id: \"caf\u{e9}) { id } }", ""),
+            vec!["1:12 the string is not closed"]
+        );
+        assert_eq!(
+            check("{ user(id: \"caf\u{e9}\") { } }", ""),
+            vec!["1:22 the selection set is empty"]
+        );
+        assert_eq!(
+            check("{ user(id: )", ""),
+            vec!["1:12 expected a value, found )"]
+        );
+        assert_eq!(
+            check("{ user(id: 1) { id } } %", ""),
+            vec!["1:24 unexpected character %"]
+        );
+        assert_eq!(
+            check("type User { id }", ""),
+            vec!["1:1 expected query, mutation, subscription or fragment, found type"]
+        );
+        assert_eq!(check("query Q(", ""), vec!["1:8 the query ends after ("]);
+
+        // lines after block strings and {{variables}} still count
+        assert_eq!(
+            check(
+                "{ search(term: \"\"\"\n  two\n  lines\"\"\") { ... on User { id, nme } }\n  user(id: {{id}}) { id } }",
+                ""
+            ),
+            vec!["3:33 User has no field nme"]
+        );
+        assert!(check("\u{feff}# comment\n{ user(id: 1) { id } }", "").is_empty());
+    }
+}
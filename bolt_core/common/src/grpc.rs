@@ -0,0 +1,321 @@
+use serde::{Deserialize, Serialize};
+use crate::prelude::MsgType;
+use crate::ws::WsMessage;
+use std::fmt;
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GrpcSource {
+    // services are read from local .proto files
+    #[default]
+    PROTO_FILES,
+    // services are asked from the server's reflection service
+    REFLECTION,
+}
+
+impl GrpcSource {
+    pub fn count() -> usize {
+        2
+    }
+}
+
+impl From<usize> for GrpcSource {
+    fn from(index: usize) -> Self {
+        match index {
+            0 => GrpcSource::PROTO_FILES,
+            1 => GrpcSource::REFLECTION,
+            _ => panic!("Invalid index for GrpcSource"),
+        }
+    }
+}
+
+impl fmt::Display for GrpcSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GrpcSource::PROTO_FILES => write!(f, "Proto files"),
+            GrpcSource::REFLECTION => write!(f, "Server reflection"),
+        }
+    }
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GrpcMethodKind {
+    #[default]
+    UNARY,
+    SERVER_STREAMING,
+    CLIENT_STREAMING,
+    BIDI_STREAMING,
+}
+
+impl GrpcMethodKind {
+    pub fn new(client_streaming: bool, server_streaming: bool) -> Self {
+        match (client_streaming, server_streaming) {
+            (false, false) => GrpcMethodKind::UNARY,
+            (false, true) => GrpcMethodKind::SERVER_STREAMING,
+            (true, false) => GrpcMethodKind::CLIENT_STREAMING,
+            (true, true) => GrpcMethodKind::BIDI_STREAMING,
+        }
+    }
+
+    // these calls send the queued messages, the others send the editor's message with the call
+    pub fn is_client_streaming(&self) -> bool {
+        matches!(
+            self,
+            GrpcMethodKind::CLIENT_STREAMING | GrpcMethodKind::BIDI_STREAMING
+        )
+    }
+}
+
+impl fmt::Display for GrpcMethodKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GrpcMethodKind::UNARY => write!(f, "unary"),
+            GrpcMethodKind::SERVER_STREAMING => write!(f, "server streaming"),
+            GrpcMethodKind::CLIENT_STREAMING => write!(f, "client streaming"),
+            GrpcMethodKind::BIDI_STREAMING => write!(f, "bidi streaming"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct GrpcMethod {
+    // fully qualified, e.g. "helloworld.Greeter"
+    pub service: String,
+    pub name: String,
+    pub kind: GrpcMethodKind,
+    pub input_type: String,
+    pub output_type: String,
+    // a JSON request with every field of the input type set to its default
+    pub template: String,
+}
+
+impl GrpcMethod {
+    pub fn path(&self) -> String {
+        format!("/{}/{}", self.service, self.name)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct GrpcStatus {
+    pub code: u32,
+    pub message: String,
+}
+
+impl GrpcStatus {
+    pub fn new(code: u32, message: &str) -> Self {
+        Self {
+            code,
+            message: message.to_string(),
+        }
+    }
+
+    pub fn code_name(&self) -> &'static str {
+        match self.code {
+            0 => "OK",
+            1 => "CANCELLED",
+            2 => "UNKNOWN",
+            3 => "INVALID_ARGUMENT",
+            4 => "DEADLINE_EXCEEDED",
+            5 => "NOT_FOUND",
+            6 => "ALREADY_EXISTS",
+            7 => "PERMISSION_DENIED",
+            8 => "RESOURCE_EXHAUSTED",
+            9 => "FAILED_PRECONDITION",
+            10 => "ABORTED",
+            11 => "OUT_OF_RANGE",
+            12 => "UNIMPLEMENTED",
+            13 => "INTERNAL",
+            14 => "UNAVAILABLE",
+            15 => "DATA_LOSS",
+            16 => "UNAUTHENTICATED",
+            _ => "UNKNOWN",
+        }
+    }
+}
+
+// where the services come from, the core loads them again when it has no copy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrpcServicesSource {
+    pub url: String,
+    pub source: GrpcSource,
+    pub proto_files: String,
+    pub import_paths: String,
+    pub verify_tls: bool,
+    pub headers: Vec<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrpcConnection {
+    pub connection_id: String,
+    pub url: String,
+    pub name: String,
+
+    pub out_tab: u8,
+    pub in_tab: u8,
+
+    pub source: GrpcSource,
+    // one path per line
+    pub proto_files: String,
+    pub import_paths: String,
+    pub verify_tls: bool,
+
+    pub methods: Vec<GrpcMethod>,
+    pub method: usize,
+    pub loading_methods: bool,
+    pub methods_error: String,
+
+    // a call is being started, is open or is being cancelled
+    pub connecting: bool,
+    pub disconnecting: bool,
+    pub failed: bool,
+    pub failed_reason: String,
+    pub connected: bool,
+    // the client asked to close its side of a streaming call
+    pub closing: bool,
+
+    pub out_buffer: String,
+    pub out_queue: Vec<WsMessage>,
+    pub out_headers: Vec<Vec<String>>,
+
+    pub msg_history: Vec<WsMessage>,
+
+    pub status: Option<GrpcStatus>,
+    pub response_headers: Vec<Vec<String>>,
+    pub trailers: Vec<Vec<String>>,
+}
+
+impl GrpcConnection {
+    pub fn new() -> Self {
+        let con_id = uuid::Uuid::new_v4()
+            .to_string()
+            .split('-')
+            .next()
+            .unwrap()
+            .to_string();
+
+        Self {
+            connection_id: con_id,
+            url: String::new(),
+            name: "gRPC connection ".to_string(),
+
+            out_tab: 1,
+            in_tab: 1,
+
+            source: GrpcSource::PROTO_FILES,
+            proto_files: String::new(),
+            import_paths: String::new(),
+            verify_tls: true,
+
+            methods: vec![],
+            method: 0,
+            loading_methods: false,
+            methods_error: String::new(),
+
+            connecting: false,
+            disconnecting: false,
+            failed: false,
+            failed_reason: String::new(),
+            connected: false,
+            closing: false,
+
+            out_buffer: String::new(),
+            out_queue: vec![],
+            out_headers: vec![vec![String::new(), String::new()]],
+
+            msg_history: vec![],
+
+            status: None,
+            response_headers: vec![],
+            trailers: vec![],
+        }
+    }
+
+    pub fn current_method(&self) -> Option<&GrpcMethod> {
+        self.methods.get(self.method)
+    }
+
+    pub fn services_source(&self) -> GrpcServicesSource {
+        GrpcServicesSource {
+            url: self.url.clone(),
+            source: self.source,
+            proto_files: self.proto_files.clone(),
+            import_paths: self.import_paths.clone(),
+            verify_tls: self.verify_tls,
+            headers: self.out_headers.clone(),
+        }
+    }
+}
+
+impl Default for GrpcConnection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AddGrpcConnectionMsg {
+    pub msg_type: MsgType,
+    pub connection_id: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LoadGrpcMethodsMsg {
+    pub msg_type: MsgType,
+    pub connection_id: String,
+    pub source: GrpcServicesSource,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GrpcMethodsMsg {
+    pub msg_type: MsgType,
+    pub connection_id: String,
+    pub methods: Vec<GrpcMethod>,
+    pub error: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GrpcCallStartedMsg {
+    pub msg_type: MsgType,
+    pub connection_id: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GrpcSentMsg {
+    pub msg_type: MsgType,
+    pub connection_id: String,
+    pub msg: WsMessage,
+}
+
+// a queued message that does not fit the input type, the call stays open
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GrpcMsgRejectedMsg {
+    pub msg_type: MsgType,
+    pub connection_id: String,
+    pub msg_id: String,
+    pub reason: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GrpcReceivedMsg {
+    pub msg_type: MsgType,
+    pub connection_id: String,
+    pub msg: WsMessage,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GrpcCallFinishedMsg {
+    pub msg_type: MsgType,
+    pub connection_id: String,
+    pub status: GrpcStatus,
+    pub headers: Vec<Vec<String>>,
+    pub trailers: Vec<Vec<String>>,
+}
+
+// the call never reached the server, there is no status to show
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GrpcCallFailedMsg {
+    pub msg_type: MsgType,
+    pub connection_id: String,
+    pub reason: String,
+}
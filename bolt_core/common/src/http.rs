@@ -1,6 +1,12 @@
+use crate::assertion::{Assertion, AssertionResult};
+use crate::auth::HttpAuth;
+use crate::extraction::ExtractionRule;
+use crate::graphql::GraphqlBody;
 use crate::prelude::MsgType;
+use crate::settings::HttpSettings;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::sync::Arc;
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct HttpRequest {
@@ -9,6 +15,29 @@ pub struct HttpRequest {
     pub headers: Vec<Vec<String>>,
     pub params: Vec<Vec<String>>,
     pub method: HttpMethod,
+    #[serde(default)]
+    pub body_type: HttpBodyType,
+    // x-www-form-urlencoded key/value rows
+    #[serde(default = "empty_rows")]
+    pub form: Vec<Vec<String>>,
+    #[serde(default = "empty_multipart")]
+    pub multipart: Vec<MultipartField>,
+    // path of the file sent as a binary body
+    #[serde(default)]
+    pub body_file: String,
+    #[serde(default)]
+    pub graphql: GraphqlBody,
+    #[serde(default)]
+    pub auth: HttpAuth,
+    // None uses the workspace defaults
+    #[serde(default)]
+    pub settings: Option<HttpSettings>,
+    // checked by bolt_http against every response
+    #[serde(default)]
+    pub assertions: Vec<Assertion>,
+    // values copied into the active environment after each response
+    #[serde(default)]
+    pub extractions: Vec<ExtractionRule>,
 
     pub response: HttpResponse,
 
@@ -19,6 +48,16 @@ pub struct HttpRequest {
     pub resp_tab: u8,
 
     pub loading: bool,
+    // id of the send in flight, responses with another id are stale
+    #[serde(default)]
+    pub request_id: String,
+    // the API operation an imported request was made from, e.g. "GET /users/{id}",
+    // re-importing the API updates the request with the same source
+    #[serde(default)]
+    pub source: String,
+    // byte offset of the cursor in the GraphQL query, completions are for the word there
+    #[serde(default)]
+    pub graphql_cursor: usize,
 }
 
 impl HttpRequest {
@@ -29,6 +68,15 @@ impl HttpRequest {
             headers: vec![vec![String::new(), String::new()]],
             params: vec![vec![String::new(), String::new()]],
             method: HttpMethod::GET,
+            body_type: HttpBodyType::RAW,
+            form: empty_rows(),
+            multipart: empty_multipart(),
+            body_file: String::new(),
+            graphql: GraphqlBody::default(),
+            auth: HttpAuth::new(),
+            settings: None,
+            assertions: vec![],
+            extractions: vec![],
 
             response: HttpResponse::new(),
 
@@ -38,14 +86,150 @@ impl HttpRequest {
             resp_tab: 1,
 
             loading: false,
+            request_id: String::new(),
+            source: String::new(),
+            graphql_cursor: 0,
         }
     }
 }
 
+impl HttpRequest {
+    // what bolt_http gets for this request, the params are appended to the url
+    pub fn to_send_request(&self, request_index: usize) -> SendHttpRequest {
+        SendHttpRequest {
+            url: parse_url(self.url.clone(), self.params.clone()),
+            method: self.method,
+            body: self.body.clone(),
+            headers: self.headers.clone(),
+            body_type: self.body_type,
+            form: self.form.clone(),
+            multipart: self.multipart.clone(),
+            body_file: self.body_file.clone(),
+            graphql: self.graphql.clone(),
+            auth: self.auth.clone(),
+            settings: self.settings.clone(),
+            assertions: self.assertions.clone(),
+            extractions: self.extractions.clone(),
+            request_index,
+        }
+    }
+}
+
+pub fn parse_url(url: String, params: Vec<Vec<String>>) -> String {
+    let mut new_url = url;
+
+    if !params.is_empty() && !params[0][0].is_empty() {
+        new_url.push('?');
+    }
+
+    for (i, param) in params.iter().enumerate() {
+        if param[0].is_empty() || param[1].is_empty() {
+            continue;
+        }
+
+        new_url.push_str(&param[0]);
+        new_url.push('=');
+        new_url.push_str(&param[1]);
+
+        if i != params.len() - 1 {
+            new_url.push('&');
+        }
+    }
+
+    new_url
+}
+
+pub fn new_request_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+fn empty_rows() -> Vec<Vec<String>> {
+    vec![vec![String::new(), String::new()]]
+}
+
+fn empty_multipart() -> Vec<MultipartField> {
+    vec![MultipartField::new()]
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HttpBodyType {
+    #[default]
+    RAW,
+    JSON,
+    FORM_URLENCODED,
+    MULTIPART,
+    BINARY,
+    GRAPHQL,
+}
+
+impl HttpBodyType {
+    pub fn count() -> usize {
+        6
+    }
+
+    // the Content-Type bolt_http sets when the user did not add one
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            HttpBodyType::RAW => "text/plain",
+            HttpBodyType::JSON => "application/json",
+            HttpBodyType::FORM_URLENCODED => "application/x-www-form-urlencoded",
+            HttpBodyType::MULTIPART => "multipart/form-data",
+            HttpBodyType::BINARY => "application/octet-stream",
+            HttpBodyType::GRAPHQL => "application/json",
+        }
+    }
+}
+
+impl From<usize> for HttpBodyType {
+    fn from(index: usize) -> Self {
+        match index {
+            0 => HttpBodyType::RAW,
+            1 => HttpBodyType::JSON,
+            2 => HttpBodyType::FORM_URLENCODED,
+            3 => HttpBodyType::MULTIPART,
+            4 => HttpBodyType::BINARY,
+            5 => HttpBodyType::GRAPHQL,
+            _ => panic!("Invalid index for HttpBodyType"),
+        }
+    }
+}
+
+impl fmt::Display for HttpBodyType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HttpBodyType::RAW => write!(f, "Raw"),
+            HttpBodyType::JSON => write!(f, "JSON"),
+            HttpBodyType::FORM_URLENCODED => write!(f, "Form URL Encoded"),
+            HttpBodyType::MULTIPART => write!(f, "Multipart Form"),
+            HttpBodyType::BINARY => write!(f, "Binary File"),
+            HttpBodyType::GRAPHQL => write!(f, "GraphQL"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct MultipartField {
+    pub name: String,
+    // the text value, or the file path when is_file is set
+    pub value: String,
+    pub is_file: bool,
+}
+
+impl MultipartField {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 pub enum HttpResponseType {
     TEXT,
     JSON,
+    HTML,
+    IMAGE,
+    PDF,
+    BINARY,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -59,10 +243,49 @@ pub struct HttpResponse {
     pub response_type: HttpResponseType,
     pub request_index: usize,
     pub failed: bool,
+    #[serde(default)]
+    pub console: Vec<String>,
+    // the body as received, base64 encoded. Only sent for bodies the text can not hold,
+    // a text body is kept once as `body`
+    #[serde(default)]
+    pub body_base64: String,
+    // the received bytes, decoded once by the client for the hex view and saves
+    #[serde(skip)]
+    pub raw_body: Option<Arc<Vec<u8>>>,
+    #[serde(default)]
+    pub content_type: String,
+    // where the body was last saved by bolt_core
+    #[serde(default)]
+    pub saved_to: String,
+    #[serde(default)]
+    pub request_id: String,
+    #[serde(default)]
+    pub cancelled: bool,
+    #[serde(default)]
+    pub tests: Vec<AssertionResult>,
+    // [variable, value] pairs written to the active environment
+    #[serde(default)]
+    pub extracted: Vec<Vec<String>>,
+    #[serde(default)]
+    pub timing: HttpTiming,
+    #[serde(default)]
+    pub connection: HttpConnectionInfo,
+    // unix time in ms when the request was sent, 0 when it never was
+    #[serde(default)]
+    pub started: u64,
+    // the "errors" of a GraphQL response
+    #[serde(default)]
+    pub graphql_errors: Vec<String>,
+}
+
+impl Default for HttpResponse {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl HttpResponse {
-    fn new() -> Self {
+    pub fn new() -> Self {
         HttpResponse {
             status: 0,
             body: String::new(),
@@ -73,11 +296,53 @@ impl HttpResponse {
             response_type: HttpResponseType::TEXT,
             request_index: 0,
             failed: false,
+            console: Vec::new(),
+            body_base64: String::new(),
+            raw_body: None,
+            content_type: String::new(),
+            saved_to: String::new(),
+            request_id: String::new(),
+            cancelled: false,
+            tests: Vec::new(),
+            extracted: Vec::new(),
+            timing: HttpTiming::default(),
+            connection: HttpConnectionInfo::default(),
+            started: 0,
+            graphql_errors: Vec::new(),
         }
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+// time spent in each phase of a request, in microseconds. Sent requests do not measure
+// connect and the TLS handshake, those only come from imported HARs
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct HttpTiming {
+    pub dns: u64,
+    // None when not measured
+    pub connect: Option<u64>,
+    pub tls: Option<u64>,
+    // until the response headers arrive, including the connection when it is not split out
+    pub ttfb: u64,
+    pub download: u64,
+    pub total: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct HttpConnectionInfo {
+    // e.g. HTTP/1.1 or HTTP/2
+    pub http_version: String,
+    pub remote_addr: String,
+
+    // leaf certificate of the server, empty for plain http
+    pub tls_subject: String,
+    pub tls_issuer: String,
+    pub tls_valid_from: String,
+    pub tls_valid_until: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HttpMethod {
     GET,
     POST,
@@ -165,7 +430,35 @@ pub struct SendHttpMsg {
     pub method: HttpMethod,
     pub body: String,
     pub headers: Vec<Vec<String>>,
+    #[serde(default)]
+    pub body_type: HttpBodyType,
+    #[serde(default)]
+    pub form: Vec<Vec<String>>,
+    #[serde(default)]
+    pub multipart: Vec<MultipartField>,
+    #[serde(default)]
+    pub body_file: String,
+    #[serde(default)]
+    pub graphql: GraphqlBody,
+    #[serde(default)]
+    pub auth: HttpAuth,
+    #[serde(default)]
+    pub settings: Option<HttpSettings>,
+    #[serde(default)]
+    pub assertions: Vec<Assertion>,
+    #[serde(default)]
+    pub extractions: Vec<ExtractionRule>,
     pub index: usize,
+    #[serde(default)]
+    pub request_id: String,
+}
+
+// aborts the send or collection run with the same id, answered with a cancelled
+// HTTP_RESPONSE or COLLECTION_FINISHED
+#[derive(Serialize, Deserialize)]
+pub struct CancelHttpMsg {
+    pub msg_type: MsgType,
+    pub request_id: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -174,6 +467,16 @@ pub struct SendHttpRequest {
     pub method: HttpMethod,
     pub body: String,
     pub headers: Vec<Vec<String>>,
+    pub body_type: HttpBodyType,
+    pub form: Vec<Vec<String>>,
+    pub multipart: Vec<MultipartField>,
+    pub body_file: String,
+    #[serde(default)]
+    pub graphql: GraphqlBody,
+    pub auth: HttpAuth,
+    pub settings: Option<HttpSettings>,
+    pub assertions: Vec<Assertion>,
+    pub extractions: Vec<ExtractionRule>,
     pub request_index: usize,
 }
 
@@ -181,6 +484,41 @@ pub struct SendHttpRequest {
 pub enum SendHttpResponseType {
     TEXT,
     JSON,
+    HTML,
+    IMAGE,
+    PDF,
+    BINARY,
+}
+
+impl SendHttpResponseType {
+    pub fn from_content_type(content_type: &str, valid_utf8: bool) -> Self {
+        let content_type = content_type.to_lowercase();
+
+        if content_type.contains("json") {
+            SendHttpResponseType::JSON
+        } else if content_type.contains("text/html") {
+            SendHttpResponseType::HTML
+        } else if content_type.starts_with("image/") {
+            SendHttpResponseType::IMAGE
+        } else if content_type.contains("application/pdf") {
+            SendHttpResponseType::PDF
+        } else if content_type.starts_with("text/")
+            || content_type.contains("xml")
+            || content_type.contains("javascript")
+            || valid_utf8
+        {
+            SendHttpResponseType::TEXT
+        } else {
+            SendHttpResponseType::BINARY
+        }
+    }
+
+    pub fn is_text(&self) -> bool {
+        matches!(
+            self,
+            SendHttpResponseType::TEXT | SendHttpResponseType::JSON | SendHttpResponseType::HTML
+        )
+    }
 }
 
 #[derive(Clone, Serialize)]
@@ -195,6 +533,23 @@ pub struct SendHttpResponse {
     pub response_type: SendHttpResponseType,
     pub request_index: usize,
     pub failed: bool,
+    pub console: Vec<String>,
+    pub body_base64: String,
+    pub content_type: String,
+    pub timing: HttpTiming,
+    pub connection: HttpConnectionInfo,
+    pub request_id: String,
+    pub cancelled: bool,
+    pub tests: Vec<AssertionResult>,
+    pub extracted: Vec<Vec<String>>,
+    pub started: u64,
+    pub graphql_errors: Vec<String>,
+}
+
+impl Default for SendHttpResponse {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SendHttpResponse {
@@ -210,6 +565,33 @@ impl SendHttpResponse {
             response_type: SendHttpResponseType::TEXT,
             request_index: 0,
             failed: false,
+            console: Vec::new(),
+            body_base64: String::new(),
+            content_type: String::new(),
+            timing: HttpTiming::default(),
+            connection: HttpConnectionInfo::default(),
+            request_id: String::new(),
+            cancelled: false,
+            tests: Vec::new(),
+            extracted: Vec::new(),
+            started: 0,
+            graphql_errors: Vec::new(),
         }
     }
 }
+
+#[derive(Serialize, Deserialize)]
+pub struct SaveResponseMsg {
+    pub msg_type: MsgType,
+    pub file_name: String,
+    pub body_base64: String,
+    // used when the response has no raw body, e.g. one restored from an older save
+    pub body: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ResponseSavedMsg {
+    pub msg_type: MsgType,
+    pub path: String,
+    pub error: String,
+}
@@ -1,13 +1,41 @@
+pub mod assertion;
+pub mod auth;
 pub mod collection;
+pub mod curl;
+pub mod cookie;
+pub mod environment;
+pub mod extraction;
+pub mod graphql;
+pub mod grpc;
+pub mod graphql_editor;
 pub mod http;
+pub mod runner;
+pub mod settings;
+pub mod snippet;
+pub mod sse;
 pub mod tcp;
+pub mod transfer;
 pub mod udp;
 pub mod ws;
 
 pub mod prelude {
+    pub use crate::assertion::*;
+    pub use crate::auth::*;
     pub use crate::collection::*;
+    pub use crate::curl::*;
+    pub use crate::cookie::*;
+    pub use crate::environment::*;
+    pub use crate::extraction::*;
+    pub use crate::graphql::*;
+    pub use crate::grpc::*;
+    pub use crate::graphql_editor::*;
     pub use crate::http::*;
+    pub use crate::runner::*;
+    pub use crate::settings::*;
+    pub use crate::snippet::*;
+    pub use crate::sse::*;
     pub use crate::tcp::*;
+    pub use crate::transfer::*;
     pub use crate::udp::*;
     pub use crate::ws::*;
 
@@ -22,7 +50,11 @@ pub mod prelude {
         Websockets,
         Tcp,
         Udp,
+        Sse,
+        Grpc,
         Servers,
+        Environments,
+        Cookies,
     }
 
     #[derive(Clone, Serialize, Deserialize)]
@@ -34,12 +66,53 @@ pub mod prelude {
         pub udp_current: usize,
         pub tcp_current: usize,
         pub col_current: Vec<usize>,
+        #[serde(default)]
+        pub env_current: usize,
+        #[serde(default)]
+        pub env_active: Option<usize>,
+        #[serde(default)]
+        pub cookie_domain: String,
 
         pub http_requests: Vec<HttpRequest>,
         pub ws_connections: Vec<WsConnection>,
         pub tcp_connections: Vec<TcpConnection>,
         pub udp_connections: Vec<UdpConnection>,
         pub collections: Vec<Collection>,
+        #[serde(default)]
+        pub environments: Vec<Environment>,
+        #[serde(default)]
+        pub cookies: Vec<Cookie>,
+        #[serde(default)]
+        pub http_settings: HttpSettings,
+        #[serde(default)]
+        pub runner_config: RunnerConfig,
+        #[serde(default)]
+        pub collection_run: CollectionRun,
+        #[serde(default)]
+        pub snippet_language: SnippetLanguage,
+        #[serde(default)]
+        pub transfer: TransferStatus,
+        #[serde(default)]
+        pub graphql_schemas: Vec<GraphqlSchema>,
+        #[serde(default)]
+        pub graphql_status: GraphqlSchemaStatus,
+        #[serde(default)]
+        pub sse_current: usize,
+        #[serde(default = "default_sse_connections")]
+        pub sse_connections: Vec<SseConnection>,
+        #[serde(default)]
+        pub grpc_current: usize,
+        #[serde(default = "default_grpc_connections")]
+        pub grpc_connections: Vec<GrpcConnection>,
+    }
+
+    // older saves have no SSE connections, the page expects at least one like the others
+    fn default_sse_connections() -> Vec<SseConnection> {
+        vec![SseConnection::new()]
+    }
+
+    fn default_grpc_connections() -> Vec<GrpcConnection> {
+        vec![GrpcConnection::new()]
     }
 
     impl MainState {
@@ -52,12 +125,28 @@ pub mod prelude {
                 tcp_current: 0,
                 udp_current: 0,
                 col_current: vec![0, 0],
+                env_current: 0,
+                env_active: None,
+                cookie_domain: String::new(),
 
                 http_requests: vec![HttpRequest::new()],
                 ws_connections: vec![WsConnection::new()],
                 tcp_connections: vec![TcpConnection::new()],
                 udp_connections: vec![UdpConnection::new()],
                 collections: vec![],
+                environments: vec![],
+                cookies: vec![],
+                http_settings: HttpSettings::new(),
+                runner_config: RunnerConfig::new(),
+                collection_run: CollectionRun::default(),
+                snippet_language: SnippetLanguage::default(),
+                transfer: TransferStatus::default(),
+                graphql_schemas: vec![],
+                graphql_status: GraphqlSchemaStatus::default(),
+                sse_current: 0,
+                sse_connections: vec![SseConnection::new()],
+                grpc_current: 0,
+                grpc_connections: vec![GrpcConnection::new()],
             }
         }
     }
@@ -76,8 +165,23 @@ pub mod prelude {
         OPEN_LINK,
         SAVE_STATE,
         SEND_HTTP,
+        CANCEL_HTTP,
         HTTP_RESPONSE,
         RESTORE_STATE,
+        COOKIES_CHANGED,
+        EDIT_COOKIES,
+        VARIABLES_CHANGED,
+        SAVE_RESPONSE,
+        RESPONSE_SAVED,
+        RUN_COLLECTION,
+        COLLECTION_PROGRESS,
+        COLLECTION_FINISHED,
+        IMPORT_COLLECTION,
+        COLLECTION_IMPORTED,
+        EXPORT_COLLECTION,
+        COLLECTION_EXPORTED,
+        FETCH_GRAPHQL_SCHEMA,
+        GRAPHQL_SCHEMA,
 
         ADD_WS_CONNECTION,
         WS_CONNECTED,
@@ -99,6 +203,23 @@ pub mod prelude {
         UDP_MSG_SENT,
         UDP_RECEIVED_MSG,
         UDP_CONNECTION_FAILED,
+
+        ADD_SSE_CONNECTION,
+        SSE_CONNECTED,
+        SSE_DISCONNECTED,
+        SSE_RECEIVED_MSG,
+        SSE_RECONNECTING,
+        SSE_CONNECTION_FAILED,
+
+        ADD_GRPC_CONNECTION,
+        LOAD_GRPC_METHODS,
+        GRPC_METHODS,
+        GRPC_CALL_STARTED,
+        GRPC_MSG_SENT,
+        GRPC_MSG_REJECTED,
+        GRPC_RECEIVED_MSG,
+        GRPC_CALL_FINISHED,
+        GRPC_CALL_FAILED,
     
         COPY_CLIPBOARD,
     }
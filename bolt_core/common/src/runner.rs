@@ -0,0 +1,176 @@
+use crate::assertion::AssertionResult;
+use crate::collection::Collection;
+use crate::http::{HttpMethod, HttpTiming};
+use crate::prelude::MsgType;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct RunnerConfig {
+    // pause between two requests, in ms
+    pub delay: u64,
+    pub iterations: u32,
+    pub stop_on_failure: bool,
+
+    // CSV with a header row or a JSON array of objects, one iteration per row.
+    // When set it decides the number of iterations.
+    pub data_file: String,
+}
+
+impl RunnerConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Default for RunnerConfig {
+    fn default() -> Self {
+        Self {
+            delay: 0,
+            iterations: 1,
+            stop_on_failure: false,
+            data_file: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct RunRequestResult {
+    pub iteration: u32,
+    pub request_index: usize,
+    pub name: String,
+    pub method: HttpMethod,
+    pub url: String,
+
+    pub status: u16,
+    // in ms
+    pub time: u32,
+    pub timing: HttpTiming,
+
+    // the request itself failed, error holds the reason
+    pub failed: bool,
+    pub error: String,
+
+    pub tests: Vec<AssertionResult>,
+    pub extracted: Vec<Vec<String>>,
+}
+
+impl RunRequestResult {
+    pub fn passed(&self) -> bool {
+        !self.failed && self.tests.iter().all(|test| test.passed)
+    }
+}
+
+impl Default for RunRequestResult {
+    fn default() -> Self {
+        Self {
+            iteration: 0,
+            request_index: 0,
+            name: String::new(),
+            method: HttpMethod::GET,
+            url: String::new(),
+            status: 0,
+            time: 0,
+            timing: HttpTiming::default(),
+            failed: false,
+            error: String::new(),
+            tests: vec![],
+            extracted: vec![],
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct RunSummary {
+    pub iterations: u32,
+
+    pub requests: u32,
+    pub failed_requests: u32,
+
+    pub assertions: u32,
+    pub failed_assertions: u32,
+
+    // sum of the response times and the slowest one, in ms
+    pub total_time: u64,
+    pub max_time: u32,
+
+    // stop on failure ended the run early
+    pub stopped: bool,
+}
+
+impl RunSummary {
+    pub fn from_results(results: &[RunRequestResult]) -> Self {
+        let mut summary = RunSummary::default();
+
+        for result in results {
+            summary.iterations = summary.iterations.max(result.iteration + 1);
+
+            summary.requests += 1;
+
+            if !result.passed() {
+                summary.failed_requests += 1;
+            }
+
+            summary.assertions += result.tests.len() as u32;
+            summary.failed_assertions += result.tests.iter().filter(|t| !t.passed).count() as u32;
+
+            summary.total_time += result.time as u64;
+            summary.max_time = summary.max_time.max(result.time);
+        }
+
+        summary
+    }
+
+    pub fn average_time(&self) -> u64 {
+        if self.requests == 0 {
+            0
+        } else {
+            self.total_time / self.requests as u64
+        }
+    }
+}
+
+// the progress of the last run, kept by the client
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct CollectionRun {
+    pub run_id: String,
+    pub collection_index: usize,
+    pub collection_name: String,
+
+    pub running: bool,
+    pub cancelled: bool,
+    pub error: String,
+
+    pub total: usize,
+    pub results: Vec<RunRequestResult>,
+    pub summary: RunSummary,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RunCollectionMsg {
+    pub msg_type: MsgType,
+    pub run_id: String,
+    pub collection: Collection,
+    pub config: RunnerConfig,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CollectionProgressMsg {
+    pub msg_type: MsgType,
+    pub run_id: String,
+    pub total: usize,
+    pub result: RunRequestResult,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CollectionFinishedMsg {
+    pub msg_type: MsgType,
+    pub run_id: String,
+    pub summary: RunSummary,
+    pub cancelled: bool,
+    // set when the run could not start, e.g. an unreadable data file
+    pub error: String,
+}
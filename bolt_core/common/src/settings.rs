@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+// how bolt_http configures the client for a request, requests without their own
+// settings use the workspace defaults in MainState
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct HttpSettings {
+    // in ms, 0 means no timeout
+    pub connect_timeout: u64,
+    pub timeout: u64,
+
+    pub follow_redirects: bool,
+    pub max_redirects: usize,
+
+    // http://, https://, socks5:// or socks5h:// url, empty for a direct connection
+    pub proxy: String,
+
+    pub verify_tls: bool,
+
+    // paths to PEM files, the client key must be PKCS#8
+    pub ca_cert: String,
+    pub client_cert: String,
+    pub client_key: String,
+}
+
+impl HttpSettings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Default for HttpSettings {
+    fn default() -> Self {
+        Self {
+            connect_timeout: 0,
+            timeout: 0,
+
+            follow_redirects: true,
+            max_redirects: 10,
+
+            proxy: String::new(),
+
+            verify_tls: true,
+
+            ca_cert: String::new(),
+            client_cert: String::new(),
+            client_key: String::new(),
+        }
+    }
+}
@@ -0,0 +1,1061 @@
+use crate::auth::{ApiKeyLocation, HttpAuthType};
+use crate::curl::{shell_quote, url_encode};
+use crate::http::{parse_url, HttpBodyType, HttpMethod, HttpRequest};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SnippetLanguage {
+    #[default]
+    REQWEST,
+    PYTHON,
+    FETCH,
+    GO,
+    HTTPIE,
+}
+
+impl SnippetLanguage {
+    pub fn count() -> usize {
+        5
+    }
+
+    // the name used on the command line
+    pub fn id(&self) -> &'static str {
+        match self {
+            SnippetLanguage::REQWEST => "reqwest",
+            SnippetLanguage::PYTHON => "python",
+            SnippetLanguage::FETCH => "fetch",
+            SnippetLanguage::GO => "go",
+            SnippetLanguage::HTTPIE => "httpie",
+        }
+    }
+}
+
+impl From<usize> for SnippetLanguage {
+    fn from(index: usize) -> Self {
+        match index {
+            0 => SnippetLanguage::REQWEST,
+            1 => SnippetLanguage::PYTHON,
+            2 => SnippetLanguage::FETCH,
+            3 => SnippetLanguage::GO,
+            4 => SnippetLanguage::HTTPIE,
+            _ => panic!("Invalid index for SnippetLanguage"),
+        }
+    }
+}
+
+impl fmt::Display for SnippetLanguage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnippetLanguage::REQWEST => write!(f, "Rust (reqwest)"),
+            SnippetLanguage::PYTHON => write!(f, "Python (requests)"),
+            SnippetLanguage::FETCH => write!(f, "JavaScript (fetch)"),
+            SnippetLanguage::GO => write!(f, "Go (net/http)"),
+            SnippetLanguage::HTTPIE => write!(f, "HTTPie"),
+        }
+    }
+}
+
+// client code that sends the request. {{variables}} are not resolved here, callers
+// resolve them first when they want the values in the code.
+pub fn generate_snippet(request: &HttpRequest, language: SnippetLanguage) -> String {
+    let parts = SnippetParts::new(request);
+
+    match language {
+        SnippetLanguage::REQWEST => reqwest_snippet(&parts),
+        SnippetLanguage::PYTHON => python_snippet(&parts),
+        SnippetLanguage::FETCH => fetch_snippet(&parts),
+        SnippetLanguage::GO => go_snippet(&parts),
+        SnippetLanguage::HTTPIE => httpie_snippet(&parts),
+    }
+}
+
+enum SnippetAuth {
+    None,
+    Basic(String, String),
+    Digest(String, String),
+}
+
+enum SnippetBody {
+    None,
+    Text(String),
+    Form(Vec<(String, String)>),
+    // name, value or path, is a file
+    Multipart(Vec<(String, String, bool)>),
+    File(String),
+}
+
+// the request the way bolt_http sends it, with the auth turned into headers where
+// every client does the same
+struct SnippetParts {
+    method: HttpMethod,
+    url: String,
+    headers: Vec<(String, String)>,
+    auth: SnippetAuth,
+    body: SnippetBody,
+}
+
+impl SnippetParts {
+    fn new(request: &HttpRequest) -> Self {
+        let mut url = parse_url(request.url.clone(), request.params.clone());
+
+        let mut headers: Vec<(String, String)> = request
+            .headers
+            .iter()
+            .filter(|header| !header[0].trim().is_empty())
+            .map(|header| (header[0].trim().to_string(), header[1].clone()))
+            .collect();
+
+        let auth = &request.auth;
+
+        let snippet_auth = match auth.auth_type {
+            HttpAuthType::BASIC => SnippetAuth::Basic(auth.username.clone(), auth.password.clone()),
+            HttpAuthType::DIGEST => {
+                SnippetAuth::Digest(auth.username.clone(), auth.password.clone())
+            }
+            HttpAuthType::BEARER => {
+                headers.push((
+                    "Authorization".to_string(),
+                    format!("Bearer {}", auth.token),
+                ));
+
+                SnippetAuth::None
+            }
+            HttpAuthType::API_KEY if !auth.key.is_empty() => {
+                match auth.location {
+                    ApiKeyLocation::HEADER => headers.push((auth.key.clone(), auth.value.clone())),
+                    ApiKeyLocation::QUERY => {
+                        url.push(if url.contains('?') { '&' } else { '?' });
+                        url.push_str(&format!(
+                            "{}={}",
+                            url_encode(&auth.key),
+                            url_encode(&auth.value)
+                        ));
+                    }
+                }
+
+                SnippetAuth::None
+            }
+            // the oauth2 token is fetched when bolt sends the request
+            _ => SnippetAuth::None,
+        };
+
+        let body = match request.body_type {
+            HttpBodyType::RAW | HttpBodyType::JSON if !request.body.is_empty() => {
+                SnippetBody::Text(request.body.clone())
+            }
+            HttpBodyType::FORM_URLENCODED => SnippetBody::Form(
+                request
+                    .form
+                    .iter()
+                    .filter(|row| !row[0].is_empty())
+                    .map(|row| (row[0].clone(), row[1].clone()))
+                    .collect(),
+            ),
+            HttpBodyType::MULTIPART => SnippetBody::Multipart(
+                request
+                    .multipart
+                    .iter()
+                    .filter(|field| !field.name.is_empty())
+                    .map(|field| (field.name.clone(), field.value.clone(), field.is_file))
+                    .collect(),
+            ),
+            HttpBodyType::BINARY if !request.body_file.is_empty() => {
+                SnippetBody::File(request.body_file.clone())
+            }
+            HttpBodyType::GRAPHQL => match request.graphql.payload() {
+                Ok(payload) => SnippetBody::Text(payload),
+                Err(_) => SnippetBody::None,
+            },
+            _ => SnippetBody::None,
+        };
+
+        // bolt_http sets the content type of text and file bodies when there is none
+        let has_content_type = headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case("content-type"));
+
+        if matches!(body, SnippetBody::Text(_) | SnippetBody::File(_)) && !has_content_type {
+            headers.push((
+                "Content-Type".to_string(),
+                request.body_type.content_type().to_string(),
+            ));
+        }
+
+        Self {
+            method: request.method,
+            url,
+            headers,
+            auth: snippet_auth,
+            body,
+        }
+    }
+}
+
+fn reqwest_snippet(parts: &SnippetParts) -> String {
+    let mut out = String::new();
+
+    out += "#[tokio::main]\n";
+    out += "async fn main() -> Result<(), Box<dyn std::error::Error>> {\n";
+    out += "    let client = reqwest::Client::new();\n\n";
+
+    if let SnippetBody::Multipart(fields) = &parts.body {
+        out += "    let form = reqwest::multipart::Form::new()";
+
+        for (name, value, is_file) in fields {
+            if *is_file {
+                out += &format!(
+                    "\n        .part(\n            {},\n            reqwest::multipart::Part::bytes(std::fs::read({})?).file_name({}),\n        )",
+                    rust_str(name),
+                    rust_str(value),
+                    rust_str(file_name(value))
+                );
+            } else {
+                out += &format!("\n        .text({}, {})", rust_str(name), rust_str(value));
+            }
+        }
+
+        out += ";\n\n";
+    }
+
+    if let SnippetAuth::Digest(_, _) = parts.auth {
+        out += "    // reqwest has no digest auth\n";
+    }
+
+    let method = match parts.method {
+        HttpMethod::GET => "get",
+        HttpMethod::POST => "post",
+        HttpMethod::PUT => "put",
+        HttpMethod::DELETE => "delete",
+        HttpMethod::HEAD => "head",
+        HttpMethod::PATCH => "patch",
+        _ => "",
+    };
+
+    out += "    let response = client\n";
+
+    if method.is_empty() {
+        out += &format!(
+            "        .request(reqwest::Method::{}, {})\n",
+            parts.method,
+            rust_str(&parts.url)
+        );
+    } else {
+        out += &format!("        .{}({})\n", method, rust_str(&parts.url));
+    }
+
+    for (name, value) in &parts.headers {
+        out += &format!("        .header({}, {})\n", rust_str(name), rust_str(value));
+    }
+
+    if let SnippetAuth::Basic(username, password) = &parts.auth {
+        out += &format!(
+            "        .basic_auth({}, Some({}))\n",
+            rust_str(username),
+            rust_str(password)
+        );
+    }
+
+    match &parts.body {
+        SnippetBody::Text(text) => out += &format!("        .body({})\n", rust_str(text)),
+        SnippetBody::Form(rows) => {
+            let rows: Vec<String> = rows
+                .iter()
+                .map(|(name, value)| format!("({}, {})", rust_str(name), rust_str(value)))
+                .collect();
+
+            out += &format!("        .form(&[{}])\n", rows.join(", "));
+        }
+        SnippetBody::Multipart(_) => out += "        .multipart(form)\n",
+        SnippetBody::File(path) => {
+            out += &format!("        .body(std::fs::read({})?)\n", rust_str(path));
+        }
+        SnippetBody::None => {}
+    }
+
+    out += "        .send()\n";
+    out += "        .await?;\n\n";
+    out += "    println!(\"{}\", response.status());\n";
+    out += "    println!(\"{}\", response.text().await?);\n\n";
+    out += "    Ok(())\n";
+    out += "}\n";
+
+    out
+}
+
+fn python_snippet(parts: &SnippetParts) -> String {
+    let mut out = String::from("import requests\n");
+
+    if let SnippetAuth::Digest(_, _) = parts.auth {
+        out += "from requests.auth import HTTPDigestAuth\n";
+    }
+
+    out += &format!("\nurl = {}\n", script_str(&parts.url));
+
+    let mut args = vec!["url".to_string()];
+
+    if !parts.headers.is_empty() {
+        out += "\nheaders = {\n";
+
+        for (name, value) in &parts.headers {
+            out += &format!("    {}: {},\n", script_str(name), script_str(value));
+        }
+
+        out += "}\n";
+
+        args.push("headers=headers".to_string());
+    }
+
+    match &parts.body {
+        SnippetBody::Text(text) => {
+            // requests encodes str bodies as latin-1
+            let encode = if text.is_ascii() { "" } else { ".encode()" };
+
+            out += &format!("\ndata = {}{encode}\n", script_str(text));
+
+            args.push("data=data".to_string());
+        }
+        SnippetBody::Form(rows) => {
+            out += "\ndata = {\n";
+
+            for (name, value) in rows {
+                out += &format!("    {}: {},\n", script_str(name), script_str(value));
+            }
+
+            out += "}\n";
+
+            args.push("data=data".to_string());
+        }
+        SnippetBody::Multipart(fields) => {
+            out += "\nfiles = {\n";
+
+            for (name, value, is_file) in fields {
+                if *is_file {
+                    out += &format!(
+                        "    {}: open({}, \"rb\"),\n",
+                        script_str(name),
+                        script_str(value)
+                    );
+                } else {
+                    out += &format!("    {}: (None, {}),\n", script_str(name), script_str(value));
+                }
+            }
+
+            out += "}\n";
+
+            args.push("files=files".to_string());
+        }
+        SnippetBody::File(path) => {
+            out += &format!("\ndata = open({}, \"rb\")\n", script_str(path));
+
+            args.push("data=data".to_string());
+        }
+        SnippetBody::None => {}
+    }
+
+    match &parts.auth {
+        SnippetAuth::Basic(username, password) => {
+            args.push(format!(
+                "auth=({}, {})",
+                script_str(username),
+                script_str(password)
+            ));
+        }
+        SnippetAuth::Digest(username, password) => {
+            args.push(format!(
+                "auth=HTTPDigestAuth({}, {})",
+                script_str(username),
+                script_str(password)
+            ));
+        }
+        SnippetAuth::None => {}
+    }
+
+    out += &format!(
+        "\nresponse = requests.request({}, {})\n\n",
+        script_str(&parts.method.to_string()),
+        args.join(", ")
+    );
+
+    out += "print(response.status_code)\n";
+    out += "print(response.text)\n";
+
+    out
+}
+
+fn fetch_snippet(parts: &SnippetParts) -> String {
+    let mut out = String::new();
+
+    let reads_files = match &parts.body {
+        SnippetBody::Multipart(fields) => fields.iter().any(|(_, _, is_file)| *is_file),
+        SnippetBody::File(_) => true,
+        _ => false,
+    };
+
+    if reads_files {
+        out += "import { readFile } from \"node:fs/promises\";\n\n";
+    }
+
+    if let SnippetBody::Multipart(fields) = &parts.body {
+        out += "const form = new FormData();\n";
+
+        for (name, value, is_file) in fields {
+            if *is_file {
+                out += &format!(
+                    "form.append({}, new Blob([await readFile({})]), {});\n",
+                    script_str(name),
+                    script_str(value),
+                    script_str(file_name(value))
+                );
+            } else {
+                out += &format!(
+                    "form.append({}, {});\n",
+                    script_str(name),
+                    script_str(value)
+                );
+            }
+        }
+
+        out.push('\n');
+    }
+
+    if let SnippetAuth::Digest(_, _) = parts.auth {
+        out += "// fetch has no digest auth\n";
+    }
+
+    let mut options: Vec<String> = vec![];
+
+    if parts.method != HttpMethod::GET {
+        options.push(format!(
+            "  method: {},\n",
+            script_str(&parts.method.to_string())
+        ));
+    }
+
+    let mut headers: Vec<String> = parts
+        .headers
+        .iter()
+        .map(|(name, value)| format!("    {}: {},\n", script_str(name), script_str(value)))
+        .collect();
+
+    if let SnippetAuth::Basic(username, password) = &parts.auth {
+        let credentials = script_str(&format!("{username}:{password}"));
+
+        // btoa only takes latin-1, other text goes in as utf-8 bytes
+        let credentials = if credentials.is_ascii() {
+            credentials
+        } else {
+            format!("String.fromCharCode(...new TextEncoder().encode({credentials}))")
+        };
+
+        headers.push(format!(
+            "    \"Authorization\": \"Basic \" + btoa({credentials}),\n"
+        ));
+    }
+
+    if !headers.is_empty() {
+        options.push(format!("  headers: {{\n{}  }},\n", headers.concat()));
+    }
+
+    match &parts.body {
+        SnippetBody::Text(text) => options.push(format!("  body: {},\n", script_str(text))),
+        SnippetBody::Form(rows) => {
+            let rows: Vec<String> = rows
+                .iter()
+                .map(|(name, value)| {
+                    format!("    [{}, {}],\n", script_str(name), script_str(value))
+                })
+                .collect();
+
+            options.push(format!(
+                "  body: new URLSearchParams([\n{}  ]),\n",
+                rows.concat()
+            ));
+        }
+        SnippetBody::Multipart(_) => options.push("  body: form,\n".to_string()),
+        SnippetBody::File(path) => {
+            options.push(format!("  body: await readFile({}),\n", script_str(path)));
+        }
+        SnippetBody::None => {}
+    }
+
+    if options.is_empty() {
+        out += &format!(
+            "const response = await fetch({});\n\n",
+            script_str(&parts.url)
+        );
+    } else {
+        out += &format!(
+            "const response = await fetch({}, {{\n{}}});\n\n",
+            script_str(&parts.url),
+            options.concat()
+        );
+    }
+
+    out += "console.log(response.status);\n";
+    out += "console.log(await response.text());\n";
+
+    out
+}
+
+fn go_snippet(parts: &SnippetParts) -> String {
+    let mut imports = vec!["fmt", "io", "net/http"];
+    let mut body = String::new();
+    let mut body_arg = "body";
+
+    match &parts.body {
+        SnippetBody::Text(text) => {
+            imports.push("strings");
+
+            body += &format!("\tbody := strings.NewReader({})\n\n", go_str(text));
+        }
+        SnippetBody::Form(rows) => {
+            imports.extend(["net/url", "strings"]);
+
+            body += "\tform := url.Values{}\n";
+
+            for (name, value) in rows {
+                body += &format!("\tform.Add({}, {})\n", go_str(name), go_str(value));
+            }
+
+            body += "\tbody := strings.NewReader(form.Encode())\n\n";
+        }
+        SnippetBody::Multipart(fields) => {
+            imports.extend(["bytes", "mime/multipart"]);
+
+            body += "\tbody := &bytes.Buffer{}\n";
+            body += "\twriter := multipart.NewWriter(body)\n\n";
+
+            let mut files = 0;
+
+            for (name, value, is_file) in fields {
+                if *is_file {
+                    files += 1;
+
+                    body += &format!("\tfile{files}, err := os.Open({})\n", go_str(value));
+                    body += GO_CHECK;
+                    body += &format!("\tdefer file{files}.Close()\n\n");
+                    body += &format!(
+                        "\tpart{files}, err := writer.CreateFormFile({}, filepath.Base({}))\n",
+                        go_str(name),
+                        go_str(value)
+                    );
+                    body += GO_CHECK;
+                    body += &format!("\tio.Copy(part{files}, file{files})\n\n");
+                } else {
+                    body += &format!("\twriter.WriteField({}, {})\n", go_str(name), go_str(value));
+                }
+            }
+
+            if files > 0 {
+                imports.extend(["os", "path/filepath"]);
+            }
+
+            body += "\twriter.Close()\n\n";
+        }
+        SnippetBody::File(path) => {
+            imports.push("os");
+
+            body += &format!("\tbody, err := os.Open({})\n", go_str(path));
+            body += GO_CHECK;
+            body += "\tdefer body.Close()\n\n";
+        }
+        SnippetBody::None => body_arg = "nil",
+    }
+
+    imports.sort_unstable();
+    imports.dedup();
+
+    let mut out = String::from("package main\n\nimport (\n");
+
+    for import in imports {
+        out += &format!("\t\"{import}\"\n");
+    }
+
+    out += ")\n\nfunc main() {\n";
+    out += &body;
+
+    out += &format!(
+        "\treq, err := http.NewRequest({}, {}, {})\n",
+        go_str(&parts.method.to_string()),
+        go_str(&parts.url),
+        body_arg
+    );
+    out += GO_CHECK;
+    out.push('\n');
+
+    for (name, value) in &parts.headers {
+        out += &format!("\treq.Header.Set({}, {})\n", go_str(name), go_str(value));
+    }
+
+    match &parts.body {
+        SnippetBody::Form(_) => {
+            out += "\treq.Header.Set(\"Content-Type\", \"application/x-www-form-urlencoded\")\n";
+        }
+        SnippetBody::Multipart(_) => {
+            out += "\treq.Header.Set(\"Content-Type\", writer.FormDataContentType())\n";
+        }
+        _ => {}
+    }
+
+    match &parts.auth {
+        SnippetAuth::Basic(username, password) => {
+            out += &format!(
+                "\treq.SetBasicAuth({}, {})\n",
+                go_str(username),
+                go_str(password)
+            );
+        }
+        SnippetAuth::Digest(_, _) => out += "\t// net/http has no digest auth\n",
+        SnippetAuth::None => {}
+    }
+
+    out += "\n\tresp, err := http.DefaultClient.Do(req)\n";
+    out += GO_CHECK;
+    out += "\tdefer resp.Body.Close()\n\n";
+    out += "\trespBody, err := io.ReadAll(resp.Body)\n";
+    out += GO_CHECK;
+    out += "\n\tfmt.Println(resp.Status)\n";
+    out += "\tfmt.Println(string(respBody))\n";
+    out += "}\n";
+
+    out
+}
+
+fn httpie_snippet(parts: &SnippetParts) -> String {
+    let mut command = vec!["http".to_string()];
+
+    match &parts.body {
+        SnippetBody::Form(_) => command.push("--form".to_string()),
+        SnippetBody::Multipart(_) => command.push("--multipart".to_string()),
+        _ => {}
+    }
+
+    match &parts.auth {
+        SnippetAuth::Basic(username, password) => {
+            command.push(format!(
+                "-a {}",
+                shell_quote(&format!("{username}:{password}"))
+            ));
+        }
+        SnippetAuth::Digest(username, password) => {
+            command.push("-A digest".to_string());
+            command.push(format!(
+                "-a {}",
+                shell_quote(&format!("{username}:{password}"))
+            ));
+        }
+        SnippetAuth::None => {}
+    }
+
+    command.push(format!("{} {}", parts.method, shell_quote(&parts.url)));
+
+    let mut args: Vec<String> = parts
+        .headers
+        .iter()
+        .map(|(name, value)| shell_quote(&format!("{}:{value}", httpie_key(name))))
+        .collect();
+
+    match &parts.body {
+        SnippetBody::Text(text) => args.push(format!("--raw {}", shell_quote(text))),
+        SnippetBody::Form(rows) => {
+            for (name, value) in rows {
+                args.push(shell_quote(&format!("{}={value}", httpie_key(name))));
+            }
+        }
+        SnippetBody::Multipart(fields) => {
+            for (name, value, is_file) in fields {
+                let separator = if *is_file { "@" } else { "=" };
+
+                args.push(shell_quote(&format!(
+                    "{}{separator}{value}",
+                    httpie_key(name)
+                )));
+            }
+        }
+        SnippetBody::File(path) => args.push(format!("< {}", shell_quote(path))),
+        SnippetBody::None => {}
+    }
+
+    let mut out = command.join(" ");
+
+    for arg in args {
+        out.push_str(" \\\n  ");
+        out.push_str(&arg);
+    }
+
+    out.push('\n');
+
+    out
+}
+
+// httpie splits items at the first separator, a backslash keeps it in the key
+fn httpie_key(name: &str) -> String {
+    let mut key = String::with_capacity(name.len());
+
+    for c in name.chars() {
+        if matches!(c, ':' | '=' | '@' | '\\') {
+            key.push('\\');
+        }
+
+        key.push(c);
+    }
+
+    key
+}
+
+const GO_CHECK: &str = "\tif err != nil {\n\t\tpanic(err)\n\t}\n";
+
+fn file_name(path: &str) -> &str {
+    path.rsplit(['/', '\\']).next().unwrap_or(path)
+}
+
+// a quoted literal that is valid in Python, JavaScript and Go
+fn quoted(text: &str, quote: char) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+
+    out.push(quote);
+
+    for c in text.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c == quote => {
+                out.push('\\');
+                out.push(c);
+            }
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+
+    out.push(quote);
+
+    out
+}
+
+// single quotes for JSON bodies and other text full of double quotes
+fn script_str(text: &str) -> String {
+    if text.contains('"') && !text.contains('\'') {
+        quoted(text, '\'')
+    } else {
+        quoted(text, '"')
+    }
+}
+
+// a raw string keeps JSON bodies readable
+fn go_str(text: &str) -> String {
+    let raw =
+        (text.contains('"') || text.contains('\n')) && !text.contains('`') && !text.contains('\r');
+
+    if raw {
+        format!("`{text}`")
+    } else {
+        quoted(text, '"')
+    }
+}
+
+// a raw string unless the text has nothing to escape, raw strings can not hold a bare \r
+fn rust_str(text: &str) -> String {
+    let raw =
+        (text.contains('"') || text.contains('\\') || text.contains('\n')) && !text.contains('\r');
+
+    if !raw {
+        return format!("{text:?}");
+    }
+
+    // one more # than the longest run of them after a quote
+    let mut hashes = 1;
+
+    for (i, _) in text.match_indices('"') {
+        let run = text[i + 1..].chars().take_while(|c| *c == '#').count();
+
+        hashes = hashes.max(run + 1);
+    }
+
+    let hashes = "#".repeat(hashes);
+
+    format!("r{hashes}\"{text}\"{hashes}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::MultipartField;
+
+    fn row(name: &str, value: &str) -> Vec<String> {
+        vec![name.to_string(), value.to_string()]
+    }
+
+    fn field(name: &str, value: &str, is_file: bool) -> MultipartField {
+        MultipartField {
+            name: name.to_string(),
+            value: value.to_string(),
+            is_file,
+        }
+    }
+
+    // every value holds something a target has to escape
+    fn tricky() -> HttpRequest {
+        let mut request = HttpRequest::new();
+
+        request.method = HttpMethod::POST;
+        request.url = "https://api.test/caf\u{e9}s".to_string();
+        request.params = vec![row("q", "it's")];
+        request.headers = vec![row("X-Quote", "say \"hi\" \\ caf\u{e9}")];
+        request.body_type = HttpBodyType::JSON;
+        request.body = "{\n  \"name\": \"a\\\\b \u{e9}\"\n}".to_string();
+
+        request
+    }
+
+    #[test]
+    fn quoted_literals() {
+        assert_eq!(quoted("plain", '"'), "\"plain\"");
+        assert_eq!(quoted("a\"b'c", '"'), "\"a\\\"b'c\"");
+        assert_eq!(quoted("a\"b'c", '\''), "'a\"b\\'c'");
+        assert_eq!(quoted("back\\slash", '"'), "\"back\\\\slash\"");
+        assert_eq!(quoted("one\ntwo\r\tthree", '"'), "\"one\\ntwo\\r\\tthree\"");
+        assert_eq!(quoted("\u{1}\u{7f}", '"'), "\"\\u0001\\u007f\"");
+        assert_eq!(
+            quoted("caf\u{e9} \u{1f600}", '"'),
+            "\"caf\u{e9} \u{1f600}\""
+        );
+    }
+
+    #[test]
+    fn script_strings_pick_the_quote() {
+        assert_eq!(script_str("plain"), "\"plain\"");
+        assert_eq!(script_str("{\"a\": 1}"), "'{\"a\": 1}'");
+        assert_eq!(script_str("it's \"both\""), "\"it's \\\"both\\\"\"");
+        assert_eq!(script_str("it's"), "\"it's\"");
+    }
+
+    #[test]
+    fn go_strings_are_raw_when_they_can_be() {
+        assert_eq!(go_str("plain"), "\"plain\"");
+        assert_eq!(go_str("{\"a\": \"\\n\"}"), "`{\"a\": \"\\n\"}`");
+        assert_eq!(go_str("one\ntwo"), "`one\ntwo`");
+        assert_eq!(go_str("back\\slash"), "\"back\\\\slash\"");
+        assert_eq!(go_str("tick ` \"q\""), "\"tick ` \\\"q\\\"\"");
+        assert_eq!(go_str("crlf\r\n\"q\""), "\"crlf\\r\\n\\\"q\\\"\"");
+        assert_eq!(go_str("caf\u{e9}"), "\"caf\u{e9}\"");
+    }
+
+    #[test]
+    fn rust_strings_are_raw_when_they_can_be() {
+        assert_eq!(rust_str("plain caf\u{e9}"), "\"plain caf\u{e9}\"");
+        assert_eq!(rust_str("tab\tquote'"), "\"tab\\tquote'\"");
+        assert_eq!(rust_str("{\"a\": 1}"), "r#\"{\"a\": 1}\"#");
+        assert_eq!(rust_str("back\\slash"), "r#\"back\\slash\"#");
+        assert_eq!(rust_str("one\ntwo"), "r#\"one\ntwo\"#");
+        assert_eq!(rust_str("\"## \"#"), "r###\"\"## \"#\"###");
+        // a bare \r is not allowed in a raw string
+        assert_eq!(rust_str("\"q\"\r\n"), "\"\\\"q\\\"\\r\\n\"");
+    }
+
+    #[test]
+    fn shell_words() {
+        assert_eq!(
+            shell_quote("https://api.test/a?b=c"),
+            "'https://api.test/a?b=c'"
+        );
+        assert_eq!(shell_quote("plain-word_1.0"), "plain-word_1.0");
+        assert_eq!(shell_quote(""), "''");
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+        assert_eq!(shell_quote("$HOME \\ \"q\"\n"), "'$HOME \\ \"q\"\n'");
+        assert_eq!(shell_quote("caf\u{e9}"), "'caf\u{e9}'");
+    }
+
+    #[test]
+    fn httpie_keys_escape_the_separators() {
+        assert_eq!(httpie_key("name"), "name");
+        assert_eq!(httpie_key("a:b=c@d\\e"), "a\\:b\\=c\\@d\\\\e");
+    }
+
+    #[test]
+    fn reqwest_snippets() {
+        let snippet = generate_snippet(&tricky(), SnippetLanguage::REQWEST);
+
+        assert!(snippet.contains("        .post(\"https://api.test/caf\u{e9}s?q=it's\")\n"));
+        assert!(snippet.contains("        .header(\"X-Quote\", r#\"say \"hi\" \\ caf\u{e9}\"#)\n"));
+        assert!(snippet.contains("        .header(\"Content-Type\", \"application/json\")\n"));
+        assert!(snippet.contains("        .body(r#\"{\n  \"name\": \"a\\\\b \u{e9}\"\n}\"#)\n"));
+    }
+
+    #[test]
+    fn python_snippets() {
+        let snippet = generate_snippet(&tricky(), SnippetLanguage::PYTHON);
+
+        assert!(snippet.contains("\nurl = \"https://api.test/caf\u{e9}s?q=it's\"\n"));
+        assert!(snippet.contains("    \"X-Quote\": 'say \"hi\" \\\\ caf\u{e9}',\n"));
+        assert!(snippet.contains("\ndata = '{\\n  \"name\": \"a\\\\\\\\b \u{e9}\"\\n}'.encode()\n"));
+        assert!(snippet.contains("requests.request(\"POST\", url, headers=headers, data=data)"));
+    }
+
+    #[test]
+    fn fetch_snippets() {
+        let snippet = generate_snippet(&tricky(), SnippetLanguage::FETCH);
+
+        assert!(snippet.contains("await fetch(\"https://api.test/caf\u{e9}s?q=it's\", {\n"));
+        assert!(snippet.contains("  method: \"POST\",\n"));
+        assert!(snippet.contains("    \"X-Quote\": 'say \"hi\" \\\\ caf\u{e9}',\n"));
+        assert!(snippet.contains("  body: '{\\n  \"name\": \"a\\\\\\\\b \u{e9}\"\\n}',\n"));
+    }
+
+    #[test]
+    fn go_snippets() {
+        let snippet = generate_snippet(&tricky(), SnippetLanguage::GO);
+
+        assert!(snippet.contains("\t\"strings\"\n"));
+        assert!(snippet
+            .contains("\tbody := strings.NewReader(`{\n  \"name\": \"a\\\\b \u{e9}\"\n}`)\n"));
+        assert!(snippet
+            .contains("http.NewRequest(\"POST\", \"https://api.test/caf\u{e9}s?q=it's\", body)"));
+        assert!(snippet.contains("\treq.Header.Set(\"X-Quote\", `say \"hi\" \\ caf\u{e9}`)\n"));
+    }
+
+    #[test]
+    fn httpie_snippets() {
+        let snippet = generate_snippet(&tricky(), SnippetLanguage::HTTPIE);
+
+        assert!(snippet.starts_with("http POST 'https://api.test/caf\u{e9}s?q=it'\\''s' \\\n"));
+        assert!(snippet.contains("  'X-Quote:say \"hi\" \\ caf\u{e9}' \\\n"));
+        assert!(snippet.contains("  --raw '{\n  \"name\": \"a\\\\b \u{e9}\"\n}'\n"));
+    }
+
+    #[test]
+    fn forms_in_every_target() {
+        let mut request = HttpRequest::new();
+
+        request.url = "https://api.test".to_string();
+        request.method = HttpMethod::POST;
+        request.body_type = HttpBodyType::FORM_URLENCODED;
+        request.form = vec![row("a=b", "it's \"x\""), row("", "skipped")];
+
+        let snippet = |language| generate_snippet(&request, language);
+
+        assert!(snippet(SnippetLanguage::REQWEST)
+            .contains("        .form(&[(\"a=b\", r#\"it's \"x\"\"#)])\n"));
+        assert!(snippet(SnippetLanguage::PYTHON).contains("    \"a=b\": \"it's \\\"x\\\"\",\n"));
+        assert!(snippet(SnippetLanguage::FETCH).contains("    [\"a=b\", \"it's \\\"x\\\"\"],\n"));
+        assert!(snippet(SnippetLanguage::GO).contains("\tform.Add(\"a=b\", `it's \"x\"`)\n"));
+
+        let httpie = snippet(SnippetLanguage::HTTPIE);
+
+        assert!(httpie.starts_with("http --form POST https://api.test"));
+        assert!(httpie.contains("  'a\\=b=it'\\''s \"x\"'"));
+        assert!(!httpie.contains("skipped"));
+    }
+
+    #[test]
+    fn multipart_and_file_bodies() {
+        let mut request = HttpRequest::new();
+
+        request.url = "https://api.test".to_string();
+        request.method = HttpMethod::PUT;
+        request.body_type = HttpBodyType::MULTIPART;
+        request.multipart = vec![
+            field("note", "caf\u{e9}", false),
+            field("upload", "/tmp/my file's.txt", true),
+        ];
+
+        let snippet = |request: &HttpRequest, language| generate_snippet(request, language);
+
+        let reqwest = snippet(&request, SnippetLanguage::REQWEST);
+        assert!(reqwest.contains("\n        .text(\"note\", \"caf\u{e9}\")"));
+        assert!(reqwest
+            .contains("std::fs::read(\"/tmp/my file's.txt\")?).file_name(\"my file's.txt\")"));
+        assert!(reqwest.contains("        .put(\"https://api.test\")\n"));
+
+        let python = snippet(&request, SnippetLanguage::PYTHON);
+        assert!(python.contains("    \"note\": (None, \"caf\u{e9}\"),\n"));
+        assert!(python.contains("    \"upload\": open(\"/tmp/my file's.txt\", \"rb\"),\n"));
+
+        let fetch = snippet(&request, SnippetLanguage::FETCH);
+        assert!(fetch.starts_with("import { readFile } from \"node:fs/promises\";\n"));
+        assert!(fetch.contains(
+            "form.append(\"upload\", new Blob([await readFile(\"/tmp/my file's.txt\")]), \"my file's.txt\");\n"
+        ));
+
+        let go = snippet(&request, SnippetLanguage::GO);
+        assert!(go.contains("\t\"path/filepath\"\n"));
+        assert!(go.contains("\tfile1, err := os.Open(\"/tmp/my file's.txt\")\n"));
+        assert!(go.contains("\twriter.WriteField(\"note\", \"caf\u{e9}\")\n"));
+
+        let httpie = snippet(&request, SnippetLanguage::HTTPIE);
+        assert!(httpie.contains("  'note=caf\u{e9}' \\\n  'upload@/tmp/my file'\\''s.txt'\n"));
+
+        request.body_type = HttpBodyType::BINARY;
+        request.body_file = "C:\\data\\body.bin".to_string();
+
+        assert!(snippet(&request, SnippetLanguage::GO)
+            .contains("\tbody, err := os.Open(\"C:\\\\data\\\\body.bin\")\n"));
+        assert!(snippet(&request, SnippetLanguage::REQWEST)
+            .contains(".body(std::fs::read(r#\"C:\\data\\body.bin\"#)?)"));
+        assert!(snippet(&request, SnippetLanguage::HTTPIE).contains("  < 'C:\\data\\body.bin'\n"));
+    }
+
+    #[test]
+    fn auth_in_every_target() {
+        let mut request = HttpRequest::new();
+
+        request.url = "https://api.test".to_string();
+        request.auth.auth_type = HttpAuthType::BASIC;
+        request.auth.username = "jos\u{e9}".to_string();
+        request.auth.password = "p\"w".to_string();
+
+        let snippet = |request: &HttpRequest, language| generate_snippet(request, language);
+
+        assert!(snippet(&request, SnippetLanguage::REQWEST)
+            .contains(".basic_auth(\"jos\u{e9}\", Some(r#\"p\"w\"#))"));
+        assert!(snippet(&request, SnippetLanguage::PYTHON).contains("auth=(\"jos\u{e9}\", 'p\"w')"));
+        assert!(snippet(&request, SnippetLanguage::FETCH).contains(
+            "\"Basic \" + btoa(String.fromCharCode(...new TextEncoder().encode('jos\u{e9}:p\"w'))),"
+        ));
+        assert!(snippet(&request, SnippetLanguage::GO)
+            .contains("\treq.SetBasicAuth(\"jos\u{e9}\", `p\"w`)\n"));
+        assert!(snippet(&request, SnippetLanguage::HTTPIE).contains("-a 'jos\u{e9}:p\"w'"));
+
+        request.auth.username = "user".to_string();
+        assert!(
+            snippet(&request, SnippetLanguage::FETCH).contains("\"Basic \" + btoa('user:p\"w'),")
+        );
+
+        request.auth.auth_type = HttpAuthType::BEARER;
+        request.auth.token = "t\"k".to_string();
+        assert!(
+            snippet(&request, SnippetLanguage::HTTPIE).contains("  'Authorization:Bearer t\"k'\n")
+        );
+
+        request.auth.auth_type = HttpAuthType::API_KEY;
+        request.auth.key = "api key".to_string();
+        request.auth.value = "caf\u{e9}".to_string();
+        request.auth.location = ApiKeyLocation::QUERY;
+        assert!(snippet(&request, SnippetLanguage::PYTHON)
+            .contains("url = \"https://api.test?api%20key=caf%C3%A9\"\n"));
+
+        request.auth.auth_type = HttpAuthType::DIGEST;
+        assert!(snippet(&request, SnippetLanguage::PYTHON)
+            .contains("auth=HTTPDigestAuth(\"user\", 'p\"w')"));
+        assert!(snippet(&request, SnippetLanguage::HTTPIE).contains("-A digest -a 'user:p\"w'"));
+    }
+
+    #[test]
+    fn graphql_bodies_are_sent_as_json() {
+        let mut request = HttpRequest::new();
+
+        request.url = "https://api.test/graphql".to_string();
+        request.method = HttpMethod::POST;
+        request.body_type = HttpBodyType::GRAPHQL;
+        request.graphql.query = "{ user(name: \"caf\u{e9}\") { id } }".to_string();
+
+        let snippet = generate_snippet(&request, SnippetLanguage::FETCH);
+
+        assert!(snippet
+            .contains("  body: '{\"query\":\"{ user(name: \\\\\"caf\u{e9}\\\\\") { id } }\"}',\n"));
+        assert!(snippet.contains("    \"Content-Type\": \"application/json\",\n"));
+    }
+}
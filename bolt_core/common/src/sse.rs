@@ -0,0 +1,369 @@
+use serde::{Deserialize, Serialize};
+use crate::prelude::MsgType;
+
+// what EventSource waits before reconnecting when the server sends no retry field
+pub const SSE_DEFAULT_RETRY: u64 = 3000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SseEvent {
+    pub event: String,
+    pub data: String,
+    // the last event id when the event was dispatched, the server does not have to repeat it
+    pub id: String,
+    pub retry: Option<u64>,
+    pub timestamp: u64,
+    pub msg_id: String,
+}
+
+impl Default for SseEvent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SseEvent {
+    pub fn new() -> Self {
+        let msg_id = uuid::Uuid::new_v4()
+            .to_string()
+            .split('-')
+            .next()
+            .unwrap()
+            .to_string();
+
+        Self {
+            event: "message".to_string(),
+            data: String::new(),
+            id: String::new(),
+            retry: None,
+            timestamp: 0,
+            msg_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SseConnection {
+    pub connection_id: String,
+    pub url: String,
+    pub name: String,
+
+    pub out_tab: u8,
+    pub in_tab: u8,
+
+    pub connecting: bool,
+    pub disconnecting: bool,
+    pub failed: bool,
+    pub failed_reason: String,
+    pub connected: bool,
+    // the stream ended and the core waits `retry` ms before trying again
+    pub reconnecting: bool,
+
+    pub out_headers: Vec<Vec<String>>,
+
+    // sent as Last-Event-ID when connecting, kept up to date as events arrive
+    pub last_event_id: String,
+    pub retry: u64,
+
+    pub msg_history: Vec<SseEvent>,
+}
+
+impl Default for SseConnection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SseConnection {
+    pub fn new() -> Self {
+        let con_id = uuid::Uuid::new_v4()
+            .to_string()
+            .split('-')
+            .next()
+            .unwrap()
+            .to_string();
+
+        Self {
+            connection_id: con_id,
+            url: String::new(),
+            name: "SSE connection ".to_string(),
+            connecting: false,
+            disconnecting: false,
+            failed: false,
+            failed_reason: String::new(),
+            connected: false,
+            reconnecting: false,
+
+            out_tab: 1,
+            in_tab: 1,
+
+            out_headers: vec![vec![String::new(), String::new()]],
+
+            last_event_id: String::new(),
+            retry: SSE_DEFAULT_RETRY,
+
+            msg_history: vec![],
+        }
+    }
+}
+
+// turns the bytes of a text/event-stream into events, following the parsing rules of the HTML spec
+#[derive(Debug, Clone, Default)]
+pub struct SseParser {
+    pub last_event_id: String,
+    pub retry: Option<u64>,
+
+    line: Vec<u8>,
+    // a CR ended the last chunk, so a LF starting the next one belongs to it
+    pending_cr: bool,
+    started: bool,
+
+    event: String,
+    data: String,
+    has_data: bool,
+}
+
+impl SseParser {
+    pub fn new(last_event_id: &str) -> Self {
+        Self {
+            last_event_id: last_event_id.to_string(),
+            ..Default::default()
+        }
+    }
+
+    // forgets a half received event, the last event id survives reconnects
+    pub fn reset(&mut self) {
+        *self = Self {
+            last_event_id: self.last_event_id.clone(),
+            retry: self.retry,
+            ..Default::default()
+        };
+    }
+
+    pub fn feed(&mut self, bytes: &[u8]) -> Vec<SseEvent> {
+        let mut events = vec![];
+
+        for &byte in bytes {
+            if self.pending_cr {
+                self.pending_cr = false;
+
+                if byte == b'\n' {
+                    continue;
+                }
+            }
+
+            match byte {
+                b'\r' => {
+                    self.pending_cr = true;
+                    self.end_line(&mut events);
+                }
+
+                b'\n' => self.end_line(&mut events),
+
+                _ => self.line.push(byte),
+            }
+        }
+
+        events
+    }
+
+    fn end_line(&mut self, events: &mut Vec<SseEvent>) {
+        let mut line = String::from_utf8_lossy(&self.line).to_string();
+        self.line.clear();
+
+        if !self.started {
+            self.started = true;
+
+            if let Some(rest) = line.strip_prefix('\u{feff}') {
+                line = rest.to_string();
+            }
+        }
+
+        if line.is_empty() {
+            if let Some(event) = self.dispatch() {
+                events.push(event);
+            }
+
+            return;
+        }
+
+        // comments keep proxies from closing an idle stream
+        if line.starts_with(':') {
+            return;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line.as_str(), ""),
+        };
+
+        match field {
+            "event" => self.event = value.to_string(),
+
+            "data" => {
+                if self.has_data {
+                    self.data.push('\n');
+                }
+
+                self.data.push_str(value);
+                self.has_data = true;
+            }
+
+            "id" if !value.contains('\0') => self.last_event_id = value.to_string(),
+
+            "retry" if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) => {
+                self.retry = value.parse().ok();
+            }
+
+            _ => {}
+        }
+    }
+
+    fn dispatch(&mut self) -> Option<SseEvent> {
+        let event = std::mem::take(&mut self.event);
+        let data = std::mem::take(&mut self.data);
+        let has_data = std::mem::replace(&mut self.has_data, false);
+
+        if !has_data {
+            return None;
+        }
+
+        let mut sse_event = SseEvent::new();
+        sse_event.data = data;
+        sse_event.id = self.last_event_id.clone();
+        sse_event.retry = self.retry;
+
+        if !event.is_empty() {
+            sse_event.event = event;
+        }
+
+        Some(sse_event)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AddSseConnectionMsg {
+    pub msg_type: MsgType,
+    pub connection_id: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SseConnectedMsg {
+    pub msg_type: MsgType,
+    pub connection_id: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SseDisconnectedMsg {
+    pub msg_type: MsgType,
+    pub connection_id: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SseReceivedMsg {
+    pub msg_type: MsgType,
+    pub connection_id: String,
+    pub msg: SseEvent,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SseReconnectingMsg {
+    pub msg_type: MsgType,
+    pub connection_id: String,
+    pub reason: String,
+    pub retry: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SseConnectionFailedMsg {
+    pub msg_type: MsgType,
+    pub connection_id: String,
+    pub reason: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(events: &[SseEvent]) -> Vec<(&str, &str, &str)> {
+        events
+            .iter()
+            .map(|e| (e.event.as_str(), e.data.as_str(), e.id.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn multi_line_data_and_event_types() {
+        let mut parser = SseParser::new("");
+
+        let events =
+            parser.feed(b"data: first\ndata:second\ndata\n\nevent: tick\ndata: {\"n\": 1}\n\n");
+
+        assert_eq!(
+            summary(&events),
+            vec![
+                ("message", "first\nsecond\n", ""),
+                ("tick", "{\"n\": 1}", ""),
+            ]
+        );
+    }
+
+    #[test]
+    fn ids_and_retry_carry_over() {
+        let mut parser = SseParser::new("7");
+
+        let events = parser.feed(
+            b"data: a\n\nid: 8\nretry: 250\ndata: b\n\nretry: soon\ndata: c\n\nid\ndata: d\n\n",
+        );
+
+        // an empty id field resets it
+        assert_eq!(
+            summary(&events),
+            vec![
+                ("message", "a", "7"),
+                ("message", "b", "8"),
+                ("message", "c", "8"),
+                ("message", "d", "")
+            ]
+        );
+        assert_eq!(events[0].retry, None);
+        assert_eq!(events[2].retry, Some(250));
+        assert_eq!(parser.retry, Some(250));
+
+        // ids with a NUL are ignored
+        parser.feed(b"id: 9\0\ndata: e\n\n");
+        assert_eq!(parser.last_event_id, "");
+    }
+
+    #[test]
+    fn line_endings_comments_and_chunks() {
+        let mut parser = SseParser::new("");
+
+        let mut events = parser.feed("\u{feff}: hello\r\ndata: one\r".as_bytes());
+        assert!(events.is_empty());
+
+        // the LF of the CRLF comes in the next chunk
+        events.extend(parser.feed(b"\n\r\nda"));
+        events.extend(parser.feed(b"ta: two\r\rid: x\n"));
+
+        assert_eq!(
+            summary(&events),
+            vec![("message", "one", ""), ("message", "two", "")]
+        );
+
+        // events without data are not dispatched
+        assert!(parser.feed(b"event: empty\n\n").is_empty());
+        assert_eq!(parser.last_event_id, "x");
+    }
+
+    #[test]
+    fn reset_keeps_the_last_event_id() {
+        let mut parser = SseParser::new("");
+
+        parser.feed(b"id: 3\nretry: 10\ndata: half");
+        parser.reset();
+
+        assert_eq!(parser.last_event_id, "3");
+        assert_eq!(parser.retry, Some(10));
+        assert!(parser.feed(b"\n\n").is_empty());
+    }
+}
@@ -0,0 +1,227 @@
+use crate::collection::Collection;
+use crate::environment::Environment;
+use crate::prelude::{MainState, MsgType};
+use crate::ws::WsConnection;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// file formats collections are imported from and exported to
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CollectionFormat {
+    #[default]
+    BOLT,
+    CURL,
+    POSTMAN,
+    OPENAPI,
+    HAR,
+    INSOMNIA,
+    BRUNO,
+}
+
+impl CollectionFormat {
+    pub fn count() -> usize {
+        7
+    }
+
+    // the name used on the command line
+    pub fn id(&self) -> &'static str {
+        match self {
+            CollectionFormat::BOLT => "bolt",
+            CollectionFormat::CURL => "curl",
+            CollectionFormat::POSTMAN => "postman",
+            CollectionFormat::OPENAPI => "openapi",
+            CollectionFormat::HAR => "har",
+            CollectionFormat::INSOMNIA => "insomnia",
+            CollectionFormat::BRUNO => "bruno",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        (0..Self::count())
+            .map(Self::from)
+            .find(|format| format.id() == id)
+    }
+
+    pub fn can_export(&self) -> bool {
+        match self {
+            CollectionFormat::BOLT
+            | CollectionFormat::CURL
+            | CollectionFormat::POSTMAN
+            | CollectionFormat::HAR => true,
+            CollectionFormat::OPENAPI | CollectionFormat::INSOMNIA | CollectionFormat::BRUNO => {
+                false
+            }
+        }
+    }
+}
+
+impl From<usize> for CollectionFormat {
+    fn from(index: usize) -> Self {
+        match index {
+            0 => CollectionFormat::BOLT,
+            1 => CollectionFormat::CURL,
+            2 => CollectionFormat::POSTMAN,
+            3 => CollectionFormat::OPENAPI,
+            4 => CollectionFormat::HAR,
+            5 => CollectionFormat::INSOMNIA,
+            6 => CollectionFormat::BRUNO,
+            _ => panic!("Invalid index for CollectionFormat"),
+        }
+    }
+}
+
+impl fmt::Display for CollectionFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CollectionFormat::BOLT => write!(f, "Bolt"),
+            CollectionFormat::CURL => write!(f, "cURL"),
+            CollectionFormat::POSTMAN => write!(f, "Postman v2.1"),
+            CollectionFormat::OPENAPI => write!(f, "OpenAPI / Swagger"),
+            CollectionFormat::HAR => write!(f, "HAR 1.2"),
+            CollectionFormat::INSOMNIA => write!(f, "Insomnia v4"),
+            CollectionFormat::BRUNO => write!(f, "Bruno"),
+        }
+    }
+}
+
+// the outcome of the last import or export, shown on the collections page
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TransferStatus {
+    pub message: String,
+    pub error: String,
+    // what could not be mapped, the rest was imported or exported
+    pub warnings: Vec<String>,
+}
+
+// path is read by the core, a folder is read as a Bruno collection. None guesses the
+// format from the content
+#[derive(Serialize, Deserialize)]
+pub struct ImportCollectionMsg {
+    pub msg_type: MsgType,
+    pub path: String,
+    #[serde(default)]
+    pub format: Option<CollectionFormat>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CollectionImportedMsg {
+    pub msg_type: MsgType,
+    pub collections: Vec<Collection>,
+    // variables that came with the collections
+    pub environments: Vec<Environment>,
+    #[serde(default)]
+    pub ws_connections: Vec<WsConnection>,
+    pub warnings: Vec<String>,
+    pub error: String,
+    // saved collections and environments with the same name are updated instead of added
+    #[serde(default)]
+    pub update: bool,
+}
+
+// written to the downloads directory, like saved responses
+#[derive(Serialize, Deserialize)]
+pub struct ExportCollectionMsg {
+    pub msg_type: MsgType,
+    pub collection: Collection,
+    pub format: CollectionFormat,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CollectionExportedMsg {
+    pub msg_type: MsgType,
+    pub path: String,
+    pub warnings: Vec<String>,
+    pub error: String,
+}
+
+// the requests open on the HTTP page, exported like a collection
+pub fn open_requests(state: &MainState) -> Collection {
+    Collection {
+        name: "HTTP requests".to_string(),
+        requests: state.http_requests.clone(),
+        collapsed: false,
+    }
+}
+
+// adds an import to the state and describes what changed, one line per collection or environment
+pub fn add_imported(
+    state: &mut MainState,
+    collections: Vec<Collection>,
+    environments: Vec<Environment>,
+    ws_connections: Vec<WsConnection>,
+    update: bool,
+) -> Vec<String> {
+    let mut changes = vec![];
+
+    for collection in collections {
+        let existing = state
+            .collections
+            .iter_mut()
+            .find(|existing| update && existing.name == collection.name);
+
+        match existing {
+            Some(existing) => {
+                let (updated, added) = existing.update_from(collection);
+
+                changes.push(format!(
+                    "Updated {}: {} request(s) updated, {} added",
+                    existing.name, updated, added
+                ));
+            }
+
+            None => {
+                changes.push(format!(
+                    "Added {} with {} request(s)",
+                    collection.name,
+                    collection.requests.len()
+                ));
+
+                state.collections.push(collection);
+            }
+        }
+    }
+
+    for environment in environments {
+        let existing = state
+            .environments
+            .iter_mut()
+            .find(|existing| update && existing.name == environment.name);
+
+        match existing {
+            // values set by hand are kept
+            Some(existing) => {
+                let mut added = 0;
+
+                for var in &environment.variables {
+                    if existing.get(&var[0]).is_none() {
+                        existing.set(&var[0], &var[1]);
+                        added += 1;
+                    }
+                }
+
+                changes.push(format!(
+                    "Updated environment {}: {} variable(s) added",
+                    existing.name, added
+                ));
+            }
+
+            None => {
+                changes.push(format!(
+                    "Added environment {} with {} variable(s)",
+                    environment.name,
+                    environment.variables.len()
+                ));
+
+                state.environments.push(environment);
+            }
+        }
+    }
+
+    for connection in ws_connections {
+        changes.push(format!("Added WebSocket connection {}", connection.name));
+
+        state.ws_connections.push(connection);
+    }
+
+    changes
+}
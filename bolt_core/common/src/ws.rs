@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
+use crate::graphql::GraphqlBody;
 use crate::prelude::MsgType;
+use std::fmt;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum WsMsgType {
@@ -13,6 +15,9 @@ pub struct WsMessage {
     pub timestamp: u64,
     pub msg_id: String,
     pub msg_type: WsMsgType,
+    // the GraphQL frame type and subscription id, e.g. "next 1f2a"
+    #[serde(default)]
+    pub label: String,
 }
 
 impl WsMessage {
@@ -29,6 +34,7 @@ impl WsMessage {
             timestamp: 0,
             msg_id,
             msg_type: WsMsgType::OUT,
+            label: String::new(),
         }
     }
 }
@@ -56,6 +62,22 @@ pub struct WsConnection {
     pub in_queue: Vec<WsMessage>,
 
     pub msg_history: Vec<WsMessage>,
+
+    #[serde(default)]
+    pub mode: WsMode,
+    #[serde(default)]
+    pub graphql_protocol: GraphqlWsProtocol,
+    // the subscription started by the next send
+    #[serde(default)]
+    pub graphql: GraphqlBody,
+    #[serde(default)]
+    pub graphql_cursor: usize,
+    // JSON payload of connection_init, e.g. an auth token
+    #[serde(default)]
+    pub graphql_init: String,
+    // ids of the subscriptions the server has not completed yet
+    #[serde(default)]
+    pub subscriptions: Vec<String>,
 }
 
 impl WsConnection {
@@ -88,6 +110,90 @@ impl WsConnection {
             in_queue: vec![],
 
             msg_history: vec![],
+
+            mode: WsMode::RAW,
+            graphql_protocol: GraphqlWsProtocol::GRAPHQL_TRANSPORT_WS,
+            graphql: GraphqlBody::default(),
+            graphql_cursor: 0,
+            graphql_init: String::new(),
+            subscriptions: vec![],
+        }
+    }
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WsMode {
+    #[default]
+    RAW,
+    // subscriptions over one of the GraphQL subprotocols
+    GRAPHQL,
+}
+
+impl WsMode {
+    pub fn count() -> usize {
+        2
+    }
+}
+
+impl From<usize> for WsMode {
+    fn from(index: usize) -> Self {
+        match index {
+            0 => WsMode::RAW,
+            1 => WsMode::GRAPHQL,
+            _ => panic!("Invalid index for WsMode"),
+        }
+    }
+}
+
+impl fmt::Display for WsMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WsMode::RAW => write!(f, "Raw"),
+            WsMode::GRAPHQL => write!(f, "GraphQL"),
+        }
+    }
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GraphqlWsProtocol {
+    // graphql-ws, the current protocol
+    #[default]
+    GRAPHQL_TRANSPORT_WS,
+    // subscriptions-transport-ws, which Apollo used before
+    GRAPHQL_WS,
+}
+
+impl GraphqlWsProtocol {
+    pub fn count() -> usize {
+        2
+    }
+
+    // the Sec-WebSocket-Protocol of the handshake
+    pub fn subprotocol(&self) -> &'static str {
+        match self {
+            GraphqlWsProtocol::GRAPHQL_TRANSPORT_WS => "graphql-transport-ws",
+            GraphqlWsProtocol::GRAPHQL_WS => "graphql-ws",
+        }
+    }
+}
+
+impl From<usize> for GraphqlWsProtocol {
+    fn from(index: usize) -> Self {
+        match index {
+            0 => GraphqlWsProtocol::GRAPHQL_TRANSPORT_WS,
+            1 => GraphqlWsProtocol::GRAPHQL_WS,
+            _ => panic!("Invalid index for GraphqlWsProtocol"),
+        }
+    }
+}
+
+impl fmt::Display for GraphqlWsProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphqlWsProtocol::GRAPHQL_TRANSPORT_WS => write!(f, "graphql-transport-ws"),
+            GraphqlWsProtocol::GRAPHQL_WS => write!(f, "graphql-ws (legacy)"),
         }
     }
 }
@@ -122,6 +228,9 @@ pub struct WsReceivedMsg {
     pub msg_type: MsgType,
     pub connection_id: String,
     pub msg: WsMessage,
+    // the subscription the server ended with complete or error
+    #[serde(default)]
+    pub finished_subscription: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -0,0 +1,65 @@
+use bolt_common::prelude::*;
+use clap::builder::PossibleValuesParser;
+use clap::builder::TypedValueParser;
+use clap::Args;
+
+use super::send::find_saved_request;
+use super::state::{load_state, select_environment, state_path};
+
+#[derive(Args)]
+pub struct CodeArgs {
+    /// Saved request as "collection/request" or the name of an HTTP tab
+    request: String,
+
+    /// Language and client library of the code
+    #[arg(
+        long,
+        default_value = "reqwest",
+        value_parser = PossibleValuesParser::new(language_ids()).map(|id| language(&id))
+    )]
+    lang: SnippetLanguage,
+
+    /// Fill in the variables of this environment instead of the active one
+    #[arg(long, value_name = "NAME")]
+    env: Option<String>,
+
+    /// Leave {{variables}} in the code
+    #[arg(long)]
+    raw: bool,
+
+    /// State file to read saved requests and environments from
+    #[arg(long, value_name = "PATH")]
+    state: Option<String>,
+}
+
+pub fn code_command(args: CodeArgs) -> Result<i32, String> {
+    let mut state = load_state(&state_path(args.state.as_deref()))?;
+
+    let mut request = find_saved_request(&state, &args.request)
+        .ok_or_else(|| format!("no saved request named {}", args.request))?;
+
+    select_environment(&mut state, args.env.as_deref())?;
+
+    if !args.raw {
+        if let Some(env) = state.active_environment() {
+            request.resolve_variables(env);
+        }
+    }
+
+    print!("{}", generate_snippet(&request, args.lang));
+
+    Ok(0)
+}
+
+fn language_ids() -> Vec<&'static str> {
+    (0..SnippetLanguage::count())
+        .map(|index| SnippetLanguage::from(index).id())
+        .collect()
+}
+
+fn language(id: &str) -> SnippetLanguage {
+    (0..SnippetLanguage::count())
+        .map(SnippetLanguage::from)
+        .find(|language| language.id() == id)
+        .unwrap_or_default()
+}
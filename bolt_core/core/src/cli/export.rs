@@ -0,0 +1,86 @@
+use bolt_common::prelude::*;
+use clap::builder::PossibleValuesParser;
+use clap::builder::TypedValueParser;
+use clap::Args;
+
+use super::state::{find_collection, load_state, state_path};
+use crate::formats::export_collection;
+
+#[derive(Args)]
+pub struct ExportArgs {
+    /// Name of a saved collection
+    #[arg(required_unless_present = "tabs")]
+    collection: Option<String>,
+
+    /// Export the requests open on the HTTP page, with their last responses in a HAR
+    #[arg(long, conflicts_with = "collection")]
+    tabs: bool,
+
+    /// Format of the export
+    #[arg(
+        long,
+        default_value = "bolt",
+        value_parser = PossibleValuesParser::new(export_format_ids()).map(|id| CollectionFormat::from_id(&id).unwrap())
+    )]
+    format: CollectionFormat,
+
+    /// File to write, stdout by default
+    #[arg(long, short, value_name = "PATH")]
+    output: Option<String>,
+
+    /// State file to read the collection from
+    #[arg(long, value_name = "PATH")]
+    state: Option<String>,
+}
+
+pub fn export_command(args: ExportArgs) -> Result<i32, String> {
+    if !args.format.can_export() {
+        return Err(format!(
+            "collections can not be exported as {}",
+            args.format
+        ));
+    }
+
+    let state = load_state(&state_path(args.state.as_deref()))?;
+
+    let mut collection = match &args.collection {
+        Some(name) => find_collection(&state, name)?,
+        None => open_requests(&state),
+    };
+
+    // a HAR records what was sent, so it gets the values of the variables
+    if args.format == CollectionFormat::HAR {
+        if let Some(env) = state.active_environment() {
+            for request in &mut collection.requests {
+                request.resolve_variables(env);
+            }
+        }
+    }
+
+    let exported = export_collection(&collection, args.format)?;
+
+    for warning in &exported.warnings {
+        eprintln!("warning: {warning}");
+    }
+
+    match args.output {
+        Some(path) => {
+            std::fs::write(&path, exported.content)
+                .map_err(|err| format!("could not write {path}: {err}"))?;
+
+            eprintln!("Exported {} to {}", collection.name, path);
+        }
+
+        None => println!("{}", exported.content),
+    }
+
+    Ok(0)
+}
+
+fn export_format_ids() -> Vec<&'static str> {
+    (0..CollectionFormat::count())
+        .map(CollectionFormat::from)
+        .filter(|format| format.can_export())
+        .map(|format| format.id())
+        .collect()
+}
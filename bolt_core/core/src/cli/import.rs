@@ -0,0 +1,103 @@
+use bolt_common::prelude::*;
+use clap::builder::PossibleValuesParser;
+use clap::builder::TypedValueParser;
+use clap::Args;
+use std::io::Read;
+
+use super::state::{load_state, running_app_port, save_state, state_path};
+use crate::formats::{import_collections, import_path};
+
+#[derive(Args)]
+pub struct ImportArgs {
+    /// File to import, - reads it from stdin. A folder is imported as a Bruno collection
+    file: String,
+
+    /// Format of the file, guessed from its content by default
+    #[arg(
+        long,
+        value_parser = PossibleValuesParser::new(format_ids()).map(|id| CollectionFormat::from_id(&id).unwrap())
+    )]
+    format: Option<CollectionFormat>,
+
+    /// Name of the imported collection, the one in the file or the file name by default.
+    /// Folders and OpenAPI tags are imported as "Name / Folder"
+    #[arg(long)]
+    name: Option<String>,
+
+    /// State file to add the collection to
+    #[arg(long, value_name = "PATH")]
+    state: Option<String>,
+}
+
+pub fn import_command(args: ImportArgs) -> Result<i32, String> {
+    let imported = if args.file == "-" {
+        import_collections(&read_stdin()?, args.format, "Imported")?
+    } else {
+        import_path(&args.file, args.format)?
+    };
+
+    for warning in &imported.warnings {
+        eprintln!("warning: {warning}");
+    }
+
+    let path = state_path(args.state.as_deref());
+
+    if path == state_path(None) {
+        if let Some(port) = running_app_port() {
+            return Err(format!(
+                "Bolt is running on port {port} and would save over the import, \
+                 import the file from the app or close it first"
+            ));
+        }
+    }
+
+    let mut state = load_state(&path)?;
+
+    let mut collections = imported.collections;
+
+    if let Some(name) = &args.name {
+        if collections.len() == 1 {
+            collections[0].name = name.clone();
+        } else {
+            // keeps the folder or tag part of "Root / Folder"
+            for collection in &mut collections {
+                collection.name = match collection.name.split_once(" / ") {
+                    Some((_, folder)) => format!("{name} / {folder}"),
+                    None => name.clone(),
+                };
+            }
+        }
+    }
+
+    let changes = add_imported(
+        &mut state,
+        collections,
+        imported.environments,
+        imported.ws_connections,
+        imported.update,
+    );
+
+    for change in changes {
+        println!("{change}");
+    }
+
+    save_state(&path, &state)?;
+
+    Ok(0)
+}
+
+fn format_ids() -> Vec<&'static str> {
+    (0..CollectionFormat::count())
+        .map(|index| CollectionFormat::from(index).id())
+        .collect()
+}
+
+fn read_stdin() -> Result<String, String> {
+    let mut content = String::new();
+
+    std::io::stdin()
+        .read_to_string(&mut content)
+        .map_err(|err| format!("could not read stdin: {err}"))?;
+
+    Ok(content)
+}
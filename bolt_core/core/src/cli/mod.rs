@@ -0,0 +1,173 @@
+mod code;
+mod export;
+mod import;
+mod report;
+mod run;
+mod send;
+mod state;
+mod terminal;
+
+use bolt_common::prelude::VERSION;
+use clap::{ArgAction, Args, Parser, Subcommand};
+
+#[derive(Parser)]
+#[command(
+    name = "bolt",
+    about = "Bolt CLI (Build and test APIs)",
+    version = VERSION,
+    disable_version_flag = true
+)]
+pub struct Cli {
+    #[command(flatten)]
+    pub serve: ServeArgs,
+
+    /// Directory for the state and static files instead of ~/bolt
+    #[arg(long, global = true, value_name = "DIR")]
+    pub home: Option<String>,
+
+    /// Reset static files
+    #[arg(long)]
+    pub reset: bool,
+
+    /// Show version
+    #[arg(short = 'v', long, action = ArgAction::Version)]
+    version: Option<bool>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+// also accepted without the serve subcommand, e.g. bolt --port 4000
+#[derive(Args)]
+pub struct ServeArgs {
+    /// Port of the core server, the UI is served on the next one
+    #[arg(long, value_parser = clap::value_parser!(u16).range(1..65535))]
+    pub port: Option<u16>,
+
+    /// Address the servers listen on
+    #[arg(long)]
+    pub address: Option<String>,
+
+    /// Do not open the browser on start
+    #[arg(long)]
+    pub no_browser: bool,
+
+    /// Only start the core server, without serving the UI
+    #[arg(long)]
+    pub headless: bool,
+
+    // started by the desktop app, which ships its own UI
+    #[arg(long, hide = true)]
+    pub tauri: bool,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Start Bolt and open the UI in the browser (default)
+    Serve(ServeArgs),
+
+    /// Send one request, a saved one or ad hoc like curl
+    Send(send::SendArgs),
+
+    /// Run a collection without the UI and exit non-zero on failures
+    Run(run::RunArgs),
+
+    /// Print client code that sends a saved request
+    Code(code::CodeArgs),
+
+    /// Add a Bolt, curl, Postman, OpenAPI, HAR, Insomnia or Bruno export to the saved collections
+    Import(import::ImportArgs),
+
+    /// Write a saved collection to a file
+    Export(export::ExportArgs),
+}
+
+// runs a subcommand other than serve, returns the exit code
+pub fn execute(command: Command) -> i32 {
+    let outcome = match command {
+        Command::Serve(_) => return 0,
+        Command::Send(args) => send::send_command(args),
+        Command::Run(args) => run::run_command(args),
+        Command::Code(args) => code::code_command(args),
+        Command::Import(args) => import::import_command(args),
+        Command::Export(args) => export::export_command(args),
+    };
+
+    match outcome {
+        Ok(code) => code,
+
+        Err(err) => {
+            eprintln!("error: {err}");
+
+            2
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::error::ErrorKind;
+
+    fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+        Cli::try_parse_from(std::iter::once("bolt").chain(args.iter().copied()))
+    }
+
+    #[test]
+    fn serve_flags_work_without_the_subcommand() {
+        let cli = parse(&["--port", "4000", "--address", "0.0.0.0", "--no-browser"]).unwrap();
+
+        assert!(cli.command.is_none());
+        assert_eq!(cli.serve.port, Some(4000));
+        assert_eq!(cli.serve.address.as_deref(), Some("0.0.0.0"));
+        assert!(cli.serve.no_browser && !cli.serve.headless);
+
+        let cli = parse(&["serve", "--headless", "--home", "/tmp/bolt"]).unwrap();
+
+        match cli.command {
+            Some(Command::Serve(serve)) => assert!(serve.headless && serve.port.is_none()),
+            _ => panic!("expected serve"),
+        }
+        assert_eq!(cli.home.as_deref(), Some("/tmp/bolt"));
+    }
+
+    #[test]
+    fn home_is_global() {
+        let cli = parse(&["run", "Shop", "--home", "/tmp/bolt", "--iterations", "2"]).unwrap();
+
+        assert_eq!(cli.home.as_deref(), Some("/tmp/bolt"));
+        assert!(matches!(cli.command, Some(Command::Run(_))));
+
+        let cli = parse(&["--reset"]).unwrap();
+        assert!(cli.reset && cli.command.is_none());
+    }
+
+    #[test]
+    fn invalid_arguments_are_rejected() {
+        let kind = |args: &[&str]| parse(args).err().map(|err| err.kind());
+
+        assert_eq!(kind(&["--port", "0"]), Some(ErrorKind::ValueValidation));
+        assert_eq!(kind(&["--port", "70000"]), Some(ErrorKind::ValueValidation));
+        assert_eq!(
+            kind(&["run", "Shop", "--iterations", "0"]),
+            Some(ErrorKind::ValueValidation)
+        );
+        assert_eq!(kind(&["run"]), Some(ErrorKind::MissingRequiredArgument));
+        assert_eq!(kind(&["send"]), Some(ErrorKind::MissingRequiredArgument));
+        assert_eq!(
+            kind(&["send", "a", "b", "c"]),
+            Some(ErrorKind::TooManyValues)
+        );
+        assert_eq!(kind(&["launch"]), Some(ErrorKind::InvalidSubcommand));
+
+        assert_eq!(kind(&["-v"]), Some(ErrorKind::DisplayVersion));
+        assert_eq!(kind(&["--version"]), Some(ErrorKind::DisplayVersion));
+    }
+
+    #[test]
+    fn the_clap_definition_is_valid() {
+        use clap::CommandFactory;
+
+        Cli::command().debug_assert();
+    }
+}
@@ -0,0 +1,287 @@
+use bolt_common::prelude::*;
+use clap::ValueEnum;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Reporter {
+    Pretty,
+    Junit,
+    Json,
+}
+
+impl Reporter {
+    pub fn default_output(&self) -> Option<&'static str> {
+        match self {
+            Reporter::Pretty => None,
+            Reporter::Junit => Some("bolt-report.xml"),
+            Reporter::Json => Some("bolt-report.json"),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    collection: &'a str,
+    environment: &'a str,
+    passed: bool,
+    summary: &'a RunSummary,
+    results: &'a [RunRequestResult],
+}
+
+pub struct Report<'a> {
+    pub collection: &'a str,
+    pub environment: &'a str,
+    pub results: &'a [RunRequestResult],
+    pub summary: &'a RunSummary,
+}
+
+impl Report<'_> {
+    pub fn render(&self, reporter: Reporter) -> String {
+        match reporter {
+            Reporter::Pretty => self.pretty(),
+            Reporter::Junit => self.junit(),
+            Reporter::Json => self.json(),
+        }
+    }
+
+    fn pretty(&self) -> String {
+        let mut out = format!("{}\n\n", self.collection);
+
+        for result in self.results {
+            out += &result_line(result);
+            out.push('\n');
+        }
+
+        out.push('\n');
+        out += &summary_text(self.summary);
+
+        out
+    }
+
+    fn json(&self) -> String {
+        let report = JsonReport {
+            collection: self.collection,
+            environment: self.environment,
+            passed: self.summary.failed_requests == 0,
+            summary: self.summary,
+            results: self.results,
+        };
+
+        serde_json::to_string_pretty(&report).unwrap()
+    }
+
+    // one testsuite per iteration, one testcase per request
+    fn junit(&self) -> String {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+
+        out += &format!(
+            "<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\" time=\"{}\">\n",
+            xml_escape(self.collection),
+            self.summary.requests,
+            self.summary.failed_requests,
+            seconds(self.summary.total_time),
+        );
+
+        for iteration in 0..self.summary.iterations {
+            let results: Vec<&RunRequestResult> = self
+                .results
+                .iter()
+                .filter(|result| result.iteration == iteration)
+                .collect();
+
+            if results.is_empty() {
+                continue;
+            }
+
+            let suite_name = if self.summary.iterations > 1 {
+                format!("{} (iteration {})", self.collection, iteration + 1)
+            } else {
+                self.collection.to_string()
+            };
+
+            let failures = results.iter().filter(|result| !result.passed()).count();
+            let time: u64 = results.iter().map(|result| result.time as u64).sum();
+
+            out += &format!(
+                "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" time=\"{}\">\n",
+                xml_escape(&suite_name),
+                results.len(),
+                failures,
+                seconds(time),
+            );
+
+            for result in results {
+                out += &format!(
+                    "    <testcase name=\"{}\" classname=\"{}\" time=\"{}\">\n",
+                    xml_escape(&format!("{} {}", result.method, result.name)),
+                    xml_escape(self.collection),
+                    seconds(result.time as u64),
+                );
+
+                if result.failed {
+                    out += &format!(
+                        "      <error message=\"{}\">{}</error>\n",
+                        xml_escape(&result.error),
+                        xml_escape(&result.url),
+                    );
+                }
+
+                for test in result.tests.iter().filter(|test| !test.passed) {
+                    out += &format!(
+                        "      <failure message=\"{}\">{}</failure>\n",
+                        xml_escape(&test.name),
+                        xml_escape(&test.message),
+                    );
+                }
+
+                out += "    </testcase>\n";
+            }
+
+            out += "  </testsuite>\n";
+        }
+
+        out += "</testsuites>\n";
+
+        out
+    }
+}
+
+// e.g. "  PASS  GET login  200  12 ms" followed by the failed assertions
+pub fn result_line(result: &RunRequestResult) -> String {
+    let verdict = if result.passed() { "PASS" } else { "FAIL" };
+
+    let status = if result.failed {
+        "---".to_string()
+    } else {
+        result.status.to_string()
+    };
+
+    let mut line = format!(
+        "  {}  {} {}  {}  {} ms",
+        verdict, result.method, result.name, status, result.time
+    );
+
+    if result.failed {
+        line += &format!("\n        {}", result.error);
+    }
+
+    for test in result.tests.iter().filter(|test| !test.passed) {
+        line += &format!("\n        {}: {}", test.name, test.message);
+    }
+
+    line
+}
+
+pub fn summary_text(summary: &RunSummary) -> String {
+    let mut out = format!(
+        "{} iteration(s), {}/{} requests passed, {}/{} assertions passed\n",
+        summary.iterations,
+        summary.requests - summary.failed_requests,
+        summary.requests,
+        summary.assertions - summary.failed_assertions,
+        summary.assertions,
+    );
+
+    out += &format!(
+        "avg {} ms, max {} ms, total {} ms\n",
+        summary.average_time(),
+        summary.max_time,
+        summary.total_time
+    );
+
+    if summary.stopped {
+        out += "stopped after the first failure\n";
+    }
+
+    out
+}
+
+fn seconds(ms: u64) -> String {
+    format!("{:.3}", ms as f64 / 1000.0)
+}
+
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // not allowed in XML 1.0
+            c if (c as u32) < 0x20 && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(iteration: u32, name: &str, time: u32) -> RunRequestResult {
+        RunRequestResult {
+            iteration,
+            name: name.to_string(),
+            url: "http://api.test/users".to_string(),
+            status: 200,
+            time,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn junit_has_a_suite_per_iteration_and_escapes_text() {
+        let mut failed_test = result(0, "create <user>", 250);
+        failed_test.tests = vec![AssertionResult {
+            name: "Status equals 201".to_string(),
+            passed: false,
+            message: "status is \"200\"".to_string(),
+        }];
+
+        let mut failed_request = result(1, "list", 0);
+        failed_request.failed = true;
+        failed_request.error = "connection refused".to_string();
+
+        let results = vec![result(0, "list", 1500), failed_test, failed_request];
+        let summary = RunSummary::from_results(&results);
+
+        let report = Report {
+            collection: "Users & co",
+            environment: "",
+            results: &results,
+            summary: &summary,
+        };
+
+        let expected = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="Users &amp; co" tests="3" failures="2" time="1.750">
+  <testsuite name="Users &amp; co (iteration 1)" tests="2" failures="1" time="1.750">
+    <testcase name="GET list" classname="Users &amp; co" time="1.500">
+    </testcase>
+    <testcase name="GET create &lt;user&gt;" classname="Users &amp; co" time="0.250">
+      <failure message="Status equals 201">status is &quot;200&quot;</failure>
+    </testcase>
+  </testsuite>
+  <testsuite name="Users &amp; co (iteration 2)" tests="1" failures="1" time="0.000">
+    <testcase name="GET list" classname="Users &amp; co" time="0.000">
+      <error message="connection refused">http://api.test/users</error>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#;
+
+        assert_eq!(report.render(Reporter::Junit), expected);
+    }
+
+    #[test]
+    fn reporters_have_lowercase_names_and_default_files() {
+        assert_eq!(Reporter::from_str("junit", false), Ok(Reporter::Junit));
+        assert_eq!(Reporter::from_str("pretty", false), Ok(Reporter::Pretty));
+        assert_eq!(Reporter::Json.default_output(), Some("bolt-report.json"));
+        assert_eq!(Reporter::Pretty.default_output(), None);
+    }
+}
@@ -0,0 +1,215 @@
+use bolt_common::prelude::*;
+use std::path::Path;
+
+use super::report::{result_line, summary_text, Report, Reporter};
+use super::state::{
+    find_collection, load_state, read_collection_file, select_environment, state_path,
+};
+use clap::Args;
+
+#[derive(Args)]
+pub struct RunArgs {
+    /// Name of a collection saved in the app, or the path to an exported collection file
+    collection: String,
+
+    /// Use this environment instead of the active one
+    #[arg(long, value_name = "NAME")]
+    env: Option<String>,
+
+    /// Report format
+    #[arg(long, value_enum, default_value = "pretty")]
+    reporter: Reporter,
+
+    /// Where to write the report, bolt-report.xml or bolt-report.json by default
+    #[arg(long, value_name = "PATH")]
+    output: Option<String>,
+
+    /// Stop at the first failure
+    #[arg(long)]
+    bail: bool,
+
+    /// Run the collection n times
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    iterations: u32,
+
+    /// Pause between two requests
+    #[arg(long, default_value_t = 0, value_name = "MS")]
+    delay: u64,
+
+    /// CSV or JSON file, one iteration per row
+    #[arg(long, value_name = "PATH")]
+    data: Option<String>,
+
+    /// State file to read collections and environments from
+    #[arg(long, value_name = "PATH")]
+    state: Option<String>,
+}
+
+// 0 when everything passed, 1 on failures
+pub fn run_command(args: RunArgs) -> Result<i32, String> {
+    let config = RunnerConfig {
+        delay: args.delay,
+        iterations: args.iterations,
+        stop_on_failure: args.bail,
+        data_file: args.data.clone().unwrap_or_default(),
+    };
+
+    let passed = run(args, config)?;
+
+    Ok(if passed { 0 } else { 1 })
+}
+
+fn run(args: RunArgs, config: RunnerConfig) -> Result<bool, String> {
+    let mut state = load_state(&state_path(args.state.as_deref()))?;
+
+    let collection = if Path::new(&args.collection).is_file() {
+        read_collection_file(&args.collection)?
+    } else {
+        find_collection(&state, &args.collection)?
+    };
+
+    let environment = select_environment(&mut state, args.env.as_deref())?;
+
+    // the run only changes the in memory state, the saved one is left alone
+    bolt_http::cookies::sync_cookies(&mut state);
+    bolt_http::set_main_state(state);
+
+    println!("Running {}", collection.name);
+
+    if !environment.is_empty() {
+        println!("Environment {environment}");
+    }
+
+    println!();
+
+    let mut results = vec![];
+
+    let runtime = tokio::runtime::Runtime::new().map_err(|err| err.to_string())?;
+
+    let summary = runtime.block_on(crate::runner::run_collection(
+        &collection,
+        &config,
+        |result, _total| {
+            println!("{}", result_line(result));
+
+            results.push(result.clone());
+        },
+    ))?;
+
+    println!();
+    print!("{}", summary_text(&summary));
+
+    let report = Report {
+        collection: &collection.name,
+        environment: &environment,
+        results: &results,
+        summary: &summary,
+    };
+
+    let output = args
+        .output
+        .or_else(|| args.reporter.default_output().map(String::from));
+
+    if let Some(path) = output {
+        std::fs::write(&path, report.render(args.reporter))
+            .map_err(|err| format!("could not write the report to {path}: {err}"))?;
+
+        println!("Report written to {path}");
+    }
+
+    Ok(summary.failed_requests == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{execute, Command};
+    use std::io::{Read, Write};
+
+    // answers every request with 200 OK
+    fn serve() -> String {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut request = [0; 4096];
+                let _ = stream.read(&mut request);
+
+                let _ = stream.write_all(
+                    b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok",
+                );
+            }
+        });
+
+        format!("http://{addr}/")
+    }
+
+    // a state with one collection whose request expects the given status
+    fn state_file(dir: &Path, url: &str, status: &str) -> String {
+        let mut request = HttpRequest::new();
+        request.name = "ping".to_string();
+        request.url = url.to_string();
+        request.assertions = vec![Assertion {
+            kind: AssertionKind::STATUS_EQUALS,
+            target: String::new(),
+            expected: status.to_string(),
+            enabled: true,
+        }];
+
+        let mut collection = Collection::new();
+        collection.name = "api".to_string();
+        collection.requests = vec![request];
+
+        let mut state = MainState::new();
+        state.collections = vec![collection];
+
+        let path = dir.join(format!("state-{status}.json"));
+        std::fs::write(&path, serde_json::to_string(&state).unwrap()).unwrap();
+
+        path.to_string_lossy().to_string()
+    }
+
+    fn run_args(state: &str, collection: &str, output: &Path) -> RunArgs {
+        RunArgs {
+            collection: collection.to_string(),
+            env: None,
+            reporter: Reporter::Junit,
+            output: Some(output.to_string_lossy().to_string()),
+            bail: false,
+            iterations: 1,
+            delay: 0,
+            data: None,
+            state: Some(state.to_string()),
+        }
+    }
+
+    #[test]
+    fn exit_codes_follow_the_results() {
+        let dir = std::env::temp_dir().join(format!("bolt-run-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let url = serve();
+        let report = dir.join("report.xml");
+        let run = |state: &str, collection: &str| {
+            execute(Command::Run(run_args(state, collection, &report)))
+        };
+
+        let passing = state_file(&dir, &url, "200");
+        assert_eq!(run(&passing, "api"), 0);
+
+        let junit = std::fs::read_to_string(&report).unwrap();
+        assert!(junit.contains("<testsuites name=\"api\" tests=\"1\" failures=\"0\""));
+
+        let failing = state_file(&dir, &url, "404");
+        assert_eq!(run(&failing, "api"), 1);
+
+        let junit = std::fs::read_to_string(&report).unwrap();
+        assert!(junit.contains("<failure message=\"Status equals 404\">status is 200</failure>"));
+
+        // errors before the run, like an unknown collection
+        assert_eq!(run(&passing, "nope"), 2);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,504 @@
+use base64::{engine::general_purpose, Engine};
+use bolt_common::prelude::*;
+use clap::Args;
+
+use super::state::{load_state, select_environment, state_path};
+use super::terminal::{pretty_json, Style};
+
+#[derive(Args)]
+pub struct SendArgs {
+    /// URL, METHOD URL, or a saved request as "collection/request" or the name of an HTTP tab
+    #[arg(required = true, num_args = 1..=2, value_name = "[METHOD] URL|REQUEST")]
+    target: Vec<String>,
+
+    /// HTTP method, GET by default or POST when a body is given
+    #[arg(short = 'X', long = "request", value_name = "METHOD")]
+    method: Option<String>,
+
+    /// Header as "Name: value", may be repeated
+    #[arg(short = 'H', long = "header", value_name = "HEADER")]
+    headers: Vec<String>,
+
+    /// Request body, @path reads it from a file
+    #[arg(short = 'd', long = "data", value_name = "BODY")]
+    data: Option<String>,
+
+    /// Use this environment instead of the active one
+    #[arg(long, value_name = "NAME")]
+    env: Option<String>,
+
+    /// Print the response as JSON, for piping
+    #[arg(long)]
+    json: bool,
+
+    /// Write the body to a file instead of printing it
+    #[arg(short, long, value_name = "PATH")]
+    output: Option<String>,
+
+    /// State file to read saved requests and environments from
+    #[arg(long, value_name = "PATH")]
+    state: Option<String>,
+}
+
+// 0 when the request went through and its assertions passed
+pub fn send_command(args: SendArgs) -> Result<i32, String> {
+    let mut state = load_state(&state_path(args.state.as_deref()))?;
+
+    let request = build_request(&args, &state)?;
+
+    select_environment(&mut state, args.env.as_deref())?;
+
+    bolt_http::cookies::sync_cookies(&mut state);
+    bolt_http::set_main_state(state);
+
+    let runtime = tokio::runtime::Runtime::new().map_err(|err| err.to_string())?;
+
+    let send_request = request.to_send_request(0);
+    let url = send_request.url.clone();
+
+    let resp = runtime.block_on(bolt_http::http_send(send_request));
+
+    if let Some(path) = &args.output {
+        if !resp.failed {
+            // text bodies only come as text
+            let bytes = if resp.body_base64.is_empty() {
+                resp.body.clone().into_bytes()
+            } else {
+                general_purpose::STANDARD
+                    .decode(&resp.body_base64)
+                    .map_err(|err| err.to_string())?
+            };
+
+            std::fs::write(path, bytes).map_err(|err| format!("could not write {path}: {err}"))?;
+        }
+    }
+
+    if args.json {
+        print_json(&request, &url, &resp, args.output.is_some());
+    } else {
+        print_pretty(&resp, args.output.as_deref());
+    }
+
+    let passed = !resp.failed && resp.tests.iter().all(|test| test.passed);
+
+    Ok(if passed { 0 } else { 1 })
+}
+
+fn build_request(args: &SendArgs, state: &MainState) -> Result<HttpRequest, String> {
+    let (positional, target) = match args.target.as_slice() {
+        [method, target] => (Some(method_arg(method)?), target.clone()),
+        [target] => (None, target.clone()),
+        _ => unreachable!(),
+    };
+
+    let method = match &args.method {
+        Some(name) => Some(method_arg(name)?),
+        None => positional,
+    };
+
+    // METHOD URL is always ad hoc
+    let saved = if positional.is_none() {
+        find_saved_request(state, &target)
+    } else {
+        None
+    };
+
+    let mut request = match saved {
+        Some(request) => request,
+
+        None => {
+            let mut request = HttpRequest::new();
+            request.url = with_scheme(&target);
+            request.params = vec![];
+            request.headers = vec![];
+
+            request
+        }
+    };
+
+    for header in &args.headers {
+        let (name, value) = header
+            .split_once(':')
+            .ok_or_else(|| format!("invalid header {header}, use \"Name: value\""))?;
+
+        request
+            .headers
+            .push(vec![name.trim().to_string(), value.trim().to_string()]);
+    }
+
+    if let Some(data) = &args.data {
+        request.body = match data.strip_prefix('@') {
+            Some(path) => std::fs::read_to_string(path)
+                .map_err(|err| format!("could not read {path}: {err}"))?,
+            None => data.clone(),
+        };
+
+        // like curl, a body that is not JSON is sent as a form
+        if serde_json::from_str::<serde::de::IgnoredAny>(&request.body).is_ok() {
+            request.body_type = HttpBodyType::JSON;
+        } else {
+            request.body_type = HttpBodyType::RAW;
+
+            let has_content_type = request
+                .headers
+                .iter()
+                .any(|h| h[0].eq_ignore_ascii_case("content-type"));
+
+            if !has_content_type {
+                request.headers.push(vec![
+                    "Content-Type".to_string(),
+                    "application/x-www-form-urlencoded".to_string(),
+                ]);
+            }
+        }
+
+        if method.is_none() && request.method == HttpMethod::GET {
+            request.method = HttpMethod::POST;
+        }
+    }
+
+    if let Some(method) = method {
+        request.method = method;
+    }
+
+    Ok(request)
+}
+
+fn method_arg(name: &str) -> Result<HttpMethod, String> {
+    parse_method(name).ok_or_else(|| format!("unknown method {name}"))
+}
+
+fn parse_method(name: &str) -> Option<HttpMethod> {
+    (0..HttpMethod::count())
+        .map(HttpMethod::from)
+        .find(|method| method.to_string().eq_ignore_ascii_case(name))
+}
+
+// "collection/request" or the name of a request in the HTTP page
+pub(super) fn find_saved_request(state: &MainState, reference: &str) -> Option<HttpRequest> {
+    if reference.contains("://") {
+        return None;
+    }
+
+    if let Some((collection, name)) = reference.split_once('/') {
+        let found = state
+            .collections
+            .iter()
+            .filter(|col| col.name.trim().eq_ignore_ascii_case(collection.trim()))
+            .flat_map(|col| col.requests.iter())
+            .find(|request| request.name.trim().eq_ignore_ascii_case(name.trim()));
+
+        if found.is_some() {
+            return found.cloned();
+        }
+    }
+
+    state
+        .http_requests
+        .iter()
+        .find(|request| request.name.trim().eq_ignore_ascii_case(reference.trim()))
+        .cloned()
+}
+
+fn with_scheme(url: &str) -> String {
+    if url.contains("://") || url.starts_with("{{") {
+        url.to_string()
+    } else {
+        format!("http://{url}")
+    }
+}
+
+fn print_pretty(resp: &SendHttpResponse, output: Option<&str>) {
+    let style = Style::stdout();
+
+    if resp.failed {
+        eprintln!("error: {}", resp.body);
+
+        return;
+    }
+
+    let reason = reqwest::StatusCode::from_u16(resp.status)
+        .ok()
+        .and_then(|status| status.canonical_reason())
+        .unwrap_or_default();
+
+    let version = if resp.connection.http_version.is_empty() {
+        "HTTP".to_string()
+    } else {
+        resp.connection.http_version.clone()
+    };
+
+    println!(
+        "{} {}",
+        style.dim(&version),
+        style.status(resp.status, format!("{} {}", resp.status, reason).trim())
+    );
+
+    for header in &resp.headers {
+        println!("{}: {}", style.bold(&header[0]), header[1]);
+    }
+
+    println!();
+    println!("{}", style.dim(&timing_line(resp)));
+
+    if !resp.tests.is_empty() {
+        println!();
+
+        for test in &resp.tests {
+            println!("{}  {}  {}", style.passed(test.passed), test.name, style.dim(&test.message));
+        }
+    }
+
+    // the server answered but the query failed, usually with a 200
+    if !resp.graphql_errors.is_empty() {
+        println!();
+
+        for error in &resp.graphql_errors {
+            println!("{}  {error}", style.paint("1;31", "GraphQL error"));
+        }
+    }
+
+    println!();
+
+    if let Some(path) = output {
+        println!("{}", style.dim(&format!("{} B written to {path}", resp.size)));
+    } else if !resp.response_type.is_text() {
+        println!(
+            "{}",
+            style.dim(&format!(
+                "{} B of {}, save it with --output <path>",
+                resp.size, resp.content_type
+            ))
+        );
+    } else if let Some(json) = pretty_json(&resp.body, &style) {
+        println!("{json}");
+    } else {
+        println!("{}", resp.body);
+    }
+}
+
+// e.g. "dns 1.2 ms  connect 0.4 ms  ttfb 20 ms  download 0.1 ms  total 22 ms  512 B"
+fn timing_line(resp: &SendHttpResponse) -> String {
+    let timing = &resp.timing;
+
+    let phases = [
+        ("dns", Some(timing.dns)),
+        ("connect", timing.connect),
+        ("tls", timing.tls),
+        ("ttfb", Some(timing.ttfb)),
+        ("download", Some(timing.download)),
+    ];
+
+    // phases that were not measured or took no time are left out
+    let mut line: Vec<String> = phases
+        .iter()
+        .filter_map(|(name, duration)| match duration {
+            Some(duration) if *duration > 0 => Some(format!("{name} {}", format_micros(*duration))),
+            _ => None,
+        })
+        .collect();
+
+    line.push(format!("total {}", format_micros(timing.total)));
+    line.push(format!("{} B", resp.size));
+
+    line.join("  ")
+}
+
+fn format_micros(micros: u64) -> String {
+    if micros < 10_000 {
+        format!("{:.1} ms", micros as f64 / 1000.0)
+    } else {
+        format!("{} ms", micros / 1000)
+    }
+}
+
+fn print_json(request: &HttpRequest, url: &str, resp: &SendHttpResponse, saved_body: bool) {
+    let body = if resp.failed || saved_body {
+        serde_json::Value::Null
+    } else if !resp.response_type.is_text() {
+        serde_json::Value::String(resp.body_base64.clone())
+    } else {
+        serde_json::from_str(&resp.body).unwrap_or_else(|_| resp.body.clone().into())
+    };
+
+    let output = serde_json::json!({
+        "method": request.method,
+        "url": url,
+        "failed": resp.failed,
+        "error": if resp.failed { resp.body.clone() } else { String::new() },
+        "status": resp.status,
+        "http_version": resp.connection.http_version,
+        "headers": resp.headers,
+        "content_type": resp.content_type,
+        "body_base64": !resp.failed && !saved_body && !resp.response_type.is_text(),
+        "body": body,
+        "size": resp.size,
+        "time": resp.time,
+        "timing": resp.timing,
+        "connection": resp.connection,
+        "tests": resp.tests,
+        "extracted": resp.extracted,
+        "graphql_errors": resp.graphql_errors,
+    });
+
+    println!("{}", serde_json::to_string_pretty(&output).unwrap());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct Send {
+        #[command(flatten)]
+        args: SendArgs,
+    }
+
+    fn build(args: &[&str], state: &MainState) -> Result<HttpRequest, String> {
+        let send = Send::try_parse_from(std::iter::once("send").chain(args.iter().copied()))
+            .map_err(|err| err.to_string())?;
+
+        build_request(&send.args, state)
+    }
+
+    // HttpRequest is not Debug
+    fn error(args: &[&str], state: &MainState) -> String {
+        build(args, state).err().unwrap()
+    }
+
+    fn saved_state() -> MainState {
+        let mut saved = HttpRequest::new();
+        saved.name = "Login".to_string();
+        saved.method = HttpMethod::PUT;
+        saved.url = "{{base}}/login".to_string();
+
+        let mut collection = Collection::new();
+        collection.name = "Shop".to_string();
+        collection.requests = vec![saved];
+
+        let mut tab = HttpRequest::new();
+        tab.name = "Health".to_string();
+        tab.url = "http://localhost/health".to_string();
+
+        let mut state = MainState::new();
+        state.collections = vec![collection];
+        state.http_requests = vec![tab];
+
+        state
+    }
+
+    #[test]
+    fn ad_hoc_requests_work_like_curl() {
+        let state = MainState::new();
+
+        let request = build(&["example.org/a?b=1"], &state).unwrap();
+        assert_eq!(request.method, HttpMethod::GET);
+        assert_eq!(request.url, "http://example.org/a?b=1");
+        assert!(request.headers.is_empty());
+
+        let request = build(
+            &["delete", "https://example.org/1", "-H", "X-Key:  a:b "],
+            &state,
+        )
+        .unwrap();
+        assert_eq!(request.method, HttpMethod::DELETE);
+        assert_eq!(request.url, "https://example.org/1");
+        assert_eq!(request.headers, vec![vec!["X-Key", "a:b"]]);
+
+        // -X wins over the positional method
+        let request = build(&["GET", "example.org", "-X", "patch"], &state).unwrap();
+        assert_eq!(request.method, HttpMethod::PATCH);
+
+        assert_eq!(
+            error(&["FETCH", "example.org"], &state),
+            "unknown method FETCH"
+        );
+        assert!(error(&["example.org", "-H", "no colon"], &state)
+            .starts_with("invalid header no colon"));
+    }
+
+    #[test]
+    fn a_body_makes_a_post() {
+        let state = MainState::new();
+
+        let request = build(&["example.org", "-d", r#"{"a": 1}"#], &state).unwrap();
+        assert_eq!(request.method, HttpMethod::POST);
+        assert_eq!(request.body_type, HttpBodyType::JSON);
+        assert_eq!(request.body, r#"{"a": 1}"#);
+        assert!(request.headers.is_empty());
+
+        // like curl, anything else is a form
+        let request = build(&["PUT", "example.org", "-d", "a=1&b=2"], &state).unwrap();
+        assert_eq!(request.method, HttpMethod::PUT);
+        assert_eq!(request.body_type, HttpBodyType::RAW);
+        assert_eq!(
+            request.headers,
+            vec![vec!["Content-Type", "application/x-www-form-urlencoded"]]
+        );
+
+        let request = build(
+            &["example.org", "-d", "<a/>", "-H", "content-type: text/xml"],
+            &state,
+        )
+        .unwrap();
+        assert_eq!(request.headers, vec![vec!["content-type", "text/xml"]]);
+
+        let path = std::env::temp_dir().join(format!("bolt-send-{}.json", uuid::Uuid::new_v4()));
+        std::fs::write(&path, "[1, 2]").unwrap();
+
+        let data = format!("@{}", path.display());
+        let request = build(&["example.org", "-d", &data], &state);
+        std::fs::remove_file(&path).unwrap();
+
+        let request = request.unwrap();
+        assert_eq!(request.body, "[1, 2]");
+        assert_eq!(request.body_type, HttpBodyType::JSON);
+
+        assert!(error(&["example.org", "-d", &data], &state)
+            .starts_with(&format!("could not read {}", path.display())));
+    }
+
+    #[test]
+    fn saved_requests_by_name() {
+        let state = saved_state();
+
+        let request = build(&["shop/login"], &state).unwrap();
+        assert_eq!(request.method, HttpMethod::PUT);
+        assert_eq!(request.url, "{{base}}/login");
+
+        // a body keeps the saved method
+        let request = build(&["Shop/Login", "-d", "{}"], &state).unwrap();
+        assert_eq!(request.method, HttpMethod::PUT);
+
+        let request = build(&["health"], &state).unwrap();
+        assert_eq!(request.url, "http://localhost/health");
+
+        // METHOD URL and full urls are never looked up
+        assert_eq!(
+            build(&["GET", "health"], &state).unwrap().url,
+            "http://health"
+        );
+        assert!(find_saved_request(&state, "http://shop/login").is_none());
+        assert!(find_saved_request(&state, "shop/logout").is_none());
+    }
+
+    #[test]
+    fn timing_lines() {
+        let mut resp = SendHttpResponse::new();
+        resp.size = 512;
+        resp.timing = HttpTiming {
+            dns: 1_200,
+            connect: None,
+            tls: Some(0),
+            ttfb: 20_000,
+            download: 100,
+            total: 21_300,
+        };
+
+        assert_eq!(
+            timing_line(&resp),
+            "dns 1.2 ms  ttfb 20 ms  download 0.1 ms  total 21 ms  512 B"
+        );
+    }
+}
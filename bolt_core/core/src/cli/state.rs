@@ -0,0 +1,149 @@
+use bolt_common::prelude::*;
+use std::path::Path;
+
+use crate::utils::get_home;
+
+pub fn state_path(custom: Option<&str>) -> String {
+    match custom {
+        Some(path) => path.to_string(),
+        None => get_home() + "state.json",
+    }
+}
+
+// a missing file is an empty state, so exported collections run on machines that never
+// opened the app
+pub fn load_state(path: &str) -> Result<MainState, String> {
+    if !Path::new(path).exists() {
+        return Ok(MainState::new());
+    }
+
+    let content =
+        std::fs::read_to_string(path).map_err(|err| format!("could not read {path}: {err}"))?;
+
+    serde_json::from_str(&content).map_err(|err| format!("invalid state file {path}: {err}"))
+}
+
+// the port of a running app that uses this home, it saves its own copy of the state over
+// state.json. A lock left behind by an app that is gone has a free port.
+pub fn running_app_port() -> Option<u16> {
+    let lock = std::fs::read_to_string(get_home() + "core.lock").ok()?;
+    let (address, port) = lock_address(&lock)?;
+
+    address_in_use(&address, port).then_some(port)
+}
+
+// address:port of the app, locks written before the address was recorded hold the port
+fn lock_address(lock: &str) -> Option<(String, u16)> {
+    let lock = lock.trim();
+
+    match lock.rsplit_once(':') {
+        Some((address, port)) => Some((address.to_string(), port.parse().ok()?)),
+        None => Some(("127.0.0.1".to_string(), lock.parse().ok()?)),
+    }
+}
+
+// binding the address the app bound is the only probe that works for any --address, a
+// listener on one interface leaves the port free on the others
+fn address_in_use(address: &str, port: u16) -> bool {
+    matches!(
+        std::net::TcpListener::bind((address, port)),
+        Err(err) if err.kind() == std::io::ErrorKind::AddrInUse
+    )
+}
+
+pub fn save_state(path: &str, state: &MainState) -> Result<(), String> {
+    if let Some(dir) = Path::new(path).parent() {
+        if !dir.as_os_str().is_empty() {
+            std::fs::create_dir_all(dir)
+                .map_err(|err| format!("could not create {}: {err}", dir.display()))?;
+        }
+    }
+
+    let content = serde_json::to_string(state).unwrap();
+
+    std::fs::write(path, content).map_err(|err| format!("could not write {path}: {err}"))
+}
+
+pub fn find_collection(state: &MainState, name: &str) -> Result<Collection, String> {
+    let found = state
+        .collections
+        .iter()
+        .find(|col| col.name == name)
+        .or_else(|| {
+            state
+                .collections
+                .iter()
+                .find(|col| col.name.trim().eq_ignore_ascii_case(name.trim()))
+        });
+
+    match found {
+        Some(collection) => Ok(collection.clone()),
+
+        None => {
+            let names: Vec<&str> = state.collections.iter().map(|col| col.name.as_str()).collect();
+
+            if names.is_empty() {
+                Err(format!("no collection named {name}, there are no saved collections"))
+            } else {
+                Err(format!(
+                    "no collection named {name}, saved collections: {}",
+                    names.join(", ")
+                ))
+            }
+        }
+    }
+}
+
+pub fn read_collection_file(path: &str) -> Result<Collection, String> {
+    let content =
+        std::fs::read_to_string(path).map_err(|err| format!("could not read {path}: {err}"))?;
+
+    serde_json::from_str(&content).map_err(|err| format!("{path} is not an exported collection: {err}"))
+}
+
+// makes the named environment the active one, returns the name of the active environment
+pub fn select_environment(state: &mut MainState, name: Option<&str>) -> Result<String, String> {
+    if let Some(name) = name {
+        let index = state
+            .environments
+            .iter()
+            .position(|env| env.name == name)
+            .ok_or_else(|| format!("no environment named {name}"))?;
+
+        state.env_active = Some(index);
+    }
+
+    Ok(state
+        .active_environment()
+        .map(|env| env.name.clone())
+        .unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locks_record_the_address() {
+        assert_eq!(
+            lock_address("0.0.0.0:5555\n"),
+            Some(("0.0.0.0".to_string(), 5555))
+        );
+        assert_eq!(lock_address("3344"), Some(("127.0.0.1".to_string(), 3344)));
+        assert_eq!(lock_address("localhost:x"), None);
+        assert_eq!(lock_address(""), None);
+    }
+
+    #[test]
+    fn apps_on_other_addresses_are_found() {
+        // another loopback address stands in for a non-loopback --address
+        let app = std::net::TcpListener::bind("127.0.0.2:0").unwrap();
+        let port = app.local_addr().unwrap().port();
+
+        assert!(address_in_use("127.0.0.2", port));
+
+        drop(app);
+
+        assert!(!address_in_use("127.0.0.2", port));
+    }
+}
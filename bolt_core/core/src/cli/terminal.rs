@@ -0,0 +1,149 @@
+use std::io::IsTerminal;
+
+pub struct Style {
+    color: bool,
+}
+
+impl Style {
+    // colors only when printing to a terminal, NO_COLOR turns them off
+    pub fn stdout() -> Self {
+        Self {
+            color: std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none(),
+        }
+    }
+
+    pub fn paint(&self, code: &str, text: &str) -> String {
+        if self.color {
+            format!("\x1b[{code}m{text}\x1b[0m")
+        } else {
+            text.to_string()
+        }
+    }
+
+    pub fn bold(&self, text: &str) -> String {
+        self.paint("1", text)
+    }
+
+    pub fn dim(&self, text: &str) -> String {
+        self.paint("2", text)
+    }
+
+    pub fn status(&self, status: u16, text: &str) -> String {
+        match status {
+            200..=299 => self.paint("1;32", text),
+            300..=399 => self.paint("1;36", text),
+            400..=499 => self.paint("1;33", text),
+            _ => self.paint("1;31", text),
+        }
+    }
+
+    pub fn passed(&self, passed: bool) -> String {
+        if passed {
+            self.paint("1;32", "PASS")
+        } else {
+            self.paint("1;31", "FAIL")
+        }
+    }
+}
+
+// indents and colors JSON text token by token, which keeps the keys in the order the
+// server sent them. None when the text is not valid JSON.
+pub fn pretty_json(text: &str, style: &Style) -> Option<String> {
+    serde_json::from_str::<serde::de::IgnoredAny>(text).ok()?;
+
+    let mut out = String::with_capacity(text.len() * 2);
+    let mut chars = text.chars().peekable();
+
+    // the open brackets, to know whether a comma starts a key
+    let mut open: Vec<char> = vec![];
+
+    // a key is a string followed by a colon
+    let mut expecting_key = false;
+
+    let newline = |out: &mut String, depth: usize| {
+        out.push('\n');
+        out.push_str(&"  ".repeat(depth));
+    };
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' | '[' => {
+                out.push(c);
+
+                // keep empty objects and arrays on one line
+                while chars.peek().is_some_and(|next| next.is_whitespace()) {
+                    chars.next();
+                }
+
+                if matches!(chars.peek(), Some('}') | Some(']')) {
+                    out.push(chars.next().unwrap());
+                } else {
+                    open.push(c);
+                    newline(&mut out, open.len());
+                    expecting_key = c == '{';
+                }
+            }
+
+            '}' | ']' => {
+                open.pop();
+                newline(&mut out, open.len());
+                out.push(c);
+            }
+
+            ',' => {
+                out.push(',');
+                newline(&mut out, open.len());
+
+                expecting_key = open.last() == Some(&'{');
+            }
+
+            ':' => {
+                out.push_str(": ");
+                expecting_key = false;
+            }
+
+            '"' => {
+                let mut string = String::from('"');
+
+                while let Some(c) = chars.next() {
+                    string.push(c);
+
+                    if c == '\\' {
+                        if let Some(escaped) = chars.next() {
+                            string.push(escaped);
+                        }
+                    } else if c == '"' {
+                        break;
+                    }
+                }
+
+                if expecting_key {
+                    out.push_str(&style.paint("34", &string));
+                } else {
+                    out.push_str(&style.paint("32", &string));
+                }
+            }
+
+            c if c.is_whitespace() => {}
+
+            _ => {
+                let mut literal = String::from(c);
+
+                while let Some(next) = chars.peek() {
+                    if next.is_whitespace() || matches!(next, ',' | '}' | ']') {
+                        break;
+                    }
+
+                    literal.push(chars.next().unwrap());
+                }
+
+                match literal.as_str() {
+                    "true" | "false" | "null" => out.push_str(&style.paint("35", &literal)),
+                    _ => out.push_str(&style.paint("33", &literal)),
+                }
+            }
+        }
+    }
+
+    Some(out)
+}
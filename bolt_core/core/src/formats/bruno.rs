@@ -0,0 +1,979 @@
+use bolt_common::prelude::*;
+use serde_json::Value;
+use std::path::Path;
+
+use super::postman::replace_path_variable;
+use super::Imported;
+
+const METHODS: [&str; 9] = [
+    "get", "post", "put", "delete", "patch", "options", "head", "connect", "trace",
+];
+
+// a request file starts with its meta block
+pub fn is_bruno(content: &str) -> bool {
+    content
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .is_some_and(|line| line == "meta {")
+}
+
+// a single .bru file, named like curl commands after the file
+pub fn import_bruno(content: &str, name: &str) -> Result<Imported, String> {
+    let mut importer = Importer::new();
+
+    let mut collection = Collection::new();
+    collection.name = name.to_string();
+
+    let blocks = parse_bru(content);
+
+    importer.item(&blocks, name, &Inherited::default(), &mut collection);
+
+    if !collection.requests.is_empty() {
+        importer.collections.push(collection);
+    }
+
+    Ok(importer.imported())
+}
+
+// a collection folder with bruno.json. every folder becomes a collection named after its
+// path, e.g. "API / Users", and the files in environments/ become environments
+pub fn import_bruno_folder(root: &Path) -> Result<Imported, String> {
+    let config = std::fs::read_to_string(root.join("bruno.json")).map_err(|_| {
+        format!(
+            "{} is not a Bruno collection, it has no bruno.json",
+            root.display()
+        )
+    })?;
+
+    let config: Value =
+        serde_json::from_str(&config).map_err(|err| format!("invalid bruno.json: {err}"))?;
+
+    let name = config["name"]
+        .as_str()
+        .map(str::to_string)
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| file_name(root));
+
+    let mut importer = Importer::new();
+
+    let mut inherited = Inherited::default();
+
+    if let Ok(content) = std::fs::read_to_string(root.join("collection.bru")) {
+        inherited = importer.inherited(&parse_bru(&content), &inherited, &name);
+    }
+
+    importer.folder(root, &name, &inherited)?;
+    importer.environments(&root.join("environments"), &name)?;
+
+    Ok(importer.imported())
+}
+
+// the headers and auth of collection.bru and folder.bru apply to the requests below them
+#[derive(Default, Clone)]
+struct Inherited {
+    headers: Vec<Vec<String>>,
+    auth: HttpAuth,
+}
+
+struct Block {
+    name: String,
+    lines: Vec<String>,
+}
+
+struct Importer {
+    collections: Vec<Collection>,
+    environments: Vec<Environment>,
+    ws_connections: Vec<WsConnection>,
+    warnings: Vec<String>,
+}
+
+impl Importer {
+    fn new() -> Self {
+        Importer {
+            collections: vec![],
+            environments: vec![],
+            ws_connections: vec![],
+            warnings: vec![],
+        }
+    }
+
+    fn imported(self) -> Imported {
+        Imported {
+            collections: self.collections,
+            environments: self.environments,
+            ws_connections: self.ws_connections,
+            warnings: self.warnings,
+            update: false,
+        }
+    }
+
+    fn folder(&mut self, dir: &Path, path: &str, inherited: &Inherited) -> Result<(), String> {
+        let entries = std::fs::read_dir(dir)
+            .map_err(|err| format!("could not read {}: {err}", dir.display()))?;
+
+        let mut files = vec![];
+        let mut folders = vec![];
+
+        for entry in entries.flatten() {
+            let entry_path = entry.path();
+            let entry_name = file_name(&entry_path);
+
+            if entry_name.starts_with('.') || entry_name == "node_modules" {
+                continue;
+            }
+
+            if entry_path.is_dir() {
+                // environments are read on their own
+                if !(entry_name == "environments" && dir.join("bruno.json").exists()) {
+                    folders.push(entry_path);
+                }
+            } else if entry_name.ends_with(".bru")
+                && entry_name != "folder.bru"
+                && entry_name != "collection.bru"
+            {
+                let content = std::fs::read_to_string(&entry_path)
+                    .map_err(|err| format!("could not read {}: {err}", entry_path.display()))?;
+
+                files.push((entry_name, parse_bru(&content)));
+            }
+        }
+
+        // in the order of the sidebar
+        files.sort_by(|(a_name, a), (b_name, b)| seq(a).cmp(&seq(b)).then(a_name.cmp(b_name)));
+
+        let mut collection = Collection::new();
+        collection.name = path.to_string();
+
+        for (file, blocks) in &files {
+            let name = pairs_of(blocks, "meta")
+                .into_iter()
+                .find(|pair| pair.0 == "name")
+                .map(|pair| pair.1)
+                .unwrap_or_else(|| file.trim_end_matches(".bru").to_string());
+
+            self.item(
+                blocks,
+                &format!("{path} / {name}"),
+                inherited,
+                &mut collection,
+            );
+        }
+
+        // the parent comes before its folders
+        if !collection.requests.is_empty() {
+            self.collections.push(collection);
+        }
+
+        let mut folders: Vec<(String, Vec<Block>, &Path)> = folders
+            .iter()
+            .map(|folder| {
+                let blocks = std::fs::read_to_string(folder.join("folder.bru"))
+                    .map(|content| parse_bru(&content))
+                    .unwrap_or_default();
+
+                let name = pairs_of(&blocks, "meta")
+                    .into_iter()
+                    .find(|pair| pair.0 == "name")
+                    .map(|pair| pair.1)
+                    .unwrap_or_else(|| file_name(folder));
+
+                (name, blocks, folder.as_path())
+            })
+            .collect();
+
+        folders
+            .sort_by(|(a_name, a, _), (b_name, b, _)| seq(a).cmp(&seq(b)).then(a_name.cmp(b_name)));
+
+        for (name, blocks, folder) in folders {
+            let path = format!("{path} / {name}");
+            let inherited = self.inherited(&blocks, inherited, &path);
+
+            self.folder(folder, &path, &inherited)?;
+        }
+
+        Ok(())
+    }
+
+    fn inherited(&mut self, blocks: &[Block], parent: &Inherited, context: &str) -> Inherited {
+        let mut inherited = parent.clone();
+
+        for (key, value, enabled) in pairs_of(blocks, "headers") {
+            if enabled {
+                inherited.headers.retain(|header| header[0] != key);
+                inherited.headers.push(vec![key, value]);
+            }
+        }
+
+        let mode = pairs_of(blocks, "auth")
+            .into_iter()
+            .find(|pair| pair.0 == "mode")
+            .map(|pair| pair.1)
+            .unwrap_or_else(|| "inherit".to_string());
+
+        if mode != "inherit" {
+            inherited.auth = self.auth(blocks, &mode, context);
+        }
+
+        self.scripts(blocks, context);
+
+        inherited
+    }
+
+    // a request goes into the collection, a WebSocket request into the connections
+    fn item(
+        &mut self,
+        blocks: &[Block],
+        context: &str,
+        inherited: &Inherited,
+        collection: &mut Collection,
+    ) {
+        let meta_pairs = pairs_of(blocks, "meta");
+        let meta = |key: &str| {
+            meta_pairs
+                .iter()
+                .find(|pair| pair.0 == key)
+                .map(|pair| pair.1.clone())
+                .unwrap_or_default()
+        };
+
+        match meta("type").as_str() {
+            "http" | "graphql" | "" => {
+                let mut request = HttpRequest::new();
+                request.name = meta("name");
+
+                let Some(method_block) = blocks
+                    .iter()
+                    .find(|block| METHODS.contains(&block.name.as_str()))
+                else {
+                    self.warn(context, "skipped, the file has no request");
+
+                    return;
+                };
+
+                request.method = parse_method(&method_block.name).unwrap_or(HttpMethod::GET);
+
+                let settings = pairs(method_block);
+                let setting = |key: &str| {
+                    settings
+                        .iter()
+                        .find(|pair| pair.0 == key)
+                        .map(|pair| pair.1.clone())
+                        .unwrap_or_default()
+                };
+
+                let mut url = setting("url");
+
+                for (key, value, _) in pairs_of(blocks, "params:path") {
+                    if value.is_empty() {
+                        self.warn(context, &format!("path variable :{key} has no value"));
+                    } else {
+                        url = replace_path_variable(&url, &key, &value);
+                    }
+                }
+
+                let params =
+                    self.enabled(pairs_of(blocks, "params:query"), "query parameter", context);
+
+                // the query is kept in params:query, the url repeats it
+                let url = url.split('?').next().unwrap_or_default().to_string();
+
+                if params.iter().all(|param| !param[1].is_empty()) {
+                    request.url = url;
+
+                    if !params.is_empty() {
+                        request.params = params;
+                    }
+                } else {
+                    let query: Vec<String> = params
+                        .iter()
+                        .map(|param| {
+                            if param[1].is_empty() {
+                                param[0].clone()
+                            } else {
+                                format!("{}={}", param[0], param[1])
+                            }
+                        })
+                        .collect();
+
+                    request.url = format!("{url}?{}", query.join("&"));
+                }
+
+                let mut headers = inherited.headers.clone();
+
+                for header in self.enabled(pairs_of(blocks, "headers"), "header", context) {
+                    headers.retain(|existing| existing[0] != header[0]);
+                    headers.push(header);
+                }
+
+                if !headers.is_empty() {
+                    request.headers = headers;
+                }
+
+                self.body(blocks, &setting("body"), &mut request, context);
+
+                request.auth = match setting("auth").as_str() {
+                    "inherit" => inherited.auth.clone(),
+                    mode => self.auth(blocks, mode, context),
+                };
+
+                self.scripts(blocks, context);
+
+                collection.requests.push(request);
+            }
+
+            "ws" => {
+                let mut connection = WsConnection::new();
+                connection.name = meta("name");
+
+                connection.url = pairs_of(blocks, "ws")
+                    .into_iter()
+                    .find(|pair| pair.0 == "url")
+                    .map(|pair| pair.1)
+                    .unwrap_or_default();
+
+                let headers = self.enabled(pairs_of(blocks, "headers"), "header", context);
+
+                if !headers.is_empty() {
+                    connection.out_headers = headers;
+                }
+
+                let params =
+                    self.enabled(pairs_of(blocks, "params:query"), "query parameter", context);
+
+                if !params.is_empty() {
+                    connection.out_params = params;
+                }
+
+                if let Some(message) = pairs_of(blocks, "body:ws")
+                    .into_iter()
+                    .find(|pair| pair.0 == "content")
+                {
+                    connection.out_buffer = message.1;
+                }
+
+                self.ws_connections.push(connection);
+            }
+
+            other => self.warn(
+                context,
+                &format!("skipped, {other} requests are not supported"),
+            ),
+        }
+    }
+
+    fn body(&mut self, blocks: &[Block], mode: &str, request: &mut HttpRequest, context: &str) {
+        match mode {
+            "json" => {
+                request.body_type = HttpBodyType::JSON;
+                request.body = text_of(blocks, "body:json");
+            }
+
+            "text" | "xml" | "sparql" => {
+                request.body_type = HttpBodyType::RAW;
+                request.body = text_of(blocks, &format!("body:{mode}"));
+            }
+
+            "graphql" => {
+                request.body_type = HttpBodyType::GRAPHQL;
+                request.graphql = GraphqlBody {
+                    query: text_of(blocks, "body:graphql"),
+                    variables: text_of(blocks, "body:graphql:vars"),
+                    operation_name: String::new(),
+                };
+            }
+
+            "formUrlEncoded" => {
+                let form = self.enabled(
+                    pairs_of(blocks, "body:form-urlencoded"),
+                    "form field",
+                    context,
+                );
+
+                request.body_type = HttpBodyType::FORM_URLENCODED;
+
+                if !form.is_empty() {
+                    request.form = form;
+                }
+            }
+
+            "multipartForm" => {
+                let mut fields = vec![];
+
+                for (key, value, enabled) in pairs_of(blocks, "body:multipart-form") {
+                    if !enabled {
+                        self.warn(
+                            context,
+                            &format!("disabled form field {key} is not imported"),
+                        );
+
+                        continue;
+                    }
+
+                    match file_path(&value) {
+                        Some(files) => {
+                            let mut files = files.split('|');
+
+                            fields.push(MultipartField {
+                                name: key.clone(),
+                                value: files.next().unwrap_or_default().to_string(),
+                                is_file: true,
+                            });
+
+                            if files.next().is_some() {
+                                self.warn(
+                                    context,
+                                    &format!("form field {key} has several files, only the first is imported"),
+                                );
+                            }
+                        }
+
+                        None => fields.push(MultipartField {
+                            name: key,
+                            value,
+                            is_file: false,
+                        }),
+                    }
+                }
+
+                request.body_type = HttpBodyType::MULTIPART;
+
+                if !fields.is_empty() {
+                    request.multipart = fields;
+                }
+            }
+
+            "file" => {
+                let file = pairs_of(blocks, "body:file")
+                    .into_iter()
+                    .filter(|pair| pair.2)
+                    .find_map(|pair| file_path(&pair.1));
+
+                if file.is_none() {
+                    self.warn(context, "the body file has no path");
+                }
+
+                request.body_type = HttpBodyType::BINARY;
+                request.body_file = file.unwrap_or_default();
+            }
+
+            "none" | "" => {}
+
+            mode => self.warn(context, &format!("{mode} bodies are not supported")),
+        }
+    }
+
+    fn auth(&mut self, blocks: &[Block], mode: &str, context: &str) -> HttpAuth {
+        let mut result = HttpAuth::new();
+
+        let params = pairs_of(blocks, &format!("auth:{mode}"));
+        let param = |key: &str| {
+            params
+                .iter()
+                .find(|pair| pair.0 == key)
+                .map(|pair| pair.1.clone())
+                .unwrap_or_default()
+        };
+
+        match mode {
+            "none" | "" => {}
+
+            "basic" | "digest" => {
+                result.auth_type = if mode == "basic" {
+                    HttpAuthType::BASIC
+                } else {
+                    HttpAuthType::DIGEST
+                };
+
+                result.username = param("username");
+                result.password = param("password");
+            }
+
+            "bearer" => {
+                result.auth_type = HttpAuthType::BEARER;
+                result.token = param("token");
+            }
+
+            "apikey" => {
+                result.auth_type = HttpAuthType::API_KEY;
+                result.key = param("key");
+                result.value = param("value");
+                result.location = if param("placement") == "queryparams" {
+                    ApiKeyLocation::QUERY
+                } else {
+                    ApiKeyLocation::HEADER
+                };
+            }
+
+            "oauth2" => {
+                result.auth_type = HttpAuthType::OAUTH2;
+
+                let oauth2 = &mut result.oauth2;
+
+                oauth2.grant_type = match param("grant_type").as_str() {
+                    "password" => OAuth2GrantType::PASSWORD,
+                    "authorization_code" => OAuth2GrantType::AUTHORIZATION_CODE,
+                    "client_credentials" | "" => OAuth2GrantType::CLIENT_CREDENTIALS,
+                    grant => {
+                        self.warn(
+                            context,
+                            &format!("the {grant} OAuth 2.0 grant is not supported"),
+                        );
+
+                        OAuth2GrantType::CLIENT_CREDENTIALS
+                    }
+                };
+
+                oauth2.token_url = param("access_token_url");
+                oauth2.auth_url = param("authorization_url");
+                oauth2.redirect_url = param("callback_url");
+                oauth2.client_id = param("client_id");
+                oauth2.client_secret = param("client_secret");
+                oauth2.scope = param("scope");
+                oauth2.client_auth_header = param("credentials_placement") != "body";
+
+                result.username = param("username");
+                result.password = param("password");
+            }
+
+            other => self.warn(context, &format!("{other} auth is not supported")),
+        }
+
+        result
+    }
+
+    fn scripts(&mut self, blocks: &[Block], context: &str) {
+        let has_script = blocks.iter().any(|block| {
+            (block.name.starts_with("script:") || block.name == "tests")
+                && block.lines.iter().any(|line| !line.trim().is_empty())
+        });
+
+        if has_script {
+            self.warn(context, "scripts and tests are not imported");
+        }
+
+        let has_vars = blocks.iter().any(|block| {
+            (block.name == "vars:pre-request" || block.name == "vars:post-response")
+                && !block.lines.iter().all(|line| line.trim().is_empty())
+        });
+
+        if has_vars {
+            self.warn(context, "request variables are not imported");
+        }
+
+        if blocks.iter().any(|block| block.name == "assert") {
+            self.warn(context, "assertions are not imported");
+        }
+    }
+
+    fn environments(&mut self, dir: &Path, name: &str) -> Result<(), String> {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return Ok(());
+        };
+
+        let mut files: Vec<_> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == "bru"))
+            .collect();
+
+        files.sort();
+
+        for file in files {
+            let content = std::fs::read_to_string(&file)
+                .map_err(|err| format!("could not read {}: {err}", file.display()))?;
+
+            let blocks = parse_bru(&content);
+
+            let environment_name = file
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default();
+
+            let context = format!("environment {environment_name}");
+
+            let mut variables = self.enabled(pairs_of(&blocks, "vars"), "variable", &context);
+
+            // secret values stay on the machine they were set on
+            let secrets: Vec<String> = blocks
+                .iter()
+                .filter(|block| block.name == "vars:secret")
+                .flat_map(list)
+                .collect();
+
+            if !secrets.is_empty() {
+                self.warn(
+                    &context,
+                    &format!(
+                        "secret values are not exported by Bruno, fill in {}",
+                        secrets.join(", ")
+                    ),
+                );
+            }
+
+            for secret in secrets {
+                variables.push(vec![secret, String::new()]);
+            }
+
+            if !variables.is_empty() {
+                self.environments.push(Environment {
+                    name: format!("{name} ({environment_name})"),
+                    variables,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn enabled(
+        &mut self,
+        pairs: Vec<(String, String, bool)>,
+        kind: &str,
+        context: &str,
+    ) -> Vec<Vec<String>> {
+        let mut rows = vec![];
+
+        for (key, value, enabled) in pairs {
+            if enabled {
+                rows.push(vec![key, value]);
+            } else {
+                self.warn(context, &format!("disabled {kind} {key} is not imported"));
+            }
+        }
+
+        rows
+    }
+
+    fn warn(&mut self, context: &str, warning: &str) {
+        self.warnings.push(format!("{context}: {warning}"));
+    }
+}
+
+// top level "name {" blocks closed by a "}" at the start of a line, their content is
+// indented by two spaces. lists use [ and ]
+fn parse_bru(content: &str) -> Vec<Block> {
+    let mut blocks = vec![];
+    let mut lines = content.lines();
+
+    while let Some(line) = lines.next() {
+        let line = line.trim_end();
+
+        let (name, close) = match (line.strip_suffix(" {"), line.strip_suffix(" [")) {
+            (Some(name), _) => (name, "}"),
+            (_, Some(name)) => (name, "]"),
+            _ => continue,
+        };
+
+        let mut block = Block {
+            name: name.trim().to_string(),
+            lines: vec![],
+        };
+
+        for line in lines.by_ref() {
+            if line.trim_end() == close {
+                break;
+            }
+
+            block
+                .lines
+                .push(line.strip_prefix("  ").unwrap_or(line).to_string());
+        }
+
+        blocks.push(block);
+    }
+
+    blocks
+}
+
+// "key: value" lines, a ~ in front disables the pair. ''' quotes values over several lines
+fn pairs(block: &Block) -> Vec<(String, String, bool)> {
+    let mut pairs = vec![];
+    let mut lines = block.lines.iter();
+
+    while let Some(line) = lines.next() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+
+        let key = key.trim();
+        let mut value = value.trim().to_string();
+
+        if value == "'''" {
+            value = lines
+                .by_ref()
+                .take_while(|line| line.trim() != "'''")
+                .map(|line| line.strip_prefix("  ").unwrap_or(line))
+                .collect::<Vec<&str>>()
+                .join("\n");
+        }
+
+        match key.strip_prefix('~') {
+            Some(key) => pairs.push((key.to_string(), value, false)),
+            None => pairs.push((key.to_string(), value, true)),
+        }
+    }
+
+    pairs
+}
+
+fn pairs_of(blocks: &[Block], name: &str) -> Vec<(String, String, bool)> {
+    blocks
+        .iter()
+        .find(|block| block.name == name)
+        .map(pairs)
+        .unwrap_or_default()
+}
+
+fn text_of(blocks: &[Block], name: &str) -> String {
+    blocks
+        .iter()
+        .find(|block| block.name == name)
+        .map(|block| block.lines.join("\n").trim_end().to_string())
+        .unwrap_or_default()
+}
+
+// enabled names of a [ list ]
+fn list(block: &Block) -> Vec<String> {
+    block
+        .lines
+        .iter()
+        .map(|line| line.trim().trim_end_matches(',').to_string())
+        .filter(|name| !name.is_empty() && !name.starts_with('~'))
+        .collect()
+}
+
+fn seq(blocks: &[Block]) -> u64 {
+    pairs_of(blocks, "meta")
+        .into_iter()
+        .find(|pair| pair.0 == "seq")
+        .and_then(|pair| pair.1.parse().ok())
+        .unwrap_or(u64::MAX)
+}
+
+// "@file(path)" and "@file(path) @contentType(type)"
+fn file_path(value: &str) -> Option<String> {
+    let start = value.find("@file(")? + "@file(".len();
+    let end = value[start..].find(')')?;
+
+    Some(value[start..start + end].to_string())
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn parse_method(name: &str) -> Option<HttpMethod> {
+    (0..HttpMethod::count())
+        .map(HttpMethod::from)
+        .find(|method| method.to_string().eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::row;
+
+    const CREATE_USER: &str = "meta {
+  name: Create user
+  type: http
+  seq: 2
+}
+
+post {
+  url: {{host}}/orgs/:org/users?notify=1
+  body: json
+  auth: bearer
+}
+
+params:path {
+  org: acme
+}
+
+params:query {
+  notify: 1
+  ~debug: true
+}
+
+headers {
+  Accept: application/json
+}
+
+auth:bearer {
+  token: {{token}}
+}
+
+body:json {
+  {
+    \"name\": \"ada\"
+  }
+}
+
+script:pre-request {
+  bru.setVar(\"a\", 1)
+}
+";
+
+    #[test]
+    fn a_request_file() {
+        assert!(is_bruno(CREATE_USER));
+        assert!(!is_bruno("get {\n}"));
+
+        let imported = import_bruno(CREATE_USER, "users").unwrap();
+
+        assert_eq!(imported.collections.len(), 1);
+        assert_eq!(imported.collections[0].name, "users");
+
+        let request = &imported.collections[0].requests[0];
+        assert_eq!(request.name, "Create user");
+        assert_eq!(request.method, HttpMethod::POST);
+        assert_eq!(request.url, "{{host}}/orgs/acme/users");
+        assert_eq!(request.params, vec![row("notify", "1")]);
+        assert_eq!(request.headers, vec![row("Accept", "application/json")]);
+        assert_eq!(request.auth.auth_type, HttpAuthType::BEARER);
+        assert_eq!(request.auth.token, "{{token}}");
+        assert_eq!(request.body_type, HttpBodyType::JSON);
+        assert_eq!(request.body, "{\n  \"name\": \"ada\"\n}");
+
+        assert_eq!(
+            imported.warnings,
+            vec![
+                "users: disabled query parameter debug is not imported",
+                "users: scripts and tests are not imported",
+            ]
+        );
+    }
+
+    #[test]
+    fn quoted_values_and_multipart_files() {
+        let content = "meta {
+  name: Upload
+}
+
+put {
+  url: http://localhost/files
+  body: multipartForm
+  auth: none
+}
+
+body:multipart-form {
+  note: '''
+    first
+    second
+  '''
+  file: @file(/tmp/a.png|/tmp/b.png)
+}
+";
+
+        let imported = import_bruno(content, "files").unwrap();
+        let request = &imported.collections[0].requests[0];
+
+        assert_eq!(request.body_type, HttpBodyType::MULTIPART);
+        assert_eq!(
+            request
+                .multipart
+                .iter()
+                .map(|f| (f.name.as_str(), f.value.as_str(), f.is_file))
+                .collect::<Vec<_>>(),
+            vec![
+                ("note", "first\nsecond", false),
+                ("file", "/tmp/a.png", true)
+            ]
+        );
+        assert_eq!(
+            imported.warnings,
+            vec!["files: form field file has several files, only the first is imported"]
+        );
+    }
+
+    #[test]
+    fn a_collection_folder() {
+        let root = std::env::temp_dir().join(format!("bolt-bruno-{}", uuid::Uuid::new_v4()));
+        let write = |path: &str, content: &str| {
+            let path = root.join(path);
+
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        };
+
+        write(
+            "bruno.json",
+            r#"{ "version": "1", "name": "API", "type": "collection" }"#,
+        );
+        write(
+            "collection.bru",
+            "headers {\n  X-Client: bolt\n}\n\nauth {\n  mode: basic\n}\n\nauth:basic {\n  username: ada\n  password: pw\n}\n",
+        );
+        write(
+            "ping.bru",
+            "meta {\n  name: Ping\n  seq: 2\n}\n\nget {\n  url: {{host}}/ping\n  auth: inherit\n}\n",
+        );
+        write(
+            "health.bru",
+            "meta {\n  name: Health\n  seq: 1\n}\n\nhead {\n  url: {{host}}/health\n  auth: none\n}\n",
+        );
+        write(
+            "users/folder.bru",
+            "meta {\n  name: Users\n}\n\nauth {\n  mode: bearer\n}\n\nauth:bearer {\n  token: t\n}\n",
+        );
+        write(
+            "users/create.bru",
+            &CREATE_USER.replace("auth: bearer", "auth: inherit"),
+        );
+        write(
+            "live.bru",
+            "meta {\n  name: Live\n  type: ws\n}\n\nws {\n  url: ws://localhost/live\n}\n\nbody:ws {\n  content: hi\n}\n",
+        );
+        write(
+            "environments/Local.bru",
+            "vars {\n  host: http://localhost\n}\n\nvars:secret [\n  token\n]\n",
+        );
+
+        let imported = import_bruno_folder(&root);
+        std::fs::remove_dir_all(&root).unwrap();
+        let imported = imported.unwrap();
+
+        let names: Vec<&str> = imported
+            .collections
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["API", "API / Users"]);
+
+        // sorted by seq
+        let api = &imported.collections[0].requests;
+        assert_eq!(api[0].name, "Health");
+        assert_eq!(api[0].method, HttpMethod::HEAD);
+        assert_eq!(api[0].auth.auth_type, HttpAuthType::NONE);
+        assert_eq!(api[1].name, "Ping");
+        assert_eq!(api[1].auth.auth_type, HttpAuthType::BASIC);
+        assert_eq!(api[1].headers, vec![row("X-Client", "bolt")]);
+
+        let create = &imported.collections[1].requests[0];
+        assert_eq!(create.auth.auth_type, HttpAuthType::BEARER);
+        assert_eq!(create.auth.token, "t");
+        assert_eq!(
+            create.headers,
+            vec![row("X-Client", "bolt"), row("Accept", "application/json")]
+        );
+
+        assert_eq!(imported.ws_connections.len(), 1);
+        assert_eq!(imported.ws_connections[0].url, "ws://localhost/live");
+        assert_eq!(imported.ws_connections[0].out_buffer, "hi");
+
+        assert_eq!(imported.environments.len(), 1);
+        assert_eq!(imported.environments[0].name, "API (Local)");
+        assert_eq!(
+            imported.environments[0].variables,
+            vec![row("host", "http://localhost"), row("token", "")]
+        );
+        assert!(imported.warnings.contains(
+            &"environment Local: secret values are not exported by Bruno, fill in token"
+                .to_string()
+        ));
+
+        assert!(import_bruno_folder(&std::env::temp_dir().join("bolt-no-bruno")).is_err());
+    }
+}
@@ -0,0 +1,965 @@
+use base64::{engine::general_purpose, Engine};
+use bolt_common::prelude::*;
+use serde_json::{json, Map, Value};
+
+use super::{Exported, Imported};
+
+// set by the client for every request, copying them would send them twice or wrong
+const SKIPPED_HEADERS: [&str; 4] = ["content-length", "accept-encoding", "connection", "host"];
+
+pub fn is_har(json: &Value) -> bool {
+    json["log"]["entries"].is_array()
+}
+
+// every entry becomes a request of one collection, with the recorded response as its response
+pub fn import_har(content: &str, name: &str) -> Result<Imported, String> {
+    let root: Value =
+        serde_json::from_str(content).map_err(|err| format!("invalid HAR file: {err}"))?;
+
+    let Some(entries) = root["log"]["entries"].as_array() else {
+        return Err("not a HAR file, it has no log entries".to_string());
+    };
+
+    let mut warnings = vec![];
+
+    let title = root["log"]["pages"]
+        .as_array()
+        .and_then(|pages| pages.first())
+        .map(|page| text(&page["title"]))
+        .unwrap_or_default();
+
+    let mut collection = Collection::new();
+    collection.name = if title.trim().is_empty() {
+        name.to_string()
+    } else {
+        title.trim().to_string()
+    };
+
+    let mut skipped = 0;
+
+    for entry in entries {
+        let url = text(&entry["request"]["url"]);
+
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            skipped += 1;
+            continue;
+        }
+
+        collection.requests.push(import_entry(entry, &mut warnings));
+    }
+
+    if skipped > 0 {
+        warnings.push(format!(
+            "{skipped} entries that are not HTTP requests, like data: URLs, are skipped"
+        ));
+    }
+
+    Ok(Imported {
+        collections: vec![collection],
+        environments: vec![],
+        ws_connections: vec![],
+        warnings,
+        update: false,
+    })
+}
+
+fn import_entry(entry: &Value, warnings: &mut Vec<String>) -> HttpRequest {
+    let source = &entry["request"];
+
+    let mut request = HttpRequest::new();
+
+    let url = text(&source["url"]);
+    let url = url.split('#').next().unwrap_or_default();
+
+    // entries exported by bolt keep the request name in the comment
+    let comment = text(&entry["comment"]);
+
+    request.name = if comment.trim().is_empty() {
+        let name = url.split_once("://").map(|(_, rest)| rest).unwrap_or(url);
+
+        name.split('?').next().unwrap_or_default().to_string()
+    } else {
+        comment
+    };
+
+    let method = text(&source["method"]);
+
+    request.method = match parse_method(&method) {
+        Some(method) => method,
+        None => {
+            warnings.push(format!(
+                "{}: {method} is not supported, imported as GET",
+                request.name
+            ));
+
+            HttpMethod::GET
+        }
+    };
+
+    // the query only moves into params when every pair has a value, like curl imports
+    request.url = url.to_string();
+
+    if let Some((base, query)) = url.split_once('?') {
+        let params: Vec<Vec<String>> = query
+            .split('&')
+            .map(|pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+
+                vec![key.to_string(), value.to_string()]
+            })
+            .collect();
+
+        if params
+            .iter()
+            .all(|param| !param[0].is_empty() && !param[1].is_empty())
+        {
+            request.url = base.to_string();
+            request.params = params;
+        }
+    }
+
+    let headers: Vec<Vec<String>> = pairs(&source["headers"])
+        .into_iter()
+        .filter(|header| {
+            // :authority and the other HTTP/2 pseudo headers
+            !header[0].starts_with(':')
+                && !SKIPPED_HEADERS.contains(&header[0].to_lowercase().as_str())
+        })
+        .collect();
+
+    if !headers.is_empty() {
+        request.headers = headers;
+    }
+
+    if !source["postData"].is_null() {
+        import_post_data(&source["postData"], &mut request, warnings);
+    }
+
+    request.response = import_response(entry);
+
+    request
+}
+
+fn import_post_data(post_data: &Value, request: &mut HttpRequest, warnings: &mut Vec<String>) {
+    let mime_type = text(&post_data["mimeType"]).to_lowercase();
+    let params = &post_data["params"];
+
+    if mime_type.contains("json") {
+        request.body_type = HttpBodyType::JSON;
+        request.body = text(&post_data["text"]);
+    } else if mime_type.starts_with("application/x-www-form-urlencoded") && params.is_array() {
+        let form = pairs(params);
+
+        request.body_type = HttpBodyType::FORM_URLENCODED;
+
+        if !form.is_empty() {
+            request.form = form;
+        }
+    } else if mime_type.starts_with("multipart/form-data") && params.is_array() {
+        let mut files = 0;
+
+        let fields: Vec<MultipartField> = params
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|param| {
+                let file_name = text(&param["fileName"]);
+
+                if !file_name.is_empty() {
+                    files += 1;
+                }
+
+                MultipartField {
+                    name: text(&param["name"]),
+                    value: if file_name.is_empty() {
+                        text(&param["value"])
+                    } else {
+                        file_name
+                    },
+                    is_file: !text(&param["fileName"]).is_empty(),
+                }
+            })
+            .collect();
+
+        if files > 0 {
+            warnings.push(format!(
+                "{}: HAR files do not contain uploaded files, choose them again",
+                request.name
+            ));
+        }
+
+        request.body_type = HttpBodyType::MULTIPART;
+
+        if !fields.is_empty() {
+            request.multipart = fields;
+        }
+    } else {
+        request.body_type = HttpBodyType::RAW;
+        request.body = text(&post_data["text"]);
+    }
+}
+
+fn import_response(entry: &Value) -> HttpResponse {
+    let source = &entry["response"];
+    let content = &source["content"];
+
+    let mut response = HttpResponse::new();
+
+    response.status = source["status"].as_u64().unwrap_or(0) as u16;
+    response.headers = pairs(&source["headers"])
+        .into_iter()
+        .map(|header| vec![header[0].to_lowercase(), header[1].clone()])
+        .collect();
+    response.content_type = text(&content["mimeType"]);
+    response.started = parse_date(&text(&entry["startedDateTime"])).unwrap_or(0);
+    response.time = entry["time"].as_f64().unwrap_or(0.0).max(0.0) as u32;
+
+    // browsers record blocked and failed requests with status 0
+    if response.status == 0 {
+        response.failed = true;
+        response.body = match text(&source["_error"]) {
+            error if error.is_empty() => "No response was recorded".to_string(),
+            error => error,
+        };
+
+        return response;
+    }
+
+    let body = text(&content["text"]);
+
+    let bytes = if text(&content["encoding"]) == "base64" {
+        general_purpose::STANDARD
+            .decode(body.trim())
+            .unwrap_or_default()
+    } else {
+        body.into_bytes()
+    };
+
+    let utf8 = std::str::from_utf8(&bytes).ok();
+
+    response.response_type = response_type(&response.content_type, utf8.is_some());
+
+    let is_text = matches!(
+        response.response_type,
+        HttpResponseType::TEXT | HttpResponseType::JSON | HttpResponseType::HTML
+    );
+
+    // kept once, like a received response
+    match utf8 {
+        Some(text) if is_text => response.body = text.to_string(),
+
+        _ => {
+            if is_text {
+                response.body = String::from_utf8_lossy(&bytes).to_string();
+            }
+
+            response.body_base64 = general_purpose::STANDARD.encode(&bytes);
+        }
+    }
+
+    response.size = match content["size"].as_i64() {
+        Some(size) if size >= 0 => size as u64,
+        _ => bytes.len() as u64,
+    };
+
+    let timings = &entry["timings"];
+    let micros = |key: &str| (timings[key].as_f64().unwrap_or(0.0).max(0.0) * 1000.0) as u64;
+
+    // -1 marks a phase that does not apply, kept apart from a real 0
+    let phase = |key: &str| {
+        timings[key]
+            .as_f64()
+            .filter(|ms| *ms >= 0.0)
+            .map(|ms| (ms * 1000.0) as u64)
+    };
+
+    let timing = &mut response.timing;
+    timing.dns = micros("dns");
+    timing.tls = phase("ssl");
+    // a HAR counts the TLS handshake as part of connect
+    timing.connect =
+        phase("connect").map(|connect| connect.saturating_sub(timing.tls.unwrap_or(0)));
+    timing.ttfb = micros("send") + micros("wait");
+    timing.download = micros("receive");
+    timing.total = timing.dns
+        + timing.connect.unwrap_or(0)
+        + timing.tls.unwrap_or(0)
+        + timing.ttfb
+        + timing.download;
+
+    response.connection.http_version = text(&source["httpVersion"]);
+    response.connection.remote_addr = text(&entry["serverIPAddress"]);
+
+    response
+}
+
+// requests that were never sent have nothing to record and are left out
+pub fn export_har(collection: &Collection) -> Exported {
+    let mut warnings = vec![];
+
+    let entries: Vec<Value> = collection
+        .requests
+        .iter()
+        .filter(|request| request.response.status != 0 || request.response.failed)
+        .map(|request| export_entry(request, &mut warnings))
+        .collect();
+
+    let unsent = collection.requests.len() - entries.len();
+
+    if unsent > 0 {
+        warnings.push(format!(
+            "{unsent} request(s) without a response are left out"
+        ));
+    }
+
+    let har = json!({
+        "log": {
+            "version": "1.2",
+            "creator": {
+                "name": "Bolt",
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+            "pages": [],
+            "entries": entries,
+        }
+    });
+
+    Exported {
+        content: serde_json::to_string_pretty(&har).unwrap(),
+        warnings,
+    }
+}
+
+fn export_entry(request: &HttpRequest, warnings: &mut Vec<String>) -> Value {
+    let response = &request.response;
+    let url = parse_url(request.url.clone(), request.params.clone());
+
+    let mut headers: Vec<Vec<String>> = request
+        .headers
+        .iter()
+        .filter(|header| !header[0].trim().is_empty())
+        .cloned()
+        .collect();
+
+    // the auth bolt adds when it sends the request
+    let auth = &request.auth;
+    let mut url = url;
+
+    match auth.auth_type {
+        HttpAuthType::BASIC => {
+            let credentials = format!("{}:{}", auth.username, auth.password);
+
+            headers.push(vec![
+                "Authorization".to_string(),
+                format!("Basic {}", general_purpose::STANDARD.encode(credentials)),
+            ]);
+        }
+
+        HttpAuthType::BEARER => {
+            headers.push(vec![
+                "Authorization".to_string(),
+                format!("Bearer {}", auth.token),
+            ]);
+        }
+
+        HttpAuthType::API_KEY => match auth.location {
+            ApiKeyLocation::HEADER => headers.push(vec![auth.key.clone(), auth.value.clone()]),
+            ApiKeyLocation::QUERY => {
+                let separator = if url.contains('?') { '&' } else { '?' };
+
+                url = format!("{url}{separator}{}={}", auth.key, auth.value);
+            }
+        },
+
+        HttpAuthType::DIGEST | HttpAuthType::OAUTH2 => warnings.push(format!(
+            "{}: the {} Authorization header is not recorded",
+            request.name,
+            if auth.auth_type == HttpAuthType::DIGEST {
+                "digest"
+            } else {
+                "OAuth 2.0"
+            }
+        )),
+
+        HttpAuthType::NONE => {}
+    }
+
+    let query: Vec<Value> = url
+        .split_once('?')
+        .map(|(_, query)| {
+            query
+                .split('&')
+                .filter(|pair| !pair.is_empty())
+                .collect::<Vec<&str>>()
+        })
+        .unwrap_or_default()
+        .into_iter()
+        .map(|pair: &str| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+
+            json!({ "name": name, "value": value })
+        })
+        .collect();
+
+    let mut har_request = Map::new();
+
+    har_request.insert("method".to_string(), json!(request.method.to_string()));
+    har_request.insert("url".to_string(), json!(url));
+    har_request.insert("httpVersion".to_string(), json!(http_version(response)));
+    har_request.insert("cookies".to_string(), json!([]));
+    har_request.insert("headers".to_string(), name_values(&headers));
+    har_request.insert("queryString".to_string(), json!(query));
+    har_request.insert("headersSize".to_string(), json!(-1));
+
+    let post_data = export_post_data(request, &headers);
+
+    let body_size = post_data
+        .as_ref()
+        .map(|data| text(&data["text"]).len() as i64)
+        .unwrap_or(0);
+
+    if let Some(post_data) = post_data {
+        har_request.insert("postData".to_string(), post_data);
+    }
+
+    har_request.insert("bodySize".to_string(), json!(body_size));
+
+    let timing = &response.timing;
+    let ms = |micros: u64| micros as f64 / 1000.0;
+
+    let started = if response.started > 0 {
+        response.started
+    } else {
+        now()
+    };
+
+    let mut entry = Map::new();
+
+    entry.insert("startedDateTime".to_string(), json!(format_date(started)));
+    entry.insert("time".to_string(), json!(response.time));
+    entry.insert("request".to_string(), Value::Object(har_request));
+    entry.insert("response".to_string(), export_response(response));
+    entry.insert("cache".to_string(), json!({}));
+    entry.insert(
+        "timings".to_string(),
+        json!({
+            "blocked": -1,
+            "dns": ms(timing.dns),
+            "connect": timing
+                .connect
+                .map_or(-1.0, |connect| ms(connect + timing.tls.unwrap_or(0))),
+            "ssl": timing.tls.map_or(-1.0, ms),
+            "send": 0,
+            "wait": ms(timing.ttfb),
+            "receive": ms(timing.download),
+        }),
+    );
+
+    if !response.connection.remote_addr.is_empty() {
+        entry.insert(
+            "serverIPAddress".to_string(),
+            json!(response.connection.remote_addr.clone()),
+        );
+    }
+
+    entry.insert("comment".to_string(), json!(request.name.trim()));
+
+    Value::Object(entry)
+}
+
+fn export_post_data(request: &HttpRequest, headers: &[Vec<String>]) -> Option<Value> {
+    let content_type = headers
+        .iter()
+        .find(|header| header[0].trim().eq_ignore_ascii_case("content-type"))
+        .map(|header| header[1].clone());
+
+    let mime_type = content_type.unwrap_or_else(|| request.body_type.content_type().to_string());
+
+    let post_data = match request.body_type {
+        HttpBodyType::RAW | HttpBodyType::JSON if !request.body.is_empty() => json!({
+            "mimeType": mime_type,
+            "text": request.body,
+        }),
+
+        HttpBodyType::FORM_URLENCODED => {
+            let rows: Vec<Vec<String>> = request
+                .form
+                .iter()
+                .filter(|row| !row[0].is_empty())
+                .cloned()
+                .collect();
+
+            let encoded = url::form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(rows.iter().map(|row| (&row[0], &row[1])))
+                .finish();
+
+            json!({
+                "mimeType": mime_type,
+                "params": name_values(&rows),
+                "text": encoded,
+            })
+        }
+
+        HttpBodyType::MULTIPART => {
+            let params: Vec<Value> = request
+                .multipart
+                .iter()
+                .filter(|field| !field.name.is_empty())
+                .map(|field| {
+                    if field.is_file {
+                        let file_name = field.value.rsplit(['/', '\\']).next().unwrap_or_default();
+
+                        json!({ "name": field.name, "fileName": file_name })
+                    } else {
+                        json!({ "name": field.name, "value": field.value })
+                    }
+                })
+                .collect();
+
+            json!({
+                "mimeType": "multipart/form-data",
+                "params": params,
+                "text": "",
+            })
+        }
+
+        // the file itself is not part of a HAR
+        HttpBodyType::BINARY if !request.body_file.is_empty() => json!({
+            "mimeType": mime_type,
+            "text": "",
+            "comment": request.body_file,
+        }),
+
+        HttpBodyType::GRAPHQL => json!({
+            "mimeType": mime_type,
+            "text": request.graphql.payload().ok()?,
+        }),
+
+        _ => return None,
+    };
+
+    Some(post_data)
+}
+
+fn export_response(response: &HttpResponse) -> Value {
+    let redirect = response
+        .headers
+        .iter()
+        .find(|header| header[0].eq_ignore_ascii_case("location"))
+        .map(|header| header[1].clone())
+        .unwrap_or_default();
+
+    let mut content = Map::new();
+
+    content.insert("size".to_string(), json!(response.size));
+    content.insert("mimeType".to_string(), json!(response.content_type));
+
+    if !response.body_base64.is_empty() {
+        content.insert("text".to_string(), json!(response.body_base64));
+        content.insert("encoding".to_string(), json!("base64"));
+    } else {
+        content.insert("text".to_string(), json!(response.body));
+    }
+
+    let mut har_response = json!({
+        "status": response.status,
+        "statusText": reqwest::StatusCode::from_u16(response.status)
+            .ok()
+            .and_then(|status| status.canonical_reason())
+            .unwrap_or_default(),
+        "httpVersion": http_version(response),
+        "cookies": [],
+        "headers": name_values(&response.headers),
+        "content": Value::Object(content),
+        "redirectURL": redirect,
+        "headersSize": -1,
+        "bodySize": response.size,
+    });
+
+    // what browsers record for requests that got no response
+    if response.failed {
+        har_response["status"] = json!(0);
+        har_response["_error"] = json!(response.body);
+    }
+
+    har_response
+}
+
+fn http_version(response: &HttpResponse) -> String {
+    if response.connection.http_version.is_empty() {
+        "HTTP/1.1".to_string()
+    } else {
+        response.connection.http_version.clone()
+    }
+}
+
+fn name_values(pairs: &[Vec<String>]) -> Value {
+    let pairs: Vec<Value> = pairs
+        .iter()
+        .map(|pair| json!({ "name": pair[0].trim(), "value": pair[1] }))
+        .collect();
+
+    json!(pairs)
+}
+
+// [{"name": .., "value": ..}] as [name, value] rows
+fn pairs(list: &Value) -> Vec<Vec<String>> {
+    list.as_array()
+        .into_iter()
+        .flatten()
+        .map(|pair| vec![text(&pair["name"]), text(&pair["value"])])
+        .filter(|pair| !pair[0].is_empty())
+        .collect()
+}
+
+fn response_type(content_type: &str, utf8: bool) -> HttpResponseType {
+    match SendHttpResponseType::from_content_type(content_type, utf8) {
+        SendHttpResponseType::TEXT => HttpResponseType::TEXT,
+        SendHttpResponseType::JSON => HttpResponseType::JSON,
+        SendHttpResponseType::HTML => HttpResponseType::HTML,
+        SendHttpResponseType::IMAGE => HttpResponseType::IMAGE,
+        SendHttpResponseType::PDF => HttpResponseType::PDF,
+        SendHttpResponseType::BINARY => HttpResponseType::BINARY,
+    }
+}
+
+fn parse_method(name: &str) -> Option<HttpMethod> {
+    (0..HttpMethod::count())
+        .map(HttpMethod::from)
+        .find(|method| method.to_string().eq_ignore_ascii_case(name))
+}
+
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        value => value.to_string(),
+    }
+}
+
+fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
+// unix ms as 2024-01-31T12:00:00.000Z
+fn format_date(ms: u64) -> String {
+    let seconds = ms / 1000;
+    let (year, month, day) = civil_from_days((seconds / 86400) as i64);
+    let time = seconds % 86400;
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+        time / 3600,
+        time % 3600 / 60,
+        time % 60,
+        ms % 1000
+    )
+}
+
+// 2024-01-31T12:00:00.123+01:00 as unix ms, browsers write the local offset
+fn parse_date(date: &str) -> Option<u64> {
+    let number = |range: std::ops::Range<usize>| date.get(range)?.parse::<i64>().ok();
+
+    let days = days_from_civil(number(0..4)?, number(5..7)?, number(8..10)?);
+    let mut seconds =
+        days * 86400 + number(11..13)? * 3600 + number(14..16)? * 60 + number(17..19)?;
+
+    let rest = date.get(19..)?;
+    let (fraction, zone) = match rest.strip_prefix('.') {
+        Some(rest) => {
+            let end = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+
+            (&rest[..end], &rest[end..])
+        }
+        None => ("", rest),
+    };
+
+    let millis = format!("{fraction:0<3}").get(..3)?.parse::<i64>().ok()?;
+
+    if let Some(sign) = zone.chars().next().filter(|c| *c == '+' || *c == '-') {
+        let hours = zone.get(1..3)?.parse::<i64>().ok()?;
+        let minutes = zone.get(4..6)?.parse::<i64>().ok()?;
+        let offset = hours * 3600 + minutes * 60;
+
+        seconds += if sign == '+' { -offset } else { offset };
+    }
+
+    u64::try_from(seconds * 1000 + millis).ok()
+}
+
+// the civil calendar algorithms from http://howardhinnant.github.io/date_algorithms.html
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
+
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    era * 146097 + doe - 719468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::row;
+
+    const HAR: &str = r#"{
+        "log": {
+            "pages": [{ "title": "Shop" }],
+            "entries": [
+                {
+                    "startedDateTime": "2024-01-31T12:00:00.123+01:00",
+                    "time": 42.5,
+                    "serverIPAddress": "10.0.0.1",
+                    "request": {
+                        "method": "GET",
+                        "url": "https://shop.test/items?page=2&sort=name#top",
+                        "headers": [
+                            { "name": ":authority", "value": "shop.test" },
+                            { "name": "Host", "value": "shop.test" },
+                            { "name": "Accept", "value": "application/json" }
+                        ]
+                    },
+                    "response": {
+                        "status": 200,
+                        "httpVersion": "HTTP/2",
+                        "headers": [{ "name": "Content-Type", "value": "application/json" }],
+                        "content": { "size": 11, "mimeType": "application/json", "text": "{\"ok\":true}" }
+                    },
+                    "timings": { "dns": 1, "connect": 5, "ssl": 3, "send": 0.5, "wait": 10, "receive": 2 }
+                },
+                {
+                    "request": { "method": "GET", "url": "data:image/png;base64,AAAA" },
+                    "response": { "status": 200, "content": {} }
+                },
+                {
+                    "comment": "Upload avatar",
+                    "request": {
+                        "method": "POST",
+                        "url": "https://shop.test/avatar?flag",
+                        "postData": {
+                            "mimeType": "multipart/form-data; boundary=x",
+                            "params": [
+                                { "name": "user", "value": "ada" },
+                                { "name": "file", "fileName": "me.png" }
+                            ]
+                        }
+                    },
+                    "response": {
+                        "status": 201,
+                        "content": { "mimeType": "image/png", "text": "iVBORw==", "encoding": "base64" }
+                    }
+                },
+                {
+                    "request": {
+                        "method": "POST",
+                        "url": "https://shop.test/login",
+                        "postData": {
+                            "mimeType": "application/x-www-form-urlencoded",
+                            "params": [{ "name": "user", "value": "ada" }]
+                        }
+                    },
+                    "response": { "status": 0, "_error": "net::ERR_BLOCKED_BY_CLIENT", "content": {} }
+                }
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn entries_become_requests_with_their_responses() {
+        let imported = import_har(HAR, "recording").unwrap();
+
+        assert_eq!(imported.collections.len(), 1);
+        assert!(!imported.update);
+
+        let collection = &imported.collections[0];
+        assert_eq!(collection.name, "Shop");
+        assert_eq!(collection.requests.len(), 3);
+
+        assert_eq!(
+            imported.warnings,
+            vec![
+                "Upload avatar: HAR files do not contain uploaded files, choose them again",
+                "1 entries that are not HTTP requests, like data: URLs, are skipped",
+            ]
+        );
+
+        let items = &collection.requests[0];
+        assert_eq!(items.name, "shop.test/items");
+        assert_eq!(items.method, HttpMethod::GET);
+        assert_eq!(items.url, "https://shop.test/items");
+        assert_eq!(items.params, vec![row("page", "2"), row("sort", "name")]);
+        assert_eq!(items.headers, vec![row("Accept", "application/json")]);
+
+        let response = &items.response;
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, "{\"ok\":true}");
+        assert!(response.body_base64.is_empty());
+        assert_eq!(response.response_type, HttpResponseType::JSON);
+        assert_eq!(
+            response.headers,
+            vec![row("content-type", "application/json")]
+        );
+        assert_eq!(response.started, 1706698800123);
+        assert_eq!(response.time, 42);
+        assert_eq!(response.size, 11);
+        assert_eq!(response.connection.http_version, "HTTP/2");
+        assert_eq!(response.connection.remote_addr, "10.0.0.1");
+
+        let timing = &response.timing;
+        assert_eq!(
+            (timing.dns, timing.connect, timing.tls),
+            (1000, Some(2000), Some(3000))
+        );
+        assert_eq!((timing.ttfb, timing.download), (10500, 2000));
+        assert_eq!(timing.total, 18500);
+    }
+
+    #[test]
+    fn bodies_and_failed_responses() {
+        let imported = import_har(HAR, "recording").unwrap();
+        let requests = &imported.collections[0].requests;
+
+        // a query without values stays in the url
+        let upload = &requests[1];
+        assert_eq!(upload.name, "Upload avatar");
+        assert_eq!(upload.url, "https://shop.test/avatar?flag");
+        assert_eq!(upload.body_type, HttpBodyType::MULTIPART);
+        assert_eq!(
+            upload
+                .multipart
+                .iter()
+                .map(|f| (f.name.as_str(), f.value.as_str(), f.is_file))
+                .collect::<Vec<_>>(),
+            vec![("user", "ada", false), ("file", "me.png", true)]
+        );
+
+        // binary responses are only kept as base64
+        assert_eq!(upload.response.response_type, HttpResponseType::IMAGE);
+        assert_eq!(upload.response.body_base64, "iVBORw==");
+        assert!(upload.response.body.is_empty());
+        assert_eq!(upload.response.size, 4);
+
+        let login = &requests[2];
+        assert_eq!(login.body_type, HttpBodyType::FORM_URLENCODED);
+        assert_eq!(login.form, vec![row("user", "ada")]);
+        assert!(login.response.failed);
+        assert_eq!(login.response.body, "net::ERR_BLOCKED_BY_CLIENT");
+    }
+
+    #[test]
+    fn exports_sent_requests_and_imports_them_back() {
+        let mut sent = HttpRequest::new();
+        sent.name = "Login".to_string();
+        sent.method = HttpMethod::POST;
+        sent.url = "https://shop.test/login".to_string();
+        sent.params = vec![row("next", "home")];
+        sent.auth.auth_type = HttpAuthType::BEARER;
+        sent.auth.token = "secret".to_string();
+        sent.body_type = HttpBodyType::FORM_URLENCODED;
+        sent.form = vec![row("user", "ada lovelace"), row("", "ignored")];
+        sent.response.status = 200;
+        sent.response.started = 1706698800123;
+        sent.response.content_type = "text/plain".to_string();
+        sent.response.body = "welcome".to_string();
+        sent.response.size = 7;
+        sent.response.timing.connect = Some(2000);
+        sent.response.timing.tls = Some(3000);
+
+        let mut collection = Collection::new();
+        collection.name = "Shop".to_string();
+        collection.requests = vec![sent, HttpRequest::new()];
+
+        let exported = export_har(&collection);
+        assert_eq!(
+            exported.warnings,
+            vec!["1 request(s) without a response are left out"]
+        );
+
+        let har: Value = serde_json::from_str(&exported.content).unwrap();
+        let entry = &har["log"]["entries"][0];
+        assert_eq!(har["log"]["entries"].as_array().unwrap().len(), 1);
+        assert_eq!(entry["startedDateTime"], "2024-01-31T11:00:00.123Z");
+        assert_eq!(entry["request"]["url"], "https://shop.test/login?next=home");
+        assert_eq!(entry["request"]["postData"]["text"], "user=ada+lovelace");
+        assert_eq!(entry["response"]["statusText"], "OK");
+        assert_eq!(entry["timings"]["connect"], 5.0);
+        assert_eq!(entry["timings"]["ssl"], 3.0);
+
+        let imported = import_har(&exported.content, "export").unwrap();
+        let request = &imported.collections[0].requests[0];
+
+        assert_eq!(imported.collections[0].name, "export");
+        assert_eq!(request.name, "Login");
+        assert_eq!(request.url, "https://shop.test/login");
+        assert_eq!(request.params, vec![row("next", "home")]);
+        assert_eq!(request.headers, vec![row("Authorization", "Bearer secret")]);
+        assert_eq!(request.form, vec![row("user", "ada lovelace")]);
+        assert_eq!(request.response.body, "welcome");
+        assert_eq!(request.response.started, 1706698800123);
+        assert_eq!(
+            (request.response.timing.connect, request.response.timing.tls),
+            (Some(2000), Some(3000))
+        );
+    }
+
+    #[test]
+    fn dates() {
+        assert_eq!(format_date(0), "1970-01-01T00:00:00.000Z");
+        assert_eq!(parse_date("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(parse_date("2024-02-29T23:59:59.5Z"), Some(1709251199500));
+        assert_eq!(parse_date("2024-03-01T01:00:00-02:30"), Some(1709263800000));
+        assert_eq!(format_date(1709251199500), "2024-02-29T23:59:59.500Z");
+        assert_eq!(parse_date("yesterday"), None);
+    }
+
+    #[test]
+    fn rejects_files_without_entries() {
+        assert!(import_har(r#"{ "log": {} }"#, "x").is_err());
+        assert!(import_har("not json", "x").is_err());
+    }
+
+    #[test]
+    fn unmeasured_phases_stay_unmeasured() {
+        let mut sent = HttpRequest::new();
+        sent.url = "http://shop.test/".to_string();
+        sent.response.status = 204;
+        sent.response.timing.dns = 1000;
+
+        let mut collection = Collection::new();
+        collection.requests = vec![sent];
+
+        let exported = export_har(&collection);
+        let har: Value = serde_json::from_str(&exported.content).unwrap();
+        let timings = &har["log"]["entries"][0]["timings"];
+
+        assert_eq!(timings["dns"], 1.0);
+        assert_eq!(timings["connect"], -1.0);
+        assert_eq!(timings["ssl"], -1.0);
+
+        let imported = import_har(&exported.content, "export").unwrap();
+        let timing = &imported.collections[0].requests[0].response.timing;
+
+        assert_eq!((timing.dns, timing.connect, timing.tls), (1000, None, None));
+    }
+}
@@ -0,0 +1,902 @@
+use bolt_common::prelude::*;
+use serde_json::Value;
+
+use super::postman::replace_path_variable;
+use super::Imported;
+
+pub fn is_insomnia(json: &Value) -> bool {
+    json["_type"].as_str() == Some("export") && json["resources"].is_array()
+}
+
+// every workspace and request group becomes a collection named after its path, e.g.
+// "API / Users". sub environments get the variables of their base environment
+pub fn import_insomnia(content: &str) -> Result<Imported, String> {
+    // inso exports YAML
+    let root: Value = match serde_json::from_str(content) {
+        Ok(root) => root,
+        Err(_) => serde_yaml::from_str(content)
+            .map_err(|err| format!("invalid Insomnia export: {err}"))?,
+    };
+
+    let Some(resources) = root["resources"].as_array() else {
+        return Err("not an Insomnia export, it has no resources".to_string());
+    };
+
+    let mut importer = Importer {
+        resources,
+        collections: vec![],
+        environments: vec![],
+        ws_connections: vec![],
+        folder_variables: vec![],
+        warnings: vec![],
+    };
+
+    let export_format = root["__export_format"].as_u64().unwrap_or_default();
+
+    if export_format != 4 {
+        importer.warnings.push(format!(
+            "export format {export_format} is not v4, imported as v4"
+        ));
+    }
+
+    for workspace in importer.of_type("workspace") {
+        let name = text(&workspace["name"]);
+        let name = if name.trim().is_empty() {
+            "Insomnia".to_string()
+        } else {
+            name
+        };
+
+        importer.folder(id(workspace), &name, None);
+        importer.environments(id(workspace), &name);
+    }
+
+    // exports of a few requests may leave out their workspace
+    let ids: Vec<&str> = resources.iter().map(id).collect();
+
+    let mut orphan_parents: Vec<&str> = vec![];
+
+    for resource in resources {
+        let parent = parent_id(resource);
+
+        if ["request", "request_group", "websocket_request"].contains(&resource_type(resource))
+            && !ids.contains(&parent)
+            && !orphan_parents.contains(&parent)
+        {
+            orphan_parents.push(parent);
+        }
+    }
+
+    for parent in orphan_parents {
+        importer.folder(parent, "Insomnia", None);
+    }
+
+    for resource in resources {
+        let name = text(&resource["name"]);
+
+        match resource_type(resource) {
+            "grpc_request" => importer
+                .warnings
+                .push(format!("{name}: gRPC requests are not supported")),
+
+            "unit_test_suite" => importer
+                .warnings
+                .push(format!("{name}: test suites are not imported")),
+
+            "cookie_jar"
+                if resource["cookies"]
+                    .as_array()
+                    .is_some_and(|cookies| !cookies.is_empty()) =>
+            {
+                importer
+                    .warnings
+                    .push(format!("{name}: cookies are not imported"))
+            }
+
+            _ => {}
+        }
+    }
+
+    Ok(Imported {
+        collections: importer.collections,
+        environments: importer.environments,
+        ws_connections: importer.ws_connections,
+        warnings: importer.warnings,
+        update: false,
+    })
+}
+
+struct Importer<'a> {
+    resources: &'a Vec<Value>,
+    collections: Vec<Collection>,
+    environments: Vec<Environment>,
+    ws_connections: Vec<WsConnection>,
+    // variables of the request groups, bolt has no folder environments
+    folder_variables: Vec<Vec<String>>,
+    warnings: Vec<String>,
+}
+
+impl<'a> Importer<'a> {
+    fn of_type(&self, resource_type_name: &str) -> Vec<&'a Value> {
+        self.resources
+            .iter()
+            .filter(|resource| resource_type(resource) == resource_type_name)
+            .collect()
+    }
+
+    // in the order of the sidebar
+    fn children(&self, parent: &str) -> Vec<&'a Value> {
+        let mut children: Vec<&Value> = self
+            .resources
+            .iter()
+            .filter(|resource| parent_id(resource) == parent)
+            .collect();
+
+        children.sort_by(|a, b| {
+            let a = a["metaSortKey"].as_f64().unwrap_or_default();
+            let b = b["metaSortKey"].as_f64().unwrap_or_default();
+
+            a.total_cmp(&b)
+        });
+
+        children
+    }
+
+    fn folder(&mut self, folder_id: &str, path: &str, inherited_auth: Option<&'a Value>) {
+        let mut collection = Collection::new();
+        collection.name = path.to_string();
+
+        let mut folders = vec![];
+
+        for child in self.children(folder_id) {
+            let name = text(&child["name"]);
+            let context = format!("{path} / {name}");
+
+            match resource_type(child) {
+                "request" => {
+                    let request = self.request(child, &context, inherited_auth);
+
+                    collection.requests.push(request);
+                }
+
+                "websocket_request" => {
+                    let connection = self.ws_connection(child, &context);
+
+                    self.ws_connections.push(connection);
+                }
+
+                "request_group" => folders.push(child),
+
+                _ => {}
+            }
+        }
+
+        // the parent comes before its folders
+        if !collection.requests.is_empty() {
+            self.collections.push(collection);
+        }
+
+        for folder in folders {
+            let name = text(&folder["name"]);
+            let path = format!("{path} / {name}");
+
+            let auth = if inherits(&folder["authentication"]) {
+                inherited_auth
+            } else {
+                Some(&folder["authentication"])
+            };
+
+            self.folder_environment(&folder["environment"], &path);
+            self.folder(id(folder), &path, auth);
+        }
+    }
+
+    fn request(
+        &mut self,
+        resource: &Value,
+        context: &str,
+        inherited_auth: Option<&Value>,
+    ) -> HttpRequest {
+        let mut request = HttpRequest::new();
+        request.name = text(&resource["name"]);
+
+        let method = resource["method"].as_str().unwrap_or("GET");
+
+        request.method = match parse_method(method) {
+            Some(method) => method,
+            None => {
+                self.warn(
+                    context,
+                    &format!("{method} is not supported, imported as GET"),
+                );
+
+                HttpMethod::GET
+            }
+        };
+
+        let mut url = self.variables(&text(&resource["url"]), context);
+
+        for param in resource["pathParameters"].as_array().into_iter().flatten() {
+            let name = text(&param["name"]);
+            let value = self.variables(&text(&param["value"]), context);
+
+            if !name.is_empty() && !value.is_empty() {
+                url = replace_path_variable(&url, &name, &value);
+            }
+        }
+
+        let params = self.rows(&resource["parameters"], "query parameter", context);
+
+        // parse_url drops params without a value, those stay in the url
+        if !url.contains('?') && params.iter().all(|param| !param[1].is_empty()) {
+            request.url = url;
+
+            if !params.is_empty() {
+                request.params = params;
+            }
+        } else {
+            let query: Vec<String> = params
+                .iter()
+                .map(|param| {
+                    if param[1].is_empty() {
+                        param[0].clone()
+                    } else {
+                        format!("{}={}", param[0], param[1])
+                    }
+                })
+                .collect();
+
+            let separator = if url.contains('?') { "&" } else { "?" };
+
+            request.url = if query.is_empty() {
+                url
+            } else {
+                format!("{url}{separator}{}", query.join("&"))
+            };
+        }
+
+        let headers = self.rows(&resource["headers"], "header", context);
+
+        if !headers.is_empty() {
+            request.headers = headers;
+        }
+
+        self.body(&resource["body"], &mut request, context);
+
+        let auth = if inherits(&resource["authentication"]) {
+            inherited_auth
+        } else {
+            Some(&resource["authentication"])
+        };
+
+        if let Some(auth) = auth {
+            request.auth = self.auth(auth, context);
+        }
+
+        match resource["settingFollowRedirects"].as_str() {
+            Some("on") | Some("off") => {
+                let mut settings = HttpSettings::new();
+                settings.follow_redirects = resource["settingFollowRedirects"] == "on";
+
+                request.settings = Some(settings);
+            }
+
+            _ => {}
+        }
+
+        let has_script = ["preRequestScript", "afterResponseScript"]
+            .iter()
+            .any(|key| !text(&resource[key]).trim().is_empty());
+
+        if has_script {
+            self.warn(
+                context,
+                "pre-request and after-response scripts are not imported",
+            );
+        }
+
+        request
+    }
+
+    fn ws_connection(&mut self, resource: &Value, context: &str) -> WsConnection {
+        let mut connection = WsConnection::new();
+        connection.name = text(&resource["name"]);
+        connection.url = self.variables(&text(&resource["url"]), context);
+
+        let headers = self.rows(&resource["headers"], "header", context);
+
+        if !headers.is_empty() {
+            connection.out_headers = headers;
+        }
+
+        let params = self.rows(&resource["parameters"], "query parameter", context);
+
+        if !params.is_empty() {
+            connection.out_params = params;
+        }
+
+        if !inherits(&resource["authentication"])
+            && resource["authentication"]["type"].as_str() != Some("none")
+        {
+            self.warn(context, "auth is not imported for WebSocket connections");
+        }
+
+        // the message being written
+        let payload = self
+            .children(id(resource))
+            .into_iter()
+            .find(|child| resource_type(child) == "websocket_payload");
+
+        if let Some(payload) = payload {
+            connection.out_buffer = text(&payload["value"]);
+        }
+
+        connection
+    }
+
+    fn rows(&mut self, rows: &Value, kind: &str, context: &str) -> Vec<Vec<String>> {
+        let mut result = vec![];
+
+        for row in rows.as_array().into_iter().flatten() {
+            let name = text(&row["name"]);
+
+            if is_disabled(row) {
+                self.warn(context, &format!("disabled {kind} {name} is not imported"));
+            } else if !name.is_empty() {
+                let value = self.variables(&text(&row["value"]), context);
+
+                result.push(vec![name, value]);
+            }
+        }
+
+        result
+    }
+
+    fn body(&mut self, body: &Value, request: &mut HttpRequest, context: &str) {
+        let mime_type = body["mimeType"].as_str().unwrap_or_default();
+        let mime_type = mime_type.split(';').next().unwrap_or_default().trim();
+
+        match mime_type {
+            "application/x-www-form-urlencoded" => {
+                let form = self.rows(&body["params"], "form field", context);
+
+                request.body_type = HttpBodyType::FORM_URLENCODED;
+
+                if !form.is_empty() {
+                    request.form = form;
+                }
+            }
+
+            "multipart/form-data" => {
+                let mut fields = vec![];
+
+                for param in body["params"].as_array().into_iter().flatten() {
+                    let name = text(&param["name"]);
+
+                    if is_disabled(param) {
+                        self.warn(
+                            context,
+                            &format!("disabled form field {name} is not imported"),
+                        );
+                    } else if name.is_empty() {
+                        continue;
+                    } else if param["type"].as_str() == Some("file") {
+                        fields.push(MultipartField {
+                            name,
+                            value: text(&param["fileName"]),
+                            is_file: true,
+                        });
+                    } else {
+                        fields.push(MultipartField {
+                            name,
+                            value: self.variables(&text(&param["value"]), context),
+                            is_file: false,
+                        });
+                    }
+                }
+
+                request.body_type = HttpBodyType::MULTIPART;
+
+                if !fields.is_empty() {
+                    request.multipart = fields;
+                }
+            }
+
+            "application/graphql" => {
+                let graphql: Value =
+                    serde_json::from_str(&text(&body["text"])).unwrap_or(Value::Null);
+
+                let variables = match &graphql["variables"] {
+                    Value::String(variables) => variables.clone(),
+                    Value::Object(variables) if !variables.is_empty() => {
+                        serde_json::to_string_pretty(variables).unwrap()
+                    }
+                    _ => String::new(),
+                };
+
+                request.body_type = HttpBodyType::GRAPHQL;
+                request.graphql = GraphqlBody {
+                    query: self.variables(&text(&graphql["query"]), context),
+                    variables: self.variables(&variables, context),
+                    operation_name: text(&graphql["operationName"]),
+                };
+            }
+
+            "application/octet-stream" => {
+                let file = text(&body["fileName"]);
+
+                if file.is_empty() {
+                    self.warn(context, "the body file has no path");
+                }
+
+                request.body_type = HttpBodyType::BINARY;
+                request.body_file = file;
+            }
+
+            _ => {
+                let body_text = text(&body["text"]);
+
+                if body_text.is_empty() {
+                    return;
+                }
+
+                request.body_type = if mime_type.ends_with("json") {
+                    HttpBodyType::JSON
+                } else {
+                    HttpBodyType::RAW
+                };
+
+                request.body = self.variables(&body_text, context);
+            }
+        }
+    }
+
+    fn auth(&mut self, auth: &Value, context: &str) -> HttpAuth {
+        let mut result = HttpAuth::new();
+
+        if auth["disabled"].as_bool() == Some(true) {
+            return result;
+        }
+
+        let param = |key: &str| text(&auth[key]);
+
+        match auth["type"].as_str().unwrap_or("none") {
+            "none" => {}
+
+            auth_type @ ("basic" | "digest") => {
+                result.auth_type = if auth_type == "basic" {
+                    HttpAuthType::BASIC
+                } else {
+                    HttpAuthType::DIGEST
+                };
+
+                result.username = self.variables(&param("username"), context);
+                result.password = self.variables(&param("password"), context);
+            }
+
+            "bearer" => {
+                let prefix = param("prefix");
+
+                if !prefix.is_empty() && prefix != "Bearer" {
+                    self.warn(
+                        context,
+                        &format!("the token prefix {prefix} is not supported, Bearer is used"),
+                    );
+                }
+
+                result.auth_type = HttpAuthType::BEARER;
+                result.token = self.variables(&param("token"), context);
+            }
+
+            "apikey" => {
+                result.auth_type = HttpAuthType::API_KEY;
+                result.key = param("key");
+                result.value = self.variables(&param("value"), context);
+                result.location = match param("addTo").as_str() {
+                    "queryParams" => ApiKeyLocation::QUERY,
+                    "cookie" => {
+                        self.warn(context, "API keys in cookies are sent as a header");
+
+                        ApiKeyLocation::HEADER
+                    }
+                    _ => ApiKeyLocation::HEADER,
+                };
+            }
+
+            "oauth2" => {
+                result.auth_type = HttpAuthType::OAUTH2;
+
+                let oauth2 = &mut result.oauth2;
+
+                oauth2.grant_type = match param("grantType").as_str() {
+                    "password" => OAuth2GrantType::PASSWORD,
+                    "authorization_code" => OAuth2GrantType::AUTHORIZATION_CODE,
+                    "client_credentials" | "" => OAuth2GrantType::CLIENT_CREDENTIALS,
+                    grant => {
+                        self.warn(
+                            context,
+                            &format!("the {grant} OAuth 2.0 grant is not supported"),
+                        );
+
+                        OAuth2GrantType::CLIENT_CREDENTIALS
+                    }
+                };
+
+                oauth2.token_url = param("accessTokenUrl");
+                oauth2.auth_url = param("authorizationUrl");
+                oauth2.redirect_url = param("redirectUrl");
+                oauth2.client_id = param("clientId");
+                oauth2.client_secret = param("clientSecret");
+                oauth2.scope = param("scope");
+                oauth2.client_auth_header = auth["credentialsInBody"].as_bool() != Some(true);
+
+                result.username = param("username");
+                result.password = param("password");
+            }
+
+            other => self.warn(context, &format!("{other} auth is not supported")),
+        }
+
+        result
+    }
+
+    fn folder_environment(&mut self, environment: &Value, path: &str) {
+        let mut variables = vec![];
+        flatten(environment, "", &mut variables);
+
+        if variables.is_empty() {
+            return;
+        }
+
+        self.warn(
+            path,
+            "folder variables are added to the workspace environments",
+        );
+
+        for var in variables {
+            if !self
+                .folder_variables
+                .iter()
+                .any(|existing| existing[0] == var[0])
+            {
+                self.folder_variables.push(var);
+            }
+        }
+    }
+
+    // one environment per sub environment, or the base environment when there is none
+    fn environments(&mut self, workspace_id: &str, name: &str) {
+        let mut folder_variables = std::mem::take(&mut self.folder_variables);
+
+        for base in self
+            .children(workspace_id)
+            .into_iter()
+            .filter(|child| resource_type(child) == "environment")
+        {
+            let mut base_variables = vec![];
+            flatten(&base["data"], "", &mut base_variables);
+
+            for var in folder_variables.drain(..) {
+                if !base_variables.iter().any(|existing| existing[0] == var[0]) {
+                    base_variables.push(var);
+                }
+            }
+
+            let subs: Vec<&Value> = self
+                .children(id(base))
+                .into_iter()
+                .filter(|child| resource_type(child) == "environment")
+                .collect();
+
+            if subs.is_empty() {
+                self.add_environment(name.to_string(), base_variables.clone(), name);
+            }
+
+            for sub in subs {
+                let mut variables = vec![];
+                flatten(&sub["data"], "", &mut variables);
+
+                for var in &base_variables {
+                    if !variables.iter().any(|existing| existing[0] == var[0]) {
+                        variables.push(var.clone());
+                    }
+                }
+
+                let sub_name = format!("{name} ({})", text(&sub["name"]));
+
+                self.add_environment(sub_name, variables, name);
+            }
+        }
+
+        // folder variables of a workspace without environments
+        if !folder_variables.is_empty() {
+            self.add_environment(name.to_string(), folder_variables, name);
+        }
+    }
+
+    fn add_environment(&mut self, name: String, variables: Vec<Vec<String>>, context: &str) {
+        if variables.is_empty() {
+            return;
+        }
+
+        let variables = variables
+            .into_iter()
+            .map(|var| vec![var[0].clone(), self.variables(&var[1], context)])
+            .collect();
+
+        self.environments.push(Environment { name, variables });
+    }
+
+    // {{ _.name }} becomes {{name}}, template tags are left as they are
+    fn variables(&mut self, value: &str, context: &str) -> String {
+        if value.contains("{%") {
+            self.warn(
+                context,
+                &format!("template tags are not supported, {value} is imported as is"),
+            );
+        }
+
+        let mut result = String::new();
+        let mut rest = value;
+
+        while let Some(start) = rest.find("{{") {
+            let Some(end) = rest[start..].find("}}") else {
+                break;
+            };
+
+            let name = rest[start + 2..start + end].trim();
+            let name = name.strip_prefix("_.").unwrap_or(name);
+
+            result.push_str(&rest[..start]);
+            result.push_str(&format!("{{{{{name}}}}}"));
+
+            rest = &rest[start + end + 2..];
+        }
+
+        result.push_str(rest);
+
+        result
+    }
+
+    fn warn(&mut self, context: &str, warning: &str) {
+        self.warnings.push(format!("{context}: {warning}"));
+    }
+}
+
+// nested values are reached with dots in insomnia, {{ _.api.url }}
+fn flatten(value: &Value, prefix: &str, variables: &mut Vec<Vec<String>>) {
+    let Some(object) = value.as_object() else {
+        return;
+    };
+
+    for (key, value) in object {
+        let key = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+
+        match value {
+            Value::Object(_) => flatten(value, &key, variables),
+            Value::Array(_) => variables.push(vec![key, value.to_string()]),
+            Value::Null => variables.push(vec![key, String::new()]),
+            value => variables.push(vec![key, text(value)]),
+        }
+    }
+}
+
+// an empty authentication inherits the one of the folder
+fn inherits(auth: &Value) -> bool {
+    auth["type"].is_null()
+}
+
+fn id(resource: &Value) -> &str {
+    resource["_id"].as_str().unwrap_or_default()
+}
+
+fn parent_id(resource: &Value) -> &str {
+    resource["parentId"].as_str().unwrap_or_default()
+}
+
+fn resource_type(resource: &Value) -> &str {
+    resource["_type"].as_str().unwrap_or_default()
+}
+
+fn parse_method(name: &str) -> Option<HttpMethod> {
+    (0..HttpMethod::count())
+        .map(HttpMethod::from)
+        .find(|method| method.to_string().eq_ignore_ascii_case(name))
+}
+
+fn is_disabled(value: &Value) -> bool {
+    value["disabled"].as_bool() == Some(true)
+}
+
+// strings as they are, numbers and booleans as text, anything else empty
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Number(number) => number.to_string(),
+        Value::Bool(boolean) => boolean.to_string(),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::row;
+
+    const EXPORT: &str = r#"{
+        "_type": "export",
+        "__export_format": 4,
+        "resources": [
+            { "_id": "wrk_1", "_type": "workspace", "name": "API" },
+            {
+                "_id": "env_base", "_type": "environment", "parentId": "wrk_1", "name": "Base",
+                "data": { "host": "api.test", "auth": { "token": "t0" } }
+            },
+            {
+                "_id": "env_prod", "_type": "environment", "parentId": "env_base", "name": "Prod",
+                "data": { "host": "prod.api.test" }
+            },
+            {
+                "_id": "req_2", "_type": "request", "parentId": "wrk_1", "name": "Health",
+                "metaSortKey": 2, "method": "GET", "url": "https://{{ _.host }}/health",
+                "authentication": {}
+            },
+            {
+                "_id": "req_1", "_type": "request", "parentId": "wrk_1", "name": "Login",
+                "metaSortKey": 1, "method": "POST", "url": "https://{{ _.host }}/login",
+                "body": {
+                    "mimeType": "application/x-www-form-urlencoded",
+                    "params": [{ "name": "user", "value": "ada" }, { "name": "debug", "value": "1", "disabled": true }]
+                },
+                "authentication": { "type": "basic", "username": "ada", "password": "{{ _.auth.token }}" },
+                "settingFollowRedirects": "off"
+            },
+            {
+                "_id": "fld_1", "_type": "request_group", "parentId": "wrk_1", "name": "Users",
+                "authentication": { "type": "bearer", "token": "{{ _.auth.token }}" },
+                "environment": { "page_size": 20 }
+            },
+            {
+                "_id": "req_3", "_type": "request", "parentId": "fld_1", "name": "Get user",
+                "method": "GET", "url": "https://{{ _.host }}/users/:id",
+                "pathParameters": [{ "name": "id", "value": "7" }],
+                "parameters": [{ "name": "expand", "value": "roles" }],
+                "headers": [{ "name": "Accept", "value": "application/json" }],
+                "authentication": {},
+                "preRequestScript": "insomnia.request.addHeader()"
+            },
+            {
+                "_id": "req_4", "_type": "request", "parentId": "fld_1", "name": "Search",
+                "method": "POST", "url": "https://{{ _.host }}/graphql",
+                "body": { "mimeType": "application/graphql", "text": "{\"query\":\"{ users { id } }\",\"variables\":{\"first\":2}}" },
+                "authentication": { "type": "none" }
+            },
+            {
+                "_id": "ws_1", "_type": "websocket_request", "parentId": "fld_1", "name": "Live",
+                "url": "wss://{{ _.host }}/live", "headers": [], "authentication": {}
+            },
+            { "_id": "wsp_1", "_type": "websocket_payload", "parentId": "ws_1", "value": "hello" },
+            { "_id": "grpc_1", "_type": "grpc_request", "parentId": "wrk_1", "name": "Greeter" }
+        ]
+    }"#;
+
+    #[test]
+    fn workspaces_and_folders_become_collections() {
+        let imported = import_insomnia(EXPORT).unwrap();
+
+        let names: Vec<&str> = imported
+            .collections
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["API", "API / Users"]);
+
+        // sorted by metaSortKey
+        let api = &imported.collections[0].requests;
+        assert_eq!(api[0].name, "Login");
+        assert_eq!(api[1].name, "Health");
+
+        assert_eq!(imported.ws_connections.len(), 1);
+        assert_eq!(imported.ws_connections[0].url, "wss://{{host}}/live");
+        assert_eq!(imported.ws_connections[0].out_buffer, "hello");
+
+        assert_eq!(
+            imported.warnings,
+            vec![
+                "API / Login: disabled form field debug is not imported",
+                "API / Users: folder variables are added to the workspace environments",
+                "API / Users / Get user: pre-request and after-response scripts are not imported",
+                "Greeter: gRPC requests are not supported",
+            ]
+        );
+    }
+
+    #[test]
+    fn requests_keep_their_bodies_and_auth() {
+        let imported = import_insomnia(EXPORT).unwrap();
+
+        let login = &imported.collections[0].requests[0];
+        assert_eq!(login.method, HttpMethod::POST);
+        assert_eq!(login.url, "https://{{host}}/login");
+        assert_eq!(login.body_type, HttpBodyType::FORM_URLENCODED);
+        assert_eq!(login.form, vec![row("user", "ada")]);
+        assert_eq!(login.auth.auth_type, HttpAuthType::BASIC);
+        assert_eq!(login.auth.password, "{{auth.token}}");
+        assert!(!login.settings.as_ref().unwrap().follow_redirects);
+
+        let users = &imported.collections[1].requests;
+
+        // the folder auth is inherited
+        let get_user = &users[0];
+        assert_eq!(get_user.url, "https://{{host}}/users/7");
+        assert_eq!(get_user.params, vec![row("expand", "roles")]);
+        assert_eq!(get_user.headers, vec![row("Accept", "application/json")]);
+        assert_eq!(get_user.auth.auth_type, HttpAuthType::BEARER);
+        assert_eq!(get_user.auth.token, "{{auth.token}}");
+
+        let search = &users[1];
+        assert_eq!(search.body_type, HttpBodyType::GRAPHQL);
+        assert_eq!(search.graphql.query, "{ users { id } }");
+        assert_eq!(
+            serde_json::from_str::<Value>(&search.graphql.variables).unwrap(),
+            serde_json::json!({ "first": 2 })
+        );
+        assert_eq!(search.auth.auth_type, HttpAuthType::NONE);
+    }
+
+    #[test]
+    fn sub_environments_get_the_base_variables() {
+        let imported = import_insomnia(EXPORT).unwrap();
+
+        assert_eq!(imported.environments.len(), 1);
+        assert_eq!(imported.environments[0].name, "API (Prod)");
+        assert_eq!(
+            imported.environments[0].variables,
+            vec![
+                row("host", "prod.api.test"),
+                row("auth.token", "t0"),
+                row("page_size", "20"),
+            ]
+        );
+    }
+
+    #[test]
+    fn requests_without_their_workspace() {
+        let export = r#"
+_type: export
+__export_format: 3
+resources:
+  - _id: req_1
+    _type: request
+    parentId: wrk_gone
+    name: Ping
+    url: "http://localhost/ping?a=1"
+    parameters:
+      - { name: flag, value: "" }
+    body: { mimeType: text/plain, text: "{% now 'iso' %}" }
+"#;
+
+        let imported = import_insomnia(export).unwrap();
+
+        assert_eq!(imported.collections[0].name, "Insomnia");
+
+        let ping = &imported.collections[0].requests[0];
+        assert_eq!(ping.url, "http://localhost/ping?a=1&flag");
+        assert_eq!(ping.body_type, HttpBodyType::RAW);
+        assert_eq!(ping.body, "{% now 'iso' %}");
+
+        assert_eq!(
+            imported.warnings,
+            vec![
+                "export format 3 is not v4, imported as v4",
+                "Insomnia / Ping: template tags are not supported, {% now 'iso' %} is imported as is",
+            ]
+        );
+
+        assert!(import_insomnia(r#"{ "_type": "export" }"#).is_err());
+    }
+}
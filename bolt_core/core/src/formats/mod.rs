@@ -0,0 +1,270 @@
+mod bruno;
+mod har;
+mod insomnia;
+mod openapi;
+mod postman;
+
+use bolt_common::prelude::*;
+use std::path::Path;
+
+// what an import made of a file, and what it had to leave out
+pub struct Imported {
+    pub collections: Vec<Collection>,
+    pub environments: Vec<Environment>,
+    pub ws_connections: Vec<WsConnection>,
+    pub warnings: Vec<String>,
+    // re-imports update the saved collections instead of adding them again
+    pub update: bool,
+}
+
+pub struct Exported {
+    pub content: String,
+    pub warnings: Vec<String>,
+}
+
+// a folder is imported as a Bruno collection, the collections of formats without a name
+// of their own are named after the file
+pub fn import_path(path: &str, format: Option<CollectionFormat>) -> Result<Imported, String> {
+    let path = Path::new(path);
+
+    if path.is_dir() {
+        if format.is_some_and(|format| format != CollectionFormat::BRUNO) {
+            return Err(format!(
+                "{} is a folder, only Bruno collections are imported from a folder",
+                path.display()
+            ));
+        }
+
+        return check_imported(bruno::import_bruno_folder(path)?);
+    }
+
+    let name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Imported".to_string());
+
+    let content = std::fs::read_to_string(path)
+        .map_err(|err| format!("could not read {}: {err}", path.display()))?;
+
+    import_collections(&content, format, &name)
+}
+
+// name is used for formats that do not name their collection, like a list of curl commands
+pub fn import_collections(
+    content: &str,
+    format: Option<CollectionFormat>,
+    name: &str,
+) -> Result<Imported, String> {
+    let format = match format {
+        Some(format) => format,
+        None => detect_format(content)
+            .ok_or("unknown file format, choose one of the supported formats")?,
+    };
+
+    let imported = match format {
+        CollectionFormat::BOLT => {
+            let collection: Collection = serde_json::from_str(content)
+                .map_err(|err| format!("not an exported Bolt collection: {err}"))?;
+
+            Imported {
+                collections: vec![collection],
+                environments: vec![],
+                ws_connections: vec![],
+                warnings: vec![],
+                update: false,
+            }
+        }
+
+        CollectionFormat::CURL => {
+            let mut collection = Collection::new();
+            collection.name = name.to_string();
+            collection.requests = parse_curl_commands(content)?;
+
+            Imported {
+                collections: vec![collection],
+                environments: vec![],
+                ws_connections: vec![],
+                warnings: vec![],
+                update: false,
+            }
+        }
+
+        CollectionFormat::POSTMAN => postman::import_postman(content)?,
+        CollectionFormat::OPENAPI => openapi::import_openapi(content)?,
+        CollectionFormat::HAR => har::import_har(content, name)?,
+        CollectionFormat::INSOMNIA => insomnia::import_insomnia(content)?,
+        CollectionFormat::BRUNO => bruno::import_bruno(content, name)?,
+    };
+
+    check_imported(imported)
+}
+
+fn check_imported(imported: Imported) -> Result<Imported, String> {
+    if imported.collections.is_empty() && imported.ws_connections.is_empty() {
+        return Err("the file has no requests".to_string());
+    }
+
+    Ok(imported)
+}
+
+pub fn export_collection(
+    collection: &Collection,
+    format: CollectionFormat,
+) -> Result<Exported, String> {
+    let mut collection = collection.clone();
+
+    // responses are not part of an export, except in a HAR which records them
+    for request in &mut collection.requests {
+        if format != CollectionFormat::HAR {
+            request.response = HttpResponse::new();
+        }
+
+        request.loading = false;
+    }
+
+    let exported = match format {
+        CollectionFormat::BOLT => Exported {
+            content: serde_json::to_string_pretty(&collection).unwrap(),
+            warnings: vec![],
+        },
+
+        CollectionFormat::CURL => Exported {
+            content: curl_commands(&collection),
+            warnings: vec![],
+        },
+
+        CollectionFormat::POSTMAN => postman::export_postman(&collection),
+        CollectionFormat::HAR => har::export_har(&collection),
+
+        CollectionFormat::OPENAPI | CollectionFormat::INSOMNIA | CollectionFormat::BRUNO => {
+            return Err(format!("collections can not be exported as {format}"));
+        }
+    };
+
+    Ok(exported)
+}
+
+// e.g. "My API.postman_collection.json"
+pub fn export_file_name(collection: &Collection, format: CollectionFormat) -> String {
+    let name: String = collection
+        .name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || " -_.".contains(c) {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    let name = if name.is_empty() {
+        "collection".to_string()
+    } else {
+        name
+    };
+
+    match format {
+        CollectionFormat::BOLT => format!("{name}.json"),
+        CollectionFormat::CURL => format!("{name}.sh"),
+        CollectionFormat::POSTMAN => format!("{name}.postman_collection.json"),
+        CollectionFormat::OPENAPI => format!("{name}.openapi.json"),
+        CollectionFormat::HAR => format!("{name}.har"),
+        CollectionFormat::INSOMNIA => format!("{name}.insomnia.json"),
+        CollectionFormat::BRUNO => format!("{name}.bru"),
+    }
+}
+
+pub fn detect_format(content: &str) -> Option<CollectionFormat> {
+    let first_line = content
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+        .unwrap_or_default();
+
+    if first_line.starts_with("curl") {
+        return Some(CollectionFormat::CURL);
+    }
+
+    if bruno::is_bruno(content) {
+        return Some(CollectionFormat::BRUNO);
+    }
+
+    // OpenAPI documents are often YAML
+    let json: serde_json::Value = match serde_json::from_str(content) {
+        Ok(json) => json,
+        Err(_) => serde_yaml::from_str(content).ok()?,
+    };
+
+    if postman::is_postman(&json) {
+        Some(CollectionFormat::POSTMAN)
+    } else if openapi::is_openapi(&json) {
+        Some(CollectionFormat::OPENAPI)
+    } else if har::is_har(&json) {
+        Some(CollectionFormat::HAR)
+    } else if insomnia::is_insomnia(&json) {
+        Some(CollectionFormat::INSOMNIA)
+    } else if json["requests"].is_array() {
+        Some(CollectionFormat::BOLT)
+    } else {
+        None
+    }
+}
+
+// every line starting with curl begins a command, unless the previous line
+// continues with a backslash. # comments between commands are skipped.
+fn parse_curl_commands(content: &str) -> Result<Vec<HttpRequest>, String> {
+    let mut commands: Vec<String> = vec![];
+    let mut continued = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+
+        if !continued && trimmed.starts_with('#') {
+            continue;
+        }
+
+        if (!continued && trimmed.starts_with("curl")) || commands.is_empty() {
+            commands.push(String::new());
+        }
+
+        let command = commands.last_mut().unwrap();
+        command.push_str(line);
+        command.push('\n');
+
+        continued = line.trim_end().ends_with('\\');
+    }
+
+    let commands: Vec<&String> = commands.iter().filter(|c| !c.trim().is_empty()).collect();
+
+    if commands.is_empty() {
+        return Err("no curl command found".to_string());
+    }
+
+    commands
+        .iter()
+        .enumerate()
+        .map(|(i, command)| parse_curl(command).map_err(|err| format!("command {}: {err}", i + 1)))
+        .collect()
+}
+
+// {{variables}} are left in, the output can be imported back
+fn curl_commands(collection: &Collection) -> String {
+    collection
+        .requests
+        .iter()
+        .map(|request| {
+            let name = request.name.trim().replace('\n', " ");
+
+            format!("# {name}\n{}\n", to_curl(&request.to_send_request(0)))
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// a [name, value] row like the importers fill headers, params and forms with
+#[cfg(test)]
+fn row(name: &str, value: &str) -> Vec<String> {
+    vec![name.to_string(), value.to_string()]
+}
@@ -0,0 +1,924 @@
+use bolt_common::prelude::*;
+use serde_json::{json, Map, Value};
+
+use super::Imported;
+
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+// how many $refs to $refs are followed
+const MAX_DEPTH: usize = 8;
+
+pub fn is_openapi(json: &Value) -> bool {
+    json["openapi"].is_string() || json["swagger"].is_string()
+}
+
+// one collection per tag, named like "Pet Store / pets". untagged operations go into a
+// collection named after the API and every server becomes an environment with base_url
+pub fn import_openapi(content: &str) -> Result<Imported, String> {
+    let root: Value = match serde_json::from_str(content) {
+        Ok(root) => root,
+        Err(_) => serde_yaml::from_str(content)
+            .map_err(|err| format!("invalid OpenAPI document: {err}"))?,
+    };
+
+    if !is_openapi(&root) {
+        return Err("not an OpenAPI or Swagger document".to_string());
+    }
+
+    let title = text(&root["info"]["title"]);
+    let title = if title.trim().is_empty() {
+        "API".to_string()
+    } else {
+        title.trim().to_string()
+    };
+
+    let mut importer = Importer {
+        root: &root,
+        swagger: root["swagger"].is_string(),
+        warnings: vec![],
+        path_variables: vec![],
+    };
+
+    // tags listed at the top come first, in their order
+    let mut collections: Vec<Collection> = root["tags"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|tag| text(&tag["name"]))
+        .filter(|tag| !tag.is_empty())
+        .map(|tag| {
+            let mut collection = Collection::new();
+            collection.name = format!("{title} / {tag}");
+
+            collection
+        })
+        .collect();
+
+    let mut untagged = Collection::new();
+    untagged.name = title.clone();
+
+    for (path, item) in root["paths"].as_object().into_iter().flatten() {
+        if item.get("$ref").is_some() {
+            importer.warn(path, "path items with $ref are not supported");
+            continue;
+        }
+
+        for method in METHODS {
+            let Some(operation) = item.get(method) else {
+                continue;
+            };
+
+            let request = importer.operation(path, method, item, operation);
+
+            let tag = operation["tags"]
+                .as_array()
+                .and_then(|tags| tags.first())
+                .map(text)
+                .unwrap_or_default();
+
+            if tag.is_empty() {
+                untagged.requests.push(request);
+                continue;
+            }
+
+            let name = format!("{title} / {tag}");
+
+            match collections.iter_mut().find(|c| c.name == name) {
+                Some(collection) => collection.requests.push(request),
+                None => {
+                    let mut collection = Collection::new();
+                    collection.name = name;
+                    collection.requests.push(request);
+
+                    collections.push(collection);
+                }
+            }
+        }
+    }
+
+    if !untagged.requests.is_empty() {
+        collections.insert(0, untagged);
+    }
+
+    collections.retain(|collection| !collection.requests.is_empty());
+
+    let environments = importer.environments(&title);
+
+    Ok(Imported {
+        collections,
+        environments,
+        ws_connections: vec![],
+        warnings: importer.warnings,
+        update: true,
+    })
+}
+
+struct Importer<'a> {
+    root: &'a Value,
+    // Swagger 2.0 has body and formData parameters instead of requestBody
+    swagger: bool,
+    warnings: Vec<String>,
+    // path parameters become variables, with their example as the value
+    path_variables: Vec<Vec<String>>,
+}
+
+impl<'a> Importer<'a> {
+    fn operation(
+        &mut self,
+        path: &str,
+        method: &str,
+        item: &Value,
+        operation: &Value,
+    ) -> HttpRequest {
+        let source = format!("{} {path}", method.to_uppercase());
+
+        let mut request = HttpRequest::new();
+        request.source = source.clone();
+
+        request.name = [&operation["summary"], &operation["operationId"]]
+            .into_iter()
+            .map(text)
+            .find(|name| !name.trim().is_empty())
+            .unwrap_or_else(|| source.clone());
+
+        request.method = match parse_method(method) {
+            Some(method) => method,
+            None => {
+                self.warn(
+                    &source,
+                    &format!(
+                        "{} is not supported, imported as GET",
+                        method.to_uppercase()
+                    ),
+                );
+
+                HttpMethod::GET
+            }
+        };
+
+        // {id} in the path is a {{id}} variable in bolt
+        request.url = format!(
+            "{{{{base_url}}}}{}",
+            path.replace('{', "{{").replace('}', "}}")
+        );
+
+        let mut params: Vec<Vec<String>> = vec![];
+        let mut headers: Vec<Vec<String>> = vec![];
+        let mut form: Vec<Value> = vec![];
+
+        for parameter in self.parameters(item, operation) {
+            let name = text(&parameter["name"]);
+            let value = self.parameter_example(&parameter);
+
+            match parameter["in"].as_str().unwrap_or_default() {
+                "path" => {
+                    if !self.path_variables.iter().any(|var| var[0] == name) {
+                        self.path_variables.push(vec![name, value]);
+                    }
+                }
+
+                "query" => params.push(vec![name, value]),
+
+                "header" => headers.push(vec![name, value]),
+
+                "body" => {
+                    let example = self.example(&parameter["schema"], &mut vec![]);
+
+                    request.body_type = HttpBodyType::JSON;
+                    request.body = serde_json::to_string_pretty(&example).unwrap();
+                }
+
+                "formData" => form.push(parameter),
+
+                location => self.warn(
+                    &source,
+                    &format!("{location} parameter {name} is not imported"),
+                ),
+            }
+        }
+
+        if !form.is_empty() {
+            self.swagger_form(&form, operation, &mut request);
+        }
+
+        if !operation["requestBody"].is_null() {
+            self.request_body(
+                &operation["requestBody"],
+                &source,
+                &mut request,
+                &mut headers,
+            );
+        }
+
+        if !params.is_empty() {
+            request.params = params;
+        }
+
+        if !headers.is_empty() {
+            request.headers = headers;
+        }
+
+        request
+    }
+
+    // the path item parameters apply to every operation, operations can override them
+    fn parameters(&mut self, item: &Value, operation: &Value) -> Vec<Value> {
+        let mut parameters: Vec<Value> = vec![];
+
+        let all = item["parameters"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .chain(operation["parameters"].as_array().into_iter().flatten());
+
+        for parameter in all {
+            let parameter = self.resolve(parameter).clone();
+
+            let same = parameters
+                .iter()
+                .position(|p| p["name"] == parameter["name"] && p["in"] == parameter["in"]);
+
+            match same {
+                Some(index) => parameters[index] = parameter,
+                None => parameters.push(parameter),
+            }
+        }
+
+        parameters
+    }
+
+    fn parameter_example(&mut self, parameter: &Value) -> String {
+        let example = if let Some(example) = parameter.get("example") {
+            example.clone()
+        } else if let Some(example) = first_example(parameter) {
+            example
+        } else if self.swagger {
+            // swagger 2.0 parameters are their own schema
+            self.example_or_null(parameter)
+        } else {
+            self.example_or_null(&parameter["schema"])
+        };
+
+        text(&example)
+    }
+
+    // only explicit values, a made up "string" is no use as a query parameter
+    fn example_or_null(&mut self, schema: &Value) -> Value {
+        let schema = self.resolve(schema);
+
+        ["example", "default"]
+            .into_iter()
+            .find_map(|key| schema.get(key).cloned())
+            .or_else(|| {
+                schema["enum"]
+                    .as_array()
+                    .and_then(|values| values.first().cloned())
+            })
+            .unwrap_or(Value::Null)
+    }
+
+    fn request_body(
+        &mut self,
+        body: &Value,
+        source: &str,
+        request: &mut HttpRequest,
+        headers: &mut Vec<Vec<String>>,
+    ) {
+        let body = self.resolve(body).clone();
+
+        let Some(content) = body["content"].as_object() else {
+            return;
+        };
+
+        // JSON is preferred when an operation accepts several media types
+        let media_type = content
+            .keys()
+            .find(|media| media.contains("json"))
+            .or_else(|| {
+                content
+                    .keys()
+                    .find(|media| *media == "application/x-www-form-urlencoded")
+            })
+            .or_else(|| content.keys().find(|media| *media == "multipart/form-data"))
+            .or_else(|| content.keys().next());
+
+        let Some(media_type) = media_type else {
+            return;
+        };
+
+        let media = &content[media_type];
+        let schema = &media["schema"];
+
+        let explicit = media
+            .get("example")
+            .cloned()
+            .or_else(|| first_example(media));
+
+        let example = match explicit.clone() {
+            Some(example) => example,
+            None => self.example(schema, &mut vec![]),
+        };
+
+        match media_type.as_str() {
+            media if media.contains("json") => {
+                request.body_type = HttpBodyType::JSON;
+                request.body = serde_json::to_string_pretty(&example).unwrap();
+
+                if media != "application/json" {
+                    headers.push(vec!["Content-Type".to_string(), media.to_string()]);
+                }
+            }
+
+            "application/x-www-form-urlencoded" => {
+                let rows: Vec<Vec<String>> = example
+                    .as_object()
+                    .into_iter()
+                    .flatten()
+                    .map(|(key, value)| vec![key.clone(), text(value)])
+                    .collect();
+
+                request.body_type = HttpBodyType::FORM_URLENCODED;
+
+                if !rows.is_empty() {
+                    request.form = rows;
+                }
+            }
+
+            "multipart/form-data" => {
+                let properties = self.resolve(schema)["properties"].clone();
+
+                let fields: Vec<MultipartField> = example
+                    .as_object()
+                    .into_iter()
+                    .flatten()
+                    .map(|(key, value)| {
+                        let is_file = is_binary(self.resolve(&properties[key]));
+
+                        MultipartField {
+                            name: key.clone(),
+                            value: if is_file { String::new() } else { text(value) },
+                            is_file,
+                        }
+                    })
+                    .collect();
+
+                request.body_type = HttpBodyType::MULTIPART;
+
+                if !fields.is_empty() {
+                    request.multipart = fields;
+                }
+            }
+
+            media => {
+                headers.push(vec!["Content-Type".to_string(), media.to_string()]);
+
+                if is_binary(self.resolve(schema)) || media == "application/octet-stream" {
+                    request.body_type = HttpBodyType::BINARY;
+                } else {
+                    // only a real example, "string" is no use as a text body
+                    let example = explicit.unwrap_or_else(|| self.example_or_null(schema));
+
+                    request.body_type = HttpBodyType::RAW;
+                    request.body = text(&example);
+
+                    if request.body.is_empty() {
+                        self.warn(source, &format!("no example for the {media} body"));
+                    }
+                }
+            }
+        }
+    }
+
+    fn swagger_form(&mut self, form: &[Value], operation: &Value, request: &mut HttpRequest) {
+        let consumes = operation["consumes"]
+            .as_array()
+            .or(self.root["consumes"].as_array())
+            .map(|consumes| consumes.iter().map(text).collect::<Vec<String>>())
+            .unwrap_or_default();
+
+        let multipart = form.iter().any(|p| p["type"] == "file")
+            || consumes.iter().any(|media| media == "multipart/form-data");
+
+        if multipart {
+            request.body_type = HttpBodyType::MULTIPART;
+            request.multipart = form
+                .iter()
+                .map(|parameter| {
+                    let is_file = parameter["type"] == "file";
+
+                    MultipartField {
+                        name: text(&parameter["name"]),
+                        value: if is_file {
+                            String::new()
+                        } else {
+                            self.parameter_example(parameter)
+                        },
+                        is_file,
+                    }
+                })
+                .collect();
+        } else {
+            request.body_type = HttpBodyType::FORM_URLENCODED;
+            request.form = form
+                .iter()
+                .map(|parameter| vec![text(&parameter["name"]), self.parameter_example(parameter)])
+                .collect();
+        }
+    }
+
+    // a value that fits the schema, from its examples where there are some. seen holds the
+    // $refs being expanded, a recursive schema stops at its second visit
+    fn example(&mut self, schema: &Value, seen: &mut Vec<String>) -> Value {
+        if let Some(reference) = schema["$ref"].as_str() {
+            if seen.iter().any(|r| r == reference) {
+                return Value::Null;
+            }
+
+            let target = self.resolve(schema).clone();
+
+            seen.push(reference.to_string());
+            let example = self.example(&target, seen);
+            seen.pop();
+
+            return example;
+        }
+
+        let schema = schema.clone();
+
+        for key in ["example", "default"] {
+            if let Some(value) = schema.get(key) {
+                return value.clone();
+            }
+        }
+
+        if let Some(value) = schema["enum"].as_array().and_then(|values| values.first()) {
+            return value.clone();
+        }
+
+        if let Some(parts) = schema["allOf"].as_array() {
+            let mut merged = Map::new();
+
+            for part in parts {
+                if let Value::Object(object) = self.example(part, seen) {
+                    merged.extend(object);
+                }
+            }
+
+            return Value::Object(merged);
+        }
+
+        for key in ["oneOf", "anyOf"] {
+            if let Some(first) = schema[key].as_array().and_then(|options| options.first()) {
+                return self.example(first, seen);
+            }
+        }
+
+        // 3.1 allows a list of types, e.g. ["string", "null"]
+        let schema_type = match &schema["type"] {
+            Value::Array(types) => types
+                .iter()
+                .map(text)
+                .find(|t| t != "null")
+                .unwrap_or_default(),
+            schema_type => text(schema_type),
+        };
+
+        match schema_type.as_str() {
+            "object" | "" if schema["properties"].is_object() => {
+                let mut object = Map::new();
+
+                for (name, property) in schema["properties"].as_object().unwrap() {
+                    let example = self.example(property, seen);
+
+                    // unresolved and recursive properties are left out
+                    if !example.is_null() {
+                        object.insert(name.clone(), example);
+                    }
+                }
+
+                Value::Object(object)
+            }
+
+            "object" => json!({}),
+
+            "array" => {
+                let item = self.example(&schema["items"], seen);
+
+                if item.is_null() {
+                    json!([])
+                } else {
+                    json!([item])
+                }
+            }
+
+            "string" => json!(match schema["format"].as_str().unwrap_or_default() {
+                "date-time" => "2024-01-01T00:00:00Z",
+                "date" => "2024-01-01",
+                "email" => "user@example.com",
+                "uuid" => "00000000-0000-0000-0000-000000000000",
+                "uri" | "url" => "https://example.com",
+                "binary" | "byte" => "",
+                _ => "string",
+            }),
+
+            "integer" => json!(0),
+
+            "number" => json!(0.0),
+
+            "boolean" => json!(true),
+
+            _ => Value::Null,
+        }
+    }
+
+    // follows local $refs like #/components/schemas/Pet, other values are returned as they are
+    fn resolve<'v>(&mut self, value: &'v Value) -> &'v Value
+    where
+        'a: 'v,
+    {
+        let mut value = value;
+
+        // refs to refs, but not forever
+        for _ in 0..MAX_DEPTH {
+            let Some(reference) = value["$ref"].as_str() else {
+                return value;
+            };
+
+            match reference
+                .strip_prefix('#')
+                .and_then(|pointer| self.root.pointer(pointer))
+            {
+                Some(target) => value = target,
+                None => {
+                    let warning = format!("{reference} can not be resolved");
+
+                    if !self.warnings.contains(&warning) {
+                        self.warnings.push(warning);
+                    }
+
+                    return &Value::Null;
+                }
+            }
+        }
+
+        value
+    }
+
+    fn environments(&mut self, title: &str) -> Vec<Environment> {
+        let mut urls: Vec<(String, String)> = vec![];
+
+        if self.swagger {
+            let host = text(&self.root["host"]);
+            let base_path = text(&self.root["basePath"]);
+
+            let schemes: Vec<String> = match self.root["schemes"].as_array() {
+                Some(schemes) => schemes.iter().map(text).collect(),
+                None => vec!["https".to_string()],
+            };
+
+            if host.is_empty() {
+                urls.push((base_path.clone(), base_path));
+            } else {
+                for scheme in schemes {
+                    let url = format!("{scheme}://{host}{base_path}");
+
+                    urls.push((url.clone(), url));
+                }
+            }
+        } else {
+            for server in self.root["servers"].as_array().into_iter().flatten() {
+                let mut url = text(&server["url"]);
+
+                // {port} and friends get their default
+                for (name, variable) in server["variables"].as_object().into_iter().flatten() {
+                    url = url.replace(&format!("{{{name}}}"), &text(&variable["default"]));
+                }
+
+                let description = text(&server["description"]);
+                let label = if description.trim().is_empty() {
+                    url.clone()
+                } else {
+                    description
+                };
+
+                urls.push((label, url));
+            }
+        }
+
+        if urls.is_empty() {
+            self.warnings.push(format!(
+                "{title} has no server, set base_url in its environment"
+            ));
+
+            urls.push((String::new(), String::new()));
+        }
+
+        urls.iter()
+            .map(|(label, url)| {
+                if url.starts_with('/') {
+                    self.warnings.push(format!(
+                        "the server {url} is relative, add the host to base_url"
+                    ));
+                }
+
+                let mut variables = vec![vec![
+                    "base_url".to_string(),
+                    url.trim_end_matches('/').to_string(),
+                ]];
+                variables.extend(self.path_variables.iter().cloned());
+
+                let name = if urls.len() == 1 {
+                    title.to_string()
+                } else {
+                    format!("{title} ({label})")
+                };
+
+                Environment { name, variables }
+            })
+            .collect()
+    }
+
+    fn warn(&mut self, context: &str, warning: &str) {
+        self.warnings.push(format!("{context}: {warning}"));
+    }
+}
+
+// the value of the first entry of examples, which OpenAPI keys by name
+fn first_example(value: &Value) -> Option<Value> {
+    let examples = value["examples"].as_object()?;
+
+    examples
+        .values()
+        .next()
+        .map(|example| example["value"].clone())
+}
+
+fn is_binary(schema: &Value) -> bool {
+    matches!(schema["format"].as_str(), Some("binary") | Some("base64"))
+}
+
+fn parse_method(name: &str) -> Option<HttpMethod> {
+    (0..HttpMethod::count())
+        .map(HttpMethod::from)
+        .find(|method| method.to_string().eq_ignore_ascii_case(name))
+}
+
+// strings as they are, other values as JSON, null as nothing
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::row;
+
+    const PETSTORE: &str = r##"{
+        "openapi": "3.0.3",
+        "info": { "title": "Pet Store", "version": "1" },
+        "tags": [{ "name": "pets" }, { "name": "unused" }],
+        "servers": [
+            { "url": "https://{region}.pets.test/v1/", "description": "Production", "variables": { "region": { "default": "eu" } } },
+            { "url": "http://localhost:8080" }
+        ],
+        "paths": {
+            "/pets/{id}": {
+                "parameters": [{ "name": "id", "in": "path", "required": true, "schema": { "type": "integer", "example": 7 } }],
+                "get": {
+                    "tags": ["pets"],
+                    "summary": "Get a pet",
+                    "parameters": [
+                        { "$ref": "#/components/parameters/Fields" },
+                        { "name": "X-Trace", "in": "header", "schema": { "type": "string", "enum": ["on", "off"] } },
+                        { "name": "session", "in": "cookie", "schema": { "type": "string" } }
+                    ]
+                }
+            },
+            "/pets": {
+                "post": {
+                    "tags": ["pets"],
+                    "operationId": "createPet",
+                    "requestBody": { "$ref": "#/components/requestBodies/Pet" }
+                }
+            },
+            "/health": {
+                "get": {}
+            },
+            "/notes": {
+                "put": {
+                    "requestBody": { "content": { "text/plain": { "schema": { "type": "string" } } } }
+                }
+            },
+            "/missing": {
+                "post": {
+                    "requestBody": { "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Nope" } } } }
+                }
+            },
+            "/shared": { "$ref": "#/components/pathItems/Shared" }
+        },
+        "components": {
+            "parameters": {
+                "Fields": { "name": "fields", "in": "query", "example": "name,tag" }
+            },
+            "requestBodies": {
+                "Pet": { "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } } } }
+            },
+            "schemas": {
+                "Pet": {
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string" },
+                        "born": { "type": "string", "format": "date" },
+                        "tags": { "type": "array", "items": { "type": "string" } },
+                        "parent": { "$ref": "#/components/schemas/Pet" }
+                    }
+                }
+            }
+        }
+    }"##;
+
+    #[test]
+    fn tags_become_collections_and_servers_environments() {
+        let imported = import_openapi(PETSTORE).unwrap();
+
+        let names: Vec<&str> = imported
+            .collections
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Pet Store", "Pet Store / pets"]);
+
+        // paths are imported sorted by their path
+        let untagged: Vec<&str> = imported.collections[0]
+            .requests
+            .iter()
+            .map(|r| r.source.as_str())
+            .collect();
+        assert_eq!(untagged, vec!["GET /health", "POST /missing", "PUT /notes"]);
+
+        let environments: Vec<(&str, &Vec<Vec<String>>)> = imported
+            .environments
+            .iter()
+            .map(|e| (e.name.as_str(), &e.variables))
+            .collect();
+        assert_eq!(
+            environments,
+            vec![
+                (
+                    "Pet Store (Production)",
+                    &vec![row("base_url", "https://eu.pets.test/v1"), row("id", "7")]
+                ),
+                (
+                    "Pet Store (http://localhost:8080)",
+                    &vec![row("base_url", "http://localhost:8080"), row("id", "7")]
+                ),
+            ]
+        );
+        assert!(imported.update);
+    }
+
+    #[test]
+    fn operations_become_requests() {
+        let imported = import_openapi(PETSTORE).unwrap();
+        let pets = &imported.collections[1].requests;
+
+        let get = pets.iter().find(|r| r.source == "GET /pets/{id}").unwrap();
+        assert_eq!(get.name, "Get a pet");
+        assert_eq!(get.method, HttpMethod::GET);
+        assert_eq!(get.url, "{{base_url}}/pets/{{id}}");
+        assert_eq!(get.params, vec![row("fields", "name,tag")]);
+        assert_eq!(get.headers, vec![row("X-Trace", "on")]);
+
+        let post = pets.iter().find(|r| r.source == "POST /pets").unwrap();
+        assert_eq!(post.name, "createPet");
+        assert_eq!(post.method, HttpMethod::POST);
+        assert_eq!(post.body_type, HttpBodyType::JSON);
+
+        // the recursive parent is left out
+        let body: Value = serde_json::from_str(&post.body).unwrap();
+        assert_eq!(
+            body,
+            json!({ "name": "string", "born": "2024-01-01", "tags": ["string"] })
+        );
+
+        let health = &imported.collections[0].requests[0];
+        assert_eq!(health.name, "GET /health");
+    }
+
+    #[test]
+    fn unsupported_parts_are_warned_about() {
+        let imported = import_openapi(PETSTORE).unwrap();
+
+        assert_eq!(
+            imported.warnings,
+            vec![
+                "#/components/schemas/Nope can not be resolved",
+                "PUT /notes: no example for the text/plain body",
+                "GET /pets/{id}: cookie parameter session is not imported",
+                "/shared: path items with $ref are not supported",
+            ]
+        );
+
+        let notes = &imported.collections[0].requests[2];
+        assert_eq!(notes.body_type, HttpBodyType::RAW);
+        assert_eq!(notes.headers, vec![row("Content-Type", "text/plain")]);
+    }
+
+    #[test]
+    fn swagger_2_forms_and_hosts() {
+        let swagger = r#"
+swagger: "2.0"
+info:
+  title: Files
+host: files.test
+basePath: /api
+schemes: [http, https]
+paths:
+  /upload:
+    post:
+      parameters:
+        - { name: note, in: formData, type: string, default: hello }
+        - { name: file, in: formData, type: file }
+  /login:
+    post:
+      consumes: [application/x-www-form-urlencoded]
+      parameters:
+        - { name: user, in: formData, type: string, example: ada }
+  /items:
+    post:
+      parameters:
+        - { name: body, in: body, schema: { type: object, properties: { count: { type: integer } } } }
+"#;
+
+        let imported = import_openapi(swagger).unwrap();
+        assert!(imported.warnings.is_empty(), "{:?}", imported.warnings);
+
+        let names: Vec<&str> = imported
+            .environments
+            .iter()
+            .map(|e| e.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "Files (http://files.test/api)",
+                "Files (https://files.test/api)"
+            ]
+        );
+
+        let requests = &imported.collections[0].requests;
+
+        let upload = requests
+            .iter()
+            .find(|r| r.source == "POST /upload")
+            .unwrap();
+        assert_eq!(upload.body_type, HttpBodyType::MULTIPART);
+        assert_eq!(
+            upload
+                .multipart
+                .iter()
+                .map(|f| (f.name.as_str(), f.value.as_str(), f.is_file))
+                .collect::<Vec<_>>(),
+            vec![("note", "hello", false), ("file", "", true)]
+        );
+
+        let login = requests.iter().find(|r| r.source == "POST /login").unwrap();
+        assert_eq!(login.body_type, HttpBodyType::FORM_URLENCODED);
+        assert_eq!(login.form, vec![row("user", "ada")]);
+
+        let items = requests.iter().find(|r| r.source == "POST /items").unwrap();
+        assert_eq!(items.body_type, HttpBodyType::JSON);
+        assert_eq!(
+            serde_json::from_str::<Value>(&items.body).unwrap(),
+            json!({ "count": 0 })
+        );
+    }
+
+    #[test]
+    fn documents_without_servers_or_openapi_field() {
+        let imported = import_openapi(
+            r#"{ "openapi": "3.1.0", "info": {}, "paths": { "/a": { "get": {} } } }"#,
+        )
+        .unwrap();
+
+        assert_eq!(imported.collections[0].name, "API");
+        assert_eq!(imported.environments[0].name, "API");
+        assert_eq!(
+            imported.environments[0].variables,
+            vec![row("base_url", "")]
+        );
+        assert_eq!(
+            imported.warnings,
+            vec!["API has no server, set base_url in its environment"]
+        );
+
+        assert!(import_openapi(r#"{ "info": {} }"#).is_err());
+        assert!(import_openapi("{ not: [valid").is_err());
+    }
+}
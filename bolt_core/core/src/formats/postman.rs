@@ -0,0 +1,1049 @@
+use bolt_common::prelude::*;
+use serde_json::{json, Map, Value};
+
+use super::{Exported, Imported};
+
+const SCHEMA: &str = "https://schema.getpostman.com/json/collection/v2.1.0/collection.json";
+
+pub fn is_postman(json: &Value) -> bool {
+    json["info"]["schema"]
+        .as_str()
+        .is_some_and(|schema| schema.contains("schema.getpostman.com"))
+}
+
+// every folder becomes a collection named after its path, e.g. "API / Users", and the
+// collection variables become an environment
+pub fn import_postman(content: &str) -> Result<Imported, String> {
+    let root: Value = serde_json::from_str(content)
+        .map_err(|err| format!("invalid Postman collection: {err}"))?;
+
+    if !root["item"].is_array() {
+        return Err("not a Postman collection, it has no item list".to_string());
+    }
+
+    let mut importer = Importer {
+        collections: vec![],
+        warnings: vec![],
+    };
+
+    let schema = root["info"]["schema"].as_str().unwrap_or_default();
+
+    if !schema.contains("v2.1") {
+        importer
+            .warnings
+            .push(format!("{schema} is not the v2.1 schema, imported as v2.1"));
+    }
+
+    let name = text(&root["info"]["name"]);
+    let name = if name.trim().is_empty() {
+        "Postman".to_string()
+    } else {
+        name
+    };
+
+    importer.folder(&root, &name, None);
+
+    let mut environments = vec![];
+
+    if let Some(variables) = root["variable"].as_array() {
+        let variables: Vec<Vec<String>> = variables
+            .iter()
+            .filter(|var| !is_disabled(var) && !text(&var["key"]).is_empty())
+            .map(|var| vec![text(&var["key"]), text(&var["value"])])
+            .collect();
+
+        if !variables.is_empty() {
+            environments.push(Environment {
+                name: name.clone(),
+                variables,
+            });
+        }
+    }
+
+    Ok(Imported {
+        collections: importer.collections,
+        environments,
+        ws_connections: vec![],
+        warnings: importer.warnings,
+        update: false,
+    })
+}
+
+struct Importer {
+    collections: Vec<Collection>,
+    warnings: Vec<String>,
+}
+
+impl Importer {
+    fn folder(&mut self, folder: &Value, path: &str, inherited_auth: Option<&Value>) {
+        let auth = folder
+            .get("auth")
+            .filter(|auth| !auth.is_null())
+            .or(inherited_auth);
+
+        self.scripts(folder, path);
+
+        let mut collection = Collection::new();
+        collection.name = path.to_string();
+
+        let mut folders = vec![];
+
+        for item in folder["item"].as_array().into_iter().flatten() {
+            let name = text(&item["name"]);
+
+            if item["item"].is_array() {
+                folders.push(item);
+            } else if !item["request"].is_null() {
+                let request = self.request(item, &format!("{path} / {name}"), auth);
+
+                collection.requests.push(request);
+            } else {
+                self.warn(
+                    path,
+                    &format!("skipped {name}, it is neither a request nor a folder"),
+                );
+            }
+        }
+
+        // the parent comes before its folders
+        if !collection.requests.is_empty() {
+            self.collections.push(collection);
+        }
+
+        for item in folders {
+            let name = text(&item["name"]);
+
+            self.folder(item, &format!("{path} / {name}"), auth);
+        }
+    }
+
+    fn request(
+        &mut self,
+        item: &Value,
+        context: &str,
+        inherited_auth: Option<&Value>,
+    ) -> HttpRequest {
+        let mut request = HttpRequest::new();
+        request.name = text(&item["name"]);
+
+        let source = &item["request"];
+
+        // a request can be just its url
+        if let Some(url) = source.as_str() {
+            request.url = url.to_string();
+
+            return request;
+        }
+
+        let method = source["method"].as_str().unwrap_or("GET");
+
+        request.method = match parse_method(method) {
+            Some(method) => method,
+            None => {
+                self.warn(
+                    context,
+                    &format!("{method} is not supported, imported as GET"),
+                );
+
+                HttpMethod::GET
+            }
+        };
+
+        self.url(&source["url"], &mut request, context);
+        self.headers(&source["header"], &mut request, context);
+        self.body(&source["body"], &mut request, context);
+
+        let auth = source
+            .get("auth")
+            .filter(|auth| !auth.is_null())
+            .or(inherited_auth);
+
+        if let Some(auth) = auth {
+            request.auth = self.auth(auth, context);
+        }
+
+        self.settings(&item["protocolProfileBehavior"], &mut request);
+        self.scripts(item, context);
+
+        if item["response"]
+            .as_array()
+            .is_some_and(|responses| !responses.is_empty())
+        {
+            self.warn(context, "saved example responses are not imported");
+        }
+
+        request
+    }
+
+    fn url(&mut self, url: &Value, request: &mut HttpRequest, context: &str) {
+        let Some(object) = url.as_object() else {
+            request.url = text(url);
+
+            return;
+        };
+
+        let mut raw = text(&url["raw"]);
+
+        if raw.is_empty() {
+            raw = url_from_parts(object);
+        }
+
+        // :name path variables are filled in, bolt only has {{variables}}
+        for var in url["variable"].as_array().into_iter().flatten() {
+            let key = text(&var["key"]);
+            let value = text(&var["value"]);
+
+            if key.is_empty() {
+                continue;
+            }
+
+            if value.is_empty() {
+                self.warn(context, &format!("path variable :{key} has no value"));
+            } else {
+                raw = replace_path_variable(&raw, &key, &value);
+            }
+        }
+
+        let Some(query) = url["query"].as_array() else {
+            request.url = raw;
+
+            return;
+        };
+
+        let base = raw.split('?').next().unwrap_or_default().to_string();
+
+        let mut params: Vec<Vec<String>> = vec![];
+
+        for param in query {
+            let key = text(&param["key"]);
+
+            if is_disabled(param) {
+                self.warn(
+                    context,
+                    &format!("disabled query parameter {key} is not imported"),
+                );
+            } else if !key.is_empty() {
+                params.push(vec![key, text(&param["value"])]);
+            }
+        }
+
+        // parse_url drops params without a value, those stay in the url
+        if params.iter().all(|param| !param[1].is_empty()) {
+            request.url = base;
+
+            if !params.is_empty() {
+                request.params = params;
+            }
+        } else {
+            let query: Vec<String> = params
+                .iter()
+                .map(|param| {
+                    if param[1].is_empty() {
+                        param[0].clone()
+                    } else {
+                        format!("{}={}", param[0], param[1])
+                    }
+                })
+                .collect();
+
+            request.url = format!("{base}?{}", query.join("&"));
+        }
+    }
+
+    fn headers(&mut self, headers: &Value, request: &mut HttpRequest, context: &str) {
+        let mut rows: Vec<Vec<String>> = vec![];
+
+        match headers {
+            Value::Array(headers) => {
+                for header in headers {
+                    let key = text(&header["key"]);
+
+                    if is_disabled(header) {
+                        self.warn(context, &format!("disabled header {key} is not imported"));
+                    } else if !key.is_empty() {
+                        rows.push(vec![key, text(&header["value"])]);
+                    }
+                }
+            }
+
+            // "Name: value" lines
+            Value::String(headers) => {
+                for line in headers.lines() {
+                    if let Some((name, value)) = line.split_once(':') {
+                        rows.push(vec![name.trim().to_string(), value.trim().to_string()]);
+                    }
+                }
+            }
+
+            _ => {}
+        }
+
+        if !rows.is_empty() {
+            request.headers = rows;
+        }
+    }
+
+    fn body(&mut self, body: &Value, request: &mut HttpRequest, context: &str) {
+        if body.is_null() || body["disabled"].as_bool() == Some(true) {
+            return;
+        }
+
+        match body["mode"].as_str().unwrap_or_default() {
+            "raw" => {
+                request.body = text(&body["raw"]);
+
+                let language = body["options"]["raw"]["language"]
+                    .as_str()
+                    .unwrap_or_default();
+
+                request.body_type = if language == "json" {
+                    HttpBodyType::JSON
+                } else {
+                    HttpBodyType::RAW
+                };
+            }
+
+            "urlencoded" => {
+                let mut form = vec![];
+
+                for row in body["urlencoded"].as_array().into_iter().flatten() {
+                    let key = text(&row["key"]);
+
+                    if is_disabled(row) {
+                        self.warn(
+                            context,
+                            &format!("disabled form field {key} is not imported"),
+                        );
+                    } else if !key.is_empty() {
+                        form.push(vec![key, text(&row["value"])]);
+                    }
+                }
+
+                request.body_type = HttpBodyType::FORM_URLENCODED;
+
+                if !form.is_empty() {
+                    request.form = form;
+                }
+            }
+
+            "formdata" => {
+                let mut fields = vec![];
+
+                for row in body["formdata"].as_array().into_iter().flatten() {
+                    let key = text(&row["key"]);
+
+                    if is_disabled(row) {
+                        self.warn(
+                            context,
+                            &format!("disabled form field {key} is not imported"),
+                        );
+                        continue;
+                    }
+
+                    if key.is_empty() {
+                        continue;
+                    }
+
+                    if row["type"].as_str() == Some("file") {
+                        let src = match &row["src"] {
+                            Value::Array(files) => {
+                                if files.len() > 1 {
+                                    self.warn(
+                                        context,
+                                        &format!("form field {key} has several files, only the first is imported"),
+                                    );
+                                }
+
+                                files.first().map(text).unwrap_or_default()
+                            }
+                            src => text(src),
+                        };
+
+                        fields.push(MultipartField {
+                            name: key,
+                            value: src,
+                            is_file: true,
+                        });
+                    } else {
+                        fields.push(MultipartField {
+                            name: key,
+                            value: text(&row["value"]),
+                            is_file: false,
+                        });
+                    }
+                }
+
+                request.body_type = HttpBodyType::MULTIPART;
+
+                if !fields.is_empty() {
+                    request.multipart = fields;
+                }
+            }
+
+            "file" => {
+                let src = text(&body["file"]["src"]);
+
+                if src.is_empty() {
+                    self.warn(context, "the body file has no path");
+                }
+
+                request.body_type = HttpBodyType::BINARY;
+                request.body_file = src;
+            }
+
+            "graphql" => {
+                request.body_type = HttpBodyType::GRAPHQL;
+                request.graphql = GraphqlBody {
+                    query: text(&body["graphql"]["query"]),
+                    variables: text(&body["graphql"]["variables"]),
+                    operation_name: String::new(),
+                };
+            }
+
+            "" => {}
+
+            mode => self.warn(context, &format!("{mode} bodies are not supported")),
+        }
+    }
+
+    fn auth(&mut self, auth: &Value, context: &str) -> HttpAuth {
+        let mut result = HttpAuth::new();
+
+        let auth_type = auth["type"].as_str().unwrap_or("noauth");
+        let param = |key: &str| auth_param(auth, auth_type, key);
+
+        match auth_type {
+            "noauth" => {}
+
+            "basic" | "digest" => {
+                result.auth_type = if auth_type == "basic" {
+                    HttpAuthType::BASIC
+                } else {
+                    HttpAuthType::DIGEST
+                };
+
+                result.username = param("username");
+                result.password = param("password");
+            }
+
+            "bearer" => {
+                result.auth_type = HttpAuthType::BEARER;
+                result.token = param("token");
+            }
+
+            "apikey" => {
+                result.auth_type = HttpAuthType::API_KEY;
+                result.key = param("key");
+                result.value = param("value");
+                result.location = if param("in") == "query" {
+                    ApiKeyLocation::QUERY
+                } else {
+                    ApiKeyLocation::HEADER
+                };
+            }
+
+            "oauth2" => {
+                result.auth_type = HttpAuthType::OAUTH2;
+
+                let oauth2 = &mut result.oauth2;
+
+                oauth2.grant_type = match param("grant_type").as_str() {
+                    "password_credentials" | "password" => OAuth2GrantType::PASSWORD,
+                    "authorization_code" | "authorization_code_with_pkce" => {
+                        OAuth2GrantType::AUTHORIZATION_CODE
+                    }
+                    "client_credentials" | "" => OAuth2GrantType::CLIENT_CREDENTIALS,
+                    grant => {
+                        self.warn(
+                            context,
+                            &format!("the {grant} OAuth 2.0 grant is not supported"),
+                        );
+
+                        OAuth2GrantType::CLIENT_CREDENTIALS
+                    }
+                };
+
+                oauth2.token_url = param("accessTokenUrl");
+                oauth2.auth_url = param("authUrl");
+                oauth2.redirect_url = param("redirect_uri");
+                oauth2.client_id = param("clientId");
+                oauth2.client_secret = param("clientSecret");
+                oauth2.scope = param("scope");
+                oauth2.client_auth_header = param("client_authentication") != "body";
+
+                result.username = param("username");
+                result.password = param("password");
+            }
+
+            other => self.warn(context, &format!("{other} auth is not supported")),
+        }
+
+        result
+    }
+
+    fn settings(&mut self, behavior: &Value, request: &mut HttpRequest) {
+        let Some(behavior) = behavior.as_object() else {
+            return;
+        };
+
+        let mut settings = HttpSettings::new();
+
+        if let Some(follow) = behavior.get("followRedirects").and_then(Value::as_bool) {
+            settings.follow_redirects = follow;
+        }
+
+        if let Some(max) = behavior.get("maxRedirects").and_then(Value::as_u64) {
+            settings.max_redirects = max as usize;
+        }
+
+        if let Some(strict) = behavior.get("strictSSL").and_then(Value::as_bool) {
+            settings.verify_tls = strict;
+        }
+
+        if settings != HttpSettings::new() {
+            request.settings = Some(settings);
+        }
+    }
+
+    fn scripts(&mut self, item: &Value, context: &str) {
+        let has_script = item["event"].as_array().into_iter().flatten().any(|event| {
+            match &event["script"]["exec"] {
+                Value::Array(lines) => lines.iter().any(|line| !text(line).trim().is_empty()),
+                exec => !text(exec).trim().is_empty(),
+            }
+        });
+
+        if has_script {
+            self.warn(context, "pre-request and test scripts are not imported");
+        }
+    }
+
+    fn warn(&mut self, context: &str, warning: &str) {
+        self.warnings.push(format!("{context}: {warning}"));
+    }
+}
+
+pub fn export_postman(collection: &Collection) -> Exported {
+    let mut warnings = vec![];
+
+    let items: Vec<Value> = collection
+        .requests
+        .iter()
+        .map(|request| {
+            let context = format!("{} / {}", collection.name, request.name);
+
+            export_request(request, &context, &mut warnings)
+        })
+        .collect();
+
+    let postman = json!({
+        "info": {
+            "_postman_id": uuid::Uuid::new_v4().to_string(),
+            "name": collection.name.trim(),
+            "schema": SCHEMA,
+        },
+        "item": items,
+    });
+
+    Exported {
+        content: serde_json::to_string_pretty(&postman).unwrap(),
+        warnings,
+    }
+}
+
+fn export_request(request: &HttpRequest, context: &str, warnings: &mut Vec<String>) -> Value {
+    let headers: Vec<Value> = request
+        .headers
+        .iter()
+        .filter(|header| !header[0].trim().is_empty())
+        .map(|header| json!({ "key": header[0].trim(), "value": header[1], "type": "text" }))
+        .collect();
+
+    let mut source = Map::new();
+
+    source.insert("method".to_string(), json!(request.method.to_string()));
+    source.insert("header".to_string(), json!(headers));
+    source.insert("url".to_string(), export_url(request));
+
+    if let Some(body) = export_body(request) {
+        source.insert("body".to_string(), body);
+    }
+
+    if let Some(auth) = export_auth(&request.auth) {
+        source.insert("auth".to_string(), auth);
+    }
+
+    let mut item = Map::new();
+
+    item.insert("name".to_string(), json!(request.name.trim()));
+    item.insert("request".to_string(), Value::Object(source));
+    item.insert("response".to_string(), json!([]));
+
+    if let Some(settings) = &request.settings {
+        item.insert(
+            "protocolProfileBehavior".to_string(),
+            json!({
+                "followRedirects": settings.follow_redirects,
+                "maxRedirects": settings.max_redirects,
+                "strictSSL": settings.verify_tls,
+            }),
+        );
+
+        let unmapped = settings.connect_timeout > 0
+            || settings.timeout > 0
+            || !settings.proxy.is_empty()
+            || !settings.ca_cert.is_empty()
+            || !settings.client_cert.is_empty()
+            || !settings.client_key.is_empty();
+
+        if unmapped {
+            warnings.push(format!(
+                "{context}: timeouts, proxy and certificates are not exported"
+            ));
+        }
+    }
+
+    if !request.assertions.is_empty() {
+        warnings.push(format!("{context}: assertions are not exported"));
+    }
+
+    if !request.extractions.is_empty() {
+        warnings.push(format!("{context}: extraction rules are not exported"));
+    }
+
+    Value::Object(item)
+}
+
+fn export_url(request: &HttpRequest) -> Value {
+    let params: Vec<&Vec<String>> = request
+        .params
+        .iter()
+        .filter(|param| !param[0].is_empty() && !param[1].is_empty())
+        .collect();
+
+    let raw = parse_url(request.url.clone(), request.params.clone());
+
+    let (base, inline_query) = match request.url.split_once('?') {
+        Some((base, query)) => (base, Some(query)),
+        None => (request.url.as_str(), None),
+    };
+
+    let mut query: Vec<Value> = vec![];
+
+    for pair in inline_query.into_iter().flat_map(|query| query.split('&')) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+
+        query.push(json!({ "key": key, "value": value }));
+    }
+
+    for param in params {
+        query.push(json!({ "key": param[0], "value": param[1] }));
+    }
+
+    let (protocol, rest) = match base.split_once("://") {
+        Some((protocol, rest)) => (Some(protocol), rest),
+        None => (None, base),
+    };
+
+    let (host, path) = match rest.split_once('/') {
+        Some((host, path)) => (host, Some(path)),
+        None => (rest, None),
+    };
+
+    let mut url = Map::new();
+
+    url.insert("raw".to_string(), json!(raw));
+
+    if let Some(protocol) = protocol {
+        url.insert("protocol".to_string(), json!(protocol));
+    }
+
+    // {{base_url}} is one host segment
+    let host: Vec<&str> = if host.starts_with("{{") {
+        vec![host]
+    } else {
+        host.split('.').collect()
+    };
+
+    url.insert("host".to_string(), json!(host));
+
+    if let Some(path) = path {
+        url.insert(
+            "path".to_string(),
+            json!(path.split('/').collect::<Vec<&str>>()),
+        );
+    }
+
+    if !query.is_empty() {
+        url.insert("query".to_string(), json!(query));
+    }
+
+    Value::Object(url)
+}
+
+fn export_body(request: &HttpRequest) -> Option<Value> {
+    let body = match request.body_type {
+        HttpBodyType::RAW if !request.body.is_empty() => json!({
+            "mode": "raw",
+            "raw": request.body,
+        }),
+
+        HttpBodyType::JSON if !request.body.is_empty() => json!({
+            "mode": "raw",
+            "raw": request.body,
+            "options": { "raw": { "language": "json" } },
+        }),
+
+        HttpBodyType::FORM_URLENCODED => {
+            let rows: Vec<Value> = request
+                .form
+                .iter()
+                .filter(|row| !row[0].is_empty())
+                .map(|row| json!({ "key": row[0], "value": row[1], "type": "text" }))
+                .collect();
+
+            json!({ "mode": "urlencoded", "urlencoded": rows })
+        }
+
+        HttpBodyType::MULTIPART => {
+            let rows: Vec<Value> = request
+                .multipart
+                .iter()
+                .filter(|field| !field.name.is_empty())
+                .map(|field| {
+                    if field.is_file {
+                        json!({ "key": field.name, "src": field.value, "type": "file" })
+                    } else {
+                        json!({ "key": field.name, "value": field.value, "type": "text" })
+                    }
+                })
+                .collect();
+
+            json!({ "mode": "formdata", "formdata": rows })
+        }
+
+        HttpBodyType::BINARY if !request.body_file.is_empty() => json!({
+            "mode": "file",
+            "file": { "src": request.body_file },
+        }),
+
+        HttpBodyType::GRAPHQL => json!({
+            "mode": "graphql",
+            "graphql": {
+                "query": request.graphql.query,
+                "variables": request.graphql.variables,
+            },
+        }),
+
+        _ => return None,
+    };
+
+    Some(body)
+}
+
+fn export_auth(auth: &HttpAuth) -> Option<Value> {
+    let param = |key: &str, value: &str| json!({ "key": key, "value": value, "type": "string" });
+
+    let auth = match auth.auth_type {
+        HttpAuthType::NONE => return None,
+
+        HttpAuthType::BASIC => json!({
+            "type": "basic",
+            "basic": [param("username", &auth.username), param("password", &auth.password)],
+        }),
+
+        HttpAuthType::DIGEST => json!({
+            "type": "digest",
+            "digest": [param("username", &auth.username), param("password", &auth.password)],
+        }),
+
+        HttpAuthType::BEARER => json!({
+            "type": "bearer",
+            "bearer": [param("token", &auth.token)],
+        }),
+
+        HttpAuthType::API_KEY => {
+            let location = match auth.location {
+                ApiKeyLocation::HEADER => "header",
+                ApiKeyLocation::QUERY => "query",
+            };
+
+            json!({
+                "type": "apikey",
+                "apikey": [param("key", &auth.key), param("value", &auth.value), param("in", location)],
+            })
+        }
+
+        HttpAuthType::OAUTH2 => {
+            let oauth2 = &auth.oauth2;
+
+            let grant = match oauth2.grant_type {
+                OAuth2GrantType::CLIENT_CREDENTIALS => "client_credentials",
+                OAuth2GrantType::PASSWORD => "password_credentials",
+                OAuth2GrantType::AUTHORIZATION_CODE => "authorization_code_with_pkce",
+            };
+
+            let client_authentication = if oauth2.client_auth_header {
+                "header"
+            } else {
+                "body"
+            };
+
+            json!({
+                "type": "oauth2",
+                "oauth2": [
+                    param("grant_type", grant),
+                    param("accessTokenUrl", &oauth2.token_url),
+                    param("authUrl", &oauth2.auth_url),
+                    param("redirect_uri", &oauth2.redirect_url),
+                    param("clientId", &oauth2.client_id),
+                    param("clientSecret", &oauth2.client_secret),
+                    param("scope", &oauth2.scope),
+                    param("client_authentication", client_authentication),
+                    param("username", &auth.username),
+                    param("password", &auth.password),
+                ],
+            })
+        }
+    };
+
+    Some(auth)
+}
+
+// v2.1 keeps auth parameters as a list of key/value pairs, v2.0 as an object
+fn auth_param(auth: &Value, auth_type: &str, key: &str) -> String {
+    match &auth[auth_type] {
+        Value::Array(params) => params
+            .iter()
+            .find(|param| param["key"].as_str() == Some(key))
+            .map(|param| text(&param["value"]))
+            .unwrap_or_default(),
+
+        params => text(&params[key]),
+    }
+}
+
+fn url_from_parts(url: &Map<String, Value>) -> String {
+    let join = |value: Option<&Value>, separator: &str| match value {
+        Some(Value::Array(parts)) => parts
+            .iter()
+            .map(text)
+            .collect::<Vec<String>>()
+            .join(separator),
+        Some(value) => text(value),
+        None => String::new(),
+    };
+
+    let mut raw = String::new();
+
+    if let Some(protocol) = url.get("protocol").and_then(Value::as_str) {
+        raw += protocol;
+        raw += "://";
+    }
+
+    raw += &join(url.get("host"), ".");
+
+    let path = join(url.get("path"), "/");
+
+    if !path.is_empty() {
+        raw.push('/');
+        raw += &path;
+    }
+
+    raw
+}
+
+// only the path is searched, the query and fragment are kept as they are
+pub(super) fn replace_path_variable(url: &str, key: &str, value: &str) -> String {
+    let placeholder = format!(":{key}");
+
+    let end = url.find(['?', '#']).unwrap_or(url.len());
+    let (path, rest) = url.split_at(end);
+
+    let path = path
+        .split('/')
+        .map(|segment| {
+            if segment == placeholder {
+                value
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<&str>>()
+        .join("/");
+
+    path + rest
+}
+
+fn parse_method(name: &str) -> Option<HttpMethod> {
+    (0..HttpMethod::count())
+        .map(HttpMethod::from)
+        .find(|method| method.to_string().eq_ignore_ascii_case(name))
+}
+
+fn is_disabled(value: &Value) -> bool {
+    value["disabled"].as_bool() == Some(true)
+}
+
+// strings as they are, numbers and booleans as text, anything else empty
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Number(number) => number.to_string(),
+        Value::Bool(boolean) => boolean.to_string(),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::row;
+
+    const COLLECTION: &str = r#"{
+        "info": { "name": "Shop", "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json" },
+        "auth": { "type": "bearer", "bearer": [{ "key": "token", "value": "{{token}}" }] },
+        "variable": [{ "key": "host", "value": "shop.test" }, { "key": "off", "value": "1", "disabled": true }],
+        "item": [
+            {
+                "name": "Health",
+                "request": "http://{{host}}/health"
+            },
+            {
+                "name": "Orders",
+                "item": [
+                    {
+                        "name": "Get order",
+                        "request": {
+                            "method": "GET",
+                            "url": {
+                                "raw": "http://{{host}}/orders/:id?expand=items",
+                                "query": [
+                                    { "key": "expand", "value": "items" },
+                                    { "key": "debug", "value": "1", "disabled": true }
+                                ],
+                                "variable": [{ "key": "id", "value": "42" }]
+                            },
+                            "header": [{ "key": "Accept", "value": "application/json" }]
+                        },
+                        "response": [{ "name": "example" }]
+                    },
+                    {
+                        "name": "Create order",
+                        "request": {
+                            "method": "POST",
+                            "url": "http://{{host}}/orders",
+                            "auth": { "type": "basic", "basic": [
+                                { "key": "username", "value": "ada" },
+                                { "key": "password", "value": "pw" }
+                            ] },
+                            "body": { "mode": "raw", "raw": "{\"sku\": 1}", "options": { "raw": { "language": "json" } } }
+                        },
+                        "protocolProfileBehavior": { "followRedirects": false }
+                    },
+                    {
+                        "name": "Upload",
+                        "request": {
+                            "method": "PUT",
+                            "url": "http://{{host}}/files",
+                            "body": { "mode": "formdata", "formdata": [
+                                { "key": "note", "value": "hi", "type": "text" },
+                                { "key": "file", "src": "/tmp/a.png", "type": "file" }
+                            ] }
+                        },
+                        "event": [{ "listen": "test", "script": { "exec": ["pm.test()"] } }]
+                    }
+                ]
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn folders_become_collections_and_variables_an_environment() {
+        let imported = import_postman(COLLECTION).unwrap();
+
+        let names: Vec<&str> = imported
+            .collections
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Shop", "Shop / Orders"]);
+
+        assert_eq!(imported.environments.len(), 1);
+        assert_eq!(imported.environments[0].name, "Shop");
+        assert_eq!(
+            imported.environments[0].variables,
+            vec![row("host", "shop.test")]
+        );
+
+        let health = &imported.collections[0].requests[0];
+        assert_eq!(health.url, "http://{{host}}/health");
+        assert_eq!(health.auth.auth_type, HttpAuthType::NONE);
+    }
+
+    #[test]
+    fn requests_keep_url_headers_body_and_auth() {
+        let imported = import_postman(COLLECTION).unwrap();
+        let orders = &imported.collections[1].requests;
+
+        let get = &orders[0];
+        assert_eq!(get.url, "http://{{host}}/orders/42");
+        assert_eq!(get.params, vec![row("expand", "items")]);
+        assert_eq!(get.headers, vec![row("Accept", "application/json")]);
+        // inherited from the collection
+        assert_eq!(get.auth.auth_type, HttpAuthType::BEARER);
+        assert_eq!(get.auth.token, "{{token}}");
+
+        let create = &orders[1];
+        assert_eq!(create.method, HttpMethod::POST);
+        assert_eq!(create.body_type, HttpBodyType::JSON);
+        assert_eq!(create.body, r#"{"sku": 1}"#);
+        assert_eq!(create.auth.auth_type, HttpAuthType::BASIC);
+        assert_eq!(create.auth.username, "ada");
+        assert!(!create.settings.as_ref().unwrap().follow_redirects);
+
+        let upload = &orders[2];
+        assert_eq!(upload.body_type, HttpBodyType::MULTIPART);
+        assert_eq!(upload.multipart.len(), 2);
+        assert_eq!(upload.multipart[1].value, "/tmp/a.png");
+        assert!(upload.multipart[1].is_file);
+    }
+
+    #[test]
+    fn what_is_not_imported_is_reported() {
+        let imported = import_postman(COLLECTION).unwrap();
+
+        assert_eq!(
+            imported.warnings,
+            vec![
+                "Shop / Orders / Get order: disabled query parameter debug is not imported",
+                "Shop / Orders / Get order: saved example responses are not imported",
+                "Shop / Orders / Upload: pre-request and test scripts are not imported",
+            ]
+        );
+
+        assert!(import_postman(r#"{"info": {}}"#).is_err());
+        assert!(import_postman("not json").is_err());
+    }
+
+    #[test]
+    fn exports_import_back() {
+        let imported = import_postman(COLLECTION).unwrap();
+        let orders = &imported.collections[1];
+
+        let exported = export_postman(orders);
+        let json: Value = serde_json::from_str(&exported.content).unwrap();
+
+        assert!(is_postman(&json));
+
+        let again = import_postman(&exported.content).unwrap();
+        let requests = &again.collections[0].requests;
+
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[0].url, orders.requests[0].url);
+        assert_eq!(requests[0].params, orders.requests[0].params);
+        assert_eq!(requests[1].body, orders.requests[1].body);
+        assert_eq!(requests[1].auth.password, "pw");
+        assert_eq!(requests[2].multipart.len(), 2);
+    }
+}
@@ -1,98 +1,64 @@
+mod cli;
+mod formats;
+pub mod runner;
 mod session;
 mod utils;
 
-use bolt_common::prelude::*;
+use bolt_grpc::start_core_grpc_service;
+use bolt_sse::start_core_sse_service;
 use bolt_tcp::start_core_tcp_service;
 use bolt_udp::start_core_udp_service;
 use bolt_ws::start_core_ws_service;
+use clap::Parser;
+use cli::{Cli, Command};
 
-static HELP: &str = r#"
-Bolt CLI (Build and test APIs)
-
-Usage:
-  bolt [OPTIONS]...
-  bolt -h | --help
-  bolt -v | --version
-Options:
-  -h --help      Show this screen.
-  -v --version   Show version.
-  --reset        Reset static files
-    "#;
-
-static ADDRESS: &str = "127.0.0.1";
+static DEFAULT_ADDRESS: &str = "127.0.0.1";
 
+// port is the default core port, --port overrides it
 pub fn start(args: Vec<String>, port: u16) {
-    let mut args = args;
-
-    args.remove(0);
+    let cli = Cli::parse_from(args);
 
-    let mut is_tauri = false;
-    let mut is_headless = false;
-    let mut launch = false;
-    let mut reset = false;
-
-    match std::env::var_os("BOLT_DEV") {
-        Some(_) => {
-            reset = true;
-        }
-        None => {}
+    if let Some(home) = &cli.home {
+        utils::set_home(home);
     }
 
-    if args.len() > 0 {
-        let flag = args[0].as_str();
-
-        match flag {
-            "--reset" => reset = true,
-
-            "-h" | "--help" => {
-                println!("{}", HELP);
-            }
-
-            "-v" | "--version" => {
-                println!("bolt {}", VERSION);
-            }
-
-            "--tauri" => {
-                is_tauri = true;
-
-                launch = true;
-            }
-
-            "--headless" => {
-                is_headless = true;
+    // a plain --reset only resets, like before subcommands existed
+    let (launch, serve) = match cli.command {
+        Some(Command::Serve(serve)) => (true, serve),
+        Some(command) => std::process::exit(cli::execute(command)),
+        None => (!cli.reset, cli.serve),
+    };
 
-                launch = true;
-            }
-
-            _ => {
-                panic!("unknown flag");
-            }
-        }
-    } else {
-        launch = true;
-    }
+    let reset = cli.reset || std::env::var_os("BOLT_DEV").is_some();
 
     if reset {
         utils::reset_home();
     }
 
     if launch {
+        let port = serve.port.unwrap_or(port);
+        let address = serve.address.unwrap_or_else(|| DEFAULT_ADDRESS.to_string());
+
         utils::verify_home();
         utils::verify_state();
 
-        if !is_tauri {
+        if !serve.tauri {
             utils::verify_dist();
         }
 
-        if !is_tauri && !is_headless {
+        if !serve.tauri && !serve.headless {
+            let asset_address = address.clone();
+            let open_browser = !serve.no_browser;
+
+            // the UI finds the core server one port below the one it is served from
             std::thread::spawn(move || {
-                session::asset::launch_asset_server(port + 1, ADDRESS.to_string());
+                session::asset::launch_asset_server(port + 1, asset_address, open_browser);
 
                 std::process::exit(0);
             });
         }
 
-        session::server::launch_core_server(port, ADDRESS.to_string());
+        session::server::launch_core_server(port, address);
     }
 }
 
@@ -102,6 +68,8 @@ fn start_services(session_id: String) {
     let ws_session_id = session_id.clone();
     let udp_session_id = session_id.clone();
     let tcp_session_id = session_id.clone();
+    let sse_session_id = session_id.clone();
+    let grpc_session_id = session_id.clone();
 
     std::thread::spawn(move || {
         start_core_ws_service(ws_session_id);
@@ -114,4 +82,12 @@ fn start_services(session_id: String) {
     std::thread::spawn(move || {
         start_core_udp_service(tcp_session_id);
     });
+
+    std::thread::spawn(move || {
+        start_core_sse_service(sse_session_id);
+    });
+
+    std::thread::spawn(move || {
+        start_core_grpc_service(grpc_session_id);
+    });
 }
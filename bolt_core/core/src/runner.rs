@@ -0,0 +1,325 @@
+use bolt_common::prelude::*;
+use std::time::Duration;
+
+// runs every request of the collection in order, once per iteration, and reports each
+// result through on_result as soon as it is known
+pub async fn run_collection<F>(
+    collection: &Collection,
+    config: &RunnerConfig,
+    mut on_result: F,
+) -> Result<RunSummary, String>
+where
+    F: FnMut(&RunRequestResult, usize),
+{
+    let data = if config.data_file.trim().is_empty() {
+        vec![]
+    } else {
+        load_data(config.data_file.trim())?
+    };
+
+    let iterations = if data.is_empty() {
+        config.iterations.max(1) as usize
+    } else {
+        data.len()
+    };
+
+    let total = iterations * collection.requests.len();
+
+    let mut results = vec![];
+    let mut stopped = false;
+
+    'run: for iteration in 0..iterations {
+        for (index, request) in collection.requests.iter().enumerate() {
+            if !results.is_empty() && config.delay > 0 {
+                tokio::time::sleep(Duration::from_millis(config.delay)).await;
+            }
+
+            let mut send_request = request.to_send_request(index);
+
+            // data columns win over the environment, which http_send resolves afterwards
+            if let Some(row) = data.get(iteration) {
+                let data_env = Environment {
+                    name: String::new(),
+                    variables: row.clone(),
+                };
+
+                send_request.resolve_variables(&data_env);
+            }
+
+            let url = send_request.url.clone();
+
+            let resp = bolt_http::http_send(send_request).await;
+
+            let result = RunRequestResult {
+                iteration: iteration as u32,
+                request_index: index,
+                name: request.name.clone(),
+                method: request.method,
+                url,
+                status: resp.status,
+                time: resp.time,
+                timing: resp.timing.clone(),
+                failed: resp.failed,
+                error: if resp.failed { resp.body.clone() } else { String::new() },
+                tests: resp.tests.clone(),
+                extracted: resp.extracted.clone(),
+            };
+
+            on_result(&result, total);
+
+            let passed = result.passed();
+
+            results.push(result);
+
+            if config.stop_on_failure && !passed {
+                stopped = true;
+
+                break 'run;
+            }
+        }
+    }
+
+    let mut summary = RunSummary::from_results(&results);
+
+    // a stopped run only counts the iterations it started
+    if !stopped {
+        summary.iterations = iterations as u32;
+    }
+    summary.stopped = stopped;
+
+    Ok(summary)
+}
+
+// one [name, value] list per iteration
+fn load_data(path: &str) -> Result<Vec<Vec<Vec<String>>>, String> {
+    let content =
+        std::fs::read_to_string(path).map_err(|err| format!("Could not read {path}: {err}"))?;
+
+    // spreadsheet exports start with a byte order mark that would stick to the first name
+    let content = content.strip_prefix('\u{feff}').unwrap_or(&content);
+
+    if path.to_lowercase().ends_with(".json") || content.trim_start().starts_with('[') {
+        parse_json_data(content).map_err(|err| format!("Invalid data file {path}: {err}"))
+    } else {
+        parse_csv_data(content).map_err(|err| format!("Invalid data file {path}: {err}"))
+    }
+}
+
+fn parse_json_data(content: &str) -> Result<Vec<Vec<Vec<String>>>, String> {
+    let value: serde_json::Value = serde_json::from_str(content).map_err(|err| err.to_string())?;
+
+    let rows = value
+        .as_array()
+        .ok_or_else(|| "expected an array of objects".to_string())?;
+
+    let mut data = vec![];
+
+    for row in rows {
+        let object = row
+            .as_object()
+            .ok_or_else(|| "expected an array of objects".to_string())?;
+
+        let variables = object
+            .iter()
+            .map(|(key, value)| {
+                let value = match value {
+                    serde_json::Value::String(text) => text.clone(),
+                    other => other.to_string(),
+                };
+
+                vec![key.clone(), value]
+            })
+            .collect();
+
+        data.push(variables);
+    }
+
+    Ok(data)
+}
+
+// the first line holds the variable names
+fn parse_csv_data(content: &str) -> Result<Vec<Vec<Vec<String>>>, String> {
+    let mut records = parse_csv(content).into_iter();
+
+    let header = records.next().ok_or_else(|| "the file is empty".to_string())?;
+
+    let mut data = vec![];
+
+    for record in records {
+        if record.iter().all(|field| field.is_empty()) {
+            continue;
+        }
+
+        let variables = header
+            .iter()
+            .zip(record.iter())
+            .map(|(key, value)| vec![key.trim().to_string(), value.clone()])
+            .collect();
+
+        data.push(variables);
+    }
+
+    Ok(data)
+}
+
+// RFC 4180 style, quoted fields may hold commas, newlines and "" for a quote
+fn parse_csv(content: &str) -> Vec<Vec<String>> {
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut field = String::new();
+    let mut in_quotes = false;
+
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes => {
+                if chars.peek() == Some(&'"') {
+                    field.push('"');
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            }
+
+            '"' if field.is_empty() => in_quotes = true,
+
+            ',' if !in_quotes => record.push(std::mem::take(&mut field)),
+
+            '\r' if !in_quotes => {}
+
+            '\n' if !in_quotes => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+
+            _ => field.push(c),
+        }
+    }
+
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(pairs: &[(&str, &str)]) -> Vec<Vec<String>> {
+        pairs
+            .iter()
+            .map(|(key, value)| vec![key.to_string(), value.to_string()])
+            .collect()
+    }
+
+    fn temp_file(name: &str, content: &str) -> String {
+        let path = std::env::temp_dir().join(format!("bolt-data-{}-{name}", uuid::Uuid::new_v4()));
+        std::fs::write(&path, content).unwrap();
+
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn quoted_csv_fields() {
+        let records = parse_csv("a,\"b, c\",\"say \"\"hi\"\"\"\r\n\"two\nlines\",,x\"y\n");
+
+        assert_eq!(
+            records,
+            vec![
+                vec!["a", "b, c", "say \"hi\""],
+                vec!["two\nlines", "", "x\"y"],
+            ]
+        );
+
+        // the last record does not need a line break
+        assert_eq!(parse_csv("a,b\n1,2"), vec![vec!["a", "b"], vec!["1", "2"]]);
+    }
+
+    #[test]
+    fn csv_rows_become_iterations() {
+        let data =
+            parse_csv_data(" user ,id\r\nada,1\r\n\r\n,\r\ngrace,\"2\r\n3\"\r\n\r\n").unwrap();
+
+        // blank lines are skipped, quoted line breaks kept as they are
+        assert_eq!(
+            data,
+            vec![
+                row(&[("user", "ada"), ("id", "1")]),
+                row(&[("user", "grace"), ("id", "2\r\n3")]),
+            ]
+        );
+
+        assert_eq!(parse_csv_data("").unwrap_err(), "the file is empty");
+    }
+
+    #[test]
+    fn ragged_csv_rows_only_set_their_columns() {
+        let data = parse_csv_data("a,b,c\n1\n1,2,3,4\n").unwrap();
+
+        assert_eq!(
+            data,
+            vec![
+                row(&[("a", "1")]),
+                row(&[("a", "1"), ("b", "2"), ("c", "3")]),
+            ]
+        );
+    }
+
+    #[test]
+    fn json_values_that_are_not_strings() {
+        let data = parse_json_data(
+            r#"[{"name": "ada", "id": 1, "admin": true, "team": null, "tags": ["a"], "meta": {"k": 1.5}}]"#,
+        )
+        .unwrap();
+
+        // serde_json keeps the keys sorted
+        assert_eq!(
+            data,
+            vec![row(&[
+                ("admin", "true"),
+                ("id", "1"),
+                ("meta", r#"{"k":1.5}"#),
+                ("name", "ada"),
+                ("tags", r#"["a"]"#),
+                ("team", "null"),
+            ])]
+        );
+
+        assert_eq!(
+            parse_json_data(r#"{"name": "ada"}"#).unwrap_err(),
+            "expected an array of objects"
+        );
+        assert_eq!(
+            parse_json_data(r#"[1]"#).unwrap_err(),
+            "expected an array of objects"
+        );
+    }
+
+    #[test]
+    fn byte_order_marks_are_dropped() {
+        let csv = temp_file("data.csv", "\u{feff}first_column,second\nx,y\n");
+        let json = temp_file("data.txt", "\u{feff}[{\"first_column\": \"x\"}]");
+
+        let from_csv = load_data(&csv);
+        let from_json = load_data(&json);
+
+        std::fs::remove_file(&csv).unwrap();
+        std::fs::remove_file(&json).unwrap();
+
+        assert_eq!(
+            from_csv.unwrap(),
+            vec![row(&[("first_column", "x"), ("second", "y")])]
+        );
+        assert_eq!(from_json.unwrap(), vec![row(&[("first_column", "x")])]);
+
+        let err = load_data("/does/not/exist.csv").unwrap_err();
+        assert!(
+            err.starts_with("Could not read /does/not/exist.csv"),
+            "{err}"
+        );
+    }
+}
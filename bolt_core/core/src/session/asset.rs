@@ -12,11 +12,13 @@ pub async fn e404(_req: HttpRequest) -> HttpResponse {
 
 
 #[actix_web::main]
-pub async fn launch_asset_server(port: u16, address: String) {
-    std::thread::spawn(move || {
-        println!("opening browser");
-        open_browser("http://localhost:".to_string() + &port.to_string());
-    });
+pub async fn launch_asset_server(port: u16, address: String, launch_browser: bool) {
+    if launch_browser {
+        std::thread::spawn(move || {
+            println!("opening browser");
+            open_browser("http://localhost:".to_string() + &port.to_string());
+        });
+    }
 
     let asset_server = HttpServer::new(|| {
         let dist_path = get_dist();
@@ -27,9 +29,17 @@ pub async fn launch_asset_server(port: u16, address: String) {
     });
 
     println!("Starting asset server on http://{}:{}", address, port);
+    let asset_server = match asset_server.bind((address.clone(), port)) {
+        Ok(asset_server) => asset_server,
+        Err(err) => {
+            eprintln!("Could not serve the UI on {address}:{port}: {err}");
+            eprintln!("Pick other ports with --port");
+
+            std::process::exit(1);
+        }
+    };
+
     asset_server
-        .bind((address, port))
-        .unwrap()
         .run()
         .await
         .unwrap();
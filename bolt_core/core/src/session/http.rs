@@ -0,0 +1,403 @@
+use std::collections::HashMap;
+use std::net::TcpStream;
+use std::sync::Mutex;
+use tungstenite::{Message, WebSocket};
+
+use bolt_common::prelude::*;
+
+// sends and collection runs execute on their own runtime so the session keeps reading
+// messages, which is what lets a cancel reach a task that is still in flight
+lazy_static::lazy_static! {
+    static ref RUNTIME: tokio::runtime::Runtime = tokio::runtime::Runtime::new().unwrap();
+    static ref IN_FLIGHT: Mutex<HashMap<String, InFlight>> = Mutex::new(HashMap::new());
+    static ref SESSION_WEBSOCKET: Mutex<Option<WebSocket<TcpStream>>> = Mutex::new(None);
+}
+
+struct InFlight {
+    handle: tokio::task::AbortHandle,
+    is_run: bool,
+}
+
+pub fn set_session_websocket(new_ws: WebSocket<TcpStream>) {
+    let mut session_websocket = SESSION_WEBSOCKET.lock().unwrap();
+    *session_websocket = Some(new_ws);
+}
+
+pub fn spawn_request(request_id: String, request: SendHttpRequest) {
+    // held while spawning so a fast request can not finish before it is tracked
+    let mut in_flight = IN_FLIGHT.lock().unwrap();
+
+    let task_id = request_id.clone();
+
+    let handle = RUNTIME.spawn(async move {
+        let cookies_before = bolt_http::cookies::get_cookies();
+        let variables_before = bolt_http::variables::active_variables();
+
+        let mut resp = bolt_http::http_send(request).await;
+
+        // also after a cancel, the values are stored either way
+        send_variables_changed(variables_before);
+
+        // a cancel that won the race already answered the client
+        if IN_FLIGHT.lock().unwrap().remove(&task_id).is_none() {
+            return;
+        }
+
+        let cookies = bolt_http::cookies::get_cookies();
+
+        // sent before the response so the client saves the new jar with its next state
+        if cookies != cookies_before {
+            let msg = CookiesChangedMsg {
+                msg_type: MsgType::COOKIES_CHANGED,
+                cookies,
+            };
+
+            session_write(serde_json::to_string(&msg).unwrap());
+        }
+
+        resp.request_id = task_id;
+
+        session_write(serde_json::to_string(&resp).unwrap());
+    });
+
+    in_flight.insert(
+        request_id,
+        InFlight {
+            handle: handle.abort_handle(),
+            is_run: false,
+        },
+    );
+}
+
+// tells the client what the responses stored in the active environment
+fn send_variables_changed(before: Option<(usize, Vec<Vec<String>>)>) {
+    let (env, before) = match before {
+        Some(before) => before,
+        None => return,
+    };
+
+    let variables = match bolt_http::variables::active_variables() {
+        Some((after_env, after)) if after_env == env => {
+            bolt_http::variables::changed_variables(&before, &after)
+        }
+
+        _ => return,
+    };
+
+    if variables.is_empty() {
+        return;
+    }
+
+    let msg = VariablesChangedMsg {
+        msg_type: MsgType::VARIABLES_CHANGED,
+        env,
+        variables,
+    };
+
+    session_write(serde_json::to_string(&msg).unwrap());
+}
+
+pub fn spawn_run(run_id: String, collection: Collection, config: RunnerConfig) {
+    let mut in_flight = IN_FLIGHT.lock().unwrap();
+
+    let task_id = run_id.clone();
+
+    let handle = RUNTIME.spawn(async move {
+        let cookies_before = bolt_http::cookies::get_cookies();
+        let variables_before = bolt_http::variables::active_variables();
+
+        let outcome = crate::runner::run_collection(&collection, &config, |result, total| {
+            let msg = CollectionProgressMsg {
+                msg_type: MsgType::COLLECTION_PROGRESS,
+                run_id: task_id.clone(),
+                total,
+                result: result.clone(),
+            };
+
+            session_write(serde_json::to_string(&msg).unwrap());
+        })
+        .await;
+
+        send_variables_changed(variables_before);
+
+        if IN_FLIGHT.lock().unwrap().remove(&task_id).is_none() {
+            return;
+        }
+
+        let cookies = bolt_http::cookies::get_cookies();
+
+        if cookies != cookies_before {
+            let msg = CookiesChangedMsg {
+                msg_type: MsgType::COOKIES_CHANGED,
+                cookies,
+            };
+
+            session_write(serde_json::to_string(&msg).unwrap());
+        }
+
+        let (summary, error) = match outcome {
+            Ok(summary) => (summary, String::new()),
+            Err(err) => (RunSummary::default(), err),
+        };
+
+        let msg = CollectionFinishedMsg {
+            msg_type: MsgType::COLLECTION_FINISHED,
+            run_id: task_id,
+            summary,
+            cancelled: false,
+            error,
+        };
+
+        session_write(serde_json::to_string(&msg).unwrap());
+    });
+
+    in_flight.insert(
+        run_id,
+        InFlight {
+            handle: handle.abort_handle(),
+            is_run: true,
+        },
+    );
+}
+
+// the schema is fetched with the headers and auth of the request the editor belongs to
+pub fn spawn_schema_fetch(endpoint: String, mut request: SendHttpRequest) {
+    request.method = HttpMethod::POST;
+    request.body_type = HttpBodyType::GRAPHQL;
+    request.graphql = GraphqlBody {
+        query: INTROSPECTION_QUERY.to_string(),
+        variables: String::new(),
+        operation_name: "IntrospectionQuery".to_string(),
+    };
+    request.assertions.clear();
+    request.extractions.clear();
+
+    RUNTIME.spawn(async move {
+        let resp = bolt_http::http_send(request).await;
+
+        let result = if resp.failed {
+            Err(format!("The schema request failed: {}", resp.body))
+        } else if !(200..300).contains(&resp.status) {
+            Err(format!(
+                "The server answered the introspection query with {}",
+                resp.status
+            ))
+        } else {
+            parse_introspection(&endpoint, &resp.body)
+        };
+
+        let msg = match result {
+            Ok(mut schema) => {
+                schema.fetched = bolt_http::get_timestamp() as u64;
+
+                GraphqlSchemaMsg {
+                    msg_type: MsgType::GRAPHQL_SCHEMA,
+                    endpoint,
+                    schema: Some(schema),
+                    error: String::new(),
+                }
+            }
+
+            Err(error) => GraphqlSchemaMsg {
+                msg_type: MsgType::GRAPHQL_SCHEMA,
+                endpoint,
+                schema: None,
+                error,
+            },
+        };
+
+        session_write(serde_json::to_string(&msg).unwrap());
+    });
+}
+
+// the client is always answered, even for an id that is no longer in flight,
+// so a request left loading by an earlier session can be cleared
+pub fn cancel_request(request_id: String) {
+    let is_run = match IN_FLIGHT.lock().unwrap().remove(&request_id) {
+        Some(task) => {
+            task.handle.abort();
+
+            task.is_run
+        }
+
+        None => false,
+    };
+
+    // a cancelled run reports no summary, the client builds it from the progress it got
+    if is_run {
+        let msg = CollectionFinishedMsg {
+            msg_type: MsgType::COLLECTION_FINISHED,
+            run_id: request_id,
+            summary: RunSummary::default(),
+            cancelled: true,
+            error: String::new(),
+        };
+
+        session_write(serde_json::to_string(&msg).unwrap());
+
+        return;
+    }
+
+    let mut resp = SendHttpResponse::new();
+
+    resp.cancelled = true;
+    resp.body = "Request cancelled".to_string();
+    resp.request_id = request_id;
+
+    session_write(serde_json::to_string(&resp).unwrap());
+}
+
+fn session_write(txt: String) {
+    let mut session_websocket = SESSION_WEBSOCKET.lock().unwrap();
+
+    // the session may be gone by the time a slow request completes
+    if let Some(websocket) = session_websocket.as_mut() {
+        if let Err(err) = websocket.write_message(Message::Text(txt)) {
+            println!("HTTP: could not deliver response: {}", err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::time::Duration;
+
+    // the session websocket is shared, tests that read it take turns
+    static SESSION: Mutex<()> = Mutex::new(());
+
+    // the client end of the session websocket, which gets what the core writes
+    fn session() -> WebSocket<TcpStream> {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+
+            set_session_websocket(tungstenite::accept(stream).unwrap());
+        });
+
+        let stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(10)))
+            .unwrap();
+
+        let (client, _) = tungstenite::client(format!("ws://127.0.0.1:{port}"), stream).unwrap();
+        server.join().unwrap();
+
+        client
+    }
+
+    fn next(client: &mut WebSocket<TcpStream>) -> Value {
+        let msg = client.read_message().unwrap().into_text().unwrap();
+
+        serde_json::from_str(&msg).unwrap()
+    }
+
+    // answers each request with `delay` before the response, a long delay never answers
+    fn server(delay: Duration) -> u16 {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+
+                std::thread::spawn(move || {
+                    let mut buf = [0; 4096];
+                    let _ = stream.read(&mut buf);
+
+                    std::thread::sleep(delay);
+
+                    let _ = stream.write_all(
+                        b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\nok",
+                    );
+                });
+            }
+        });
+
+        port
+    }
+
+    fn request(port: u16) -> SendHttpRequest {
+        let mut request = HttpRequest::new();
+        request.url = format!("http://127.0.0.1:{port}/");
+
+        request.to_send_request(0)
+    }
+
+    #[test]
+    fn cancelled_requests_answer_once() {
+        let _session = SESSION.lock().unwrap_or_else(|err| err.into_inner());
+        let mut client = session();
+
+        let port = server(Duration::from_secs(60));
+        spawn_request("slow".to_string(), request(port));
+
+        std::thread::sleep(Duration::from_millis(200));
+        cancel_request("slow".to_string());
+
+        let resp = next(&mut client);
+        assert_eq!(resp["request_id"], "slow");
+        assert_eq!(resp["cancelled"], true);
+        assert_eq!(resp["body"], "Request cancelled");
+
+        assert!(!IN_FLIGHT.lock().unwrap().contains_key("slow"));
+
+        // the aborted send does not answer later
+        client
+            .get_mut()
+            .set_read_timeout(Some(Duration::from_millis(500)))
+            .unwrap();
+        assert!(client.read_message().is_err());
+    }
+
+    #[test]
+    fn finished_requests_answer_and_late_cancels_are_still_answered() {
+        let _session = SESSION.lock().unwrap_or_else(|err| err.into_inner());
+        let mut client = session();
+
+        let port = server(Duration::ZERO);
+        spawn_request("fast".to_string(), request(port));
+
+        let resp = next(&mut client);
+        assert_eq!(resp["request_id"], "fast");
+        assert_eq!(resp["cancelled"], false);
+        assert_eq!(resp["body"], "ok");
+
+        // a request left loading by an earlier session can still be cleared
+        cancel_request("fast".to_string());
+
+        let resp = next(&mut client);
+        assert_eq!(resp["request_id"], "fast");
+        assert_eq!(resp["cancelled"], true);
+    }
+
+    #[test]
+    fn cancelled_runs_finish_without_a_summary() {
+        let _session = SESSION.lock().unwrap_or_else(|err| err.into_inner());
+        let mut client = session();
+
+        let port = server(Duration::from_secs(60));
+
+        let mut slow = HttpRequest::new();
+        slow.url = format!("http://127.0.0.1:{port}/");
+
+        let mut collection = Collection::new();
+        collection.requests = vec![slow];
+
+        spawn_run("run".to_string(), collection, RunnerConfig::new());
+
+        std::thread::sleep(Duration::from_millis(200));
+        cancel_request("run".to_string());
+
+        let msg = next(&mut client);
+        assert_eq!(msg["msg_type"], "COLLECTION_FINISHED");
+        assert_eq!(msg["run_id"], "run");
+        assert_eq!(msg["cancelled"], true);
+        assert_eq!(msg["summary"]["requests"], 0);
+    }
+}
@@ -1,3 +1,4 @@
 pub mod server;
 pub mod asset;
+pub mod http;
 pub mod utils;
\ No newline at end of file
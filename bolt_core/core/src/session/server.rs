@@ -11,6 +11,7 @@ use tungstenite::{
 };
 
 use crate::session::utils::*;
+use base64::{engine::general_purpose, Engine};
 use bolt_common::prelude::*;
 
 fn process_message(websocket: &mut WebSocket<TcpStream>, session_id: &String, msg: Message) {
@@ -51,11 +52,51 @@ fn process_message(websocket: &mut WebSocket<TcpStream>, session_id: &String, ms
                     handle_send_http(websocket, session_id, txt);
                 }
 
+                MsgType::CANCEL_HTTP => {
+                    handle_cancel_http(websocket, session_id, txt);
+                }
+
+                MsgType::RUN_COLLECTION => {
+                    handle_run_collection(websocket, session_id, txt);
+                }
+
                 MsgType::RESTORE_STATE => {
                     handle_restore_state(websocket, session_id, txt);
                 }
 
+                MsgType::EDIT_COOKIES => {
+                    handle_edit_cookies(websocket, session_id, txt);
+                }
+
+                MsgType::SAVE_RESPONSE => {
+                    handle_save_response(websocket, session_id, txt);
+                }
+
+                MsgType::IMPORT_COLLECTION => {
+                    handle_import_collection(websocket, session_id, txt);
+                }
+
+                MsgType::EXPORT_COLLECTION => {
+                    handle_export_collection(websocket, session_id, txt);
+                }
+
+                MsgType::FETCH_GRAPHQL_SCHEMA => {
+                    handle_fetch_graphql_schema(websocket, session_id, txt);
+                }
+
+                MsgType::LOAD_GRPC_METHODS => {
+                    handle_load_grpc_methods(websocket, session_id, txt);
+                }
+
                 MsgType::HTTP_RESPONSE
+                | MsgType::COOKIES_CHANGED
+                | MsgType::VARIABLES_CHANGED
+                | MsgType::RESPONSE_SAVED
+                | MsgType::COLLECTION_PROGRESS
+                | MsgType::COLLECTION_FINISHED
+                | MsgType::COLLECTION_IMPORTED
+                | MsgType::COLLECTION_EXPORTED
+                | MsgType::GRAPHQL_SCHEMA
                 | MsgType::WS_CONNECTED
                 | MsgType::WS_DISCONNECTED
                 | MsgType::WS_MSG_SENT
@@ -70,7 +111,19 @@ fn process_message(websocket: &mut WebSocket<TcpStream>, session_id: &String, ms
                 | MsgType::UDP_DISCONNECTED
                 | MsgType::UDP_MSG_SENT
                 | MsgType::UDP_RECEIVED_MSG
-                | MsgType::UDP_CONNECTION_FAILED => {
+                | MsgType::UDP_CONNECTION_FAILED
+                | MsgType::SSE_CONNECTED
+                | MsgType::SSE_DISCONNECTED
+                | MsgType::SSE_RECEIVED_MSG
+                | MsgType::SSE_RECONNECTING
+                | MsgType::SSE_CONNECTION_FAILED
+                | MsgType::GRPC_METHODS
+                | MsgType::GRPC_CALL_STARTED
+                | MsgType::GRPC_MSG_SENT
+                | MsgType::GRPC_MSG_REJECTED
+                | MsgType::GRPC_RECEIVED_MSG
+                | MsgType::GRPC_CALL_FINISHED
+                | MsgType::GRPC_CALL_FAILED => {
                     return;
                 }
 
@@ -84,6 +137,12 @@ fn process_message(websocket: &mut WebSocket<TcpStream>, session_id: &String, ms
                 MsgType::ADD_TCP_CONNECTION => {
                     handle_add_tcp_connection(websocket, session_id, txt);
                 }
+                MsgType::ADD_SSE_CONNECTION => {
+                    handle_add_sse_connection(websocket, session_id, txt);
+                }
+                MsgType::ADD_GRPC_CONNECTION => {
+                    handle_add_grpc_connection(websocket, session_id, txt);
+                }
             },
 
             Err(_err) => {
@@ -104,6 +163,22 @@ fn handle_add_tcp_connection(
     // println!("adding tcp connection with id: {}", &msg.connection_id);
 }
 
+fn handle_add_sse_connection(
+    _websocket: &mut WebSocket<TcpStream>,
+    _session_id: &String,
+    txt: String,
+) {
+    let _msg: AddSseConnectionMsg = serde_json::from_str(&txt).unwrap();
+}
+
+fn handle_add_grpc_connection(
+    _websocket: &mut WebSocket<TcpStream>,
+    _session_id: &String,
+    txt: String,
+) {
+    let _msg: AddGrpcConnectionMsg = serde_json::from_str(&txt).unwrap();
+}
+
 fn handle_add_udp_connection(
     _websocket: &mut WebSocket<TcpStream>,
     _session_id: &String,
@@ -124,8 +199,7 @@ fn handle_add_ws_connection(
     // println!("adding ws connection with id: {}", &msg.connection_id);
 }
 
-#[tokio::main]
-async fn handle_send_http(websocket: &mut WebSocket<TcpStream>, _session_id: &String, txt: String) {
+fn handle_send_http(_websocket: &mut WebSocket<TcpStream>, _session_id: &String, txt: String) {
     // println!("{txt}");
 
     let msg: SendHttpMsg = serde_json::from_str(&txt).unwrap();
@@ -135,16 +209,55 @@ async fn handle_send_http(websocket: &mut WebSocket<TcpStream>, _session_id: &St
         method: msg.method,
         body: msg.body,
         headers: msg.headers,
+        body_type: msg.body_type,
+        form: msg.form,
+        multipart: msg.multipart,
+        body_file: msg.body_file,
+        graphql: msg.graphql,
+        auth: msg.auth,
+        settings: msg.settings,
+        assertions: msg.assertions,
+        extractions: msg.extractions,
         request_index: msg.index,
     };
 
-    let resp = bolt_http::http_send(request).await;
+    super::http::spawn_request(msg.request_id, request);
+}
 
-    let response = serde_json::to_string(&resp).unwrap();
+fn handle_cancel_http(_websocket: &mut WebSocket<TcpStream>, _session_id: &String, txt: String) {
+    let msg: CancelHttpMsg = serde_json::from_str(&txt).unwrap();
 
-    // println!("{}", response);
+    super::http::cancel_request(msg.request_id);
+}
 
-    ws_write(websocket, response);
+fn handle_fetch_graphql_schema(
+    _websocket: &mut WebSocket<TcpStream>,
+    _session_id: &String,
+    txt: String,
+) {
+    let msg: FetchGraphqlSchemaMsg = serde_json::from_str(&txt).unwrap();
+
+    super::http::spawn_schema_fetch(msg.endpoint, msg.request);
+}
+
+fn handle_load_grpc_methods(
+    _websocket: &mut WebSocket<TcpStream>,
+    _session_id: &String,
+    txt: String,
+) {
+    let msg: LoadGrpcMethodsMsg = serde_json::from_str(&txt).unwrap();
+
+    bolt_grpc::spawn_load_methods(msg.connection_id, msg.source);
+}
+
+fn handle_run_collection(
+    _websocket: &mut WebSocket<TcpStream>,
+    _session_id: &String,
+    txt: String,
+) {
+    let msg: RunCollectionMsg = serde_json::from_str(&txt).unwrap();
+
+    super::http::spawn_run(msg.run_id, msg.collection, msg.config);
 }
 
 fn handle_save_state(_websocket: &mut WebSocket<TcpStream>, _session_id: &String, txt: String) {
@@ -152,19 +265,38 @@ fn handle_save_state(_websocket: &mut WebSocket<TcpStream>, _session_id: &String
 
     // println!("{}: saving state", _session_id);
 
-    let client_state: MainState = serde_json::from_str(&msg.save).unwrap();
+    let mut client_state: MainState = serde_json::from_str(&msg.save).unwrap();
+
+    // the jar belongs to bolt_http, the client's copy can miss what a running request stored
+    bolt_http::cookies::sync_cookies(&mut client_state);
+    bolt_http::variables::sync_variables(&mut client_state);
 
     let save_state = serde_json::to_string(&client_state).unwrap();
     std::fs::write(get_home() + "state.json", save_state).unwrap();
 
+    bolt_http::set_main_state(client_state.clone());
     bolt_ws::set_main_state(client_state.clone());
     bolt_udp::set_main_state(client_state.clone());
     bolt_tcp::set_main_state(client_state.clone());
+    bolt_sse::set_main_state(client_state.clone());
+    bolt_grpc::set_main_state(client_state.clone());
 }
 
 fn handle_restore_state(websocket: &mut WebSocket<TcpStream>, _session_id: &String, _txt: String) {
     let save = std::fs::read_to_string(get_home() + "state.json").unwrap();
 
+    // a reloaded page gets the cookies core holds, they can be newer than the last save
+    let save = match serde_json::from_str::<MainState>(&save) {
+        Ok(mut state) => {
+            bolt_http::cookies::sync_cookies(&mut state);
+            bolt_http::variables::sync_variables(&mut state);
+
+            serde_json::to_string(&state).unwrap()
+        }
+
+        Err(_) => save,
+    };
+
     let msg = RestoreStateMsg {
         msg_type: MsgType::RESTORE_STATE,
         save,
@@ -175,6 +307,122 @@ fn handle_restore_state(websocket: &mut WebSocket<TcpStream>, _session_id: &Stri
     ws_write(websocket, response);
 }
 
+fn handle_edit_cookies(_websocket: &mut WebSocket<TcpStream>, _session_id: &String, txt: String) {
+    let msg: EditCookiesMsg = serde_json::from_str(&txt).unwrap();
+
+    bolt_http::cookies::edit_cookies(&msg.edit);
+}
+
+fn handle_save_response(websocket: &mut WebSocket<TcpStream>, _session_id: &String, txt: String) {
+    let msg: SaveResponseMsg = serde_json::from_str(&txt).unwrap();
+
+    let bytes = if msg.body_base64.is_empty() {
+        Ok(msg.body.into_bytes())
+    } else {
+        general_purpose::STANDARD.decode(&msg.body_base64)
+    };
+
+    let result = match bytes {
+        Ok(bytes) => {
+            let path = get_download_path(&msg.file_name);
+
+            std::fs::write(&path, bytes)
+                .map(|_| path)
+                .map_err(|err| err.to_string())
+        }
+
+        Err(err) => Err(err.to_string()),
+    };
+
+    let response = match result {
+        Ok(path) => ResponseSavedMsg {
+            msg_type: MsgType::RESPONSE_SAVED,
+            path,
+            error: String::new(),
+        },
+
+        Err(error) => ResponseSavedMsg {
+            msg_type: MsgType::RESPONSE_SAVED,
+            path: String::new(),
+            error,
+        },
+    };
+
+    ws_write(websocket, serde_json::to_string(&response).unwrap());
+}
+
+fn handle_import_collection(
+    websocket: &mut WebSocket<TcpStream>,
+    _session_id: &String,
+    txt: String,
+) {
+    let msg: ImportCollectionMsg = serde_json::from_str(&txt).unwrap();
+
+    let result = crate::formats::import_path(&msg.path, msg.format);
+
+    let response = match result {
+        Ok(imported) => CollectionImportedMsg {
+            msg_type: MsgType::COLLECTION_IMPORTED,
+            collections: imported.collections,
+            environments: imported.environments,
+            ws_connections: imported.ws_connections,
+            warnings: imported.warnings,
+            error: String::new(),
+            update: imported.update,
+        },
+
+        Err(error) => CollectionImportedMsg {
+            msg_type: MsgType::COLLECTION_IMPORTED,
+            collections: vec![],
+            environments: vec![],
+            ws_connections: vec![],
+            warnings: vec![],
+            error,
+            update: false,
+        },
+    };
+
+    ws_write(websocket, serde_json::to_string(&response).unwrap());
+}
+
+fn handle_export_collection(
+    websocket: &mut WebSocket<TcpStream>,
+    _session_id: &String,
+    txt: String,
+) {
+    let msg: ExportCollectionMsg = serde_json::from_str(&txt).unwrap();
+
+    let result =
+        crate::formats::export_collection(&msg.collection, msg.format).and_then(|exported| {
+            let path = get_download_path(&crate::formats::export_file_name(
+                &msg.collection,
+                msg.format,
+            ));
+
+            std::fs::write(&path, exported.content)
+                .map(|_| (path, exported.warnings))
+                .map_err(|err| err.to_string())
+        });
+
+    let response = match result {
+        Ok((path, warnings)) => CollectionExportedMsg {
+            msg_type: MsgType::COLLECTION_EXPORTED,
+            path,
+            warnings,
+            error: String::new(),
+        },
+
+        Err(error) => CollectionExportedMsg {
+            msg_type: MsgType::COLLECTION_EXPORTED,
+            path: String::new(),
+            warnings: vec![],
+            error,
+        },
+    };
+
+    ws_write(websocket, serde_json::to_string(&response).unwrap());
+}
+
 fn handle_open_link(_websocket: &mut WebSocket<TcpStream>, _session_id: &String, txt: String) {
     let msg: OpenLinkMsg = serde_json::from_str(&txt).unwrap();
 
@@ -251,7 +499,18 @@ fn process_connection(_req: &Request, mut response: Response, _session_id: &Stri
 pub fn launch_core_server(port: u16, address: String) {
     // println!("Starting WS server on ws://{}:{}", address, port);
 
-    let server = TcpListener::bind(address + ":" + &port.to_string()).unwrap();
+    let server = match TcpListener::bind(address.clone() + ":" + &port.to_string()) {
+        Ok(server) => server,
+        Err(err) => {
+            eprintln!("Could not start the core server on {address}:{port}: {err}");
+            eprintln!("Is another Bolt running? Pick other ports with --port");
+
+            std::process::exit(1);
+        }
+    };
+
+    // tells the CLI that this process holds state.json, see cli::state::running_app_port
+    let _ = std::fs::write(get_home() + "core.lock", format!("{address}:{port}"));
 
     for mut stream in server.incoming() {
         spawn(move || {
@@ -286,11 +545,32 @@ pub fn launch_core_server(port: u16, address: String) {
                 None,
             );
 
+            let new_session_ws_for_sse = WebSocket::from_raw_socket(
+                stream.as_mut().unwrap().try_clone().unwrap(),
+                tungstenite::protocol::Role::Server,
+                None,
+            );
+
+            let new_session_ws_for_grpc = WebSocket::from_raw_socket(
+                stream.as_mut().unwrap().try_clone().unwrap(),
+                tungstenite::protocol::Role::Server,
+                None,
+            );
+
+            let new_session_ws_for_http = WebSocket::from_raw_socket(
+                stream.as_mut().unwrap().try_clone().unwrap(),
+                tungstenite::protocol::Role::Server,
+                None,
+            );
+
             let mut session_websocket = accept_hdr(stream.unwrap(), callback).unwrap();
 
             bolt_ws::set_session_websocket(new_session_ws_for_ws);
             bolt_udp::set_session_websocket(new_session_ws_for_udp);
             bolt_tcp::set_session_websocket(new_session_ws_for_tcp);
+            bolt_sse::set_session_websocket(new_session_ws_for_sse);
+            bolt_grpc::set_session_websocket(new_session_ws_for_grpc);
+            super::http::set_session_websocket(new_session_ws_for_http);
 
             crate::start_services(session_id.clone());
 
@@ -1,4 +1,27 @@
-pub fn get_home() -> String {
-    let path = dirs::home_dir().unwrap().to_str().unwrap().to_string() + "/bolt/";
-    path
+pub use crate::utils::get_home;
+
+// a free path in the downloads folder, "name (1).ext" style when the file exists
+pub fn get_download_path(file_name: &str) -> String {
+    let dir = dirs::download_dir().unwrap_or_else(|| std::path::PathBuf::from(get_home()));
+
+    let file_name = std::path::Path::new(file_name)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "response".to_string());
+
+    let (stem, extension) = match file_name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem.to_string(), format!(".{extension}")),
+        _ => (file_name.clone(), String::new()),
+    };
+
+    let mut path = dir.join(&file_name);
+    let mut counter = 1;
+
+    while path.exists() {
+        path = dir.join(format!("{stem} ({counter}){extension}"));
+        counter += 1;
+    }
+
+    path.to_string_lossy().to_string()
 }
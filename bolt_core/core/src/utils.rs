@@ -1,5 +1,6 @@
 use std::path::Path;
 use std::process::Command;
+use std::sync::Mutex;
 use std::time::SystemTime;
 use std::time::UNIX_EPOCH;
 
@@ -185,7 +186,26 @@ pub fn get_dist() -> String {
     get_home() + "dist/"
 }
 
+lazy_static::lazy_static! {
+    static ref HOME_DIR: Mutex<Option<String>> = Mutex::new(None);
+}
+
+// replaces ~/bolt/, e.g. to run a second instance with its own state
+pub fn set_home(dir: &str) {
+    let mut dir = dir.to_string();
+
+    if !dir.ends_with('/') && !dir.ends_with('\\') {
+        dir.push('/');
+    }
+
+    *HOME_DIR.lock().unwrap() = Some(dir);
+}
+
 pub fn get_home() -> String {
+    if let Some(dir) = HOME_DIR.lock().unwrap().as_ref() {
+        return dir.clone();
+    }
+
     let path = dirs::home_dir().unwrap().to_str().unwrap().to_string() + "/bolt/";
     path
 }
@@ -216,7 +236,7 @@ pub fn dir_exists(path: &String) -> bool {
 }
 
 pub fn create_home(path: &String) {
-    std::fs::create_dir(path).unwrap();
+    std::fs::create_dir_all(path).unwrap();
 }
 
 pub fn _get_timestamp() -> u64 {
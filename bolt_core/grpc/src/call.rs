@@ -0,0 +1,584 @@
+use crate::channel::{self, Channel, FrameDecoder};
+use crate::descriptors;
+use crate::{cache_pool, cached_pool, load_pool, session_write, utils, ServicesSource};
+use bolt_common::prelude::*;
+use bytes::Bytes;
+use prost_reflect::MethodDescriptor;
+use std::thread::JoinHandle;
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+
+pub enum CallInput {
+    Message(WsMessage),
+    // the client is done sending, the server can still answer
+    End,
+    Cancel,
+}
+
+pub struct CallRequest {
+    pub connection_id: String,
+    pub url: String,
+    pub verify_tls: bool,
+    pub metadata: Vec<(String, String)>,
+    pub method: GrpcMethod,
+    // sent with the call when the client does not stream
+    pub message: String,
+    pub source: ServicesSource,
+}
+
+pub fn spawn_call(
+    connection_id: String,
+    request: CallRequest,
+) -> Result<(UnboundedSender<CallInput>, JoinHandle<()>), String> {
+    let (inputs, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+
+    let handle = std::thread::Builder::new()
+        .name(connection_id.clone())
+        .spawn(move || {
+            let runtime = match tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+            {
+                Ok(runtime) => runtime,
+                Err(err) => {
+                    send_failed(
+                        &connection_id,
+                        format!("Could not start the gRPC runtime: {err}"),
+                    );
+
+                    return;
+                }
+            };
+
+            let outcome = runtime.block_on(async {
+                // only a cancel can arrive before the call is open
+                let opened = tokio::select! {
+                    opened = open(&request) => opened?,
+                    _ = receiver.recv() => return Ok(cancelled()),
+                };
+
+                let started_msg = GrpcCallStartedMsg {
+                    msg_type: MsgType::GRPC_CALL_STARTED,
+                    connection_id: connection_id.clone(),
+                };
+
+                session_write(serde_json::to_string(&started_msg).unwrap());
+
+                let finished = stream(&connection_id, &request, opened, &mut receiver)
+                    .await
+                    .unwrap_or_else(|reason| Finished {
+                        status: GrpcStatus::new(13, &reason),
+                        headers: vec![],
+                        trailers: vec![],
+                    });
+
+                Ok::<_, String>(finished)
+            });
+
+            match outcome {
+                Ok(finished) => send_finished(&connection_id, finished),
+                Err(reason) => send_failed(&connection_id, reason),
+            }
+        })
+        .map_err(|err| format!("Could not start the call: {err}"))?;
+
+    Ok((inputs, handle))
+}
+
+struct Finished {
+    status: GrpcStatus,
+    headers: Vec<Vec<String>>,
+    trailers: Vec<Vec<String>>,
+}
+
+fn cancelled() -> Finished {
+    Finished {
+        status: GrpcStatus::new(1, "Cancelled by the client"),
+        headers: vec![],
+        trailers: vec![],
+    }
+}
+
+struct Opened {
+    // streams stop when the connection is dropped
+    _channel: Channel,
+    method: MethodDescriptor,
+    first: Option<Vec<u8>>,
+    response: h2::client::ResponseFuture,
+    send: h2::SendStream<Bytes>,
+}
+
+// setting the call up can fail, after that every way out carries a status
+async fn open(request: &CallRequest) -> Result<Opened, String> {
+    let method = find_method(request).await?;
+
+    // the editor's message is checked before anything goes out
+    let first = if request.method.kind.is_client_streaming() {
+        None
+    } else {
+        Some(descriptors::encode_message(
+            &method.input(),
+            &request.message,
+        )?)
+    };
+
+    let mut channel = channel::connect(&request.url, request.verify_tls).await?;
+
+    let (response, send) = channel
+        .start_call(&request.method.path(), &request.metadata)
+        .await?;
+
+    Ok(Opened {
+        _channel: channel,
+        method,
+        first,
+        response,
+        send,
+    })
+}
+
+async fn stream(
+    connection_id: &str,
+    request: &CallRequest,
+    opened: Opened,
+    inputs: &mut UnboundedReceiver<CallInput>,
+) -> Result<Finished, String> {
+    let Opened {
+        _channel,
+        method,
+        first,
+        response,
+        mut send,
+    } = opened;
+
+    let input = method.input();
+    let output = method.output();
+    let input_name = input.name().to_string();
+    let output_name = output.name().to_string();
+
+    let mut half_closed = false;
+
+    if let Some(first) = first {
+        send.send_data(channel::encode_frame(&first), true)
+            .map_err(|err| err.to_string())?;
+        half_closed = true;
+
+        send_sent(connection_id, &input_name, new_out_msg(&request.message));
+    }
+
+    let mut response = Some(response);
+    let mut body: Option<h2::RecvStream> = None;
+    let mut headers = vec![];
+    let mut decoder = FrameDecoder::default();
+
+    loop {
+        tokio::select! {
+            input_msg = inputs.recv() => match input_msg {
+                Some(CallInput::Message(msg)) if half_closed => {
+                    send_rejected(connection_id, &msg.msg_id, "The call no longer takes messages".to_string());
+                }
+
+                Some(CallInput::Message(msg)) => match descriptors::encode_message(&input, &msg.txt) {
+                    Ok(bytes) => {
+                        send.send_data(channel::encode_frame(&bytes), false)
+                            .map_err(|err| err.to_string())?;
+
+                        send_sent(connection_id, &input_name, msg);
+                    }
+
+                    Err(reason) => send_rejected(connection_id, &msg.msg_id, reason),
+                },
+
+                Some(CallInput::End) => {
+                    if !half_closed {
+                        send.send_data(Bytes::new(), true).map_err(|err| err.to_string())?;
+                        half_closed = true;
+                    }
+                }
+
+                Some(CallInput::Cancel) | None => {
+                    send.send_reset(h2::Reason::CANCEL);
+
+                    return Ok(Finished { headers, ..cancelled() });
+                }
+            },
+
+            result = async { response.as_mut().unwrap().await }, if response.is_some() => {
+                response = None;
+
+                let result = match result {
+                    Ok(result) => result,
+                    Err(err) => return Ok(Finished {
+                        status: channel::status_from_h2(&err),
+                        headers,
+                        trailers: vec![],
+                    }),
+                };
+
+                headers = channel::header_rows(result.headers());
+
+                // a trailers-only answer, the call ended before any message
+                if let Some(status) = channel::status_from(result.headers()) {
+                    return Ok(Finished { status, headers: vec![], trailers: headers });
+                }
+
+                if result.status() != http::StatusCode::OK {
+                    return Ok(Finished {
+                        status: channel::status_from_http(result.status()),
+                        headers,
+                        trailers: vec![],
+                    });
+                }
+
+                body = Some(result.into_body());
+            },
+
+            data = async { body.as_mut().unwrap().data().await }, if body.is_some() => match data {
+                Some(Ok(data)) => {
+                    let _ = body.as_mut().unwrap().flow_control().release_capacity(data.len());
+
+                    decoder.push(&data);
+
+                    while let Some(message) = decoder.next_message()? {
+                        let mut in_msg = WsMessage::new();
+                        in_msg.msg_type = WsMsgType::IN;
+                        in_msg.timestamp = utils::get_timestamp();
+                        in_msg.label = output_name.clone();
+
+                        in_msg.txt = match descriptors::decode_message(&output, &message) {
+                            Ok(json) => json,
+                            Err(reason) => reason,
+                        };
+
+                        let received_msg = GrpcReceivedMsg {
+                            msg_type: MsgType::GRPC_RECEIVED_MSG,
+                            connection_id: connection_id.to_string(),
+                            msg: in_msg,
+                        };
+
+                        session_write(serde_json::to_string(&received_msg).unwrap());
+                    }
+                }
+
+                Some(Err(err)) => return Ok(Finished {
+                    status: channel::status_from_h2(&err),
+                    headers,
+                    trailers: vec![],
+                }),
+
+                None => {
+                    let trailers = body.take().unwrap().trailers().await;
+
+                    let (status, trailers) = match trailers {
+                        Ok(Some(trailers)) => (
+                            channel::status_from(&trailers).unwrap_or_else(channel::missing_status),
+                            channel::header_rows(&trailers),
+                        ),
+                        Ok(None) => (channel::missing_status(), vec![]),
+                        Err(err) => (channel::status_from_h2(&err), vec![]),
+                    };
+
+                    return Ok(Finished { status, headers, trailers });
+                }
+            },
+        }
+    }
+}
+
+// the descriptors of the last load, or loaded again after a restart
+async fn find_method(request: &CallRequest) -> Result<MethodDescriptor, String> {
+    let pool = match cached_pool(&request.connection_id) {
+        Some(pool) => pool,
+
+        None => {
+            let pool = load_pool(&request.source).await?;
+            cache_pool(&request.connection_id, pool.clone());
+
+            pool
+        }
+    };
+
+    descriptors::find_method(&pool, &request.method).ok_or_else(|| {
+        format!(
+            "{} is not in the loaded services, load them again",
+            request.method.path()
+        )
+    })
+}
+
+fn new_out_msg(txt: &str) -> WsMessage {
+    let mut msg = WsMessage::new();
+    msg.msg_type = WsMsgType::OUT;
+    msg.txt = txt.to_string();
+
+    msg
+}
+
+fn send_sent(connection_id: &str, label: &str, mut msg: WsMessage) {
+    msg.msg_type = WsMsgType::OUT;
+    msg.timestamp = utils::get_timestamp();
+    msg.label = label.to_string();
+
+    let sent_msg = GrpcSentMsg {
+        msg_type: MsgType::GRPC_MSG_SENT,
+        connection_id: connection_id.to_string(),
+        msg,
+    };
+
+    session_write(serde_json::to_string(&sent_msg).unwrap());
+}
+
+fn send_rejected(connection_id: &str, msg_id: &str, reason: String) {
+    let rejected_msg = GrpcMsgRejectedMsg {
+        msg_type: MsgType::GRPC_MSG_REJECTED,
+        connection_id: connection_id.to_string(),
+        msg_id: msg_id.to_string(),
+        reason,
+    };
+
+    session_write(serde_json::to_string(&rejected_msg).unwrap());
+}
+
+fn send_finished(connection_id: &str, finished: Finished) {
+    let finished_msg = GrpcCallFinishedMsg {
+        msg_type: MsgType::GRPC_CALL_FINISHED,
+        connection_id: connection_id.to_string(),
+        status: finished.status,
+        headers: finished.headers,
+        trailers: finished.trailers,
+    };
+
+    session_write(serde_json::to_string(&finished_msg).unwrap());
+}
+
+// a cancel with no call running still has to reach the client
+pub fn send_cancelled(connection_id: &str) {
+    send_finished(connection_id, cancelled());
+}
+
+pub fn send_failed(connection_id: &str, reason: String) {
+    let failed_msg = GrpcCallFailedMsg {
+        msg_type: MsgType::GRPC_CALL_FAILED,
+        connection_id: connection_id.to_string(),
+        reason,
+    };
+
+    session_write(serde_json::to_string(&failed_msg).unwrap());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+    use std::net::{TcpListener, TcpStream};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+    use std::time::Duration;
+    use tungstenite::WebSocket;
+
+    // the calls share the session websocket
+    static SESSION: Mutex<()> = Mutex::new(());
+
+    // the client end of the session websocket, which gets what the core writes
+    fn session() -> WebSocket<TcpStream> {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+
+            crate::set_session_websocket(tungstenite::accept(stream).unwrap());
+        });
+
+        let stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(10)))
+            .unwrap();
+
+        let (client, _) = tungstenite::client(format!("ws://127.0.0.1:{port}"), stream).unwrap();
+        server.join().unwrap();
+
+        client
+    }
+
+    fn call_request(port: u16, method: &str, message: &str) -> CallRequest {
+        let source = ServicesSource {
+            url: format!("127.0.0.1:{port}"),
+            source: GrpcSource::PROTO_FILES,
+            proto_files: format!("{}/echo/echo.proto", grpc_server::PROTO_DIR),
+            import_paths: grpc_server::PROTO_DIR.to_string(),
+            verify_tls: true,
+            metadata: vec![],
+        };
+
+        let pool = crate::descriptors::from_proto_files(&source.proto_files, &source.import_paths)
+            .unwrap();
+
+        let method = crate::descriptors::list_methods(&pool)
+            .into_iter()
+            .find(|candidate| candidate.name == method)
+            .unwrap();
+
+        CallRequest {
+            connection_id: connection_id(),
+            url: source.url.clone(),
+            verify_tls: true,
+            metadata: vec![("x-token".to_string(), "abc".to_string())],
+            method,
+            message: message.to_string(),
+            source,
+        }
+    }
+
+    // every call loads its own descriptors
+    fn connection_id() -> String {
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+        format!("grpc-test-{}", CALLS.fetch_add(1, Ordering::Relaxed))
+    }
+
+    fn next(client: &mut WebSocket<TcpStream>) -> Value {
+        let msg = client.read_message().unwrap().into_text().unwrap();
+
+        serde_json::from_str(&msg).unwrap()
+    }
+
+    // the types of the messages up to the end of the call, and the last one
+    fn until_finished(client: &mut WebSocket<TcpStream>) -> (Vec<String>, Vec<Value>, Value) {
+        let mut types = vec![];
+        let mut received = vec![];
+
+        loop {
+            let msg = next(client);
+            let msg_type = msg["msg_type"].as_str().unwrap().to_string();
+
+            if msg_type == "GRPC_RECEIVED_MSG" {
+                received.push(serde_json::from_str(msg["msg"]["txt"].as_str().unwrap()).unwrap());
+            }
+
+            types.push(msg_type.clone());
+
+            if msg_type == "GRPC_CALL_FINISHED" || msg_type == "GRPC_CALL_FAILED" {
+                return (types, received, msg);
+            }
+        }
+    }
+
+    #[test]
+    fn unary_calls() {
+        let _session = SESSION.lock().unwrap_or_else(|err| err.into_inner());
+        let port = grpc_server::spawn();
+        let mut client = session();
+
+        let request = call_request(port, "Unary", r#"{ "message": "hi", "mood": "HAPPY" }"#);
+        let (_inputs, handle) = spawn_call(request.connection_id.clone(), request).unwrap();
+
+        let (types, received, finished) = until_finished(&mut client);
+        handle.join().unwrap();
+
+        assert_eq!(
+            types,
+            vec![
+                "GRPC_CALL_STARTED",
+                "GRPC_MSG_SENT",
+                "GRPC_RECEIVED_MSG",
+                "GRPC_CALL_FINISHED"
+            ]
+        );
+        assert_eq!(
+            received,
+            vec![json!({ "message": "hi", "index": 0, "mood": "HAPPY" })]
+        );
+        assert_eq!(finished["status"], json!({ "code": 0, "message": "" }));
+        assert!(finished["headers"]
+            .as_array()
+            .unwrap()
+            .contains(&json!(["x-token", "abc"])));
+
+        // an error status ends the call like any other
+        let request = call_request(port, "Unary", r#"{ "message": "fail" }"#);
+        let (_inputs, handle) = spawn_call(request.connection_id.clone(), request).unwrap();
+
+        let (_, received, finished) = until_finished(&mut client);
+        handle.join().unwrap();
+
+        assert!(received.is_empty());
+        assert_eq!(
+            finished["status"],
+            json!({ "code": 3, "message": "fail is not a message" })
+        );
+
+        // a message that does not fit fails before anything is sent
+        let request = call_request(port, "Unary", r#"{ "message": 1 }"#);
+        let (_inputs, handle) = spawn_call(request.connection_id.clone(), request).unwrap();
+
+        let (types, _, failed) = until_finished(&mut client);
+        handle.join().unwrap();
+
+        assert_eq!(types, vec!["GRPC_CALL_FAILED"]);
+        assert!(failed["reason"]
+            .as_str()
+            .unwrap()
+            .starts_with("The message is not a valid echo.EchoRequest"));
+    }
+
+    #[test]
+    fn server_streaming_calls() {
+        let _session = SESSION.lock().unwrap_or_else(|err| err.into_inner());
+        let port = grpc_server::spawn();
+        let mut client = session();
+
+        let request = call_request(port, "ServerStream", r#"{ "message": "tick", "count": 3 }"#);
+        let (_inputs, handle) = spawn_call(request.connection_id.clone(), request).unwrap();
+
+        let (_, received, finished) = until_finished(&mut client);
+        handle.join().unwrap();
+
+        let indexes: Vec<&Value> = received.iter().map(|reply| &reply["index"]).collect();
+        assert_eq!(indexes, vec![0, 1, 2]);
+        assert!(received.iter().all(|reply| reply["message"] == "tick"));
+        assert_eq!(finished["status"]["code"], 0);
+    }
+
+    #[test]
+    fn bidi_streaming_calls() {
+        let _session = SESSION.lock().unwrap_or_else(|err| err.into_inner());
+        let port = grpc_server::spawn();
+        let mut client = session();
+
+        let request = call_request(port, "Chat", "");
+        let (inputs, handle) = spawn_call(request.connection_id.clone(), request).unwrap();
+
+        // anything sent before the call is open cancels it
+        assert_eq!(next(&mut client)["msg_type"], "GRPC_CALL_STARTED");
+
+        for txt in [
+            r#"{ "message": "one" }"#,
+            r#"{ "message": 2 }"#,
+            r#"{ "message": "three" }"#,
+        ] {
+            let mut msg = WsMessage::new();
+            msg.txt = txt.to_string();
+
+            inputs.send(CallInput::Message(msg)).unwrap();
+        }
+
+        inputs.send(CallInput::End).unwrap();
+
+        let (types, received, finished) = until_finished(&mut client);
+        handle.join().unwrap();
+
+        assert_eq!(types.iter().filter(|t| *t == "GRPC_MSG_SENT").count(), 2);
+        assert_eq!(
+            types.iter().filter(|t| *t == "GRPC_MSG_REJECTED").count(),
+            1
+        );
+        assert_eq!(
+            received,
+            vec![
+                json!({ "message": "ONE", "index": 0, "mood": "NEUTRAL" }),
+                json!({ "message": "THREE", "index": 1, "mood": "NEUTRAL" }),
+            ]
+        );
+        assert_eq!(finished["status"]["code"], 0);
+    }
+}
@@ -0,0 +1,310 @@
+use bolt_common::prelude::*;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use h2::client::{ResponseFuture, SendRequest};
+use h2::SendStream;
+use http::{HeaderMap, StatusCode};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpStream;
+use url::Url;
+
+pub struct Channel {
+    send_request: SendRequest<Bytes>,
+    scheme: &'static str,
+    authority: String,
+}
+
+// http:// and grpc:// are plaintext HTTP/2 (h2c), https:// and grpcs:// use TLS
+pub async fn connect(url: &str, verify_tls: bool) -> Result<Channel, String> {
+    let url = if url.contains("://") {
+        url.to_string()
+    } else {
+        format!("http://{url}")
+    };
+
+    let url = Url::parse(&url).map_err(|err| format!("Invalid url: {err}"))?;
+
+    let tls = match url.scheme() {
+        "http" | "grpc" => false,
+        "https" | "grpcs" => true,
+        scheme => {
+            return Err(format!(
+                "Unsupported scheme {scheme:?}, use http:// for h2c or https:// for TLS"
+            ))
+        }
+    };
+
+    let Some(host) = url.host_str() else {
+        return Err("The url has no host".to_string());
+    };
+
+    let port = url.port().unwrap_or(if tls { 443 } else { 80 });
+    let authority = format!("{host}:{port}");
+
+    let tcp = TcpStream::connect(&authority)
+        .await
+        .map_err(|err| format!("Could not connect to {authority}: {err}"))?;
+
+    let _ = tcp.set_nodelay(true);
+
+    let send_request = if tls {
+        let connector = native_tls::TlsConnector::builder()
+            .request_alpns(&["h2"])
+            .danger_accept_invalid_certs(!verify_tls)
+            .build()
+            .map_err(|err| err.to_string())?;
+
+        let connector = tokio_native_tls::TlsConnector::from(connector);
+
+        let stream = connector
+            .connect(host.trim_start_matches('[').trim_end_matches(']'), tcp)
+            .await
+            .map_err(|err| format!("TLS handshake with {authority} failed: {err}"))?;
+
+        handshake(stream).await?
+    } else {
+        handshake(tcp).await?
+    };
+
+    Ok(Channel {
+        send_request,
+        scheme: if tls { "https" } else { "http" },
+        authority,
+    })
+}
+
+async fn handshake<T>(io: T) -> Result<SendRequest<Bytes>, String>
+where
+    T: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let (send_request, connection) = h2::client::handshake(io)
+        .await
+        .map_err(|err| format!("HTTP/2 handshake failed: {err}"))?;
+
+    tokio::spawn(async move {
+        let _ = connection.await;
+    });
+
+    Ok(send_request)
+}
+
+impl Channel {
+    pub async fn start_call(
+        &mut self,
+        path: &str,
+        metadata: &[(String, String)],
+    ) -> Result<(ResponseFuture, SendStream<Bytes>), String> {
+        let mut builder = http::Request::builder()
+            .method("POST")
+            .uri(format!("{}://{}{}", self.scheme, self.authority, path))
+            .header("content-type", "application/grpc")
+            .header("te", "trailers")
+            .header("user-agent", format!("bolt/{VERSION}"));
+
+        for (key, value) in metadata {
+            builder = builder.header(key.to_lowercase(), value);
+        }
+
+        let request = builder
+            .body(())
+            .map_err(|err| format!("Invalid metadata: {err}"))?;
+
+        let mut send_request = self
+            .send_request
+            .clone()
+            .ready()
+            .await
+            .map_err(|err| format!("The connection was closed: {err}"))?;
+
+        send_request
+            .send_request(request, false)
+            .map_err(|err| format!("Could not start the call: {err}"))
+    }
+}
+
+// a unary call on raw bytes, for the reflection service
+pub async fn call_once(
+    channel: &mut Channel,
+    path: &str,
+    metadata: &[(String, String)],
+    message: &[u8],
+) -> Result<Vec<Bytes>, GrpcStatus> {
+    let unavailable = |err: String| GrpcStatus::new(14, &err);
+
+    let (response, mut send) = channel
+        .start_call(path, metadata)
+        .await
+        .map_err(unavailable)?;
+
+    send.send_data(encode_frame(message), true)
+        .map_err(|err| unavailable(err.to_string()))?;
+
+    let response = response.await.map_err(|err| status_from_h2(&err))?;
+
+    if let Some(status) = status_from(response.headers()) {
+        return Err(status);
+    }
+
+    if response.status() != StatusCode::OK {
+        return Err(status_from_http(response.status()));
+    }
+
+    let mut body = response.into_body();
+    let mut decoder = FrameDecoder::default();
+    let mut messages = vec![];
+
+    while let Some(data) = body.data().await {
+        let data = data.map_err(|err| status_from_h2(&err))?;
+        let _ = body.flow_control().release_capacity(data.len());
+
+        decoder.push(&data);
+
+        while let Some(message) = decoder
+            .next_message()
+            .map_err(|err| GrpcStatus::new(13, &err))?
+        {
+            messages.push(message);
+        }
+    }
+
+    let trailers = body.trailers().await.map_err(|err| status_from_h2(&err))?;
+
+    match trailers.as_ref().and_then(status_from) {
+        Some(status) if status.code == 0 => Ok(messages),
+        Some(status) => Err(status),
+        None => Err(missing_status()),
+    }
+}
+
+// every message goes out uncompressed behind a flag byte and its length
+pub fn encode_frame(message: &[u8]) -> Bytes {
+    let mut frame = BytesMut::with_capacity(message.len() + 5);
+    frame.put_u8(0);
+    frame.put_u32(message.len() as u32);
+    frame.put_slice(message);
+
+    frame.freeze()
+}
+
+// DATA frames do not line up with messages, a message can be split or share a frame
+#[derive(Default)]
+pub struct FrameDecoder {
+    buffer: BytesMut,
+}
+
+impl FrameDecoder {
+    pub fn push(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+    }
+
+    pub fn next_message(&mut self) -> Result<Option<Bytes>, String> {
+        if self.buffer.len() < 5 {
+            return Ok(None);
+        }
+
+        let compressed = self.buffer[0] != 0;
+        let length = u32::from_be_bytes([
+            self.buffer[1],
+            self.buffer[2],
+            self.buffer[3],
+            self.buffer[4],
+        ]) as usize;
+
+        if self.buffer.len() < length + 5 {
+            return Ok(None);
+        }
+
+        self.buffer.advance(5);
+        let message = self.buffer.split_to(length).freeze();
+
+        // no grpc-accept-encoding is sent, so a compressed message is a server bug
+        if compressed {
+            return Err("The server sent a compressed message without being asked to".to_string());
+        }
+
+        Ok(Some(message))
+    }
+}
+
+pub fn status_from(headers: &HeaderMap) -> Option<GrpcStatus> {
+    let code = headers.get("grpc-status")?;
+
+    let code = code
+        .to_str()
+        .ok()
+        .and_then(|code| code.trim().parse().ok())
+        .unwrap_or(2);
+
+    let message = headers
+        .get("grpc-message")
+        .map(|message| percent_decode(message.as_bytes()))
+        .unwrap_or_default();
+
+    Some(GrpcStatus { code, message })
+}
+
+// what the gRPC spec maps HTTP statuses to when a proxy answers instead of the server
+pub fn status_from_http(status: StatusCode) -> GrpcStatus {
+    let code = match status.as_u16() {
+        400 => 13,
+        401 => 16,
+        403 => 7,
+        404 => 12,
+        429 | 502 | 503 | 504 => 14,
+        _ => 2,
+    };
+
+    GrpcStatus::new(code, &format!("The server answered HTTP {status}"))
+}
+
+pub fn status_from_h2(err: &h2::Error) -> GrpcStatus {
+    let code = match err.reason() {
+        Some(h2::Reason::CANCEL) => 1,
+        Some(h2::Reason::REFUSED_STREAM) => 14,
+        Some(_) => 13,
+        None => 14,
+    };
+
+    GrpcStatus::new(code, &err.to_string())
+}
+
+pub fn missing_status() -> GrpcStatus {
+    GrpcStatus::new(13, "The server ended the call without a grpc-status")
+}
+
+pub fn header_rows(headers: &HeaderMap) -> Vec<Vec<String>> {
+    headers
+        .iter()
+        .map(|(key, value)| {
+            vec![
+                key.to_string(),
+                String::from_utf8_lossy(value.as_bytes()).to_string(),
+            ]
+        })
+        .collect()
+}
+
+fn percent_decode(bytes: &[u8]) -> String {
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let hex = bytes
+            .get(index + 1..index + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+        match (bytes[index], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                index += 3;
+            }
+
+            (byte, _) => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).to_string()
+}
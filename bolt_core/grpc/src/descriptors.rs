@@ -0,0 +1,289 @@
+use bolt_common::prelude::*;
+use prost::Message;
+use prost_reflect::prost_types::FileDescriptorProto;
+use prost_reflect::{DescriptorPool, DynamicMessage, Kind, MessageDescriptor, SerializeOptions};
+use serde_json::{json, Map, Value};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+// recursive messages stop being expanded after this many levels
+const TEMPLATE_DEPTH: usize = 4;
+
+pub fn from_proto_files(proto_files: &str, import_paths: &str) -> Result<DescriptorPool, String> {
+    let files: Vec<PathBuf> = lines(proto_files).map(PathBuf::from).collect();
+
+    if files.is_empty() {
+        return Err("Add the path of at least one .proto file".to_string());
+    }
+
+    let mut includes: Vec<PathBuf> = lines(import_paths).map(PathBuf::from).collect();
+
+    // a file outside every import path is read from its own folder
+    for file in &files {
+        if !includes.iter().any(|include| file.starts_with(include)) {
+            let folder = match file.parent() {
+                Some(folder) if folder != Path::new("") => folder.to_path_buf(),
+                _ => PathBuf::from("."),
+            };
+
+            includes.push(folder);
+        }
+    }
+
+    let parsed = protobuf_parse::Parser::new()
+        .pure()
+        .includes(&includes)
+        .inputs(&files)
+        .parse_and_typecheck()
+        .map_err(|err| format!("{err:#}"))?;
+
+    let mut protos = vec![];
+
+    for file in parsed.file_descriptors {
+        let bytes = protobuf::Message::write_to_bytes(&file).map_err(|err| err.to_string())?;
+        let proto = FileDescriptorProto::decode(bytes.as_slice()).map_err(|err| err.to_string())?;
+
+        protos.push(proto);
+    }
+
+    let mut pool = DescriptorPool::global();
+
+    pool.add_file_descriptor_protos(protos)
+        .map_err(|err| err.to_string())?;
+
+    Ok(pool)
+}
+
+fn lines(input: &str) -> impl Iterator<Item = &str> {
+    input.lines().map(str::trim).filter(|line| !line.is_empty())
+}
+
+pub fn list_methods(pool: &DescriptorPool) -> Vec<GrpcMethod> {
+    let mut methods = vec![];
+
+    for service in pool.services() {
+        if service.full_name().starts_with("grpc.reflection.") {
+            continue;
+        }
+
+        for method in service.methods() {
+            let template =
+                serde_json::to_string_pretty(&message_template(&method.input(), 0)).unwrap();
+
+            methods.push(GrpcMethod {
+                service: service.full_name().to_string(),
+                name: method.name().to_string(),
+                kind: GrpcMethodKind::new(
+                    method.is_client_streaming(),
+                    method.is_server_streaming(),
+                ),
+                input_type: method.input().full_name().to_string(),
+                output_type: method.output().full_name().to_string(),
+                template,
+            });
+        }
+    }
+
+    methods
+}
+
+pub fn find_method(
+    pool: &DescriptorPool,
+    method: &GrpcMethod,
+) -> Option<prost_reflect::MethodDescriptor> {
+    pool.get_service_by_name(&method.service)?
+        .methods()
+        .find(|candidate| candidate.name() == method.name)
+}
+
+// the well-known types have their own JSON form, the rest is every field at its default
+fn message_template(message: &MessageDescriptor, depth: usize) -> Value {
+    match message.full_name() {
+        "google.protobuf.Timestamp" => return json!("1970-01-01T00:00:00Z"),
+        "google.protobuf.Duration" => return json!("0s"),
+        "google.protobuf.FieldMask" => return json!(""),
+        "google.protobuf.Value" => return Value::Null,
+        "google.protobuf.ListValue" => return json!([]),
+        "google.protobuf.Struct" | "google.protobuf.Empty" => return json!({}),
+        "google.protobuf.Any" => return json!({ "@type": "" }),
+        "google.protobuf.StringValue" | "google.protobuf.BytesValue" => return json!(""),
+        "google.protobuf.BoolValue" => return json!(false),
+        "google.protobuf.Int64Value" | "google.protobuf.UInt64Value" => return json!("0"),
+        "google.protobuf.DoubleValue"
+        | "google.protobuf.FloatValue"
+        | "google.protobuf.Int32Value"
+        | "google.protobuf.UInt32Value" => return json!(0),
+        _ => {}
+    }
+
+    let mut fields = Map::new();
+
+    if depth >= TEMPLATE_DEPTH {
+        return Value::Object(fields);
+    }
+
+    // only one field of a oneof can be set
+    let mut oneofs = HashSet::new();
+
+    for field in message.fields() {
+        if let Some(oneof) = field.containing_oneof() {
+            if !oneofs.insert(oneof.full_name().to_string()) {
+                continue;
+            }
+        }
+
+        let value = if field.is_map() {
+            json!({})
+        } else if field.is_list() {
+            json!([kind_template(&field.kind(), depth)])
+        } else {
+            kind_template(&field.kind(), depth)
+        };
+
+        fields.insert(field.json_name().to_string(), value);
+    }
+
+    Value::Object(fields)
+}
+
+fn kind_template(kind: &Kind, depth: usize) -> Value {
+    match kind {
+        Kind::Double | Kind::Float => json!(0.0),
+        Kind::Int32 | Kind::Sint32 | Kind::Sfixed32 | Kind::Uint32 | Kind::Fixed32 => json!(0),
+        // 64 bit integers are strings in JSON so they survive JavaScript
+        Kind::Int64 | Kind::Sint64 | Kind::Sfixed64 | Kind::Uint64 | Kind::Fixed64 => json!("0"),
+        Kind::Bool => json!(false),
+        Kind::String | Kind::Bytes => json!(""),
+        Kind::Message(message) => message_template(message, depth + 1),
+        Kind::Enum(enumeration) => json!(enumeration.default_value().name()),
+    }
+}
+
+pub fn encode_message(message: &MessageDescriptor, json: &str) -> Result<Vec<u8>, String> {
+    let json = if json.trim().is_empty() { "{}" } else { json };
+
+    let invalid = |err: serde_json::Error| {
+        format!("The message is not a valid {}: {err}", message.full_name())
+    };
+
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+
+    let dynamic =
+        DynamicMessage::deserialize(message.clone(), &mut deserializer).map_err(invalid)?;
+    deserializer.end().map_err(invalid)?;
+
+    Ok(dynamic.encode_to_vec())
+}
+
+// default values are shown too, an empty response would otherwise look like {}
+pub fn decode_message(message: &MessageDescriptor, bytes: &[u8]) -> Result<String, String> {
+    let dynamic = DynamicMessage::decode(message.clone(), bytes)
+        .map_err(|err| format!("The message is not a valid {}: {err}", message.full_name()))?;
+
+    let mut json = vec![];
+    let mut serializer = serde_json::Serializer::pretty(&mut json);
+
+    dynamic
+        .serialize_with_options(
+            &mut serializer,
+            &SerializeOptions::new().skip_default_fields(false),
+        )
+        .map_err(|err| err.to_string())?;
+
+    Ok(String::from_utf8(json).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use grpc_server::PROTO_DIR;
+
+    fn echo_proto() -> String {
+        format!("{PROTO_DIR}/echo/echo.proto")
+    }
+
+    #[test]
+    fn proto_files_with_an_import_path() {
+        let pool = from_proto_files(&format!("\n  {}  \n", echo_proto()), PROTO_DIR).unwrap();
+
+        let methods: Vec<(String, GrpcMethodKind)> = list_methods(&pool)
+            .into_iter()
+            .map(|method| (method.path(), method.kind))
+            .collect();
+
+        assert_eq!(
+            methods,
+            vec![
+                ("/echo.Echo/Unary".to_string(), GrpcMethodKind::UNARY),
+                (
+                    "/echo.Echo/ServerStream".to_string(),
+                    GrpcMethodKind::SERVER_STREAMING
+                ),
+                (
+                    "/echo.Echo/Chat".to_string(),
+                    GrpcMethodKind::BIDI_STREAMING
+                ),
+            ]
+        );
+
+        let unary = &list_methods(&pool)[0];
+        assert_eq!(unary.input_type, "echo.EchoRequest");
+        assert_eq!(unary.output_type, "echo.EchoReply");
+        assert_eq!(
+            serde_json::from_str::<Value>(&unary.template).unwrap(),
+            json!({ "message": "", "count": 0, "mood": "NEUTRAL" })
+        );
+    }
+
+    #[test]
+    fn proto_files_that_do_not_load() {
+        assert_eq!(
+            from_proto_files("  \n", "").unwrap_err(),
+            "Add the path of at least one .proto file"
+        );
+
+        // common/mood.proto is only found through the proto folder
+        let err = from_proto_files(&echo_proto(), "").unwrap_err();
+        assert!(err.contains("common/mood.proto"), "{err}");
+
+        assert!(from_proto_files(&format!("{PROTO_DIR}/missing.proto"), PROTO_DIR).is_err());
+    }
+
+    #[test]
+    fn messages_to_and_from_json() {
+        let pool = from_proto_files(&echo_proto(), PROTO_DIR).unwrap();
+        let method = pool
+            .get_service_by_name("echo.Echo")
+            .unwrap()
+            .methods()
+            .next()
+            .unwrap();
+
+        let bytes =
+            encode_message(&method.input(), r#"{ "message": "hi", "mood": "HAPPY" }"#).unwrap();
+        let reply = decode_message(&method.output(), &bytes).unwrap();
+
+        // the reply has the same fields, defaults included
+        assert_eq!(
+            serde_json::from_str::<Value>(&reply).unwrap(),
+            json!({ "message": "hi", "index": 0, "mood": "HAPPY" })
+        );
+
+        assert_eq!(
+            encode_message(&method.input(), " ").unwrap(),
+            Vec::<u8>::new()
+        );
+
+        let err = encode_message(&method.input(), r#"{ "nope": 1 }"#).unwrap_err();
+        assert!(
+            err.starts_with("The message is not a valid echo.EchoRequest"),
+            "{err}"
+        );
+
+        let err = decode_message(&method.output(), &[0xff]).unwrap_err();
+        assert!(
+            err.starts_with("The message is not a valid echo.EchoReply"),
+            "{err}"
+        );
+    }
+}
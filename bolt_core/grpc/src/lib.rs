@@ -0,0 +1,315 @@
+mod call;
+mod channel;
+mod descriptors;
+mod reflection;
+mod utils;
+
+use bolt_common::prelude::*;
+use call::{CallInput, CallRequest};
+use prost_reflect::DescriptorPool;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+use tokio::sync::mpsc::UnboundedSender;
+use tungstenite::WebSocket;
+
+const GRPC_SERVICE_REFRESH_RATE: u64 = 500;
+const SERVICE_SYNC_REFRESH_RATE: u64 = 1000;
+
+lazy_static::lazy_static! {
+ static ref CORE_STATE: Arc<Mutex<CoreState>> = Arc::new(Mutex::new(CoreState::new()));
+}
+
+#[derive(Clone)]
+struct GrpcService {
+    connection_id: String,
+}
+
+struct CoreState {
+    main_state: MainState,
+    session_websocket: Option<WebSocket<std::net::TcpStream>>,
+    grpc_services: Vec<GrpcService>,
+    // the descriptors last loaded for each connection
+    pools: HashMap<String, DescriptorPool>,
+}
+
+impl CoreState {
+    fn new() -> Self {
+        Self {
+            main_state: MainState::new(),
+            session_websocket: None,
+            grpc_services: vec![],
+            pools: HashMap::new(),
+        }
+    }
+}
+
+// what loading the services needs, with the variables already resolved
+#[derive(Clone)]
+struct ServicesSource {
+    url: String,
+    source: GrpcSource,
+    proto_files: String,
+    import_paths: String,
+    verify_tls: bool,
+    metadata: Vec<(String, String)>,
+}
+
+pub fn set_session_websocket(new_ws: WebSocket<std::net::TcpStream>) {
+    let mut core_state = CORE_STATE.lock().unwrap();
+    core_state.session_websocket = Some(new_ws);
+}
+
+pub fn set_main_state(client_state: MainState) {
+    let mut core_state = CORE_STATE.lock().unwrap();
+    core_state.main_state = client_state;
+}
+
+fn resolve_variables(input: &str) -> String {
+    let core_state = CORE_STATE.lock().unwrap();
+
+    core_state.main_state.resolve_variables(input)
+}
+
+fn resolve_metadata(headers: &[Vec<String>]) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .filter(|header| !header[0].trim().is_empty())
+        .map(|header| {
+            (
+                resolve_variables(header[0].trim()),
+                resolve_variables(&header[1]),
+            )
+        })
+        .collect()
+}
+
+fn resolve_source(source: &GrpcServicesSource) -> ServicesSource {
+    ServicesSource {
+        url: resolve_variables(source.url.trim()),
+        source: source.source,
+        proto_files: resolve_variables(&source.proto_files),
+        import_paths: resolve_variables(&source.import_paths),
+        verify_tls: source.verify_tls,
+        metadata: resolve_metadata(&source.headers),
+    }
+}
+
+fn session_write(txt: String) {
+    let mut core_state = CORE_STATE.lock().unwrap();
+
+    // the session may be gone by the time a stream sends its next message
+    if let Some(websocket) = core_state.session_websocket.as_mut() {
+        if let Err(err) = websocket.write_message(tungstenite::Message::Text(txt)) {
+            println!("gRPC: could not deliver message: {}", err);
+        }
+    }
+}
+
+async fn load_pool(source: &ServicesSource) -> Result<DescriptorPool, String> {
+    match source.source {
+        GrpcSource::PROTO_FILES => {
+            descriptors::from_proto_files(&source.proto_files, &source.import_paths)
+        }
+
+        GrpcSource::REFLECTION => {
+            reflection::fetch_pool(&source.url, source.verify_tls, &source.metadata).await
+        }
+    }
+}
+
+fn cached_pool(connection_id: &str) -> Option<DescriptorPool> {
+    let core_state = CORE_STATE.lock().unwrap();
+
+    core_state.pools.get(connection_id).cloned()
+}
+
+fn cache_pool(connection_id: &str, pool: DescriptorPool) {
+    let mut core_state = CORE_STATE.lock().unwrap();
+
+    core_state.pools.insert(connection_id.to_string(), pool);
+}
+
+pub fn spawn_load_methods(connection_id: String, source: GrpcServicesSource) {
+    let source = resolve_source(&source);
+
+    std::thread::spawn(move || {
+        let loaded = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|err| format!("Could not start the gRPC runtime: {err}"))
+            .and_then(|runtime| runtime.block_on(load_pool(&source)));
+
+        let (methods, error) = match loaded {
+            Ok(pool) => {
+                let methods = descriptors::list_methods(&pool);
+                cache_pool(&connection_id, pool);
+
+                if methods.is_empty() {
+                    (methods, "No services were found".to_string())
+                } else {
+                    (methods, String::new())
+                }
+            }
+
+            Err(error) => (vec![], error),
+        };
+
+        let methods_msg = GrpcMethodsMsg {
+            msg_type: MsgType::GRPC_METHODS,
+            connection_id,
+            methods,
+            error,
+        };
+
+        session_write(serde_json::to_string(&methods_msg).unwrap());
+    });
+}
+
+pub fn start_core_grpc_service(_session_id: String) {
+    std::thread::spawn(|| loop {
+        let mut core_state = CORE_STATE.lock().unwrap();
+
+        let connections = core_state.main_state.grpc_connections.clone();
+        let grpc_services = core_state.grpc_services.clone();
+
+        for grpc_con in connections.clone() {
+            let exists = grpc_services
+                .iter()
+                .any(|x| x.connection_id == grpc_con.connection_id);
+
+            if !exists {
+                // tried again on the next sync
+                if let Err(err) = spawn_grpc_service(grpc_con.connection_id.clone()) {
+                    println!("gRPC: {}", err);
+
+                    continue;
+                }
+
+                core_state.grpc_services.push(GrpcService {
+                    connection_id: grpc_con.connection_id,
+                });
+            }
+        }
+
+        drop(core_state);
+        std::thread::sleep(Duration::from_millis(SERVICE_SYNC_REFRESH_RATE));
+    });
+}
+
+pub fn spawn_grpc_service(connection_id: String) -> Result<(), String> {
+    std::thread::Builder::new()
+        .name(connection_id.clone())
+        .spawn(move || {
+            let mut call: Option<(UnboundedSender<CallInput>, JoinHandle<()>)> = None;
+
+            // the client keeps a message queued until it hears it was sent
+            let mut sent: Vec<String> = vec![];
+            let mut closed = false;
+            let mut cancelled = false;
+
+            loop {
+                let mut core_state = CORE_STATE.lock().unwrap();
+                let grpc_connections = core_state.main_state.grpc_connections.clone();
+
+                let Some(grpc_con) = grpc_connections
+                    .iter()
+                    .find(|con| con.connection_id == connection_id)
+                else {
+                    core_state
+                        .grpc_services
+                        .retain(|service| service.connection_id != connection_id);
+                    core_state.pools.remove(&connection_id);
+
+                    return;
+                };
+
+                drop(core_state);
+
+                let running = call
+                    .as_ref()
+                    .is_some_and(|(_, handle)| !handle.is_finished());
+
+                if grpc_con.disconnecting {
+                    if !cancelled {
+                        cancelled = true;
+
+                        match call.take() {
+                            Some((inputs, handle)) if !handle.is_finished() => {
+                                let _ = inputs.send(CallInput::Cancel);
+                            }
+
+                            _ => call::send_cancelled(&connection_id),
+                        }
+                    }
+                } else if grpc_con.connecting && !grpc_con.connected && !running {
+                    cancelled = false;
+                    sent.clear();
+                    closed = false;
+
+                    match grpc_con.current_method() {
+                        Some(method) => {
+                            let request = CallRequest {
+                                connection_id: connection_id.clone(),
+                                url: resolve_variables(grpc_con.url.trim()),
+                                verify_tls: grpc_con.verify_tls,
+                                metadata: resolve_metadata(&grpc_con.out_headers),
+                                method: method.clone(),
+                                message: resolve_variables(&grpc_con.out_buffer),
+                                source: resolve_source(&grpc_con.services_source()),
+                            };
+
+                            match call::spawn_call(connection_id.clone(), request) {
+                                Ok(started) => call = Some(started),
+                                Err(reason) => call::send_failed(&connection_id, reason),
+                            }
+                        }
+
+                        None => call::send_failed(
+                            &connection_id,
+                            "Load the services and pick a method first".to_string(),
+                        ),
+                    }
+
+                    let mut core_state = CORE_STATE.lock().unwrap();
+
+                    for grpc_con in core_state
+                        .main_state
+                        .grpc_connections
+                        .iter_mut()
+                        .filter(|con| con.connection_id == connection_id)
+                    {
+                        grpc_con.connecting = false;
+                    }
+                } else if grpc_con.connected && running {
+                    cancelled = false;
+
+                    let (inputs, _) = call.as_ref().unwrap();
+
+                    for out_msg in &grpc_con.out_queue {
+                        if sent.contains(&out_msg.msg_id) {
+                            continue;
+                        }
+
+                        let mut out_msg = out_msg.clone();
+                        out_msg.txt = resolve_variables(&out_msg.txt);
+
+                        sent.push(out_msg.msg_id.clone());
+                        let _ = inputs.send(CallInput::Message(out_msg));
+                    }
+
+                    if grpc_con.closing && !closed {
+                        closed = true;
+                        let _ = inputs.send(CallInput::End);
+                    }
+                } else {
+                    cancelled = false;
+                }
+
+                std::thread::sleep(Duration::from_millis(GRPC_SERVICE_REFRESH_RATE));
+            }
+        })
+        .map(|_| ())
+        .map_err(|err| format!("Could not start a connection service: {err}"))
+}
@@ -0,0 +1,278 @@
+use crate::channel::{self, Channel};
+use bolt_common::prelude::*;
+use prost::Message;
+use prost_reflect::prost_types::FileDescriptorProto;
+use prost_reflect::DescriptorPool;
+use std::collections::{HashMap, HashSet};
+
+// servers that predate v1 only serve v1alpha, the messages are the same
+const REFLECTION_V1: &str = "/grpc.reflection.v1.ServerReflection/ServerReflectionInfo";
+const REFLECTION_V1ALPHA: &str = "/grpc.reflection.v1alpha.ServerReflection/ServerReflectionInfo";
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ServerReflectionRequest {
+    #[prost(string, tag = "1")]
+    host: String,
+    #[prost(oneof = "MessageRequest", tags = "3, 4, 7")]
+    message_request: Option<MessageRequest>,
+}
+
+#[derive(Clone, PartialEq, prost::Oneof)]
+enum MessageRequest {
+    #[prost(string, tag = "3")]
+    FileByFilename(String),
+    #[prost(string, tag = "4")]
+    FileContainingSymbol(String),
+    #[prost(string, tag = "7")]
+    ListServices(String),
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ServerReflectionResponse {
+    #[prost(string, tag = "1")]
+    valid_host: String,
+    #[prost(oneof = "MessageResponse", tags = "4, 6, 7")]
+    message_response: Option<MessageResponse>,
+}
+
+#[derive(Clone, PartialEq, prost::Oneof)]
+enum MessageResponse {
+    #[prost(message, tag = "4")]
+    FileDescriptors(FileDescriptorResponse),
+    #[prost(message, tag = "6")]
+    Services(ListServiceResponse),
+    #[prost(message, tag = "7")]
+    Error(ErrorResponse),
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct FileDescriptorResponse {
+    #[prost(bytes = "vec", repeated, tag = "1")]
+    file_descriptor_proto: Vec<Vec<u8>>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ListServiceResponse {
+    #[prost(message, repeated, tag = "1")]
+    service: Vec<ServiceResponse>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ServiceResponse {
+    #[prost(string, tag = "1")]
+    name: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ErrorResponse {
+    #[prost(int32, tag = "1")]
+    error_code: i32,
+    #[prost(string, tag = "2")]
+    error_message: String,
+}
+
+struct Reflection<'a> {
+    channel: Channel,
+    path: &'static str,
+    metadata: &'a [(String, String)],
+}
+
+pub async fn fetch_pool(
+    url: &str,
+    verify_tls: bool,
+    metadata: &[(String, String)],
+) -> Result<DescriptorPool, String> {
+    let channel = channel::connect(url, verify_tls).await?;
+
+    let mut reflection = Reflection {
+        channel,
+        path: REFLECTION_V1,
+        metadata,
+    };
+
+    let services = match reflection
+        .ask(MessageRequest::ListServices(String::new()))
+        .await
+    {
+        Err(status) if status.code == 12 => {
+            reflection.path = REFLECTION_V1ALPHA;
+            reflection
+                .ask(MessageRequest::ListServices(String::new()))
+                .await
+        }
+
+        response => response,
+    };
+
+    let services = match services.map_err(reflection_failed)? {
+        MessageResponse::Services(list) => list.service,
+        _ => return Err("The server did not answer with its services".to_string()),
+    };
+
+    let mut files: HashMap<String, FileDescriptorProto> = HashMap::new();
+
+    for service in services {
+        if service.name.starts_with("grpc.reflection.") {
+            continue;
+        }
+
+        let response = reflection
+            .ask(MessageRequest::FileContainingSymbol(service.name))
+            .await
+            .map_err(reflection_failed)?;
+
+        add_files(&mut files, response)?;
+    }
+
+    // servers usually send the imports along, the rest is asked for by name
+    let mut asked = HashSet::new();
+
+    loop {
+        let missing: Vec<String> = files
+            .values()
+            .flat_map(|file| file.dependency.iter())
+            .filter(|dependency| !files.contains_key(*dependency))
+            .cloned()
+            .collect();
+
+        let Some(name) = missing.into_iter().next() else {
+            break;
+        };
+
+        if !asked.insert(name.clone()) {
+            return Err(format!("The server did not send {name}"));
+        }
+
+        match reflection
+            .ask(MessageRequest::FileByFilename(name.clone()))
+            .await
+        {
+            Ok(response) => add_files(&mut files, response)?,
+
+            // the well-known types are built in, the server does not have to know them
+            Err(_) if name.starts_with("google/protobuf/") => {
+                let Some(file) = DescriptorPool::global().get_file_by_name(&name) else {
+                    return Err(format!("The server does not know {name}"));
+                };
+
+                files.insert(name, file.file_descriptor_proto().clone());
+            }
+
+            Err(status) => return Err(reflection_failed(status)),
+        }
+    }
+
+    let mut pool = DescriptorPool::global();
+
+    pool.add_file_descriptor_protos(files.into_values())
+        .map_err(|err| format!("The server sent invalid descriptors: {err}"))?;
+
+    Ok(pool)
+}
+
+impl Reflection<'_> {
+    async fn ask(&mut self, request: MessageRequest) -> Result<MessageResponse, GrpcStatus> {
+        let request = ServerReflectionRequest {
+            host: String::new(),
+            message_request: Some(request),
+        };
+
+        let messages = channel::call_once(
+            &mut self.channel,
+            self.path,
+            self.metadata,
+            &request.encode_to_vec(),
+        )
+        .await?;
+
+        let Some(message) = messages.first() else {
+            return Err(GrpcStatus::new(13, "The reflection service sent no answer"));
+        };
+
+        let response = ServerReflectionResponse::decode(message.clone())
+            .map_err(|err| GrpcStatus::new(13, &err.to_string()))?;
+
+        match response.message_response {
+            Some(MessageResponse::Error(error)) => Err(GrpcStatus::new(
+                error.error_code as u32,
+                &error.error_message,
+            )),
+
+            Some(response) => Ok(response),
+
+            None => Err(GrpcStatus::new(
+                13,
+                "The reflection service sent an empty answer",
+            )),
+        }
+    }
+}
+
+fn add_files(
+    files: &mut HashMap<String, FileDescriptorProto>,
+    response: MessageResponse,
+) -> Result<(), String> {
+    let MessageResponse::FileDescriptors(response) = response else {
+        return Err("The server did not answer with file descriptors".to_string());
+    };
+
+    for bytes in response.file_descriptor_proto {
+        let file = FileDescriptorProto::decode(bytes.as_slice())
+            .map_err(|err| format!("The server sent an invalid file descriptor: {err}"))?;
+
+        files.insert(file.name().to_string(), file);
+    }
+
+    Ok(())
+}
+
+fn reflection_failed(status: GrpcStatus) -> String {
+    format!(
+        "Server reflection failed with {} ({}): {}",
+        status.code_name(),
+        status.code,
+        status.message
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::descriptors;
+
+    #[tokio::test]
+    async fn lists_the_services_of_the_server() {
+        let port = grpc_server::spawn();
+
+        let pool = fetch_pool(&format!("127.0.0.1:{port}"), true, &[])
+            .await
+            .unwrap();
+
+        // the reflection service itself is left out
+        let services: Vec<String> = pool.services().map(|s| s.full_name().to_string()).collect();
+        assert_eq!(services, vec!["echo.Echo"]);
+
+        let methods: Vec<String> = descriptors::list_methods(&pool)
+            .iter()
+            .map(|method| method.name.clone())
+            .collect();
+        assert_eq!(methods, vec!["Unary", "ServerStream", "Chat"]);
+
+        // the import came along
+        assert!(pool.get_enum_by_name("common.Mood").is_some());
+    }
+
+    #[tokio::test]
+    async fn fails_without_a_server() {
+        let port = std::net::TcpListener::bind(("127.0.0.1", 0))
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+
+        let err = fetch_pool(&format!("http://127.0.0.1:{port}"), true, &[])
+            .await
+            .unwrap_err();
+        assert!(err.starts_with("Could not connect to 127.0.0.1"), "{err}");
+    }
+}
@@ -0,0 +1,298 @@
+use bolt_common::prelude::*;
+use serde_json::Value;
+
+use crate::json_path;
+
+pub fn run_assertions(assertions: &[Assertion], resp: &SendHttpResponse) -> Vec<AssertionResult> {
+    let json: Option<Value> = serde_json::from_str(&resp.body).ok();
+
+    assertions
+        .iter()
+        .filter(|assertion| assertion.enabled)
+        .map(|assertion| {
+            let outcome = if resp.failed {
+                Err(format!("no response: {}", resp.body))
+            } else {
+                check(assertion, resp, json.as_ref())
+            };
+
+            match outcome {
+                Ok(message) => AssertionResult {
+                    name: assertion.describe(),
+                    passed: true,
+                    message,
+                },
+
+                Err(message) => AssertionResult {
+                    name: assertion.describe(),
+                    passed: false,
+                    message,
+                },
+            }
+        })
+        .collect()
+}
+
+// Ok holds what was found, Err why the assertion failed
+fn check(
+    assertion: &Assertion,
+    resp: &SendHttpResponse,
+    json: Option<&Value>,
+) -> Result<String, String> {
+    let expected = assertion.expected.trim();
+
+    match assertion.kind {
+        AssertionKind::STATUS_EQUALS => {
+            let status = parse_number(expected)? as u16;
+
+            compare(resp.status == status, format!("status is {}", resp.status))
+        }
+
+        AssertionKind::STATUS_IN_RANGE => {
+            let (low, high) = expected
+                .split_once('-')
+                .ok_or_else(|| format!("expected a range like 200-299, got \"{expected}\""))?;
+
+            let low = parse_number(low)? as u16;
+            let high = parse_number(high)? as u16;
+
+            compare(
+                resp.status >= low && resp.status <= high,
+                format!("status is {}", resp.status),
+            )
+        }
+
+        AssertionKind::HEADER_PRESENT => match header_value(resp, &assertion.target) {
+            Some(value) => Ok(format!("{} is \"{value}\"", assertion.target)),
+            None => Err(format!("{} is missing", assertion.target)),
+        },
+
+        AssertionKind::HEADER_MATCHES => {
+            let regex = build_regex(expected)?;
+
+            match header_value(resp, &assertion.target) {
+                Some(value) => compare(
+                    regex.is_match(value),
+                    format!("{} is \"{value}\"", assertion.target),
+                ),
+
+                None => Err(format!("{} is missing", assertion.target)),
+            }
+        }
+
+        AssertionKind::JSON_PATH_EQUALS => {
+            let value = select(json, &assertion.target)?;
+
+            // anything that is not valid JSON is compared as a string
+            let expected_value = serde_json::from_str(expected)
+                .unwrap_or_else(|_| Value::String(assertion.expected.clone()));
+
+            compare(
+                json_equals(value, &expected_value),
+                format!("{} is {value}", assertion.target),
+            )
+        }
+
+        AssertionKind::JSON_PATH_EXISTS => {
+            let value = select(json, &assertion.target)?;
+
+            Ok(format!("{} is {value}", assertion.target))
+        }
+
+        AssertionKind::JSON_PATH_TYPE => {
+            let value = select(json, &assertion.target)?;
+            let found = json_path::type_name(value);
+
+            compare(
+                found.eq_ignore_ascii_case(expected),
+                format!("{} is {found}", assertion.target),
+            )
+        }
+
+        AssertionKind::BODY_CONTAINS => compare(
+            resp.body.contains(&assertion.expected),
+            if resp.body.contains(&assertion.expected) {
+                "found in the body".to_string()
+            } else {
+                "not found in the body".to_string()
+            },
+        ),
+
+        AssertionKind::BODY_MATCHES => {
+            let regex = build_regex(expected)?;
+
+            match regex.find(&resp.body) {
+                Some(found) => Ok(format!("matched \"{}\"", found.as_str())),
+                None => Err("no match in the body".to_string()),
+            }
+        }
+
+        AssertionKind::RESPONSE_TIME_UNDER => {
+            let limit = parse_number(expected)?;
+
+            compare(
+                (resp.time as u64) < limit,
+                format!("took {} ms", resp.time),
+            )
+        }
+    }
+}
+
+fn compare(passed: bool, found: String) -> Result<String, String> {
+    if passed {
+        Ok(found)
+    } else {
+        Err(found)
+    }
+}
+
+fn parse_number(value: &str) -> Result<u64, String> {
+    value
+        .trim()
+        .parse::<u64>()
+        .map_err(|_| format!("expected a number, got \"{}\"", value.trim()))
+}
+
+fn build_regex(pattern: &str) -> Result<regex::Regex, String> {
+    regex::Regex::new(pattern).map_err(|err| format!("invalid regex: {err}"))
+}
+
+fn header_value<'a>(resp: &'a SendHttpResponse, name: &str) -> Option<&'a str> {
+    resp.headers
+        .iter()
+        .find(|header| header[0].eq_ignore_ascii_case(name.trim()))
+        .map(|header| header[1].as_str())
+}
+
+fn select<'a>(json: Option<&'a Value>, path: &str) -> Result<&'a Value, String> {
+    let json = json.ok_or_else(|| "the body is not JSON".to_string())?;
+
+    json_path::select(json, path)?.ok_or_else(|| format!("{path} does not exist"))
+}
+
+// 1 and 1.0 are the same number
+fn json_equals(value: &Value, expected: &Value) -> bool {
+    match (value, expected) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64() == b.as_f64(),
+        _ => value == expected,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use AssertionKind::*;
+
+    fn response(body: &str) -> SendHttpResponse {
+        let mut resp = SendHttpResponse::new();
+
+        resp.status = 201;
+        resp.time = 120;
+        resp.body = body.to_string();
+        resp.headers = vec![vec![
+            "content-type".to_string(),
+            "application/json; charset=utf-8".to_string(),
+        ]];
+
+        resp
+    }
+
+    fn assertion(kind: AssertionKind, target: &str, expected: &str) -> Assertion {
+        Assertion {
+            kind,
+            target: target.to_string(),
+            expected: expected.to_string(),
+            enabled: true,
+        }
+    }
+
+    // (kind, target, expected, passes)
+    fn check_all(resp: &SendHttpResponse, cases: &[(AssertionKind, &str, &str, bool)]) {
+        for (kind, target, expected, passes) in cases {
+            let results = run_assertions(&[assertion(*kind, target, expected)], resp);
+
+            assert_eq!(results[0].passed, *passes, "{}", results[0].name);
+        }
+    }
+
+    #[test]
+    fn status_and_header_assertions() {
+        check_all(
+            &response(""),
+            &[
+                (STATUS_EQUALS, "", "201", true),
+                (STATUS_EQUALS, "", "200", false),
+                (STATUS_IN_RANGE, "", "200-299", true),
+                (STATUS_IN_RANGE, "", "300-399", false),
+                (HEADER_PRESENT, "Content-Type", "", true),
+                (HEADER_PRESENT, "etag", "", false),
+                (HEADER_MATCHES, "content-type", "^application/json", true),
+                (HEADER_MATCHES, "content-type", "xml", false),
+            ],
+        );
+    }
+
+    #[test]
+    fn json_path_assertions() {
+        check_all(
+            &response(r#"{"user": {"id": 1, "name": "ada", "tags": []}}"#),
+            &[
+                (JSON_PATH_EQUALS, "$.user.id", "1.0", true),
+                (JSON_PATH_EQUALS, "$.user.name", "ada", true),
+                (JSON_PATH_EQUALS, "$.user.name", "\"ada\"", true),
+                (JSON_PATH_EQUALS, "$.user.id", "2", false),
+                (JSON_PATH_EXISTS, "$.user.tags", "", true),
+                (JSON_PATH_EXISTS, "$.user.email", "", false),
+                (JSON_PATH_TYPE, "$.user.tags", "Array", true),
+                (JSON_PATH_TYPE, "$.user.id", "string", false),
+            ],
+        );
+    }
+
+    #[test]
+    fn body_and_time_assertions() {
+        check_all(
+            &response("order 42 created"),
+            &[
+                (BODY_CONTAINS, "", "42", true),
+                (BODY_CONTAINS, "", "43", false),
+                (BODY_MATCHES, "", r"order \d+", true),
+                (RESPONSE_TIME_UNDER, "", "500", true),
+                (RESPONSE_TIME_UNDER, "", "120", false),
+            ],
+        );
+    }
+
+    #[test]
+    fn bad_input_fails_with_a_reason() {
+        let results = run_assertions(
+            &[
+                assertion(STATUS_EQUALS, "", "abc"),
+                assertion(BODY_MATCHES, "", "("),
+                assertion(JSON_PATH_EXISTS, "$.id", ""),
+            ],
+            &response("not json"),
+        );
+
+        assert!(results.iter().all(|result| !result.passed));
+        assert_eq!(results[0].message, "expected a number, got \"abc\"");
+        assert!(results[1].message.starts_with("invalid regex"));
+        assert_eq!(results[2].message, "the body is not JSON");
+    }
+
+    #[test]
+    fn disabled_assertions_are_skipped_and_failed_requests_fail_all() {
+        let mut disabled = assertion(STATUS_EQUALS, "", "201");
+        disabled.enabled = false;
+
+        assert!(run_assertions(&[disabled], &response("")).is_empty());
+
+        let mut resp = response("connection refused");
+        resp.failed = true;
+
+        let results = run_assertions(&[assertion(BODY_CONTAINS, "", "refused")], &resp);
+
+        assert!(!results[0].passed);
+        assert_eq!(results[0].message, "no response: connection refused");
+    }
+}
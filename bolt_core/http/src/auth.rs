@@ -0,0 +1,447 @@
+use bolt_common::prelude::*;
+use md5::Md5;
+use sha2::{Digest, Sha256};
+
+pub fn apply_auth(builder: reqwest::RequestBuilder, auth: &HttpAuth) -> reqwest::RequestBuilder {
+    match auth.auth_type {
+        HttpAuthType::NONE | HttpAuthType::DIGEST => builder,
+
+        HttpAuthType::BASIC => builder.basic_auth(&auth.username, Some(&auth.password)),
+
+        // for oauth2 the token is filled in by http_send once it has been acquired
+        HttpAuthType::BEARER | HttpAuthType::OAUTH2 => builder.bearer_auth(&auth.token),
+
+        HttpAuthType::API_KEY => {
+            if auth.key.is_empty() {
+                return builder;
+            }
+
+            match auth.location {
+                ApiKeyLocation::HEADER => builder.header(&auth.key, &auth.value),
+                ApiKeyLocation::QUERY => builder.query(&[(&auth.key, &auth.value)]),
+            }
+        }
+    }
+}
+
+// returns the WWW-Authenticate header holding a digest challenge of a 401 response, if any
+pub fn digest_challenge(resp: &reqwest::Response) -> Option<String> {
+    if resp.status() != reqwest::StatusCode::UNAUTHORIZED {
+        return None;
+    }
+
+    resp.headers()
+        .get_all(reqwest::header::WWW_AUTHENTICATE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .find(|value| digest_params(value).is_some())
+        .map(|value| value.to_string())
+}
+
+// a header can carry several challenges, "Basic realm=a, Digest realm=b, nonce=c", a new one
+// starts with a scheme that is not followed by "="
+fn parse_challenges(header: &str) -> Vec<(String, Vec<(String, String)>)> {
+    let mut challenges: Vec<(String, Vec<(String, String)>)> = Vec::new();
+    let mut rest = header;
+
+    loop {
+        rest = rest.trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+
+        if rest.is_empty() {
+            break;
+        }
+
+        let end = rest
+            .find(|c: char| c == '=' || c == ',' || c.is_whitespace())
+            .unwrap_or(rest.len());
+
+        let token = &rest[..end];
+        rest = rest[end..].trim_start();
+
+        if !rest.starts_with('=') {
+            challenges.push((token.to_string(), Vec::new()));
+            continue;
+        }
+
+        rest = rest[1..].trim_start();
+
+        let value;
+
+        if let Some(quoted) = rest.strip_prefix('"') {
+            let mut unquoted = String::new();
+            let mut chars = quoted.char_indices();
+            let mut end = quoted.len();
+
+            while let Some((i, c)) = chars.next() {
+                match c {
+                    '\\' => {
+                        if let Some((_, escaped)) = chars.next() {
+                            unquoted.push(escaped);
+                        }
+                    }
+
+                    '"' => {
+                        end = i + 1;
+                        break;
+                    }
+
+                    _ => unquoted.push(c),
+                }
+            }
+
+            value = unquoted;
+            rest = &quoted[end..];
+        } else {
+            let end = rest.find(',').unwrap_or(rest.len());
+            value = rest[..end].trim().to_string();
+            rest = &rest[end..];
+        }
+
+        // a parameter before any scheme has nothing to belong to
+        if let Some((_, params)) = challenges.last_mut() {
+            params.push((token.to_lowercase(), value));
+        }
+    }
+
+    challenges
+}
+
+fn digest_params(header: &str) -> Option<Vec<(String, String)>> {
+    parse_challenges(header)
+        .into_iter()
+        .find(|(scheme, _)| scheme.eq_ignore_ascii_case("digest"))
+        .map(|(_, params)| params)
+}
+
+fn hash(algorithm: &str, data: &[u8]) -> String {
+    if algorithm.starts_with("SHA-256") {
+        let digest = Sha256::digest(data);
+
+        digest.iter().map(|b| format!("{:02x}", b)).collect()
+    } else {
+        let digest = Md5::digest(data);
+
+        digest.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+}
+
+// builds the Authorization header answering a digest challenge (RFC 7616), the body is
+// None when it is a stream that cannot be hashed for auth-int
+pub fn digest_authorization(
+    challenge: &str,
+    auth: &HttpAuth,
+    method: &str,
+    url: &reqwest::Url,
+    body: Option<&[u8]>,
+) -> Result<String, String> {
+    let cnonce = uuid::Uuid::new_v4().simple().to_string()[..16].to_string();
+
+    authorization(challenge, auth, method, url, body, &cnonce)
+}
+
+fn authorization(
+    challenge: &str,
+    auth: &HttpAuth,
+    method: &str,
+    url: &reqwest::Url,
+    body: Option<&[u8]>,
+    cnonce: &str,
+) -> Result<String, String> {
+    let params =
+        digest_params(challenge).ok_or_else(|| "No digest challenge to answer".to_string())?;
+
+    let param = |name: &str| -> String {
+        params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.clone())
+            .unwrap_or_default()
+    };
+
+    let realm = param("realm");
+    let nonce = param("nonce");
+    let opaque = param("opaque");
+    let algorithm = {
+        let algorithm = param("algorithm");
+
+        if algorithm.is_empty() {
+            "MD5".to_string()
+        } else {
+            algorithm.to_uppercase()
+        }
+    };
+
+    let offered = param("qop");
+    let offered: Vec<&str> = offered.split(',').map(|q| q.trim()).collect();
+
+    // auth is preferred as it does not depend on the body, no qop at all is RFC 2069
+    let qop = if offered.contains(&"auth") {
+        "auth"
+    } else if offered.contains(&"auth-int") {
+        "auth-int"
+    } else if offered == [""] {
+        ""
+    } else {
+        return Err(format!("Unsupported digest qop: {}", param("qop")));
+    };
+
+    let uri = match url.query() {
+        Some(query) => format!("{}?{}", url.path(), query),
+        None => url.path().to_string(),
+    };
+
+    let nc = "00000001";
+
+    let mut ha1 = hash(
+        &algorithm,
+        format!("{}:{}:{}", auth.username, realm, auth.password).as_bytes(),
+    );
+
+    if algorithm.ends_with("-SESS") {
+        ha1 = hash(
+            &algorithm,
+            format!("{}:{}:{}", ha1, nonce, cnonce).as_bytes(),
+        );
+    }
+
+    let ha2 = if qop == "auth-int" {
+        let body =
+            body.ok_or_else(|| "Digest auth-int cannot hash a multipart body".to_string())?;
+
+        hash(
+            &algorithm,
+            format!("{}:{}:{}", method, uri, hash(&algorithm, body)).as_bytes(),
+        )
+    } else {
+        hash(&algorithm, format!("{}:{}", method, uri).as_bytes())
+    };
+
+    let response = if qop.is_empty() {
+        hash(&algorithm, format!("{}:{}:{}", ha1, nonce, ha2).as_bytes())
+    } else {
+        hash(
+            &algorithm,
+            format!("{}:{}:{}:{}:{}:{}", ha1, nonce, nc, cnonce, qop, ha2).as_bytes(),
+        )
+    };
+
+    let mut header = format!(
+        "Digest username=\"{}\", realm=\"{}\", nonce=\"{}\", uri=\"{}\", algorithm={}, response=\"{}\"",
+        auth.username, realm, nonce, uri, algorithm, response
+    );
+
+    if !qop.is_empty() {
+        header.push_str(&format!(", qop={}, nc={}, cnonce=\"{}\"", qop, nc, cnonce));
+    }
+
+    if !opaque.is_empty() {
+        header.push_str(&format!(", opaque=\"{}\"", opaque));
+    }
+
+    Ok(header)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mufasa(password: &str) -> HttpAuth {
+        HttpAuth {
+            auth_type: HttpAuthType::DIGEST,
+            username: "Mufasa".to_string(),
+            password: password.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn answer(
+        challenge: &str,
+        auth: &HttpAuth,
+        method: &str,
+        body: Option<&[u8]>,
+        cnonce: &str,
+    ) -> Result<String, String> {
+        let url = reqwest::Url::parse("http://www.example.org/dir/index.html").unwrap();
+
+        authorization(challenge, auth, method, &url, body, cnonce)
+    }
+
+    fn header_param(header: &str, name: &str) -> String {
+        digest_params(header)
+            .unwrap()
+            .into_iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value)
+            .unwrap_or_default()
+    }
+
+    // RFC 2617, section 3.5
+    const RFC_2617: &str = "Digest realm=\"testrealm@host.com\", qop=\"auth,auth-int\", nonce=\"dcd98b7102dd2f0e8b11d0f600bfb0c093\", opaque=\"5ccc069c403ebaf9f0171e9517f40e41\"";
+
+    // RFC 7616, section 3.9.1
+    const RFC_7616: &str = "Digest realm=\"http-auth@example.org\", qop=\"auth, auth-int\", algorithm={}, nonce=\"7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v\", opaque=\"FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS\"";
+
+    #[test]
+    fn rfc_2617_md5_with_qop() {
+        let header = answer(RFC_2617, &mufasa("Circle Of Life"), "GET", None, "0a4f113b").unwrap();
+
+        assert_eq!(
+            header_param(&header, "response"),
+            "6629fae49393a05397450978507c4ef1"
+        );
+        assert_eq!(header_param(&header, "qop"), "auth");
+        assert_eq!(header_param(&header, "nc"), "00000001");
+        assert_eq!(header_param(&header, "cnonce"), "0a4f113b");
+        assert_eq!(header_param(&header, "uri"), "/dir/index.html");
+        assert_eq!(
+            header_param(&header, "opaque"),
+            "5ccc069c403ebaf9f0171e9517f40e41"
+        );
+    }
+
+    #[test]
+    fn rfc_7616_md5_and_sha_256() {
+        let cnonce = "f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ";
+        let auth = mufasa("Circle of Life");
+
+        let md5 = answer(&RFC_7616.replace("{}", "MD5"), &auth, "GET", None, cnonce).unwrap();
+        assert_eq!(
+            header_param(&md5, "response"),
+            "8ca523f5e9506fed4657c9700eebdbec"
+        );
+        assert_eq!(header_param(&md5, "algorithm"), "MD5");
+
+        let sha = answer(
+            &RFC_7616.replace("{}", "SHA-256"),
+            &auth,
+            "GET",
+            None,
+            cnonce,
+        )
+        .unwrap();
+        assert_eq!(
+            header_param(&sha, "response"),
+            "753927fa0e85d155564e2e272a28d1802ca10daf4496794697cf8db5856cb6c1"
+        );
+        assert_eq!(header_param(&sha, "algorithm"), "SHA-256");
+    }
+
+    #[test]
+    fn challenges_without_qop_use_rfc_2069() {
+        let challenge =
+            "Digest realm=\"testrealm@host.com\", nonce=\"dcd98b7102dd2f0e8b11d0f600bfb0c093\"";
+
+        let header = answer(
+            challenge,
+            &mufasa("Circle Of Life"),
+            "GET",
+            None,
+            "0a4f113b",
+        )
+        .unwrap();
+
+        // MD5(HA1:nonce:HA2), without nc or cnonce
+        assert_eq!(
+            header_param(&header, "response"),
+            "670fd8c2df070c60b045671b8b24ff02"
+        );
+        assert!(!header.contains("qop="));
+        assert!(!header.contains("cnonce="));
+    }
+
+    #[test]
+    fn auth_int_hashes_the_body() {
+        let challenge = RFC_2617.replace("auth,auth-int", "auth-int");
+        let auth = mufasa("Circle Of Life");
+
+        let header = answer(&challenge, &auth, "POST", Some(b"name=value"), "0a4f113b").unwrap();
+        assert_eq!(header_param(&header, "qop"), "auth-int");
+        assert_eq!(
+            header_param(&header, "response"),
+            "ea36e36c4b23cd72e3fecfae37f536f9"
+        );
+
+        // a multipart stream cannot be hashed
+        assert!(answer(&challenge, &auth, "POST", None, "0a4f113b").is_err());
+
+        let unknown = RFC_2617.replace("auth,auth-int", "auth-conf");
+        assert_eq!(
+            answer(&unknown, &auth, "GET", None, "0a4f113b").unwrap_err(),
+            "Unsupported digest qop: auth-conf"
+        );
+    }
+
+    #[test]
+    fn digest_is_found_among_other_challenges() {
+        let header = format!(
+            "Basic realm=\"basic, realm\", {}",
+            RFC_2617.replace("Digest", "dIgEsT")
+        );
+
+        let params = digest_params(&header).unwrap();
+        assert_eq!(
+            params[0],
+            ("realm".to_string(), "testrealm@host.com".to_string())
+        );
+        assert_eq!(params.len(), 4);
+
+        let header = answer(&header, &mufasa("Circle Of Life"), "GET", None, "0a4f113b").unwrap();
+        assert_eq!(
+            header_param(&header, "response"),
+            "6629fae49393a05397450978507c4ef1"
+        );
+
+        let escaped = digest_params("Digest realm=\"a \\\"b\\\"\", nonce=n").unwrap();
+        assert_eq!(escaped[0].1, "a \"b\"");
+        assert_eq!(escaped[1].1, "n");
+
+        for header in [
+            "",
+            "Dig",
+            "Digest",
+            "Basic realm=x",
+            "Digestive realm=x",
+            "realm=x",
+        ] {
+            assert!(
+                digest_params(header).unwrap_or_default().is_empty(),
+                "{header}"
+            );
+        }
+    }
+
+    #[test]
+    fn basic_bearer_and_api_keys() {
+        let apply = |auth: HttpAuth| {
+            apply_auth(reqwest::Client::new().get("http://example.org/a"), &auth)
+                .build()
+                .unwrap()
+        };
+
+        let basic = apply(HttpAuth {
+            auth_type: HttpAuthType::BASIC,
+            username: "user".to_string(),
+            password: "pass".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(basic.headers()["authorization"], "Basic dXNlcjpwYXNz");
+
+        let bearer = apply(HttpAuth {
+            auth_type: HttpAuthType::BEARER,
+            token: "abc".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(bearer.headers()["authorization"], "Bearer abc");
+
+        let mut key = HttpAuth {
+            auth_type: HttpAuthType::API_KEY,
+            key: "X-Key".to_string(),
+            value: "a b".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(apply(key.clone()).headers()["x-key"], "a b");
+
+        key.location = ApiKeyLocation::QUERY;
+        assert_eq!(apply(key).url().as_str(), "http://example.org/a?X-Key=a+b");
+    }
+}
@@ -0,0 +1,307 @@
+use bolt_common::prelude::*;
+
+// encodes the body for its type, the Content-Type is only set when the user did not add one
+pub fn apply_body(
+    builder: reqwest::RequestBuilder,
+    req: &SendHttpRequest,
+    console: &mut Vec<String>,
+) -> Result<reqwest::RequestBuilder, String> {
+    let user_content_type = req
+        .headers
+        .iter()
+        .any(|h| h[0].trim().eq_ignore_ascii_case("content-type") && !h[1].is_empty());
+
+    let with_content_type = |builder: reqwest::RequestBuilder| {
+        if user_content_type {
+            builder
+        } else {
+            builder.header(reqwest::header::CONTENT_TYPE, req.body_type.content_type())
+        }
+    };
+
+    match req.body_type {
+        HttpBodyType::RAW | HttpBodyType::JSON => {
+            if req.body.is_empty() {
+                return Ok(builder.body(String::new()));
+            }
+
+            Ok(with_content_type(builder.body(req.body.clone())))
+        }
+
+        HttpBodyType::FORM_URLENCODED => {
+            let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+
+            for row in &req.form {
+                if row.len() > 1 && !row[0].is_empty() {
+                    serializer.append_pair(&row[0], &row[1]);
+                }
+            }
+
+            Ok(with_content_type(builder.body(serializer.finish())))
+        }
+
+        HttpBodyType::MULTIPART => {
+            let mut form = reqwest::multipart::Form::new();
+
+            for field in &req.multipart {
+                if field.name.is_empty() {
+                    continue;
+                }
+
+                if field.is_file {
+                    let bytes = read_file(&field.value)?;
+
+                    let file_name = std::path::Path::new(&field.value)
+                        .file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                        .unwrap_or_default();
+
+                    console.push(format!(
+                        "Multipart: attaching {} ({} B) as {}",
+                        field.value,
+                        bytes.len(),
+                        field.name
+                    ));
+
+                    let part = reqwest::multipart::Part::bytes(bytes)
+                        .file_name(file_name)
+                        .mime_str("application/octet-stream")
+                        .unwrap();
+
+                    form = form.part(field.name.clone(), part);
+                } else {
+                    form = form.text(field.name.clone(), field.value.clone());
+                }
+            }
+
+            // the encoder owns the boundary so its Content-Type always wins
+            if user_content_type {
+                console.push(
+                    "Multipart: the Content-Type header is replaced to carry the form boundary"
+                        .to_string(),
+                );
+            }
+
+            Ok(builder.multipart(form))
+        }
+
+        HttpBodyType::BINARY => {
+            if req.body_file.is_empty() {
+                return Err("No file selected for the binary body".to_string());
+            }
+
+            let bytes = read_file(&req.body_file)?;
+
+            console.push(format!(
+                "Sending {} ({} B) as the body",
+                req.body_file,
+                bytes.len()
+            ));
+
+            Ok(with_content_type(builder.body(bytes)))
+        }
+
+        HttpBodyType::GRAPHQL => {
+            if req.graphql.query.trim().is_empty() {
+                return Err("The GraphQL query is empty".to_string());
+            }
+
+            let payload = req.graphql.payload()?;
+
+            let user_accept = req
+                .headers
+                .iter()
+                .any(|h| h[0].trim().eq_ignore_ascii_case("accept") && !h[1].is_empty());
+
+            let builder = if user_accept {
+                builder
+            } else {
+                builder.header(
+                    reqwest::header::ACCEPT,
+                    "application/graphql-response+json, application/json",
+                )
+            };
+
+            Ok(with_content_type(builder.body(payload)))
+        }
+    }
+}
+
+fn read_file(path: &str) -> Result<Vec<u8>, String> {
+    std::fs::read(path).map_err(|err| format!("Could not read {path}: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(body_type: HttpBodyType) -> SendHttpRequest {
+        let mut request = HttpRequest::new();
+        request.url = "http://example.org/".to_string();
+        request.body_type = body_type;
+        request.headers = vec![];
+
+        request.to_send_request(0)
+    }
+
+    fn encode(
+        req: &SendHttpRequest,
+        console: &mut Vec<String>,
+    ) -> Result<reqwest::Request, String> {
+        let builder = reqwest::Client::new().post(&req.url);
+
+        apply_body(builder, req, console).map(|builder| builder.build().unwrap())
+    }
+
+    fn body(request: &reqwest::Request) -> &str {
+        std::str::from_utf8(request.body().unwrap().as_bytes().unwrap()).unwrap()
+    }
+
+    fn temp_file(contents: &[u8]) -> String {
+        let path = std::env::temp_dir().join(format!("bolt-body-{}.bin", uuid::Uuid::new_v4()));
+        std::fs::write(&path, contents).unwrap();
+
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn forms_are_url_encoded() {
+        let mut req = request(HttpBodyType::FORM_URLENCODED);
+        req.form = vec![
+            vec!["name".to_string(), "a b&c=é".to_string()],
+            vec![String::new(), "skipped".to_string()],
+            vec!["empty".to_string(), String::new()],
+        ];
+
+        let encoded = encode(&req, &mut vec![]).unwrap();
+
+        assert_eq!(body(&encoded), "name=a+b%26c%3D%C3%A9&empty=");
+        assert_eq!(
+            encoded.headers()["content-type"],
+            "application/x-www-form-urlencoded"
+        );
+    }
+
+    #[test]
+    fn a_user_content_type_wins() {
+        let mut req = request(HttpBodyType::JSON);
+        req.body = "{}".to_string();
+        req.headers = vec![vec![
+            "Content-Type".to_string(),
+            "application/vnd.api+json".to_string(),
+        ]];
+
+        let encoded = encode(&req, &mut vec![]).unwrap();
+
+        // prepare_request adds the user's header itself
+        assert!(encoded.headers().get("content-type").is_none());
+        assert_eq!(body(&encoded), "{}");
+
+        let mut req = request(HttpBodyType::JSON);
+        req.body = "{}".to_string();
+
+        let encoded = encode(&req, &mut vec![]).unwrap();
+        assert_eq!(encoded.headers()["content-type"], "application/json");
+    }
+
+    #[test]
+    fn multipart_forms_attach_files() {
+        let path = temp_file(b"12345");
+
+        let mut req = request(HttpBodyType::MULTIPART);
+        req.headers = vec![vec!["content-type".to_string(), "text/plain".to_string()]];
+        req.multipart = vec![
+            MultipartField {
+                name: "text".to_string(),
+                value: "value".to_string(),
+                is_file: false,
+            },
+            MultipartField {
+                name: "upload".to_string(),
+                value: path.clone(),
+                is_file: true,
+            },
+        ];
+
+        let mut console = vec![];
+        let encoded = encode(&req, &mut console).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let content_type = encoded.headers()["content-type"].to_str().unwrap();
+        assert!(content_type.starts_with("multipart/form-data; boundary="));
+
+        assert_eq!(
+            console,
+            vec![
+                format!("Multipart: attaching {path} (5 B) as upload"),
+                "Multipart: the Content-Type header is replaced to carry the form boundary"
+                    .to_string(),
+            ]
+        );
+
+        // the file is gone now
+        let err = encode(&req, &mut vec![]).unwrap_err();
+        assert!(
+            err.starts_with(&format!("Could not read {path}: ")),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn binary_bodies_send_the_file() {
+        let path = temp_file(&[0, 159, 146, 150]);
+
+        let mut req = request(HttpBodyType::BINARY);
+        req.body_file = path.clone();
+
+        let mut console = vec![];
+        let encoded = encode(&req, &mut console).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            encoded.body().unwrap().as_bytes().unwrap(),
+            &[0, 159, 146, 150]
+        );
+        assert_eq!(
+            encoded.headers()["content-type"],
+            "application/octet-stream"
+        );
+        assert_eq!(console, vec![format!("Sending {path} (4 B) as the body")]);
+
+        req.body_file = String::new();
+        assert_eq!(
+            encode(&req, &mut vec![]).unwrap_err(),
+            "No file selected for the binary body"
+        );
+    }
+
+    #[test]
+    fn graphql_bodies_are_json_payloads() {
+        let mut req = request(HttpBodyType::GRAPHQL);
+        req.graphql.query = "query Q($id: ID) { user(id: $id) { name } }".to_string();
+        req.graphql.variables = r#"{"id": 1}"#.to_string();
+        req.graphql.operation_name = "Q".to_string();
+
+        let encoded = encode(&req, &mut vec![]).unwrap();
+        let payload: serde_json::Value = serde_json::from_str(body(&encoded)).unwrap();
+
+        assert_eq!(
+            payload,
+            serde_json::json!({
+                "query": "query Q($id: ID) { user(id: $id) { name } }",
+                "variables": {"id": 1},
+                "operationName": "Q",
+            })
+        );
+        assert_eq!(
+            encoded.headers()["accept"],
+            "application/graphql-response+json, application/json"
+        );
+
+        req.graphql.query = " ".to_string();
+        assert_eq!(
+            encode(&req, &mut vec![]).unwrap_err(),
+            "The GraphQL query is empty"
+        );
+    }
+}
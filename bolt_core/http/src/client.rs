@@ -0,0 +1,202 @@
+use bolt_common::prelude::*;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::cookies;
+use crate::timing::DnsTimer;
+
+pub fn build_client(
+    settings: &HttpSettings,
+    console: &mut Vec<String>,
+) -> Result<reqwest::Client, String> {
+    client_builder(settings, console)?
+        .build()
+        .map_err(|err| format!("Could not build HTTP client: {err}"))
+}
+
+// the same client, adding the time of each host lookup to `dns`
+pub fn build_timed_client(
+    settings: &HttpSettings,
+    console: &mut Vec<String>,
+    dns: &DnsTimer,
+) -> Result<reqwest::Client, String> {
+    client_builder(settings, console)?
+        .dns_resolver(Arc::new(dns.clone()))
+        .build()
+        .map_err(|err| format!("Could not build HTTP client: {err}"))
+}
+
+fn client_builder(
+    settings: &HttpSettings,
+    console: &mut Vec<String>,
+) -> Result<reqwest::ClientBuilder, String> {
+    let mut builder = reqwest::Client::builder()
+        .cookie_provider(Arc::new(cookies::CookieJar))
+        .tls_info(true);
+
+    if settings.connect_timeout > 0 {
+        builder = builder.connect_timeout(Duration::from_millis(settings.connect_timeout));
+    }
+
+    if settings.timeout > 0 {
+        builder = builder.timeout(Duration::from_millis(settings.timeout));
+    }
+
+    builder = if settings.follow_redirects {
+        builder.redirect(reqwest::redirect::Policy::limited(settings.max_redirects))
+    } else {
+        builder.redirect(reqwest::redirect::Policy::none())
+    };
+
+    let proxy = settings.proxy.trim();
+
+    if !proxy.is_empty() {
+        let proxy = reqwest::Proxy::all(proxy).map_err(|err| format!("Invalid proxy: {err}"))?;
+
+        console.push(format!("Using proxy {}", settings.proxy.trim()));
+
+        builder = builder.proxy(proxy);
+    }
+
+    if !settings.verify_tls {
+        console.push("TLS certificate verification is disabled".to_string());
+
+        builder = builder
+            .danger_accept_invalid_certs(true)
+            .danger_accept_invalid_hostnames(true);
+    }
+
+    if !settings.ca_cert.is_empty() {
+        for cert in read_ca_bundle(&settings.ca_cert)? {
+            builder = builder.add_root_certificate(cert);
+        }
+
+        console.push(format!("Trusting CA bundle {}", settings.ca_cert));
+    }
+
+    if !settings.client_cert.is_empty() {
+        builder = builder.identity(read_identity(settings)?);
+
+        console.push(format!("Using client certificate {}", settings.client_cert));
+    }
+
+    Ok(builder)
+}
+
+// a bundle can hold several PEM certificates, each one is trusted
+fn read_ca_bundle(path: &str) -> Result<Vec<reqwest::Certificate>, String> {
+    let pem = std::fs::read_to_string(path)
+        .map_err(|err| format!("Could not read CA bundle {path}: {err}"))?;
+
+    let end_marker = "-----END CERTIFICATE-----";
+
+    let mut certs = vec![];
+
+    for block in pem.split_inclusive(end_marker) {
+        if !block.contains("-----BEGIN CERTIFICATE-----") {
+            continue;
+        }
+
+        let cert = reqwest::Certificate::from_pem(block.trim().as_bytes())
+            .map_err(|err| format!("Invalid certificate in {path}: {err}"))?;
+
+        certs.push(cert);
+    }
+
+    if certs.is_empty() {
+        return Err(format!("No PEM certificate found in {path}"));
+    }
+
+    Ok(certs)
+}
+
+fn read_identity(settings: &HttpSettings) -> Result<reqwest::Identity, String> {
+    let cert = std::fs::read(&settings.client_cert).map_err(|err| {
+        format!(
+            "Could not read client certificate {}: {err}",
+            settings.client_cert
+        )
+    })?;
+
+    // the key may live in the same file as the certificate
+    let key = if settings.client_key.is_empty() {
+        cert.clone()
+    } else {
+        std::fs::read(&settings.client_key).map_err(|err| {
+            format!("Could not read client key {}: {err}", settings.client_key)
+        })?
+    };
+
+    reqwest::Identity::from_pkcs8_pem(&cert, &key)
+        .map_err(|err| format!("Invalid client certificate or key: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_file(name: &str, contents: &str) -> String {
+        let path = std::env::temp_dir().join(format!("bolt-{}-{name}", uuid::Uuid::new_v4()));
+        std::fs::write(&path, contents).unwrap();
+
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn settings_are_reported_in_the_console() {
+        let mut settings = HttpSettings::new();
+        settings.proxy = " socks5h://127.0.0.1:1080 ".to_string();
+        settings.verify_tls = false;
+
+        let mut console = vec![];
+        build_client(&settings, &mut console).unwrap();
+
+        assert_eq!(
+            console,
+            vec![
+                "Using proxy socks5h://127.0.0.1:1080",
+                "TLS certificate verification is disabled",
+            ]
+        );
+
+        let mut console = vec![];
+        build_client(&HttpSettings::new(), &mut console).unwrap();
+        assert!(console.is_empty());
+    }
+
+    #[test]
+    fn bad_settings_fail_the_request() {
+        let build = |settings: HttpSettings| build_client(&settings, &mut vec![]).unwrap_err();
+
+        let mut settings = HttpSettings::new();
+        settings.proxy = "not a proxy".to_string();
+        assert!(build(settings).starts_with("Invalid proxy"));
+
+        let mut settings = HttpSettings::new();
+        settings.ca_cert = "/does/not/exist.pem".to_string();
+        assert!(build(settings).starts_with("Could not read CA bundle /does/not/exist.pem"));
+
+        let mut settings = HttpSettings::new();
+        settings.ca_cert = temp_file("empty.pem", "no certificates here");
+        let err = build(settings.clone());
+        std::fs::remove_file(&settings.ca_cert).unwrap();
+        assert!(err.starts_with("No PEM certificate found in"), "{err}");
+
+        let mut settings = HttpSettings::new();
+        settings.client_cert = "/does/not/exist.pem".to_string();
+        assert!(build(settings).starts_with("Could not read client certificate"));
+    }
+
+    #[test]
+    fn bundles_with_broken_certificates_are_rejected() {
+        let bundle = temp_file(
+            "broken.pem",
+            "-----BEGIN CERTIFICATE-----\nbm90IGEgY2VydA==\n-----END CERTIFICATE-----\n",
+        );
+
+        let err = read_ca_bundle(&bundle).unwrap_err();
+        std::fs::remove_file(&bundle).unwrap();
+
+        assert!(err.starts_with("Invalid certificate in"), "{err}");
+    }
+}
@@ -0,0 +1,192 @@
+use bolt_common::prelude::*;
+use reqwest::header::HeaderValue;
+
+use crate::CORE_STATE;
+
+// backs the reqwest client with the jar in CoreState, which client saves never replace
+pub struct CookieJar;
+
+impl reqwest::cookie::CookieStore for CookieJar {
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &url::Url) {
+        let now = crate::get_timestamp() as u64;
+
+        let mut core_state = CORE_STATE.lock().unwrap();
+        let jar = core_state.cookies.get_or_insert_with(Vec::new);
+
+        for header in cookie_headers {
+            let header = match header.to_str() {
+                Ok(header) => header,
+                Err(_) => continue,
+            };
+
+            if let Some(cookie) = parse_set_cookie(header, url, now) {
+                store_cookie(jar, cookie, now);
+            }
+        }
+    }
+
+    fn cookies(&self, url: &url::Url) -> Option<HeaderValue> {
+        let core_state = CORE_STATE.lock().unwrap();
+
+        let header = cookie_header(
+            core_state.cookies.as_deref().unwrap_or_default(),
+            url.host_str().unwrap_or_default(),
+            url.path(),
+            url.scheme() == "https",
+            crate::get_timestamp() as u64,
+        )?;
+
+        HeaderValue::from_str(&header).ok()
+    }
+}
+
+pub fn get_cookies() -> Vec<Cookie> {
+    let core_state = CORE_STATE.lock().unwrap();
+
+    core_state.cookies.clone().unwrap_or_default()
+}
+
+// the first state seeds the jar, every later one gets its cookies from it because a
+// save can be older than the cookies a running request just stored
+pub fn sync_cookies(state: &mut MainState) {
+    let mut core_state = CORE_STATE.lock().unwrap();
+
+    match &core_state.cookies {
+        Some(jar) => state.cookies = jar.clone(),
+        None => core_state.cookies = Some(state.cookies.clone()),
+    }
+}
+
+pub fn edit_cookies(edit: &CookieEdit) {
+    let mut core_state = CORE_STATE.lock().unwrap();
+
+    edit.apply(core_state.cookies.get_or_insert_with(Vec::new));
+}
+
+fn parse_set_cookie(header: &str, url: &url::Url, now: u64) -> Option<Cookie> {
+    let parsed = cookie::Cookie::parse(header).ok()?;
+
+    let host = url.host_str().unwrap_or_default().to_lowercase();
+
+    let mut new_cookie = Cookie::new();
+
+    new_cookie.name = parsed.name().to_string();
+    new_cookie.value = parsed.value().to_string();
+    new_cookie.secure = parsed.secure().unwrap_or(false);
+    new_cookie.http_only = parsed.http_only().unwrap_or(false);
+
+    match parsed.domain() {
+        Some(domain) if !domain.is_empty() => {
+            let domain = domain.trim_start_matches('.').to_lowercase();
+
+            // a server can only set cookies for itself or a parent domain
+            if host != domain && !host.ends_with(&format!(".{domain}")) {
+                return None;
+            }
+
+            new_cookie.domain = domain;
+            new_cookie.host_only = false;
+        }
+
+        _ => {
+            new_cookie.domain = host;
+            new_cookie.host_only = true;
+        }
+    }
+
+    new_cookie.path = match parsed.path() {
+        Some(path) if path.starts_with('/') => path.to_string(),
+
+        // default path is the directory of the request path (RFC 6265 5.1.4)
+        _ => match url.path().rfind('/') {
+            Some(0) | None => "/".to_string(),
+            Some(index) => url.path()[..index].to_string(),
+        },
+    };
+
+    // max-age wins over expires
+    if let Some(max_age) = parsed.max_age() {
+        let seconds = max_age.whole_seconds();
+
+        new_cookie.expires = if seconds <= 0 {
+            1
+        } else {
+            now + seconds as u64 * 1000
+        };
+    } else if let Some(expires) = parsed.expires_datetime() {
+        let timestamp = expires.unix_timestamp();
+
+        new_cookie.expires = if timestamp <= 0 {
+            1
+        } else {
+            timestamp as u64 * 1000
+        };
+    }
+
+    Some(new_cookie)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(header: &str, url: &str) -> Option<Cookie> {
+        parse_set_cookie(header, &url::Url::parse(url).unwrap(), 1_000)
+    }
+
+    #[test]
+    fn cookies_without_a_domain_are_host_only() {
+        let cookie = parse("id=1; Secure; HttpOnly", "https://API.example.com/a/b").unwrap();
+
+        assert_eq!(cookie.domain, "api.example.com");
+        assert!(cookie.host_only);
+        assert!(cookie.secure && cookie.http_only);
+        // the directory of the request path
+        assert_eq!(cookie.path, "/a");
+    }
+
+    #[test]
+    fn servers_can_only_set_cookies_for_their_own_domains() {
+        let parent = parse("id=1; Domain=.example.com", "http://api.example.com/").unwrap();
+
+        assert_eq!(parent.domain, "example.com");
+        assert!(!parent.host_only);
+
+        assert!(parse("id=1; Domain=other.com", "http://api.example.com/").is_none());
+        assert!(parse("id=1; Domain=i.example.com", "http://api.example.com/").is_none());
+    }
+
+    #[test]
+    fn max_age_wins_over_expires() {
+        let cookie = parse(
+            "id=1; Max-Age=60; Expires=Wed, 21 Oct 2015 07:28:00 GMT",
+            "http://example.com/",
+        )
+        .unwrap();
+
+        assert_eq!(cookie.expires, 61_000);
+
+        let deleted = parse("id=1; Max-Age=0", "http://example.com/").unwrap();
+        assert!(deleted.is_expired(1_000));
+    }
+
+    #[test]
+    fn client_saves_do_not_replace_the_jar() {
+        let mut first = MainState::new();
+        first.cookies = vec![parse("seed=1", "http://seed.test/").unwrap()];
+        sync_cookies(&mut first);
+
+        edit_cookies(&CookieEdit::Set {
+            old: None,
+            cookie: parse("set=1", "http://seed.test/").unwrap(),
+        });
+
+        // an older save still has only the seed cookie
+        let mut stale = first.clone();
+        sync_cookies(&mut stale);
+
+        let names: Vec<String> = stale.cookies.iter().map(|c| c.name.clone()).collect();
+        assert_eq!(names, vec!["seed", "set"]);
+        assert_eq!(get_cookies(), stale.cookies);
+    }
+}
@@ -0,0 +1,186 @@
+use bolt_common::prelude::*;
+
+use crate::json_path;
+
+// [variable, value] pairs for every rule that found something, misses are logged
+pub fn run_extractions(
+    rules: &[ExtractionRule],
+    resp: &SendHttpResponse,
+    url: &str,
+    console: &mut Vec<String>,
+) -> Vec<Vec<String>> {
+    let mut extracted = vec![];
+
+    for rule in rules.iter().filter(|rule| rule.enabled) {
+        let variable = rule.variable.trim();
+
+        if variable.is_empty() {
+            continue;
+        }
+
+        match extract(rule, resp, url) {
+            Ok(value) => {
+                console.push(format!("Extracted {variable} from {}", rule.source));
+
+                extracted.push(vec![variable.to_string(), value]);
+            }
+
+            Err(err) => console.push(format!("Could not extract {variable}: {err}")),
+        }
+    }
+
+    extracted
+}
+
+fn extract(rule: &ExtractionRule, resp: &SendHttpResponse, url: &str) -> Result<String, String> {
+    let expression = rule.expression.trim();
+
+    match rule.source {
+        ExtractionSource::JSON_PATH => {
+            let json: serde_json::Value = serde_json::from_str(&resp.body)
+                .map_err(|_| "the body is not JSON".to_string())?;
+
+            let value = json_path::select(&json, expression)?
+                .ok_or_else(|| format!("{expression} does not exist"))?;
+
+            // strings are stored without their quotes, anything else as JSON
+            Ok(match value {
+                serde_json::Value::String(text) => text.clone(),
+                other => other.to_string(),
+            })
+        }
+
+        ExtractionSource::HEADER => resp
+            .headers
+            .iter()
+            .find(|header| header[0].eq_ignore_ascii_case(expression))
+            .map(|header| header[1].clone())
+            .ok_or_else(|| format!("no {expression} header")),
+
+        ExtractionSource::COOKIE => {
+            // a cookie set by this response wins over the one already in the jar
+            let set_cookie = resp
+                .headers
+                .iter()
+                .filter(|header| header[0].eq_ignore_ascii_case("set-cookie"))
+                .filter_map(|header| cookie::Cookie::parse(header[1].clone()).ok())
+                .find(|cookie| cookie.name() == expression)
+                .map(|cookie| cookie.value().to_string());
+
+            if let Some(value) = set_cookie {
+                return Ok(value);
+            }
+
+            let url = url::Url::parse(url).map_err(|err| err.to_string())?;
+
+            let host = url.host_str().unwrap_or_default();
+            let secure = url.scheme() == "https";
+            let now = crate::get_timestamp() as u64;
+
+            crate::cookies::get_cookies()
+                .into_iter()
+                .find(|cookie| {
+                    cookie.name == expression && cookie.matches(host, url.path(), secure, now)
+                })
+                .map(|cookie| cookie.value)
+                .ok_or_else(|| format!("no {expression} cookie"))
+        }
+
+        ExtractionSource::REGEX => {
+            let regex = regex::Regex::new(expression).map_err(|err| format!("invalid regex: {err}"))?;
+
+            let captures = regex
+                .captures(&resp.body)
+                .ok_or_else(|| "no match in the body".to_string())?;
+
+            let found = captures.get(1).or_else(|| captures.get(0)).unwrap();
+
+            Ok(found.as_str().to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ExtractionSource::*;
+
+    fn response() -> SendHttpResponse {
+        let mut resp = SendHttpResponse::new();
+
+        resp.body = r#"{"token": "abc", "user": {"id": 7, "roles": ["admin"]}}"#.to_string();
+        resp.headers = vec![
+            vec!["X-Request-Id".to_string(), "req-1".to_string()],
+            vec!["set-cookie".to_string(), "session=s1; Path=/".to_string()],
+        ];
+
+        resp
+    }
+
+    fn rule(source: ExtractionSource, expression: &str, variable: &str) -> ExtractionRule {
+        ExtractionRule {
+            source,
+            expression: expression.to_string(),
+            variable: variable.to_string(),
+            enabled: true,
+        }
+    }
+
+    fn pair(name: &str, value: &str) -> Vec<String> {
+        vec![name.to_string(), value.to_string()]
+    }
+
+    #[test]
+    fn extracts_from_every_source() {
+        let rules = [
+            rule(JSON_PATH, "$.token", "token"),
+            rule(JSON_PATH, "$.user.id", "id"),
+            rule(JSON_PATH, "$.user.roles", "roles"),
+            rule(HEADER, "x-request-id", "request"),
+            rule(COOKIE, "session", "session"),
+            rule(REGEX, r#""id": (\d+)"#, "group"),
+            rule(REGEX, "adm[a-z]+", "whole"),
+        ];
+
+        let extracted = run_extractions(&rules, &response(), "http://api.test/", &mut vec![]);
+
+        assert_eq!(
+            extracted,
+            vec![
+                pair("token", "abc"),
+                pair("id", "7"),
+                pair("roles", r#"["admin"]"#),
+                pair("request", "req-1"),
+                pair("session", "s1"),
+                pair("group", "7"),
+                pair("whole", "admin"),
+            ]
+        );
+    }
+
+    #[test]
+    fn misses_are_logged_and_skipped() {
+        let mut disabled = rule(HEADER, "x-request-id", "disabled");
+        disabled.enabled = false;
+
+        let rules = [
+            rule(JSON_PATH, "$.missing", "missing"),
+            rule(HEADER, "etag", "etag"),
+            rule(REGEX, "(", "broken"),
+            rule(JSON_PATH, "$.token", "  "),
+            disabled,
+        ];
+
+        let mut console = vec![];
+        let extracted = run_extractions(&rules, &response(), "http://api.test/", &mut console);
+
+        assert!(extracted.is_empty());
+        assert_eq!(console.len(), 3);
+        assert_eq!(
+            console[0],
+            "Could not extract missing: $.missing does not exist"
+        );
+        assert_eq!(console[1], "Could not extract etag: no etag header");
+        assert!(console[2].starts_with("Could not extract broken: invalid regex"));
+    }
+}
@@ -0,0 +1,116 @@
+use serde_json::Value;
+
+// a small JSONPath subset: $.key, .key, [0], ['key'] and ["key"], the leading $ is optional
+pub fn select<'a>(value: &'a Value, path: &str) -> Result<Option<&'a Value>, String> {
+    let mut current = value;
+
+    for segment in parse(path)? {
+        let next = match segment {
+            Segment::Key(key) => current.get(key.as_str()),
+            Segment::Index(index) => current.get(index),
+        };
+
+        match next {
+            Some(next) => current = next,
+            None => return Ok(None),
+        }
+    }
+
+    Ok(Some(current))
+}
+
+pub fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+fn parse(path: &str) -> Result<Vec<Segment>, String> {
+    let trimmed = path.trim();
+    let chars: Vec<char> = trimmed.strip_prefix('$').unwrap_or(trimmed).chars().collect();
+
+    let mut segments = vec![];
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '.' => {
+                i += 1;
+            }
+
+            '[' => {
+                let end = chars[i..]
+                    .iter()
+                    .position(|c| *c == ']')
+                    .map(|offset| i + offset)
+                    .ok_or_else(|| format!("Unclosed [ in JSON path {path}"))?;
+
+                let inner: String = chars[i + 1..end].iter().collect();
+                let inner = inner.trim();
+
+                let quoted = inner.len() >= 2
+                    && ((inner.starts_with('\'') && inner.ends_with('\''))
+                        || (inner.starts_with('"') && inner.ends_with('"')));
+
+                if quoted {
+                    segments.push(Segment::Key(inner[1..inner.len() - 1].to_string()));
+                } else {
+                    let index = inner
+                        .parse::<usize>()
+                        .map_err(|_| format!("Invalid index [{inner}] in JSON path"))?;
+
+                    segments.push(Segment::Index(index));
+                }
+
+                i = end + 1;
+            }
+
+            _ => {
+                let start = i;
+
+                while i < chars.len() && chars[i] != '.' && chars[i] != '[' {
+                    i += 1;
+                }
+
+                segments.push(Segment::Key(chars[start..i].iter().collect()));
+            }
+        }
+    }
+
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn selects_keys_indexes_and_quoted_keys() {
+        let value = json!({ "data": { "items": [{ "id": 7 }], "odd key": true } });
+
+        assert_eq!(select(&value, "$.data.items[0].id"), Ok(Some(&json!(7))));
+        assert_eq!(select(&value, "data['odd key']"), Ok(Some(&json!(true))));
+        assert_eq!(select(&value, "$"), Ok(Some(&value)));
+    }
+
+    #[test]
+    fn missing_values_and_bad_paths() {
+        let value = json!({ "items": [] });
+
+        assert_eq!(select(&value, "$.items[3]"), Ok(None));
+        assert_eq!(select(&value, "$.nope.deeper"), Ok(None));
+        assert!(select(&value, "$.items[0").is_err());
+        assert!(select(&value, "$.items[x]").is_err());
+    }
+}
@@ -1,42 +1,248 @@
+mod assertions;
+mod auth;
+mod body;
+pub mod client;
+mod extraction;
+pub mod cookies;
+mod json_path;
+pub mod oauth2;
+mod timing;
 mod utils;
+pub mod variables;
 
+use base64::{engine::general_purpose, Engine};
 use bolt_common::prelude::*;
-use std::time::SystemTime;
+use std::sync::{Arc, Mutex};
+use std::time::{Instant, SystemTime};
+
+lazy_static::lazy_static! {
+ static ref CORE_STATE: Arc<Mutex<CoreState>> = Arc::new(Mutex::new(CoreState::new()));
+}
+
+pub struct CoreState {
+    main_state: MainState,
+    oauth2_tokens: Vec<oauth2::CachedToken>,
+    // the cookie jar, None until the first state seeds it
+    cookies: Option<Vec<Cookie>>,
+    // extracted values the client has not saved yet
+    variables: Vec<variables::StoredVariable>,
+}
+
+impl Default for CoreState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CoreState {
+    pub fn new() -> Self {
+        Self {
+            main_state: MainState::new(),
+            oauth2_tokens: vec![],
+            cookies: None,
+            variables: vec![],
+        }
+    }
+}
+
+pub fn set_main_state(client_state: MainState) {
+    let mut core_state = CORE_STATE.lock().unwrap();
+    core_state.main_state = client_state;
+}
+
+pub fn active_environment() -> Option<Environment> {
+    let core_state = CORE_STATE.lock().unwrap();
+
+    core_state.main_state.active_environment().cloned()
+}
+
+pub fn default_settings() -> HttpSettings {
+    let core_state = CORE_STATE.lock().unwrap();
+
+    core_state.main_state.http_settings.clone()
+}
+
+fn failed_response(request_index: usize, reason: String, console: Vec<String>) -> SendHttpResponse {
+    let mut err_resp = SendHttpResponse::new();
+
+    err_resp.failed = true;
+    err_resp.body = reason;
+    err_resp.console = console;
+    err_resp.request_index = request_index;
+
+    err_resp
+}
 
 pub async fn http_send(mut req: SendHttpRequest) -> SendHttpResponse {
+    if req.settings.is_none() {
+        req.settings = Some(default_settings());
+    }
+
+    if let Some(env) = active_environment() {
+        req.resolve_variables(&env);
+    }
+
+    let assertions = std::mem::take(&mut req.assertions);
+    let extractions = std::mem::take(&mut req.extractions);
+    let url = req.url.clone();
+    let graphql = req.body_type == HttpBodyType::GRAPHQL;
+
+    let mut http_response = send_request(req).await;
+
+    if graphql && !http_response.failed {
+        http_response.graphql_errors = graphql_errors(&http_response.body);
+    }
+
+    http_response.tests = assertions::run_assertions(&assertions, &http_response);
+
+    if !http_response.failed && !extractions.is_empty() {
+        let mut console = std::mem::take(&mut http_response.console);
+
+        http_response.extracted =
+            extraction::run_extractions(&extractions, &http_response, &url, &mut console);
+
+        variables::store_variables(&http_response.extracted, &mut console);
+
+        http_response.console = console;
+    }
+
+    http_response
+}
+
+async fn send_request(mut req: SendHttpRequest) -> SendHttpResponse {
     if !req.url.contains("http") {
         let new_url = "http://".to_string() + &req.url;
 
         req.url = new_url;
     }
 
-    let mut request = prepare_request(req.clone());
+    let mut console = vec![];
 
-    for h in req.headers {
-        if h[0] != "" && h[1] != "" {
-            request = request.header(h[0].clone(), h[1].clone());
+    let dns = timing::DnsTimer::default();
+
+    let client = match client::build_timed_client(req.settings.as_ref().unwrap(), &mut console, &dns) {
+        Ok(client) => client,
+        Err(err) => return failed_response(req.request_index, err, console),
+    };
+
+    if req.auth.auth_type == HttpAuthType::OAUTH2 {
+        match oauth2::get_access_token(&req.auth, req.settings.as_ref().unwrap(), &mut console)
+            .await
+        {
+            Ok(token) => req.auth.token = token,
+            Err(err) => return failed_response(req.request_index, err, console),
         }
     }
 
-    let start = get_timestamp();
-    let response = request.send().await;
-    let end = get_timestamp();
+    let request = match prepare_request(&client, req.clone(), &mut console) {
+        Ok(request) => request,
+        Err(err) => return failed_response(req.request_index, err, console),
+    };
+
+    let started = get_timestamp() as u64;
+    let start = Instant::now();
+    let mut response = request.send().await;
+
+    if req.auth.auth_type == HttpAuthType::DIGEST {
+        let challenge = match &response {
+            Ok(resp) => auth::digest_challenge(resp).map(|c| (c, resp.url().clone())),
+            Err(_) => None,
+        };
+
+        if let Some((challenge, url)) = challenge {
+            // the body is encoded again, a multipart form can only be sent once
+            let request = prepare_request(&client, req.clone(), &mut vec![])
+                .and_then(|builder| builder.build().map_err(|err| err.to_string()));
+
+            let mut request = match request {
+                Ok(request) => request,
+                Err(err) => return failed_response(req.request_index, err, console),
+            };
+
+            // a multipart body is a stream, auth-int cannot hash it
+            let body = match request.body() {
+                Some(body) => body.as_bytes(),
+                None => Some(&[][..]),
+            };
+
+            let authorization = auth::digest_authorization(
+                &challenge,
+                &req.auth,
+                &req.method.to_string(),
+                &url,
+                body,
+            )
+            .and_then(|authorization| {
+                reqwest::header::HeaderValue::from_str(&authorization)
+                    .map_err(|err| format!("Invalid digest authorization: {err}"))
+            });
+
+            match authorization {
+                Ok(authorization) => {
+                    request
+                        .headers_mut()
+                        .insert(reqwest::header::AUTHORIZATION, authorization);
+
+                    response = client.execute(request).await;
+                }
+
+                Err(err) => return failed_response(req.request_index, err, console),
+            }
+        }
+    }
 
     let mut http_response = match response {
         Ok(resp) => {
             let mut new_response = SendHttpResponse::new();
 
+            let headers_received = start.elapsed();
+
             new_response.headers = extract_headers(resp.headers());
             new_response.status = resp.status().as_u16();
-            new_response.time = (end - start) as u32;
-            new_response.body = resp.text().await.unwrap();
-            new_response.size = new_response.body.len() as u64;
+            new_response.connection = timing::connection_info(&resp);
 
             for header in &new_response.headers {
                 if header[0] == "content-type" {
-                    if header[1].contains("application/json") {
-                        new_response.response_type = SendHttpResponseType::JSON;
+                    new_response.content_type = header[1].clone();
+                }
+            }
+
+            let bytes = match resp.bytes().await {
+                Ok(bytes) => bytes,
+                Err(err) => {
+                    return failed_response(
+                        req.request_index,
+                        format!("Failed to read the response body: {err}"),
+                        console,
+                    )
+                }
+            };
+
+            new_response.timing =
+                timing::phase_timing(dns.micros(), headers_received, start.elapsed());
+            new_response.time = (new_response.timing.total / 1000) as u32;
+            new_response.size = bytes.len() as u64;
+
+            let text = std::str::from_utf8(&bytes).ok();
+
+            new_response.response_type = SendHttpResponseType::from_content_type(
+                &new_response.content_type,
+                text.is_some(),
+            );
+
+            // the body is sent once, as text when the text holds every byte, otherwise as
+            // base64 next to what the viewers show
+            match text {
+                Some(text) if new_response.response_type.is_text() => {
+                    new_response.body = text.to_string();
+                }
+
+                _ => {
+                    if new_response.response_type.is_text() {
+                        new_response.body = String::from_utf8_lossy(&bytes).to_string();
                     }
+
+                    new_response.body_base64 = general_purpose::STANDARD.encode(&bytes);
                 }
             }
 
@@ -44,6 +250,12 @@ pub async fn http_send(mut req: SendHttpRequest) -> SendHttpResponse {
         }
 
         Err(err) => {
+            if err.is_timeout() {
+                console.push("Request timed out".to_string());
+            } else if err.is_redirect() {
+                console.push("Redirect limit reached".to_string());
+            }
+
             let mut err_resp = SendHttpResponse::new();
 
             err_resp.failed = true;
@@ -55,6 +267,8 @@ pub async fn http_send(mut req: SendHttpRequest) -> SendHttpResponse {
     };
 
     http_response.request_index = req.request_index;
+    http_response.console = console;
+    http_response.started = started;
 
     return http_response;
 }
@@ -66,7 +280,7 @@ pub fn extract_headers(map: &reqwest::header::HeaderMap) -> Vec<Vec<String>> {
         let mut header: Vec<String> = Vec::new();
 
         header.push(key.to_string());
-        header.push(value.to_str().unwrap().to_string());
+        header.push(String::from_utf8_lossy(value.as_bytes()).to_string());
 
         headers.push(header);
     }
@@ -81,25 +295,39 @@ pub fn get_timestamp() -> u128 {
         .as_millis();
 }
 
-pub fn prepare_request(req: SendHttpRequest) -> reqwest::RequestBuilder {
-    let client = reqwest::Client::new();
-
+pub fn prepare_request(
+    client: &reqwest::Client,
+    req: SendHttpRequest,
+    console: &mut Vec<String>,
+) -> Result<reqwest::RequestBuilder, String> {
     let builder = match req.method {
-        HttpMethod::GET => client.get(req.url).body(req.body),
-        HttpMethod::POST => client.post(req.url).body(req.body),
-        HttpMethod::PUT => client.put(req.url).body(req.body),
-        HttpMethod::DELETE => client.delete(req.url).body(req.body),
-        HttpMethod::HEAD => client.head(req.url).body(req.body),
-        HttpMethod::PATCH => client.patch(req.url).body(req.body),
-        HttpMethod::OPTIONS => client
-            .request(reqwest::Method::OPTIONS, req.url)
-            .body(req.body),
-        HttpMethod::CONNECT => client
-            .request(reqwest::Method::CONNECT, req.url)
-            .body(req.body),
+        HttpMethod::GET => client.get(&req.url),
+        HttpMethod::POST => client.post(&req.url),
+        HttpMethod::PUT => client.put(&req.url),
+        HttpMethod::DELETE => client.delete(&req.url),
+        HttpMethod::HEAD => client.head(&req.url),
+        HttpMethod::PATCH => client.patch(&req.url),
+        HttpMethod::OPTIONS => client.request(reqwest::Method::OPTIONS, &req.url),
+        HttpMethod::CONNECT => client.request(reqwest::Method::CONNECT, &req.url),
     };
 
-    return builder;
+    let builder = body::apply_body(builder, &req, console)?;
+
+    let mut builder = auth::apply_auth(builder, &req.auth);
+
+    let multipart = req.body_type == HttpBodyType::MULTIPART;
+
+    for h in req.headers {
+        if h[0] != "" && h[1] != "" {
+            if multipart && h[0].trim().eq_ignore_ascii_case("content-type") {
+                continue;
+            }
+
+            builder = builder.header(h[0].clone(), h[1].clone());
+        }
+    }
+
+    return Ok(builder);
 }
 
 pub fn launch_http_service() {
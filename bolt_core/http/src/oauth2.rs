@@ -0,0 +1,747 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use bolt_common::prelude::*;
+use sha2::{Digest, Sha256};
+use std::io::{Read, Write};
+use std::net::TcpListener;
+
+use crate::CORE_STATE;
+
+const AUTHORIZATION_TIMEOUT: u64 = 120;
+// a client that connects to the redirect port but sends nothing is dropped after this
+const REDIRECT_READ_TIMEOUT: u64 = 5;
+
+#[derive(Clone)]
+pub struct CachedToken {
+    key: String,
+    token: OAuth2Token,
+}
+
+// tokens are shared by every request using the same client, grant and scope
+fn cache_key(auth: &HttpAuth) -> String {
+    let config = &auth.oauth2;
+
+    format!(
+        "{:?}|{}|{}|{}|{}",
+        config.grant_type, config.token_url, config.client_id, config.scope, auth.username
+    )
+}
+
+fn get_cached_token(key: &str) -> Option<OAuth2Token> {
+    let core_state = CORE_STATE.lock().unwrap();
+
+    core_state
+        .oauth2_tokens
+        .iter()
+        .find(|cached| cached.key == key)
+        .map(|cached| cached.token.clone())
+}
+
+fn set_cached_token(key: String, token: OAuth2Token) {
+    let mut core_state = CORE_STATE.lock().unwrap();
+
+    core_state.oauth2_tokens.retain(|cached| cached.key != key);
+    core_state.oauth2_tokens.push(CachedToken { key, token });
+}
+
+pub fn clear_cached_tokens() {
+    let mut core_state = CORE_STATE.lock().unwrap();
+
+    core_state.oauth2_tokens.clear();
+}
+
+// returns a valid access token, using the cache, the refresh token or a new grant in that order
+pub async fn get_access_token(
+    auth: &HttpAuth,
+    settings: &HttpSettings,
+    console: &mut Vec<String>,
+) -> Result<String, String> {
+    access_token(auth, settings, console, open_browser).await
+}
+
+fn open_browser(url: &str) -> bool {
+    webbrowser::open(url).is_ok()
+}
+
+// `open` shows the authorization page to the user, tests follow the redirects themselves
+async fn access_token(
+    auth: &HttpAuth,
+    settings: &HttpSettings,
+    console: &mut Vec<String>,
+    open: fn(&str) -> bool,
+) -> Result<String, String> {
+    let key = cache_key(auth);
+    let now = crate::get_timestamp() as u64;
+
+    if let Some(token) = get_cached_token(&key) {
+        if !token.is_expired(now) {
+            console.push("OAuth2: using cached access token".to_string());
+
+            return Ok(token.access_token);
+        }
+
+        if !token.refresh_token.is_empty() {
+            console.push("OAuth2: access token expired, refreshing".to_string());
+
+            let params = vec![
+                ("grant_type".to_string(), "refresh_token".to_string()),
+                ("refresh_token".to_string(), token.refresh_token.clone()),
+            ];
+
+            match request_token(&auth.oauth2, params, settings, console).await {
+                Ok(mut new_token) => {
+                    if new_token.refresh_token.is_empty() {
+                        new_token.refresh_token = token.refresh_token;
+                    }
+
+                    let access_token = new_token.access_token.clone();
+                    set_cached_token(key, new_token);
+
+                    return Ok(access_token);
+                }
+
+                Err(err) => {
+                    console.push(format!(
+                        "OAuth2: refresh failed ({err}), requesting a new token"
+                    ));
+                }
+            }
+        }
+    }
+
+    let token = fetch_token(auth, settings, console, open).await?;
+    let access_token = token.access_token.clone();
+
+    set_cached_token(key, token);
+
+    Ok(access_token)
+}
+
+async fn fetch_token(
+    auth: &HttpAuth,
+    settings: &HttpSettings,
+    console: &mut Vec<String>,
+    open: fn(&str) -> bool,
+) -> Result<OAuth2Token, String> {
+    let config = &auth.oauth2;
+
+    let mut params = vec![];
+
+    match config.grant_type {
+        OAuth2GrantType::CLIENT_CREDENTIALS => {
+            params.push(("grant_type".to_string(), "client_credentials".to_string()));
+        }
+
+        OAuth2GrantType::PASSWORD => {
+            params.push(("grant_type".to_string(), "password".to_string()));
+            params.push(("username".to_string(), auth.username.clone()));
+            params.push(("password".to_string(), auth.password.clone()));
+        }
+
+        OAuth2GrantType::AUTHORIZATION_CODE => {
+            let (code, verifier) = authorize(config, console, open).await?;
+
+            params.push(("grant_type".to_string(), "authorization_code".to_string()));
+            params.push(("code".to_string(), code));
+            params.push(("redirect_uri".to_string(), config.redirect_url.clone()));
+            params.push(("code_verifier".to_string(), verifier));
+        }
+    }
+
+    if !config.scope.is_empty() && config.grant_type != OAuth2GrantType::AUTHORIZATION_CODE {
+        params.push(("scope".to_string(), config.scope.clone()));
+    }
+
+    request_token(config, params, settings, console).await
+}
+
+// the client authenticates with basic auth or with its id and secret in the form
+fn token_request(
+    client: &reqwest::Client,
+    config: &OAuth2Config,
+    mut params: Vec<(String, String)>,
+) -> reqwest::RequestBuilder {
+    let mut builder = client.post(&config.token_url);
+
+    if config.client_auth_header {
+        builder = builder.basic_auth(&config.client_id, Some(&config.client_secret));
+    } else {
+        params.push(("client_id".to_string(), config.client_id.clone()));
+
+        if !config.client_secret.is_empty() {
+            params.push(("client_secret".to_string(), config.client_secret.clone()));
+        }
+    }
+
+    builder
+        .header(reqwest::header::ACCEPT, "application/json")
+        .form(&params)
+}
+
+async fn request_token(
+    config: &OAuth2Config,
+    params: Vec<(String, String)>,
+    settings: &HttpSettings,
+    console: &mut Vec<String>,
+) -> Result<OAuth2Token, String> {
+    if config.token_url.is_empty() {
+        return Err("OAuth2: token URL is empty".to_string());
+    }
+
+    // the token goes through the same proxy and TLS setup as the request, which already
+    // logged them
+    let client = crate::client::build_client(settings, &mut vec![])?;
+
+    let grant = params
+        .iter()
+        .find(|(key, _)| key == "grant_type")
+        .map(|(_, value)| value.clone())
+        .unwrap_or_default();
+
+    console.push(format!(
+        "OAuth2: POST {} (grant_type={})",
+        config.token_url, grant
+    ));
+
+    let start = crate::get_timestamp();
+
+    let response = token_request(&client, config, params)
+        .send()
+        .await
+        .map_err(|err| format!("OAuth2: token request failed: {err}"))?;
+
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+
+    console.push(format!(
+        "OAuth2: token endpoint answered {} in {} ms",
+        status.as_u16(),
+        crate::get_timestamp() - start
+    ));
+
+    if !status.is_success() {
+        console.push(format!("OAuth2: {}", body));
+
+        return Err(format!(
+            "OAuth2: token endpoint returned {}: {}",
+            status.as_u16(),
+            body
+        ));
+    }
+
+    parse_token(&body)
+}
+
+fn parse_token(body: &str) -> Result<OAuth2Token, String> {
+    let value: serde_json::Value = serde_json::from_str(body)
+        .map_err(|err| format!("OAuth2: invalid token response: {err}"))?;
+
+    let field = |name: &str| -> String {
+        value
+            .get(name)
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+
+    let access_token = field("access_token");
+
+    if access_token.is_empty() {
+        return Err(format!(
+            "OAuth2: token response has no access_token: {body}"
+        ));
+    }
+
+    let expires_in = match value.get("expires_in") {
+        Some(serde_json::Value::Number(n)) => n.as_u64().unwrap_or(0),
+        Some(serde_json::Value::String(s)) => s.parse::<u64>().unwrap_or(0),
+        _ => 0,
+    };
+
+    let expires_at = if expires_in == 0 {
+        0
+    } else {
+        crate::get_timestamp() as u64 + expires_in * 1000
+    };
+
+    Ok(OAuth2Token {
+        access_token,
+        token_type: field("token_type"),
+        refresh_token: field("refresh_token"),
+        expires_at,
+    })
+}
+
+fn random_string() -> String {
+    uuid::Uuid::new_v4().simple().to_string() + &uuid::Uuid::new_v4().simple().to_string()
+}
+
+pub fn pkce_challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+// opens the authorization page and waits for the redirect carrying the code
+async fn authorize(
+    config: &OAuth2Config,
+    console: &mut Vec<String>,
+    open: fn(&str) -> bool,
+) -> Result<(String, String), String> {
+    let verifier = random_string();
+    let state = random_string();
+
+    let mut auth_url = reqwest::Url::parse(&config.auth_url)
+        .map_err(|err| format!("OAuth2: invalid authorization URL: {err}"))?;
+
+    auth_url
+        .query_pairs_mut()
+        .append_pair("response_type", "code")
+        .append_pair("client_id", &config.client_id)
+        .append_pair("redirect_uri", &config.redirect_url)
+        .append_pair("state", &state)
+        .append_pair("code_challenge", &pkce_challenge(&verifier))
+        .append_pair("code_challenge_method", "S256");
+
+    if !config.scope.is_empty() {
+        auth_url
+            .query_pairs_mut()
+            .append_pair("scope", &config.scope);
+    }
+
+    let redirect_url = reqwest::Url::parse(&config.redirect_url)
+        .map_err(|err| format!("OAuth2: invalid redirect URL: {err}"))?;
+
+    let host = redirect_url.host_str().unwrap_or_default().to_string();
+
+    if host != "localhost" && host != "127.0.0.1" {
+        return Err(
+            "OAuth2: the redirect URL must point to localhost so Bolt can receive the code"
+                .to_string(),
+        );
+    }
+
+    let port = redirect_url.port_or_known_default().unwrap_or(80);
+
+    let listener = TcpListener::bind(("127.0.0.1", port))
+        .map_err(|err| format!("OAuth2: could not listen on redirect port {port}: {err}"))?;
+
+    console.push(format!("OAuth2: opening {}", auth_url));
+
+    if !open(auth_url.as_str()) {
+        console.push("OAuth2: could not open a browser, open the URL above manually".to_string());
+    }
+
+    let query = tokio::task::spawn_blocking(move || wait_for_redirect(listener))
+        .await
+        .map_err(|err| format!("OAuth2: {err}"))??;
+
+    let params: Vec<(String, String)> = url::form_urlencoded::parse(query.as_bytes())
+        .into_owned()
+        .collect();
+
+    let param = |name: &str| {
+        params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.clone())
+    };
+
+    if let Some(error) = param("error") {
+        return Err(format!("OAuth2: authorization failed: {error}"));
+    }
+
+    if param("state").as_deref() != Some(state.as_str()) {
+        return Err("OAuth2: state mismatch in authorization response".to_string());
+    }
+
+    match param("code") {
+        Some(code) => {
+            console.push("OAuth2: received authorization code".to_string());
+
+            Ok((code, verifier))
+        }
+
+        None => Err("OAuth2: authorization response has no code".to_string()),
+    }
+}
+
+fn wait_for_redirect(listener: TcpListener) -> Result<String, String> {
+    listener
+        .set_nonblocking(true)
+        .map_err(|err| format!("OAuth2: {err}"))?;
+
+    let deadline =
+        std::time::Instant::now() + std::time::Duration::from_secs(AUTHORIZATION_TIMEOUT);
+
+    loop {
+        match listener.accept() {
+            Ok((mut stream, _)) => {
+                let ready = stream.set_nonblocking(false).and_then(|_| {
+                    stream.set_read_timeout(Some(std::time::Duration::from_secs(
+                        REDIRECT_READ_TIMEOUT,
+                    )))
+                });
+
+                if ready.is_err() {
+                    continue;
+                }
+
+                let mut buf = [0; 4096];
+                let read = stream.read(&mut buf).unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..read]).to_string();
+
+                // GET /callback?code=...&state=... HTTP/1.1
+                let target = request.split_whitespace().nth(1).unwrap_or_default();
+
+                let query = match target.split_once('?') {
+                    Some((_, query)) => query.to_string(),
+                    None => continue,
+                };
+
+                let body = "Authorization complete, you can close this window and return to Bolt.";
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: text/plain\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+
+                let _ = stream.write_all(response.as_bytes());
+
+                return Ok(query);
+            }
+
+            Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => {
+                if std::time::Instant::now() > deadline {
+                    return Err(
+                        "OAuth2: timed out waiting for the authorization redirect".to_string()
+                    );
+                }
+
+                std::thread::sleep(std::time::Duration::from_millis(100));
+            }
+
+            Err(err) => return Err(format!("OAuth2: {err}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::engine::general_purpose;
+    use std::net::TcpStream;
+
+    fn oauth2_auth(port: u16, grant_type: OAuth2GrantType) -> HttpAuth {
+        let mut auth = HttpAuth::new();
+
+        auth.auth_type = HttpAuthType::OAUTH2;
+        auth.oauth2 = OAuth2Config {
+            grant_type,
+            token_url: format!("http://127.0.0.1:{port}/token"),
+            auth_url: format!("http://127.0.0.1:{port}/authorize"),
+            client_id: "bolt".to_string(),
+            client_secret: "secret".to_string(),
+            ..Default::default()
+        };
+
+        auth
+    }
+
+    fn free_port() -> u16 {
+        TcpListener::bind(("127.0.0.1", 0))
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port()
+    }
+
+    // stands in for the browser, the provider redirects straight back with the code
+    fn follow_redirects(url: &str) -> bool {
+        let url = url.to_string();
+
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+
+            runtime.block_on(reqwest::get(url)).unwrap();
+        });
+
+        true
+    }
+
+    fn no_browser(_url: &str) -> bool {
+        panic!("this grant must not open a browser");
+    }
+
+    async fn protected_status(port: u16, token: &str) -> u16 {
+        reqwest::Client::new()
+            .get(format!("http://127.0.0.1:{port}/protected"))
+            .bearer_auth(token)
+            .send()
+            .await
+            .unwrap()
+            .status()
+            .as_u16()
+    }
+
+    #[tokio::test]
+    async fn client_credentials_token_is_cached() {
+        let port = oauth_server::spawn();
+        let auth = oauth2_auth(port, OAuth2GrantType::CLIENT_CREDENTIALS);
+
+        let mut console = vec![];
+        let token = access_token(&auth, &HttpSettings::new(), &mut console, no_browser)
+            .await
+            .unwrap();
+
+        assert_eq!(protected_status(port, &token).await, 200);
+
+        let mut console = vec![];
+        let cached = access_token(&auth, &HttpSettings::new(), &mut console, no_browser)
+            .await
+            .unwrap();
+
+        assert_eq!(cached, token);
+        assert_eq!(console, vec!["OAuth2: using cached access token"]);
+    }
+
+    #[tokio::test]
+    async fn client_credentials_with_a_wrong_secret_fail() {
+        let port = oauth_server::spawn();
+        let mut auth = oauth2_auth(port, OAuth2GrantType::CLIENT_CREDENTIALS);
+        auth.oauth2.client_secret = "wrong".to_string();
+        auth.oauth2.client_auth_header = true;
+
+        let mut console = vec![];
+        let err = access_token(&auth, &HttpSettings::new(), &mut console, no_browser)
+            .await
+            .unwrap_err();
+
+        assert!(err.contains("400"), "{err}");
+        assert!(err.contains("invalid_client"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn expired_tokens_are_refreshed() {
+        let port = oauth_server::spawn();
+        let mut auth = oauth2_auth(port, OAuth2GrantType::PASSWORD);
+        auth.username = "user".to_string();
+        auth.password = "pass".to_string();
+
+        let mut console = vec![];
+        let first = access_token(&auth, &HttpSettings::new(), &mut console, no_browser)
+            .await
+            .unwrap();
+
+        let mut expired = get_cached_token(&cache_key(&auth)).unwrap();
+        expired.expires_at = 1;
+        set_cached_token(cache_key(&auth), expired);
+
+        let mut console = vec![];
+        let refreshed = access_token(&auth, &HttpSettings::new(), &mut console, no_browser)
+            .await
+            .unwrap();
+
+        assert_ne!(refreshed, first);
+        assert_eq!(console[0], "OAuth2: access token expired, refreshing");
+        assert!(console[1].contains("grant_type=refresh_token"));
+        assert_eq!(protected_status(port, &refreshed).await, 200);
+    }
+
+    #[tokio::test]
+    async fn a_rejected_refresh_falls_back_to_a_new_grant() {
+        let port = oauth_server::spawn();
+        let auth = oauth2_auth(port, OAuth2GrantType::CLIENT_CREDENTIALS);
+
+        set_cached_token(
+            cache_key(&auth),
+            OAuth2Token {
+                access_token: "stale".to_string(),
+                refresh_token: "unknown".to_string(),
+                expires_at: 1,
+                ..Default::default()
+            },
+        );
+
+        let mut console = vec![];
+        let token = access_token(&auth, &HttpSettings::new(), &mut console, no_browser)
+            .await
+            .unwrap();
+
+        assert_eq!(protected_status(port, &token).await, 200);
+        assert!(console.iter().any(|line| line.contains("refresh failed")));
+        assert!(console
+            .iter()
+            .any(|line| line.contains("grant_type=client_credentials")));
+    }
+
+    #[tokio::test]
+    async fn authorization_code_flow_with_pkce() {
+        let port = oauth_server::spawn();
+        let mut auth = oauth2_auth(port, OAuth2GrantType::AUTHORIZATION_CODE);
+        auth.oauth2.redirect_url = format!("http://127.0.0.1:{}/callback", free_port());
+
+        let mut console = vec![];
+        let token = access_token(&auth, &HttpSettings::new(), &mut console, follow_redirects)
+            .await
+            .unwrap();
+
+        assert_eq!(protected_status(port, &token).await, 200);
+        assert!(console
+            .iter()
+            .any(|line| line == "OAuth2: received authorization code"));
+        assert!(console
+            .iter()
+            .any(|line| line.contains("grant_type=authorization_code")));
+    }
+
+    #[test]
+    fn a_silent_client_does_not_block_the_redirect() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let _silent = TcpStream::connect(address).unwrap();
+
+        let mut redirect = TcpStream::connect(address).unwrap();
+        redirect
+            .write_all(b"GET /callback?code=abc&state=xyz HTTP/1.1\r\n\r\n")
+            .unwrap();
+
+        assert_eq!(wait_for_redirect(listener).unwrap(), "code=abc&state=xyz");
+    }
+
+    #[test]
+    fn parses_token_responses() {
+        let token = parse_token(
+            r#"{"access_token":"a","token_type":"Bearer","expires_in":"60","refresh_token":"r"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(token.access_token, "a");
+        assert_eq!(token.refresh_token, "r");
+        assert!(token.expires_at > crate::get_timestamp() as u64);
+
+        assert_eq!(
+            parse_token(r#"{"access_token":"a"}"#).unwrap().expires_at,
+            0
+        );
+        assert!(parse_token(r#"{"error":"invalid_grant"}"#).is_err());
+        assert!(parse_token("not json").is_err());
+    }
+
+    #[tokio::test]
+    async fn tokens_are_cached_per_client_and_scope() {
+        // nothing answers here, only the cache can give a token
+        let mut auth = oauth2_auth(free_port(), OAuth2GrantType::CLIENT_CREDENTIALS);
+        auth.oauth2.scope = "read".to_string();
+
+        set_cached_token(
+            cache_key(&auth),
+            OAuth2Token {
+                access_token: "cached".to_string(),
+                expires_at: crate::get_timestamp() as u64 + 60_000,
+                ..Default::default()
+            },
+        );
+
+        let settings = HttpSettings::new();
+
+        let mut console = vec![];
+        let token = access_token(&auth, &settings, &mut console, no_browser).await;
+        assert_eq!(token.unwrap(), "cached");
+
+        auth.oauth2.scope = "write".to_string();
+
+        let mut console = vec![];
+        let err = access_token(&auth, &settings, &mut console, no_browser)
+            .await
+            .unwrap_err();
+        assert!(err.starts_with("OAuth2: token request failed"), "{err}");
+    }
+
+    #[test]
+    fn tokens_expire_a_little_early() {
+        let token = |expires_at| OAuth2Token {
+            expires_at,
+            ..Default::default()
+        };
+
+        // no expires_in, the token is used until the server rejects it
+        assert!(!token(0).is_expired(u64::MAX / 2));
+
+        assert!(!token(100_000).is_expired(89_999));
+        assert!(token(100_000).is_expired(90_000));
+        assert!(token(1).is_expired(0));
+    }
+
+    #[test]
+    fn token_requests_send_a_form() {
+        let client = reqwest::Client::new();
+        let mut auth = oauth2_auth(8080, OAuth2GrantType::PASSWORD);
+        auth.oauth2.client_secret = "s&cret".to_string();
+
+        let params = vec![
+            ("grant_type".to_string(), "password".to_string()),
+            ("username".to_string(), "a user".to_string()),
+            ("scope".to_string(), "read write".to_string()),
+        ];
+
+        let request = token_request(&client, &auth.oauth2, params.clone())
+            .build()
+            .unwrap();
+        let body = request.body().and_then(|body| body.as_bytes()).unwrap();
+
+        assert_eq!(request.url().as_str(), "http://127.0.0.1:8080/token");
+        assert_eq!(
+            request.headers()["content-type"],
+            "application/x-www-form-urlencoded"
+        );
+        assert_eq!(request.headers()["accept"], "application/json");
+        assert_eq!(
+            std::str::from_utf8(body).unwrap(),
+            "grant_type=password&username=a+user&scope=read+write&client_id=bolt&client_secret=s%26cret"
+        );
+
+        // with basic auth the client does not repeat itself in the form
+        auth.oauth2.client_auth_header = true;
+
+        let request = token_request(&client, &auth.oauth2, params)
+            .build()
+            .unwrap();
+        let body = request.body().and_then(|body| body.as_bytes()).unwrap();
+
+        assert_eq!(
+            request.headers()["authorization"],
+            format!("Basic {}", general_purpose::STANDARD.encode("bolt:s&cret"))
+        );
+        assert_eq!(
+            std::str::from_utf8(body).unwrap(),
+            "grant_type=password&username=a+user&scope=read+write"
+        );
+    }
+
+    #[tokio::test]
+    async fn token_requests_use_the_client_settings() {
+        let port = oauth_server::spawn();
+        let auth = oauth2_auth(port, OAuth2GrantType::CLIENT_CREDENTIALS);
+
+        // the provider runs but the proxy in front of it does not
+        let mut settings = HttpSettings::new();
+        settings.proxy = format!("http://127.0.0.1:{}", free_port());
+
+        let mut console = vec![];
+        let err = access_token(&auth, &settings, &mut console, no_browser)
+            .await
+            .unwrap_err();
+
+        assert!(err.starts_with("OAuth2: token request failed"), "{err}");
+
+        settings.proxy = "not a proxy".to_string();
+
+        let err = access_token(&auth, &settings, &mut console, no_browser)
+            .await
+            .unwrap_err();
+
+        assert!(err.starts_with("Invalid proxy"), "{err}");
+    }
+}
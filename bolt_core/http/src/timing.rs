@@ -0,0 +1,123 @@
+use bolt_common::prelude::*;
+use hyper::client::connect::dns::Name;
+use reqwest::dns::{Addrs, Resolve, Resolving};
+use std::net::ToSocketAddrs;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+// reqwest 0.11 takes a resolver but not a connector, so the lookup is timed while connect
+// and the TLS handshake stay inside the time to first byte
+
+// resolves like the default resolver and adds up the time spent, in microseconds
+#[derive(Clone, Default)]
+pub struct DnsTimer {
+    spent: Arc<AtomicU64>,
+}
+
+impl DnsTimer {
+    pub fn micros(&self) -> u64 {
+        self.spent.load(Ordering::Relaxed)
+    }
+}
+
+impl Resolve for DnsTimer {
+    fn resolve(&self, name: Name) -> Resolving {
+        let spent = self.spent.clone();
+
+        Box::pin(async move {
+            let start = Instant::now();
+
+            let host = name.as_str().to_string();
+            let addrs =
+                tokio::task::spawn_blocking(move || (host.as_str(), 0).to_socket_addrs()).await??;
+
+            spent.fetch_add(start.elapsed().as_micros() as u64, Ordering::Relaxed);
+
+            Ok(Box::new(addrs) as Addrs)
+        })
+    }
+}
+
+// connect and tls are left as not measured
+pub fn phase_timing(dns: u64, headers: Duration, total: Duration) -> HttpTiming {
+    HttpTiming {
+        dns,
+        connect: None,
+        tls: None,
+        ttfb: (headers.as_micros() as u64).saturating_sub(dns),
+        download: total.saturating_sub(headers).as_micros() as u64,
+        total: total.as_micros() as u64,
+    }
+}
+
+pub fn connection_info(resp: &reqwest::Response) -> HttpConnectionInfo {
+    let mut info = HttpConnectionInfo {
+        http_version: format!("{:?}", resp.version()),
+        remote_addr: resp
+            .remote_addr()
+            .map(|addr| addr.to_string())
+            .unwrap_or_default(),
+        ..Default::default()
+    };
+
+    let der = resp
+        .extensions()
+        .get::<reqwest::tls::TlsInfo>()
+        .and_then(|tls| tls.peer_certificate());
+
+    if let Some(der) = der {
+        if let Ok((_, cert)) = x509_parser::parse_x509_certificate(der) {
+            info.tls_subject = cert.subject().to_string();
+            info.tls_issuer = cert.issuer().to_string();
+            info.tls_valid_from = cert.validity().not_before.to_string();
+            info.tls_valid_until = cert.validity().not_after.to_string();
+        }
+    }
+
+    info
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_lookup_is_taken_out_of_the_time_to_first_byte() {
+        let timing = phase_timing(1_000, Duration::from_millis(5), Duration::from_millis(7));
+
+        assert_eq!(timing.dns, 1_000);
+        assert_eq!((timing.connect, timing.tls), (None, None));
+        assert_eq!(
+            (timing.ttfb, timing.download, timing.total),
+            (4_000, 2_000, 7_000)
+        );
+    }
+
+    #[tokio::test]
+    async fn host_lookups_are_timed() {
+        let port = oauth_server::spawn();
+
+        let dns = DnsTimer::default();
+        let client =
+            crate::client::build_timed_client(&HttpSettings::new(), &mut vec![], &dns).unwrap();
+
+        client
+            .get(format!("http://localhost:{port}/protected"))
+            .send()
+            .await
+            .unwrap();
+
+        let looked_up = dns.micros();
+        assert!(looked_up > 0);
+
+        // an address needs no lookup
+        client
+            .get(format!("http://127.0.0.1:{port}/protected"))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(dns.micros(), looked_up);
+    }
+}
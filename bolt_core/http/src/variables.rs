@@ -0,0 +1,208 @@
+use bolt_common::prelude::*;
+
+use crate::CORE_STATE;
+
+// an extracted value the client has not saved yet. Its saves can be older than the
+// response, so the value is put back into them until one of them has it
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredVariable {
+    env: usize,
+    env_name: String,
+    name: String,
+    value: String,
+}
+
+// written to the active environment so the next send sees the values right away
+pub fn store_variables(extracted: &[Vec<String>], console: &mut Vec<String>) {
+    if extracted.is_empty() {
+        return;
+    }
+
+    let mut core_state = CORE_STATE.lock().unwrap();
+    let core_state = &mut *core_state;
+
+    let env = match core_state.main_state.env_active {
+        Some(env) if env < core_state.main_state.environments.len() => env,
+
+        _ => {
+            let names: Vec<&str> = extracted.iter().map(|var| var[0].as_str()).collect();
+
+            console.push(format!(
+                "Warning: no active environment, {} not stored",
+                names.join(", ")
+            ));
+
+            return;
+        }
+    };
+
+    let environment = &mut core_state.main_state.environments[env];
+
+    for var in extracted {
+        environment.set(&var[0], &var[1]);
+
+        let stored = StoredVariable {
+            env,
+            env_name: environment.name.clone(),
+            name: var[0].clone(),
+            value: var[1].clone(),
+        };
+
+        core_state
+            .variables
+            .retain(|other| other.env != env || other.name != stored.name);
+
+        core_state.variables.push(stored);
+    }
+}
+
+// values of the active environment, compared before and after a send to tell the client
+// what changed
+pub fn active_variables() -> Option<(usize, Vec<Vec<String>>)> {
+    let core_state = CORE_STATE.lock().unwrap();
+
+    let env = core_state.main_state.env_active?;
+    let environment = core_state.main_state.environments.get(env)?;
+
+    Some((env, environment.variables.clone()))
+}
+
+// the [variable, value] pairs of `after` that are new or differ from `before`
+pub fn changed_variables(before: &[Vec<String>], after: &[Vec<String>]) -> Vec<Vec<String>> {
+    after
+        .iter()
+        .filter(|var| var.len() > 1 && !var[0].is_empty())
+        .filter(|var| !before.contains(var))
+        .cloned()
+        .collect()
+}
+
+// puts the stored values into a client save, the ones it already has are done
+pub fn sync_variables(state: &mut MainState) {
+    let mut core_state = CORE_STATE.lock().unwrap();
+
+    core_state.variables.retain(|stored| {
+        let environment = match state.environments.get_mut(stored.env) {
+            Some(environment) if environment.name == stored.env_name => environment,
+
+            // the environment was removed or moved, the value goes with it
+            _ => return false,
+        };
+
+        if environment.get(&stored.name) == Some(&stored.value) {
+            return false;
+        }
+
+        environment.set(&stored.name, &stored.value);
+
+        true
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::set_main_state;
+
+    // CORE_STATE is shared by every test in the crate
+    static LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+    fn state(active: Option<usize>) -> MainState {
+        let mut state = MainState::new();
+
+        let mut env = Environment::new();
+        env.name = "dev".to_string();
+
+        state.environments = vec![env];
+        state.env_active = active;
+
+        state
+    }
+
+    fn pair(name: &str, value: &str) -> Vec<String> {
+        vec![name.to_string(), value.to_string()]
+    }
+
+    fn reset(active: Option<usize>) {
+        set_main_state(state(active));
+
+        CORE_STATE.lock().unwrap().variables.clear();
+    }
+
+    #[test]
+    fn stale_saves_keep_stored_values_until_one_has_them() {
+        let _lock = LOCK.lock().unwrap();
+        reset(Some(0));
+
+        let mut console = vec![];
+        store_variables(&[pair("token", "abc")], &mut console);
+
+        assert!(console.is_empty());
+        assert_eq!(active_variables(), Some((0, vec![pair("token", "abc")])));
+
+        // a save sent before the client got the response
+        let mut stale = state(Some(0));
+        sync_variables(&mut stale);
+
+        assert_eq!(stale.environments[0].get("token"), Some(&"abc".to_string()));
+        assert_eq!(CORE_STATE.lock().unwrap().variables.len(), 1);
+
+        // the client caught up, later edits are its own
+        let mut current = state(Some(0));
+        current.environments[0].set("token", "abc");
+        sync_variables(&mut current);
+
+        assert!(CORE_STATE.lock().unwrap().variables.is_empty());
+
+        let mut edited = state(Some(0));
+        edited.environments[0].set("token", "edited");
+        sync_variables(&mut edited);
+
+        assert_eq!(
+            edited.environments[0].get("token"),
+            Some(&"edited".to_string())
+        );
+    }
+
+    #[test]
+    fn removed_environments_drop_their_values() {
+        let _lock = LOCK.lock().unwrap();
+        reset(Some(0));
+
+        store_variables(&[pair("token", "abc")], &mut vec![]);
+
+        let mut renamed = state(Some(0));
+        renamed.environments[0].name = "prod".to_string();
+        sync_variables(&mut renamed);
+
+        assert_eq!(renamed.environments[0].get("token"), None);
+        assert!(CORE_STATE.lock().unwrap().variables.is_empty());
+    }
+
+    #[test]
+    fn without_an_environment_values_are_not_stored() {
+        let _lock = LOCK.lock().unwrap();
+        reset(None);
+
+        let mut console = vec![];
+        store_variables(&[pair("token", "abc"), pair("id", "1")], &mut console);
+
+        assert_eq!(
+            console,
+            vec!["Warning: no active environment, token, id not stored".to_string()]
+        );
+        assert!(CORE_STATE.lock().unwrap().variables.is_empty());
+        assert_eq!(active_variables(), None);
+    }
+
+    #[test]
+    fn changed_variables_are_the_new_and_updated_pairs() {
+        let before = vec![pair("a", "1"), pair("b", "2"), pair("", "")];
+        let after = vec![pair("a", "1"), pair("b", "3"), pair("c", "4"), pair("", "")];
+
+        assert_eq!(
+            changed_variables(&before, &after),
+            vec![pair("b", "3"), pair("c", "4")]
+        );
+    }
+}
@@ -0,0 +1,514 @@
+mod utils;
+
+use bolt_common::prelude::*;
+use std::future::Future;
+use std::sync::mpsc::{Receiver, RecvTimeoutError, Sender, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+use tokio::runtime::Runtime;
+use tungstenite::WebSocket;
+
+const SSE_SERVICE_REFRESH_RATE: u64 = 500;
+const SERVICE_SYNC_REFRESH_RATE: u64 = 1000;
+// how long a read waits before checking if the connection was closed
+const STREAM_POLL_RATE: u64 = 400;
+
+lazy_static::lazy_static! {
+ static ref CORE_STATE: Arc<Mutex<CoreState>> = Arc::new(Mutex::new(CoreState::new()));
+}
+
+#[derive(Clone)]
+struct SseService {
+    connection_id: String,
+}
+
+struct CoreState {
+    main_state: MainState,
+    session_websocket: Option<WebSocket<std::net::TcpStream>>,
+    sse_services: Vec<SseService>,
+}
+
+impl CoreState {
+    fn new() -> Self {
+        Self {
+            main_state: MainState::new(),
+            session_websocket: None,
+            sse_services: vec![],
+        }
+    }
+}
+
+// what the stream thread needs to (re)connect, resolved when Connect was pressed
+struct StreamRequest {
+    url: String,
+    headers: Vec<(String, String)>,
+    last_event_id: String,
+    retry: u64,
+    settings: HttpSettings,
+}
+
+pub fn set_session_websocket(new_ws: WebSocket<std::net::TcpStream>) {
+    let mut core_state = CORE_STATE.lock().unwrap();
+    core_state.session_websocket = Some(new_ws);
+}
+
+pub fn set_main_state(client_state: MainState) {
+    let mut core_state = CORE_STATE.lock().unwrap();
+    core_state.main_state = client_state;
+}
+
+fn resolve_variables(input: &str) -> String {
+    let core_state = CORE_STATE.lock().unwrap();
+
+    core_state.main_state.resolve_variables(input)
+}
+
+fn session_write(txt: String) {
+    let mut core_state = CORE_STATE.lock().unwrap();
+
+    core_state
+        .session_websocket
+        .as_mut()
+        .unwrap()
+        .write_message(tungstenite::Message::Text(txt))
+        .unwrap();
+}
+
+// the workspace client settings, the stream stays open so only connecting can time out
+fn stream_settings() -> HttpSettings {
+    let core_state = CORE_STATE.lock().unwrap();
+
+    let mut settings = core_state.main_state.http_settings.clone();
+
+    if let Some(env) = core_state.main_state.active_environment() {
+        settings.resolve_variables(env);
+    }
+
+    settings.timeout = 0;
+
+    settings
+}
+
+pub fn start_core_sse_service(_session_id: String) {
+    std::thread::spawn(|| loop {
+        let mut core_state = CORE_STATE.lock().unwrap();
+
+        let connections = core_state.main_state.sse_connections.clone();
+        let sse_services = core_state.sse_services.clone();
+
+        for sse_con in connections.clone() {
+            let exists = sse_services
+                .iter()
+                .any(|x| x.connection_id == sse_con.connection_id);
+
+            if !exists {
+                spawn_sse_service(sse_con.connection_id.clone());
+
+                core_state.sse_services.push(SseService {
+                    connection_id: sse_con.connection_id,
+                });
+            }
+        }
+
+        drop(core_state);
+        std::thread::sleep(Duration::from_millis(SERVICE_SYNC_REFRESH_RATE));
+    });
+}
+
+pub fn spawn_sse_service(connection_id: String) {
+    let _handle = std::thread::Builder::new()
+        .name(connection_id.clone())
+        .spawn(move || {
+            // dropping the sender stops the stream thread too
+            let mut stream: Option<(Sender<()>, JoinHandle<()>)> = None;
+
+            loop {
+                let mut core_state = CORE_STATE.lock().unwrap();
+                let sse_connections = core_state.main_state.sse_connections.clone();
+
+                let Some(sse_con) = sse_connections
+                    .iter()
+                    .find(|con| con.connection_id == connection_id)
+                else {
+                    core_state
+                        .sse_services
+                        .retain(|service| service.connection_id != connection_id);
+
+                    return;
+                };
+
+                drop(core_state);
+
+                let running = stream
+                    .as_ref()
+                    .is_some_and(|(_, handle)| !handle.is_finished());
+
+                if sse_con.disconnecting {
+                    if let Some((kill, _)) = stream.take() {
+                        let _ = kill.send(());
+                    }
+
+                    let disconnected_msg = SseDisconnectedMsg {
+                        msg_type: MsgType::SSE_DISCONNECTED,
+                        connection_id: connection_id.clone(),
+                    };
+
+                    session_write(serde_json::to_string(&disconnected_msg).unwrap());
+                } else if sse_con.connecting && !sse_con.connected && !running {
+                    let headers = sse_con
+                        .out_headers
+                        .iter()
+                        .filter(|header| !header[0].trim().is_empty())
+                        .map(|header| {
+                            (
+                                resolve_variables(header[0].trim()),
+                                resolve_variables(&header[1]),
+                            )
+                        })
+                        .collect();
+
+                    let request = StreamRequest {
+                        url: resolve_variables(sse_con.url.trim()),
+                        headers,
+                        last_event_id: resolve_variables(&sse_con.last_event_id),
+                        retry: sse_con.retry,
+                        settings: stream_settings(),
+                    };
+
+                    let (kill, receiver) = std::sync::mpsc::channel();
+                    let handle = spawn_stream_service(connection_id.clone(), request, receiver);
+
+                    stream = Some((kill, handle));
+
+                    let mut core_state = CORE_STATE.lock().unwrap();
+
+                    for sse_con in core_state
+                        .main_state
+                        .sse_connections
+                        .iter_mut()
+                        .filter(|con| con.connection_id == connection_id)
+                    {
+                        sse_con.connecting = false;
+                    }
+                }
+
+                std::thread::sleep(Duration::from_millis(SSE_SERVICE_REFRESH_RATE));
+            }
+        })
+        .unwrap();
+}
+
+fn spawn_stream_service(
+    connection_id: String,
+    request: StreamRequest,
+    kill: Receiver<()>,
+) -> JoinHandle<()> {
+    std::thread::Builder::new()
+        .name(connection_id.clone())
+        .spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+
+            // the shared builder brings the proxy, TLS settings and cookie jar of http requests
+            let client = match bolt_http::client::build_client(&request.settings, &mut vec![]) {
+                Ok(client) => client,
+                Err(err) => {
+                    send_connection_failed(&connection_id, err);
+
+                    return;
+                }
+            };
+
+            let mut parser = SseParser::new(&request.last_event_id);
+            let mut connected_once = false;
+
+            loop {
+                let mut builder = client
+                    .get(&request.url)
+                    .header("Accept", "text/event-stream")
+                    .header("Cache-Control", "no-cache");
+
+                for (key, value) in &request.headers {
+                    builder = builder.header(key, value);
+                }
+
+                if !parser.last_event_id.is_empty() {
+                    builder = builder.header("Last-Event-ID", &parser.last_event_id);
+                }
+
+                let Some(result) = wait(&runtime, builder.send(), &kill) else {
+                    return;
+                };
+
+                let reason = match result {
+                    // the first connection fails right away, EventSource would retry forever
+                    Err(err) if !connected_once => {
+                        send_connection_failed(&connection_id, err.to_string());
+
+                        return;
+                    }
+
+                    Err(err) => err.to_string(),
+
+                    Ok(response) => {
+                        if let Err(reason) = check_response(&response) {
+                            send_connection_failed(&connection_id, reason);
+
+                            return;
+                        }
+
+                        connected_once = true;
+                        parser.reset();
+
+                        let connected_msg = SseConnectedMsg {
+                            msg_type: MsgType::SSE_CONNECTED,
+                            connection_id: connection_id.clone(),
+                        };
+
+                        session_write(serde_json::to_string(&connected_msg).unwrap());
+
+                        match read_stream(&runtime, response, &mut parser, &kill, &connection_id) {
+                            Some(reason) => reason,
+                            None => return,
+                        }
+                    }
+                };
+
+                let retry = parser.retry.unwrap_or(request.retry);
+
+                let reconnecting_msg = SseReconnectingMsg {
+                    msg_type: MsgType::SSE_RECONNECTING,
+                    connection_id: connection_id.clone(),
+                    reason,
+                    retry,
+                };
+
+                session_write(serde_json::to_string(&reconnecting_msg).unwrap());
+
+                match kill.recv_timeout(Duration::from_millis(retry)) {
+                    Err(RecvTimeoutError::Timeout) => {}
+                    _ => return,
+                }
+            }
+        })
+        .unwrap()
+}
+
+// the reasons the spec gives for failing a connection instead of reconnecting
+fn check_response(response: &reqwest::Response) -> Result<(), String> {
+    let status = response.status();
+
+    if status == reqwest::StatusCode::NO_CONTENT {
+        return Err("The server answered 204 No Content, asking the client to stop".to_string());
+    }
+
+    if status != reqwest::StatusCode::OK {
+        return Err(format!("The server answered {status}"));
+    }
+
+    let content_type = response
+        .headers()
+        .get("content-type")
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+
+    if !content_type.starts_with("text/event-stream") {
+        return Err(format!(
+            "Expected a text/event-stream response but got {:?}",
+            content_type
+        ));
+    }
+
+    Ok(())
+}
+
+// returns why the stream ended, or None when the connection was closed from here
+fn read_stream(
+    runtime: &Runtime,
+    mut response: reqwest::Response,
+    parser: &mut SseParser,
+    kill: &Receiver<()>,
+    connection_id: &str,
+) -> Option<String> {
+    loop {
+        match wait(runtime, response.chunk(), kill)? {
+            Ok(Some(bytes)) => {
+                for mut event in parser.feed(&bytes) {
+                    event.timestamp = utils::get_timestamp();
+
+                    let received_msg = SseReceivedMsg {
+                        msg_type: MsgType::SSE_RECEIVED_MSG,
+                        connection_id: connection_id.to_string(),
+                        msg: event,
+                    };
+
+                    session_write(serde_json::to_string(&received_msg).unwrap());
+                }
+            }
+
+            Ok(None) => return Some("The server closed the stream".to_string()),
+
+            Err(err) => return Some(err.to_string()),
+        }
+    }
+}
+
+// drives a request future in short steps so a disconnect does not wait for the server
+fn wait<F: Future>(runtime: &Runtime, future: F, kill: &Receiver<()>) -> Option<F::Output> {
+    let mut future = Box::pin(future);
+
+    loop {
+        let step = runtime.block_on(async {
+            tokio::time::timeout(Duration::from_millis(STREAM_POLL_RATE), &mut future).await
+        });
+
+        if let Ok(output) = step {
+            return Some(output);
+        }
+
+        match kill.try_recv() {
+            Err(TryRecvError::Empty) => {}
+            _ => return None,
+        }
+    }
+}
+
+fn send_connection_failed(connection_id: &str, reason: String) {
+    let failed_msg = SseConnectionFailedMsg {
+        msg_type: MsgType::SSE_CONNECTION_FAILED,
+        connection_id: connection_id.to_string(),
+        reason,
+    };
+
+    session_write(serde_json::to_string(&failed_msg).unwrap());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use std::net::{TcpListener, TcpStream};
+
+    // the session websocket is shared, tests that read it take turns
+    static SESSION: Mutex<()> = Mutex::new(());
+
+    // the client end of the session websocket, which gets what the core writes
+    fn session() -> WebSocket<TcpStream> {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+
+            set_session_websocket(tungstenite::accept(stream).unwrap());
+        });
+
+        let stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(10)))
+            .unwrap();
+
+        let (client, _) = tungstenite::client(format!("ws://127.0.0.1:{port}"), stream).unwrap();
+        server.join().unwrap();
+
+        client
+    }
+
+    #[test]
+    fn reconnects_resume_after_the_last_event_id() {
+        let _session = SESSION.lock().unwrap_or_else(|err| err.into_inner());
+
+        let port = sse_server::spawn();
+        let mut client = session();
+
+        let request = StreamRequest {
+            url: format!("http://127.0.0.1:{port}/events"),
+            headers: vec![],
+            last_event_id: "1".to_string(),
+            retry: SSE_DEFAULT_RETRY,
+            settings: HttpSettings::new(),
+        };
+
+        let (_kill, receiver) = std::sync::mpsc::channel();
+        let handle = spawn_stream_service("sse-test".to_string(), request, receiver);
+
+        let mut events = vec![];
+        let mut retries = vec![];
+        let mut connected = 0;
+
+        // the server answers 204 once all events were sent
+        let reason = loop {
+            let msg = client.read_message().unwrap().into_text().unwrap();
+            let msg: Value = serde_json::from_str(&msg).unwrap();
+
+            match msg["msg_type"].as_str().unwrap() {
+                "SSE_CONNECTED" => connected += 1,
+                "SSE_RECEIVED_MSG" => events.push((
+                    msg["msg"]["id"].as_str().unwrap().to_string(),
+                    msg["msg"]["data"].as_str().unwrap().to_string(),
+                )),
+                "SSE_RECONNECTING" => retries.push(msg["retry"].as_u64().unwrap()),
+                "SSE_CONNECTION_FAILED" => break msg["reason"].as_str().unwrap().to_string(),
+                other => panic!("unexpected {other}"),
+            }
+        };
+
+        handle.join().unwrap();
+
+        let event = |id: u64, after: &str| (id.to_string(), format!("event {id}\n{after}"));
+
+        assert_eq!(
+            events,
+            vec![
+                event(2, "after 1"),
+                event(3, "after 1"),
+                event(4, "after 3"),
+                event(5, "after 3"),
+                event(6, "after 5"),
+            ]
+        );
+        assert_eq!(connected, 3);
+        assert_eq!(retries, vec![sse_server::RETRY; 3]);
+        assert_eq!(
+            reason,
+            "The server answered 204 No Content, asking the client to stop"
+        );
+    }
+
+    #[test]
+    fn streams_use_the_client_settings() {
+        let _session = SESSION.lock().unwrap_or_else(|err| err.into_inner());
+
+        let port = sse_server::spawn();
+        let mut client = session();
+
+        // nothing listens on the proxy port, so the running server is never reached
+        let closed = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let proxy = format!("http://127.0.0.1:{}", closed.local_addr().unwrap().port());
+        drop(closed);
+
+        let mut settings = HttpSettings::new();
+        settings.proxy = proxy;
+
+        let request = StreamRequest {
+            url: format!("http://127.0.0.1:{port}/events"),
+            headers: vec![],
+            last_event_id: String::new(),
+            retry: SSE_DEFAULT_RETRY,
+            settings,
+        };
+
+        let (_kill, receiver) = std::sync::mpsc::channel();
+        spawn_stream_service("sse-proxy-test".to_string(), request, receiver)
+            .join()
+            .unwrap();
+
+        let msg = client.read_message().unwrap().into_text().unwrap();
+        let msg: Value = serde_json::from_str(&msg).unwrap();
+
+        assert_eq!(msg["msg_type"], "SSE_CONNECTION_FAILED");
+    }
+}
@@ -46,6 +46,12 @@ pub fn set_main_state(client_state: MainState) {
     core_state.main_state = client_state;
 }
 
+fn resolve_variables(input: &str) -> String {
+    let core_state = CORE_STATE.lock().unwrap();
+
+    core_state.main_state.resolve_variables(input)
+}
+
 pub fn start_core_tcp_service(_session_id: String) {
     std::thread::spawn(|| {
         // comment
@@ -162,8 +168,10 @@ pub fn spawn_tcp_service(connection_id: String) {
                 } else if connecting && !connected {
                     // println!("TCP {} CONNECTING", connection_id);
 
+                    let peer_address = resolve_variables(&tcp_con.peer_address);
+
                     let (connected_succeded, mut new_stream) =
-                        open_tcp_connection(&tcp_con.peer_address, tcp_con.connection_id.clone());
+                        open_tcp_connection(&peer_address, tcp_con.connection_id.clone());
 
                     if connected_succeded {
                         new_stream
@@ -1,13 +1,23 @@
 mod utils;
 
 use bolt_common::prelude::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
 use std::sync::{Arc, Mutex};
+use tungstenite::client::IntoClientRequest;
 use tungstenite::stream::MaybeTlsStream;
 use tungstenite::{connect, WebSocket};
 use url::Url;
 
 const WS_SERVICE_REFRESH_RATE: u64 = 500;
 const SERVICE_SYNC_REFRESH_RATE: u64 = 1000;
+// how long the read service holds the socket waiting for a message, and how long it then
+// leaves it to the service thread
+const WS_READ_TIMEOUT: u64 = 100;
+const WS_READ_PAUSE: u64 = 5;
+
+// shared by the service thread, which writes, and the read service
+type Socket = Arc<Mutex<WebSocket<MaybeTlsStream<std::net::TcpStream>>>>;
 
 lazy_static::lazy_static! {
  static ref CORE_STATE: Arc<Mutex<CoreState>> = Arc::new(Mutex::new(CoreState::new()));
@@ -44,6 +54,25 @@ pub fn set_main_state(client_state: MainState) {
     core_state.main_state = client_state;
 }
 
+fn resolve_variables(input: &str) -> String {
+    let core_state = CORE_STATE.lock().unwrap();
+
+    core_state.main_state.resolve_variables(input)
+}
+
+// cookies from the shared jar are sent with the handshake like any other http request
+fn handshake_cookies(url: &Url) -> Option<String> {
+    let core_state = CORE_STATE.lock().unwrap();
+
+    cookie_header(
+        &core_state.main_state.cookies,
+        url.host_str().unwrap_or_default(),
+        url.path(),
+        url.scheme() == "wss",
+        utils::get_timestamp(),
+    )
+}
+
 pub fn start_core_ws_service(_session_id: String) {
     std::thread::spawn(|| {
         // comment
@@ -80,7 +109,13 @@ pub fn spawn_ws_service(connection_id: String) {
         .spawn(move || {
             // comment
 
-            let mut socket: Option<WebSocket<MaybeTlsStream<std::net::TcpStream>>> = None;
+            let mut socket: Option<Socket> = None;
+
+            // frames the read service answers with, only this thread writes to the socket
+            let mut replies: Option<Receiver<String>> = None;
+
+            // GraphQL servers close connections that subscribe before connection_ack
+            let acked = Arc::new(AtomicBool::new(false));
 
             loop {
                 let mut core_state = CORE_STATE.lock().unwrap();
@@ -126,8 +161,24 @@ pub fn spawn_ws_service(connection_id: String) {
                 let disconnecting = ws_con.disconnecting;
                 let connected = ws_con.connected;
 
+                if let (Some(socket), Some(replies)) = (&socket, &replies) {
+                    for reply in replies.try_iter() {
+                        let reply = tungstenite::Message::Text(reply);
+
+                        if let Err(err) = socket.lock().unwrap().write_message(reply) {
+                            println!("WS: could not answer ping: {}", err);
+                        }
+                    }
+                }
+
                 if disconnecting {
-                    socket.as_mut().unwrap().close(None).unwrap();
+                    socket
+                        .as_ref()
+                        .unwrap()
+                        .lock()
+                        .unwrap()
+                        .close(None)
+                        .unwrap();
 
                     let disconnected_msg = WsDisconnectedMsg {
                         msg_type: MsgType::WS_DISCONNECTED,
@@ -145,8 +196,28 @@ pub fn spawn_ws_service(connection_id: String) {
                         .write_message(msg)
                         .unwrap();
                 } else if connecting && !connected {
-                    let (connected_succeded, w_socket, _response) =
-                        open_ws_connection(&ws_con.url, ws_con.connection_id.clone());
+                    let url = resolve_variables(&ws_con.url);
+
+                    let graphql = match ws_con.mode {
+                        WsMode::GRAPHQL => Some(ws_con.graphql_protocol),
+                        WsMode::RAW => None,
+                    };
+
+                    // checked before connecting so a bad payload does not leave a half open socket
+                    let init = match graphql {
+                        Some(_) => graphql_ws_init(&resolve_variables(&ws_con.graphql_init)),
+                        None => Ok(String::new()),
+                    };
+
+                    let (connected_succeded, w_socket, _response) = match &init {
+                        Ok(_) => open_ws_connection(&url, ws_con.connection_id.clone(), graphql),
+
+                        Err(err) => {
+                            send_connection_failed(&ws_con.connection_id, err.clone());
+
+                            (false, None, None)
+                        }
+                    };
 
                     if !connected_succeded {
                         let mut core_state = CORE_STATE.lock().unwrap();
@@ -164,9 +235,19 @@ pub fn spawn_ws_service(connection_id: String) {
                         continue;
                     }
 
-                    let w_socket = w_socket.unwrap();
+                    let mut w_socket = w_socket.unwrap();
                     let _response = _response.unwrap();
 
+                    // the server answers with connection_ack, which the read service shows and
+                    // which lets the queued subscriptions go out
+                    if graphql.is_some() {
+                        let init = tungstenite::Message::Text(init.unwrap());
+
+                        if let Err(err) = w_socket.write_message(init) {
+                            println!("WS: could not send connection_init: {}", err);
+                        }
+                    }
+
                     let connected_msg = WsConnectedMsg {
                         msg_type: MsgType::WS_CONNECTED,
                         connection_id: ws_con.connection_id.clone(),
@@ -183,9 +264,23 @@ pub fn spawn_ws_service(connection_id: String) {
                         .write_message(msg)
                         .unwrap();
 
-                    socket = Some(w_socket);
+                    if let Err(err) = set_read_timeout(&w_socket) {
+                        println!("WS: could not set the read timeout: {}", err);
+                    }
+
+                    socket = Some(Arc::new(Mutex::new(w_socket)));
+
+                    let (reply_sender, reply_receiver) = channel();
+                    replies = Some(reply_receiver);
+                    acked.store(false, Ordering::SeqCst);
 
-                    spawn_read_service(socket.as_mut().unwrap(), connection_id.clone());
+                    spawn_read_service(
+                        socket.clone().unwrap(),
+                        connection_id.clone(),
+                        graphql,
+                        reply_sender,
+                        acked.clone(),
+                    );
 
                     for (_index, ws_con) in core_state
                         .main_state
@@ -197,17 +292,40 @@ pub fn spawn_ws_service(connection_id: String) {
                         ws_con.connecting = false;
                     }
                 } else if connected {
-                    for out_msg in ws_con.out_queue.clone() {
-                        let txt = serde_json::to_string(&out_msg.txt).unwrap();
+                    let ready = match ws_con.mode {
+                        WsMode::GRAPHQL => acked.load(Ordering::SeqCst),
+                        WsMode::RAW => true,
+                    };
+
+                    // held in the queue until the server is ready for them
+                    let out_queue = if ready {
+                        ws_con.out_queue.clone()
+                    } else {
+                        vec![]
+                    };
+
+                    for out_msg in out_queue {
+                        // GraphQL frames are built by the client and go out as they are
+                        let txt = match ws_con.mode {
+                            WsMode::GRAPHQL => out_msg.txt.clone(),
+                            WsMode::RAW => serde_json::to_string(&out_msg.txt).unwrap(),
+                        };
                         let msg = tungstenite::Message::Text(txt);
 
-                        socket.as_mut().unwrap().write_message(msg).unwrap();
+                        socket
+                            .as_ref()
+                            .unwrap()
+                            .lock()
+                            .unwrap()
+                            .write_message(msg)
+                            .unwrap();
 
                         let mut new_msg = WsMessage::new();
                         new_msg.timestamp = utils::get_timestamp();
                         new_msg.msg_type = WsMsgType::OUT;
                         new_msg.txt = out_msg.txt;
                         new_msg.msg_id = out_msg.msg_id;
+                        new_msg.label = out_msg.label;
 
                         let msg_sent = WsSentMsg {
                             msg_type: MsgType::WS_MSG_SENT,
@@ -235,28 +353,85 @@ pub fn spawn_ws_service(connection_id: String) {
 }
 
 pub fn spawn_read_service(
-    current_ws: *const WebSocket<MaybeTlsStream<std::net::TcpStream>>,
+    socket: Socket,
     connection_id: String,
+    graphql: Option<GraphqlWsProtocol>,
+    replies: Sender<String>,
+    acked: Arc<AtomicBool>,
 ) {
-    let new_ws = unsafe { &mut *current_ws.cast_mut() };
     let con_id = connection_id.clone();
 
     let _handle = std::thread::Builder::new()
         .name(con_id.clone())
         .spawn(move || loop {
-            match new_ws.read_message() {
-                Ok(txt) => {
-                    let mut core_state = CORE_STATE.lock().unwrap();
+            // the lock is let go when the read times out, so the service thread can write
+            let message = socket.lock().unwrap().read_message();
+
+            match message {
+                Err(tungstenite::Error::Io(err))
+                    if matches!(
+                        err.kind(),
+                        std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                    ) =>
+                {
+                    std::thread::sleep(std::time::Duration::from_millis(WS_READ_PAUSE));
+                }
 
+                Ok(txt) => {
                     let mut new_msg = WsMessage::new();
                     new_msg.msg_type = WsMsgType::IN;
                     new_msg.txt = txt.to_string();
                     new_msg.timestamp = utils::get_timestamp();
 
+                    let mut finished_subscription = None;
+
+                    // frames that are not GraphQL JSON are shown as they came
+                    let frame = graphql.and_then(|_| parse_graphql_ws_frame(&new_msg.txt));
+
+                    if let Some(frame) = frame {
+                        match frame.kind.as_str() {
+                            // graphql-transport-ws servers close connections that do not answer
+                            // the service thread writes it
+                            "ping" => {
+                                let pong = serde_json::json!({ "type": "pong" }).to_string();
+                                let _ = replies.send(pong);
+
+                                continue;
+                            }
+
+                            "connection_ack" => acked.store(true, Ordering::SeqCst),
+
+                            // keep alives of graphql-ws
+                            "pong" | "ka" => continue,
+
+                            "complete" | "error" => {
+                                finished_subscription = Some(frame.id.clone());
+                            }
+
+                            _ => {}
+                        }
+
+                        // data is what graphql-ws calls next
+                        let kind = if frame.kind == "data" {
+                            "next"
+                        } else {
+                            frame.kind.as_str()
+                        };
+
+                        new_msg.label = format!("{kind} {}", frame.id).trim().to_string();
+                        new_msg.txt = match frame.payload {
+                            serde_json::Value::Null => String::new(),
+                            payload => serde_json::to_string_pretty(&payload).unwrap(),
+                        };
+                    }
+
+                    let mut core_state = CORE_STATE.lock().unwrap();
+
                     let out = WsReceivedMsg {
                         msg_type: MsgType::WS_RECEIVED_MSG,
                         connection_id: con_id.clone(),
                         msg: new_msg,
+                        finished_subscription,
                     };
 
                     let out_txt = serde_json::to_string(&out).unwrap();
@@ -297,38 +472,291 @@ pub fn spawn_read_service(
 pub fn open_ws_connection(
     url: &String,
     connection_id: String,
+    graphql: Option<GraphqlWsProtocol>,
 ) -> (
     bool,
     Option<WebSocket<MaybeTlsStream<std::net::TcpStream>>>,
     Option<tungstenite::http::Response<Option<Vec<u8>>>>,
 ) {
-    match connect(Url::parse(url).unwrap()) {
-        Ok((socket, response)) => return (true, Some(socket), Some(response)),
+    let request = Url::parse(url)
+        .map_err(|err| err.to_string())
+        .and_then(|url| {
+            let request = url.clone().into_client_request().map_err(|err| err.to_string())?;
+
+            Ok((url, request))
+        });
+
+    let (url, mut request) = match request {
+        Ok(request) => request,
 
         Err(err) => {
-            let mut core_state = CORE_STATE.lock().unwrap();
+            send_connection_failed(&connection_id, format!("Invalid URL: {err}"));
 
-            let disconnected_msg = WsConnectionFailedMsg {
-                msg_type: MsgType::WS_CONNECTION_FAILED,
-                connection_id: connection_id.clone(),
-                reason: err.to_string(),
-            };
+            return (false, None, None);
+        }
+    };
 
-            let txt = serde_json::to_string(&disconnected_msg).unwrap();
-            let msg = tungstenite::Message::Text(txt);
+    if let Some(cookies) = handshake_cookies(&url) {
+        if let Ok(value) = cookies.parse() {
+            request.headers_mut().insert("Cookie", value);
+        }
+    }
 
-            core_state
-                .session_websocket
-                .as_mut()
-                .unwrap()
-                .write_message(msg)
-                .unwrap();
+    if let Some(protocol) = graphql {
+        request.headers_mut().insert(
+            "Sec-WebSocket-Protocol",
+            protocol.subprotocol().parse().unwrap(),
+        );
+    }
+
+    match connect(request) {
+        Ok((mut socket, response)) => {
+            let accepted = response
+                .headers()
+                .get("Sec-WebSocket-Protocol")
+                .and_then(|value| value.to_str().ok());
+
+            // servers that leave the header out are given the benefit of the doubt
+            if let (Some(protocol), Some(accepted)) = (graphql, accepted) {
+                if accepted != protocol.subprotocol() {
+                    let _ = socket.close(None);
+
+                    send_connection_failed(
+                        &connection_id,
+                        format!(
+                            "The server chose the {accepted} subprotocol instead of {}",
+                            protocol.subprotocol()
+                        ),
+                    );
+
+                    return (false, None, None);
+                }
+            }
+
+            return (true, Some(socket), Some(response));
+        }
+
+        Err(err) => {
+            send_connection_failed(&connection_id, err.to_string());
 
             return (false, None, None);
         }
     };
 }
 
+fn set_read_timeout(
+    socket: &WebSocket<MaybeTlsStream<std::net::TcpStream>>,
+) -> std::io::Result<()> {
+    let timeout = Some(std::time::Duration::from_millis(WS_READ_TIMEOUT));
+
+    match socket.get_ref() {
+        MaybeTlsStream::Plain(stream) => stream.set_read_timeout(timeout),
+        MaybeTlsStream::NativeTls(stream) => stream.get_ref().set_read_timeout(timeout),
+        _ => Ok(()),
+    }
+}
+
+fn send_connection_failed(connection_id: &str, reason: String) {
+    let mut core_state = CORE_STATE.lock().unwrap();
+
+    let disconnected_msg = WsConnectionFailedMsg {
+        msg_type: MsgType::WS_CONNECTION_FAILED,
+        connection_id: connection_id.to_string(),
+        reason,
+    };
+
+    let txt = serde_json::to_string(&disconnected_msg).unwrap();
+    let msg = tungstenite::Message::Text(txt);
+
+    core_state
+        .session_websocket
+        .as_mut()
+        .unwrap()
+        .write_message(msg)
+        .unwrap();
+}
+
 fn _close_ws_connection(socket: &mut WebSocket<MaybeTlsStream<std::net::TcpStream>>) {
     socket.close(None).unwrap();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+    use std::net::{TcpListener, TcpStream};
+    use std::time::Duration;
+
+    // the client end of the session websocket, which gets what the core writes
+    fn session() -> WebSocket<TcpStream> {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+
+            set_session_websocket(tungstenite::accept(stream).unwrap());
+        });
+
+        let stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(10)))
+            .unwrap();
+
+        let (client, _) = tungstenite::client(format!("ws://127.0.0.1:{port}"), stream).unwrap();
+        server.join().unwrap();
+
+        client
+    }
+
+    fn next(socket: &mut WebSocket<TcpStream>) -> Option<Value> {
+        match socket.read_message() {
+            Ok(msg) => Some(serde_json::from_str(&msg.into_text().unwrap()).unwrap()),
+            Err(tungstenite::Error::Io(err)) if err.kind() == std::io::ErrorKind::WouldBlock => {
+                None
+            }
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    fn send(socket: &mut WebSocket<TcpStream>, frame: Value) {
+        socket
+            .write_message(tungstenite::Message::Text(frame.to_string()))
+            .unwrap();
+    }
+
+    // the app's part, it marks the connection connected and takes sent messages off the queue
+    fn update(connection_id: &str, change: impl FnOnce(&mut WsConnection)) {
+        let mut core_state = CORE_STATE.lock().unwrap();
+
+        let connection = core_state
+            .main_state
+            .ws_connections
+            .iter_mut()
+            .find(|con| con.connection_id == connection_id)
+            .unwrap();
+
+        change(connection);
+    }
+
+    // a graphql-transport-ws server that pings before it acks, returns the frames it got
+    fn graphql_server(listener: TcpListener) -> Vec<String> {
+        let (stream, _) = listener.accept().unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(10)))
+            .unwrap();
+
+        let mut socket = tungstenite::accept(stream).unwrap();
+        let mut frames = vec![];
+
+        let mut read = |socket: &mut WebSocket<TcpStream>| {
+            if let Some(frame) = next(socket) {
+                frames.push(format!("{} {}", frame["type"], frame["id"]));
+            }
+        };
+
+        read(&mut socket);
+
+        send(&mut socket, json!({ "type": "ping" }));
+        read(&mut socket);
+
+        // nothing is subscribed before the ack, the service sends every 500ms
+        socket
+            .get_ref()
+            .set_read_timeout(Some(Duration::from_millis(1500)))
+            .unwrap();
+        read(&mut socket);
+        socket
+            .get_ref()
+            .set_read_timeout(Some(Duration::from_secs(10)))
+            .unwrap();
+
+        send(&mut socket, json!({ "type": "connection_ack" }));
+        read(&mut socket);
+
+        send(
+            &mut socket,
+            json!({ "id": "s1", "type": "next", "payload": { "data": { "n": 1 } } }),
+        );
+        send(&mut socket, json!({ "id": "s1", "type": "complete" }));
+
+        frames
+    }
+
+    #[test]
+    fn subscriptions_wait_for_the_ack_and_pings_are_answered() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || graphql_server(listener));
+
+        let mut client = session();
+
+        let body = GraphqlBody {
+            query: "subscription { n }".to_string(),
+            ..Default::default()
+        };
+
+        let mut subscribe = WsMessage::new();
+        subscribe.txt =
+            graphql_ws_subscribe(GraphqlWsProtocol::GRAPHQL_TRANSPORT_WS, "s1", &body).unwrap();
+
+        let mut connection = WsConnection::new();
+        connection.url = format!("ws://127.0.0.1:{port}");
+        connection.mode = WsMode::GRAPHQL;
+        connection.connecting = true;
+        connection.out_queue.push(subscribe);
+
+        let connection_id = connection.connection_id.clone();
+
+        let mut state = MainState::new();
+        state.ws_connections.push(connection);
+        set_main_state(state);
+
+        CORE_STATE.lock().unwrap().ws_services.push(WsService {
+            connection_id: connection_id.clone(),
+        });
+
+        spawn_ws_service(connection_id.clone());
+
+        let mut events = vec![];
+
+        loop {
+            let msg = next(&mut client).unwrap();
+
+            match msg["msg_type"].as_str().unwrap() {
+                "WS_CONNECTED" => update(&connection_id, |con| con.connected = true),
+                "WS_MSG_SENT" => update(&connection_id, |con| con.out_queue.clear()),
+                "WS_DISCONNECTED" => break,
+                _ => {}
+            }
+
+            events.push(format!(
+                "{} {}",
+                msg["msg_type"].as_str().unwrap(),
+                msg["msg"]["label"].as_str().unwrap_or_default()
+            ));
+        }
+
+        assert_eq!(
+            server.join().unwrap(),
+            vec![
+                r#""connection_init" null"#,
+                r#""pong" null"#,
+                r#""subscribe" "s1""#,
+            ]
+        );
+        assert_eq!(
+            events,
+            vec![
+                "WS_CONNECTED ",
+                "WS_RECEIVED_MSG connection_ack",
+                "WS_MSG_SENT ",
+                "WS_RECEIVED_MSG next s1",
+                "WS_RECEIVED_MSG complete s1",
+            ]
+        );
+
+        // the service stops once the app drops the connection
+        CORE_STATE.lock().unwrap().main_state.ws_connections.clear();
+    }
+}
@@ -4,6 +4,11 @@ pub enum HttpReqTabs {
     Body,
     Params,
     Headers,
+    Auth,
+    Settings,
+    Tests,
+    Extract,
+    Code,
 }
 
 impl From<u8> for HttpReqTabs {
@@ -12,6 +17,11 @@ impl From<u8> for HttpReqTabs {
             1 => HttpReqTabs::Body,
             2 => HttpReqTabs::Params,
             3 => HttpReqTabs::Headers,
+            4 => HttpReqTabs::Auth,
+            5 => HttpReqTabs::Settings,
+            6 => HttpReqTabs::Tests,
+            7 => HttpReqTabs::Extract,
+            8 => HttpReqTabs::Code,
             _ => panic!("Invalid value for HttpReqTabs"),
         }
     }
@@ -23,6 +33,11 @@ impl From<HttpReqTabs> for u8 {
             HttpReqTabs::Body => 1,
             HttpReqTabs::Params => 2,
             HttpReqTabs::Headers => 3,
+            HttpReqTabs::Auth => 4,
+            HttpReqTabs::Settings => 5,
+            HttpReqTabs::Tests => 6,
+            HttpReqTabs::Extract => 7,
+            HttpReqTabs::Code => 8,
         }
     }
 }
@@ -30,6 +45,10 @@ impl From<HttpReqTabs> for u8 {
 pub enum HttpRespTabs {
     Body,
     Headers,
+    Console,
+    Raw,
+    Timing,
+    Tests,
 }
 
 impl From<u8> for HttpRespTabs {
@@ -37,6 +56,10 @@ impl From<u8> for HttpRespTabs {
         match value {
             1 => HttpRespTabs::Body,
             2 => HttpRespTabs::Headers,
+            3 => HttpRespTabs::Console,
+            4 => HttpRespTabs::Raw,
+            5 => HttpRespTabs::Timing,
+            6 => HttpRespTabs::Tests,
             _ => panic!("Invalid value for HttpRespTabs"),
         }
     }
@@ -47,6 +70,10 @@ impl From<HttpRespTabs> for u8 {
         match tab {
             HttpRespTabs::Body => 1,
             HttpRespTabs::Headers => 2,
+            HttpRespTabs::Console => 3,
+            HttpRespTabs::Raw => 4,
+            HttpRespTabs::Timing => 5,
+            HttpRespTabs::Tests => 6,
         }
     }
 }
@@ -55,6 +82,7 @@ pub enum WsOutTabs {
     Message,
     Params,
     Headers,
+    Init,
 }
 
 impl From<u8> for WsOutTabs {
@@ -63,6 +91,7 @@ impl From<u8> for WsOutTabs {
             1 => WsOutTabs::Message,
             2 => WsOutTabs::Params,
             3 => WsOutTabs::Headers,
+            4 => WsOutTabs::Init,
             _ => panic!("Invalid value for WsOutTabs"),
         }
     }
@@ -74,6 +103,58 @@ impl From<WsOutTabs> for u8 {
             WsOutTabs::Message => 1,
             WsOutTabs::Params => 2,
             WsOutTabs::Headers => 3,
+            WsOutTabs::Init => 4,
+        }
+    }
+}
+
+pub enum SseOutTabs {
+    Headers,
+    Reconnect,
+}
+
+impl From<u8> for SseOutTabs {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => SseOutTabs::Headers,
+            2 => SseOutTabs::Reconnect,
+            _ => panic!("Invalid value for SseOutTabs"),
+        }
+    }
+}
+
+impl From<SseOutTabs> for u8 {
+    fn from(tab: SseOutTabs) -> Self {
+        match tab {
+            SseOutTabs::Headers => 1,
+            SseOutTabs::Reconnect => 2,
+        }
+    }
+}
+
+pub enum GrpcOutTabs {
+    Message,
+    Metadata,
+    Service,
+}
+
+impl From<u8> for GrpcOutTabs {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => GrpcOutTabs::Message,
+            2 => GrpcOutTabs::Metadata,
+            3 => GrpcOutTabs::Service,
+            _ => panic!("Invalid value for GrpcOutTabs"),
+        }
+    }
+}
+
+impl From<GrpcOutTabs> for u8 {
+    fn from(tab: GrpcOutTabs) -> Self {
+        match tab {
+            GrpcOutTabs::Message => 1,
+            GrpcOutTabs::Metadata => 2,
+            GrpcOutTabs::Service => 3,
         }
     }
 }
@@ -21,24 +21,59 @@ pub enum Msg {
     // HTTP
     HttpReqSelectedMethod(HttpMethod),
     SendHttpPressed,
+    CancelHttpPressed,
     RemoveHttpRequest(usize),
     SelectHttpRequest(usize),
     AddHttpRequest,
     HttpReqParamChanged(usize),
     HttpReqBodyChanged,
+    HttpReqBodyTypeChanged,
+    HttpReqBodyFileChanged,
+    HttpReqGraphqlChanged,
+    HttpReqGraphqlCompletionPicked(usize),
+    FetchGraphqlSchemaPressed,
+    HttpReqFormChanged(usize),
+    HttpReqAddFormField,
+    HttpReqRemoveFormField(usize),
+    HttpReqMultipartChanged(usize),
+    HttpReqAddMultipartField,
+    HttpReqRemoveMultipartField(usize),
+    HttpReqTestsPressed,
+    HttpReqAssertionChanged(usize),
+    HttpReqAddAssertion,
+    HttpReqRemoveAssertion(usize),
+    HttpReqExtractPressed,
+    HttpReqExtractionChanged(usize),
+    HttpReqAddExtraction,
+    HttpReqRemoveExtraction(usize),
+    HttpReqCodePressed,
+    SnippetLanguageChanged,
+    CopySnippetPressed,
     HttpReqHeaderChanged(usize),
     HttpReceivedResponse,
     HttpReqMethodChanged,
     HttpReqBodyPressed,
     HttpReqHeadersPressed,
     HttpReqParamsPressed,
+    HttpReqAuthPressed,
+    HttpReqAuthTypeChanged,
+    HttpReqAuthChanged,
+    HttpReqSettingsPressed,
+    HttpReqSettingsScopeChanged,
+    HttpReqSettingsChanged,
     HttpRespBodyPressed,
     HttpRespHeadersPressed,
+    HttpRespConsolePressed,
+    HttpRespRawPressed,
+    HttpRespTimingPressed,
+    HttpRespTestsPressed,
     HttpReqAddHeader,
     HttpReqRemoveHeader(usize),
     HttpReqAddParam,
     HttpReqRemoveParam(usize),
     CopyHttpResponsePressed,
+    CopyHttpCurlPressed,
+    SaveHttpResponsePressed,
 
     // WEBSOCKETS
     SendWsPressed,
@@ -50,6 +85,14 @@ pub enum Msg {
     RemoveWsConnection(usize),
     SelectWsConnection(usize),
     CopyWsMsgClicked(usize),
+    WsModeChanged,
+    WsGraphqlProtocolChanged,
+    WsGraphqlChanged,
+    WsGraphqlCompletionPicked(usize),
+    FetchWsGraphqlSchemaPressed,
+    WsOutInitPressed,
+    WsGraphqlInitChanged,
+    StopWsSubscription(String),
 
     // TCP
     SendTcpPressed,
@@ -75,15 +118,80 @@ pub enum Msg {
     SelectUdpConnection(usize),
     CopyUdpMsgClicked(usize),
 
+    // SSE
+    ConnectSsePressed,
+    DisconnectSsePressed,
+    AddSseConnection,
+    RemoveSseConnection(usize),
+    SelectSseConnection(usize),
+    SseOutHeadersPressed,
+    SseOutReconnectPressed,
+    SseHeaderChanged(usize),
+    SseAddHeader,
+    SseRemoveHeader(usize),
+    SseReconnectChanged,
+    CopySseMsgClicked(usize),
+    ClearSseHistory,
+
+    // GRPC
+    InvokeGrpcPressed,
+    CancelGrpcPressed,
+    AddGrpcConnection,
+    RemoveGrpcConnection(usize),
+    SelectGrpcConnection(usize),
+    GrpcMethodChanged,
+    GrpcOutMessagePressed,
+    GrpcOutMetadataPressed,
+    GrpcOutServicePressed,
+    GrpcOutMessageChanged,
+    GrpcTemplatePressed,
+    GrpcHeaderChanged(usize),
+    GrpcAddHeader,
+    GrpcRemoveHeader(usize),
+    GrpcServiceChanged,
+    LoadGrpcMethodsPressed,
+    SendGrpcPressed,
+    EndGrpcPressed,
+    GrpcInMessagesPressed,
+    GrpcInHeadersPressed,
+    GrpcInTrailersPressed,
+    CopyGrpcMsgClicked(usize),
+    ClearGrpcHistory,
+
     // COLLECTION
     AddCollection,
     RemoveCollection(usize),
     AddToCollection(usize),
     SelectFromCollection(usize, usize),
     RemoveFromCollection(usize, usize),
+    RunCollection(usize),
+    StopCollectionRun,
+    RunnerConfigChanged,
+    ImportCollectionPressed,
+    ExportCollectionPressed,
+
+    // ENVIRONMENTS
+    AddEnvironment,
+    RemoveEnvironment(usize),
+    SelectEnvironment(usize),
+    EnvNameChanged,
+    EnvVariableChanged(usize),
+    EnvAddVariable,
+    EnvRemoveVariable(usize),
+    ActivateEnvironment,
+    DeactivateEnvironment,
+    EnvSelectChanged,
+
+    AddCookie,
+    RemoveCookie(usize),
+    CookieChanged(usize),
+    SelectCookieDomain(String),
+    ClearCookieDomain(String),
+    ClearCookies,
 
     // OTHER
     UrlChanged,
+    UrlPasted,
     ToggleCollapsed(usize),
     Update,
     HelpPressed,
@@ -137,6 +245,25 @@ lazy_static::lazy_static! {
 static BACKEND_WS: &str = "ws://127.0.0.1";
 static WS_PORT: u16 = 3344;
 
+// the CLI serves the UI one port above the core server, so a Bolt started with --port
+// is found from the page address. The desktop app has no port and uses the default.
+fn backend_ws_url() -> String {
+    let location = web_sys::window().unwrap().location();
+
+    let asset_port = location
+        .port()
+        .ok()
+        .and_then(|port| port.parse::<u16>().ok());
+
+    match (location.hostname(), asset_port) {
+        (Ok(host), Some(port)) if !host.is_empty() && port > 1 => {
+            format!("ws://{}:{}", host, port - 1)
+        }
+
+        _ => BACKEND_WS.to_string() + ":" + &WS_PORT.to_string(),
+    }
+}
+
 fn main() {
     yew::Renderer::<BoltApp>::new().render();
 }
@@ -179,10 +306,18 @@ impl Component for BoltApp {
             view::tcp::tcp_view(&mut state.bctx)
         } else if page == Page::Udp {
             view::udp::udp_view(&mut state.bctx)
+        } else if page == Page::Sse {
+            view::sse::sse_view(&mut state.bctx)
+        } else if page == Page::Grpc {
+            view::grpc::grpc_view(&mut state.bctx)
         } else if page == Page::Websockets {
             view::websockets::websockets_view(&mut state.bctx)
         } else if page == Page::Servers {
             view::servers::servers_view(&mut state.bctx)
+        } else if page == Page::Environments {
+            view::environments::environments_view(&mut state.bctx)
+        } else if page == Page::Cookies {
+            view::cookies::cookies_view(&mut state.bctx)
         } else {
             view::http::http_view(&mut state.bctx)
         }
@@ -194,7 +329,7 @@ fn init(bctx: &mut BoltContext) {
 
     bctx.main_state.http_requests.push(HttpRequest::new());
 
-    let ws = WebSocket::open(&(BACKEND_WS.to_string() + ":" + &WS_PORT.to_string())).unwrap();
+    let ws = WebSocket::open(&backend_ws_url()).unwrap();
     let (write, mut read) = ws.split();
 
     bctx.ws_tx = Some(write);
@@ -218,6 +353,7 @@ fn init(bctx: &mut BoltContext) {
 
 fn send_http_request(request: &mut HttpRequest) {
     request.loading = true;
+    request.request_id = new_request_id();
     invoke_send(request);
 }
 
@@ -237,6 +373,67 @@ fn send_ws(connection: &mut WsConnection) {
     connection.out_queue.push(msg);
 }
 
+// the message id doubles as the subscription id, so the sent frame and the replies line up
+fn subscribe_ws(connection: &mut WsConnection, graphql: &GraphqlBody) {
+    let mut msg = WsMessage::new();
+    msg.msg_type = WsMsgType::OUT;
+
+    let Ok(frame) = graphql_ws_subscribe(connection.graphql_protocol, &msg.msg_id, graphql) else {
+        // the editor already lists what is wrong with the query or the variables
+        return;
+    };
+
+    msg.txt = frame;
+    msg.label = format!("subscribe {}", msg.msg_id);
+
+    connection.subscriptions.push(msg.msg_id.clone());
+    connection.out_queue.push(msg);
+}
+
+fn stop_ws_subscription(connection: &mut WsConnection, id: &str) {
+    let mut msg = WsMessage::new();
+    msg.msg_type = WsMsgType::OUT;
+    msg.txt = graphql_ws_stop(connection.graphql_protocol, id);
+    msg.label = format!("complete {id}");
+
+    connection.subscriptions.retain(|sub| sub != id);
+    connection.out_queue.push(msg);
+}
+
+fn connect_sse(connection: &mut SseConnection) {
+    connection.failed = false;
+    connection.connecting = true;
+}
+
+fn disconnect_sse(connection: &mut SseConnection) {
+    connection.disconnecting = true;
+}
+
+// the previous call's answer is cleared, its messages stay in the history
+fn connect_grpc(connection: &mut GrpcConnection) {
+    connection.failed = false;
+    connection.failed_reason = String::new();
+    connection.status = None;
+    connection.response_headers.clear();
+    connection.trailers.clear();
+    connection.out_queue.clear();
+    connection.closing = false;
+    connection.connecting = true;
+}
+
+fn disconnect_grpc(connection: &mut GrpcConnection) {
+    connection.disconnecting = true;
+}
+
+fn send_grpc(connection: &mut GrpcConnection) {
+    let mut msg = WsMessage::new();
+    msg.txt = get_body();
+    msg.msg_type = WsMsgType::OUT;
+
+    connection.failed_reason = String::new();
+    connection.out_queue.push(msg);
+}
+
 fn connect_tcp(connection: &mut TcpConnection) {
     connection.connecting = true;
 }
@@ -305,14 +502,30 @@ pub fn http_receive_response(data: String) {
 
     let mut response: HttpResponse = serde_json::from_str(&data).unwrap();
 
+    // decoded once, renders and saves use these bytes until the app restarts
+    response.raw_body = if response.body_base64.is_empty() {
+        Some(Arc::new(response.body.clone().into_bytes()))
+    } else {
+        decode_base64(&response.body_base64).map(Arc::new)
+    };
+
     if response.response_type == HttpResponseType::JSON {
         response.body = format_json(&response.body);
         response.body_highlight = highlight_body(&response.body);
     }
 
-    let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
-    current.response = response;
-    current.loading = false;
+    // responses go to the request that sent them, a late one after a cancel is dropped
+    let target = bctx
+        .main_state
+        .http_requests
+        .iter()
+        .position(|req| req.loading && req.request_id == response.request_id);
+
+    if let Some(index) = target {
+        let target = &mut bctx.main_state.http_requests[index];
+        target.response = response;
+        target.loading = false;
+    }
 
     let link = state.bctx.link.as_ref().unwrap();
 
@@ -329,13 +542,23 @@ pub fn handle_core_message(txt: String) {
             }
 
             MsgType::SEND_HTTP
+            | MsgType::CANCEL_HTTP
             | MsgType::SAVE_STATE
+            | MsgType::EDIT_COOKIES
             | MsgType::LOG
             | MsgType::PANIC
             | MsgType::OPEN_LINK
             | MsgType::ADD_UDP_CONNECTION
             | MsgType::ADD_TCP_CONNECTION
             | MsgType::ADD_WS_CONNECTION
+            | MsgType::ADD_SSE_CONNECTION
+            | MsgType::ADD_GRPC_CONNECTION
+            | MsgType::LOAD_GRPC_METHODS
+            | MsgType::SAVE_RESPONSE
+            | MsgType::RUN_COLLECTION
+            | MsgType::IMPORT_COLLECTION
+            | MsgType::EXPORT_COLLECTION
+            | MsgType::FETCH_GRAPHQL_SCHEMA
             | MsgType::COPY_CLIPBOARD => {
                 return;
             }
@@ -348,6 +571,34 @@ pub fn handle_core_message(txt: String) {
                 handle_restore_response_msg(txt);
             }
 
+            MsgType::COOKIES_CHANGED => {
+                handle_cookies_changed_msg(txt);
+            }
+
+            MsgType::VARIABLES_CHANGED => {
+                handle_variables_changed_msg(txt);
+            }
+
+            MsgType::RESPONSE_SAVED => {
+                handle_response_saved_msg(txt);
+            }
+
+            MsgType::COLLECTION_PROGRESS => {
+                handle_collection_progress_msg(txt);
+            }
+            MsgType::COLLECTION_FINISHED => {
+                handle_collection_finished_msg(txt);
+            }
+            MsgType::COLLECTION_IMPORTED => {
+                handle_collection_imported_msg(txt);
+            }
+            MsgType::COLLECTION_EXPORTED => {
+                handle_collection_exported_msg(txt);
+            }
+            MsgType::GRAPHQL_SCHEMA => {
+                handle_graphql_schema_msg(txt);
+            }
+
             MsgType::WS_CONNECTED => {
                 handle_ws_connected_msg(txt);
             }
@@ -395,6 +646,44 @@ pub fn handle_core_message(txt: String) {
             MsgType::UDP_RECEIVED_MSG => {
                 handle_udp_received_msg(txt);
             }
+
+            MsgType::SSE_CONNECTED => {
+                handle_sse_connected_msg(txt);
+            }
+            MsgType::SSE_DISCONNECTED => {
+                handle_sse_disconnected_msg(txt);
+            }
+            MsgType::SSE_RECONNECTING => {
+                handle_sse_reconnecting_msg(txt);
+            }
+            MsgType::SSE_CONNECTION_FAILED => {
+                handle_sse_connection_failed_msg(txt);
+            }
+            MsgType::SSE_RECEIVED_MSG => {
+                handle_sse_received_msg(txt);
+            }
+
+            MsgType::GRPC_METHODS => {
+                handle_grpc_methods_msg(txt);
+            }
+            MsgType::GRPC_CALL_STARTED => {
+                handle_grpc_call_started_msg(txt);
+            }
+            MsgType::GRPC_MSG_SENT => {
+                handle_grpc_sent_msg(txt);
+            }
+            MsgType::GRPC_MSG_REJECTED => {
+                handle_grpc_msg_rejected_msg(txt);
+            }
+            MsgType::GRPC_RECEIVED_MSG => {
+                handle_grpc_received_msg(txt);
+            }
+            MsgType::GRPC_CALL_FINISHED => {
+                handle_grpc_call_finished_msg(txt);
+            }
+            MsgType::GRPC_CALL_FAILED => {
+                handle_grpc_call_failed_msg(txt);
+            }
         },
 
         Err(_err) => {
@@ -579,6 +868,236 @@ fn handle_udp_received_msg(txt: String) {
     link.send_message(Msg::Update);
 }
 
+fn handle_sse_connected_msg(txt: String) {
+    let msg: SseConnectedMsg = serde_json::from_str(&txt).unwrap();
+
+    let mut global_state = GLOBAL_STATE.lock().unwrap();
+
+    for con in &mut global_state.bctx.main_state.sse_connections {
+        if msg.connection_id == con.connection_id {
+            con.failed = false;
+            con.connecting = false;
+            con.reconnecting = false;
+            con.connected = true;
+        }
+    }
+
+    let link = global_state.bctx.link.as_ref().unwrap();
+    link.send_message(Msg::Update);
+}
+
+fn handle_sse_disconnected_msg(txt: String) {
+    let msg: SseDisconnectedMsg = serde_json::from_str(&txt).unwrap();
+
+    let mut global_state = GLOBAL_STATE.lock().unwrap();
+
+    for con in &mut global_state.bctx.main_state.sse_connections {
+        if msg.connection_id == con.connection_id {
+            con.disconnecting = false;
+            con.connecting = false;
+            con.reconnecting = false;
+            con.connected = false;
+        }
+    }
+
+    let link = global_state.bctx.link.as_ref().unwrap();
+    link.send_message(Msg::Update);
+}
+
+// the stream dropped, the core tries again after `retry` ms with the last event id
+fn handle_sse_reconnecting_msg(txt: String) {
+    let msg: SseReconnectingMsg = serde_json::from_str(&txt).unwrap();
+
+    let mut global_state = GLOBAL_STATE.lock().unwrap();
+
+    for con in &mut global_state.bctx.main_state.sse_connections {
+        if msg.connection_id == con.connection_id {
+            con.reconnecting = true;
+            con.failed_reason = format!("{}, reconnecting in {} ms", msg.reason, msg.retry);
+        }
+    }
+
+    let link = global_state.bctx.link.as_ref().unwrap();
+    link.send_message(Msg::Update);
+}
+
+fn handle_sse_connection_failed_msg(txt: String) {
+    let msg: SseConnectionFailedMsg = serde_json::from_str(&txt).unwrap();
+
+    let mut global_state = GLOBAL_STATE.lock().unwrap();
+
+    for con in &mut global_state.bctx.main_state.sse_connections {
+        if msg.connection_id == con.connection_id {
+            con.failed = true;
+            con.failed_reason = msg.reason.clone();
+            con.disconnecting = false;
+            con.connecting = false;
+            con.reconnecting = false;
+            con.connected = false;
+        }
+    }
+
+    let link = global_state.bctx.link.as_ref().unwrap();
+    link.send_message(Msg::Update);
+}
+
+fn handle_sse_received_msg(txt: String) {
+    let received_msg: SseReceivedMsg = serde_json::from_str(&txt).unwrap();
+
+    let mut global_state = GLOBAL_STATE.lock().unwrap();
+
+    for con in &mut global_state.bctx.main_state.sse_connections {
+        if con.connection_id == received_msg.connection_id {
+            con.last_event_id = received_msg.msg.id.clone();
+
+            if let Some(retry) = received_msg.msg.retry {
+                con.retry = retry;
+            }
+
+            con.msg_history.push(received_msg.msg.clone());
+        }
+    }
+
+    let link = global_state.bctx.link.as_ref().unwrap();
+    link.send_message(Msg::Update);
+}
+
+fn handle_grpc_methods_msg(txt: String) {
+    let msg: GrpcMethodsMsg = serde_json::from_str(&txt).unwrap();
+
+    let mut global_state = GLOBAL_STATE.lock().unwrap();
+
+    for con in &mut global_state.bctx.main_state.grpc_connections {
+        if msg.connection_id == con.connection_id {
+            con.loading_methods = false;
+            con.methods_error = msg.error.clone();
+            con.methods = msg.methods.clone();
+
+            if con.method >= con.methods.len() {
+                con.method = 0;
+            }
+
+            // an empty editor starts from the request message of the method
+            if con.out_buffer.trim().is_empty() {
+                if let Some(method) = con.current_method() {
+                    con.out_buffer = method.template.clone();
+                }
+            }
+        }
+    }
+
+    let link = global_state.bctx.link.as_ref().unwrap();
+    link.send_message(Msg::Update);
+}
+
+fn handle_grpc_call_started_msg(txt: String) {
+    let msg: GrpcCallStartedMsg = serde_json::from_str(&txt).unwrap();
+
+    let mut global_state = GLOBAL_STATE.lock().unwrap();
+
+    for con in &mut global_state.bctx.main_state.grpc_connections {
+        if msg.connection_id == con.connection_id {
+            con.failed = false;
+            con.connecting = false;
+            con.connected = true;
+        }
+    }
+
+    let link = global_state.bctx.link.as_ref().unwrap();
+    link.send_message(Msg::Update);
+}
+
+// the message sent with the call was never queued, it only goes to the history
+fn handle_grpc_sent_msg(txt: String) {
+    let sent_msg: GrpcSentMsg = serde_json::from_str(&txt).unwrap();
+
+    let mut global_state = GLOBAL_STATE.lock().unwrap();
+
+    for con in &mut global_state.bctx.main_state.grpc_connections {
+        if sent_msg.connection_id == con.connection_id {
+            con.out_queue.retain(|out_msg| out_msg.msg_id != sent_msg.msg.msg_id);
+            con.msg_history.push(sent_msg.msg.clone());
+        }
+    }
+
+    let link = global_state.bctx.link.as_ref().unwrap();
+    link.send_message(Msg::Update);
+}
+
+fn handle_grpc_msg_rejected_msg(txt: String) {
+    let msg: GrpcMsgRejectedMsg = serde_json::from_str(&txt).unwrap();
+
+    let mut global_state = GLOBAL_STATE.lock().unwrap();
+
+    for con in &mut global_state.bctx.main_state.grpc_connections {
+        if msg.connection_id == con.connection_id {
+            con.out_queue.retain(|out_msg| out_msg.msg_id != msg.msg_id);
+            con.failed_reason = msg.reason.clone();
+        }
+    }
+
+    let link = global_state.bctx.link.as_ref().unwrap();
+    link.send_message(Msg::Update);
+}
+
+fn handle_grpc_received_msg(txt: String) {
+    let received_msg: GrpcReceivedMsg = serde_json::from_str(&txt).unwrap();
+
+    let mut global_state = GLOBAL_STATE.lock().unwrap();
+
+    for con in &mut global_state.bctx.main_state.grpc_connections {
+        if con.connection_id == received_msg.connection_id {
+            con.msg_history.push(received_msg.msg.clone());
+        }
+    }
+
+    let link = global_state.bctx.link.as_ref().unwrap();
+    link.send_message(Msg::Update);
+}
+
+fn handle_grpc_call_finished_msg(txt: String) {
+    let msg: GrpcCallFinishedMsg = serde_json::from_str(&txt).unwrap();
+
+    let mut global_state = GLOBAL_STATE.lock().unwrap();
+
+    for con in &mut global_state.bctx.main_state.grpc_connections {
+        if msg.connection_id == con.connection_id {
+            con.status = Some(msg.status.clone());
+            con.response_headers = msg.headers.clone();
+            con.trailers = msg.trailers.clone();
+            con.out_queue.clear();
+            con.closing = false;
+            con.disconnecting = false;
+            con.connecting = false;
+            con.connected = false;
+        }
+    }
+
+    let link = global_state.bctx.link.as_ref().unwrap();
+    link.send_message(Msg::Update);
+}
+
+fn handle_grpc_call_failed_msg(txt: String) {
+    let msg: GrpcCallFailedMsg = serde_json::from_str(&txt).unwrap();
+
+    let mut global_state = GLOBAL_STATE.lock().unwrap();
+
+    for con in &mut global_state.bctx.main_state.grpc_connections {
+        if msg.connection_id == con.connection_id {
+            con.failed = true;
+            con.failed_reason = msg.reason.clone();
+            con.out_queue.clear();
+            con.closing = false;
+            con.disconnecting = false;
+            con.connecting = false;
+            con.connected = false;
+        }
+    }
+
+    let link = global_state.bctx.link.as_ref().unwrap();
+    link.send_message(Msg::Update);
+}
+
 fn handle_ws_connected_msg(txt: String) {
     let msg: WsConnectedMsg = serde_json::from_str(&txt).unwrap();
 
@@ -606,6 +1125,7 @@ fn handle_ws_disconnected_msg(txt: String) {
             con.disconnecting = false;
             con.connecting = false;
             con.connected = false;
+            con.subscriptions.clear();
         }
     }
 
@@ -660,6 +1180,10 @@ fn handle_ws_received_msg(txt: String) {
     for con in &mut global_state.bctx.main_state.ws_connections {
         if con.connection_id == received_msg.connection_id {
             con.msg_history.push(received_msg.msg.clone());
+
+            if let Some(id) = &received_msg.finished_subscription {
+                con.subscriptions.retain(|sub| sub != id);
+            }
         }
     }
 
@@ -671,6 +1195,193 @@ fn handle_http_response_msg(txt: String) {
     http_receive_response(txt);
 }
 
+fn handle_cookies_changed_msg(txt: String) {
+    let msg: CookiesChangedMsg = serde_json::from_str(&txt).unwrap();
+
+    let mut global_state = GLOBAL_STATE.lock().unwrap();
+
+    global_state.bctx.main_state.cookies = msg.cookies;
+
+    let link = global_state.bctx.link.as_ref().unwrap();
+    link.send_message(Msg::Update);
+}
+
+// the core already uses the new values, the client keeps them in its saved state
+fn handle_variables_changed_msg(txt: String) {
+    let msg: VariablesChangedMsg = serde_json::from_str(&txt).unwrap();
+
+    let mut global_state = GLOBAL_STATE.lock().unwrap();
+
+    if let Some(env) = global_state.bctx.main_state.environments.get_mut(msg.env) {
+        for var in &msg.variables {
+            env.set(&var[0], &var[1]);
+        }
+    }
+
+    let link = global_state.bctx.link.as_ref().unwrap();
+    link.send_message(Msg::Update);
+}
+
+fn handle_response_saved_msg(txt: String) {
+    let msg: ResponseSavedMsg = serde_json::from_str(&txt).unwrap();
+
+    let mut global_state = GLOBAL_STATE.lock().unwrap();
+    let bctx = &mut global_state.bctx;
+
+    let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
+
+    if msg.error.is_empty() {
+        current.response.saved_to = msg.path.clone();
+        current.response.console.push(format!("Saved response to {}", msg.path));
+    } else {
+        current.response.console.push(format!("Could not save response: {}", msg.error));
+    }
+
+    let link = global_state.bctx.link.as_ref().unwrap();
+    link.send_message(Msg::Update);
+}
+
+fn handle_collection_progress_msg(txt: String) {
+    let msg: CollectionProgressMsg = serde_json::from_str(&txt).unwrap();
+
+    let mut global_state = GLOBAL_STATE.lock().unwrap();
+    let bctx = &mut global_state.bctx;
+
+    // results of an older run that was stopped
+    if msg.run_id != bctx.main_state.collection_run.run_id {
+        return;
+    }
+
+    let run = &mut bctx.main_state.collection_run;
+    run.total = msg.total;
+    run.results.push(msg.result);
+
+    let link = global_state.bctx.link.as_ref().unwrap();
+    link.send_message(Msg::Update);
+}
+
+fn handle_collection_finished_msg(txt: String) {
+    let msg: CollectionFinishedMsg = serde_json::from_str(&txt).unwrap();
+
+    let mut global_state = GLOBAL_STATE.lock().unwrap();
+    let bctx = &mut global_state.bctx;
+
+    let run = &mut bctx.main_state.collection_run;
+
+    if msg.run_id != run.run_id {
+        return;
+    }
+
+    run.running = false;
+    run.cancelled = msg.cancelled;
+    run.error = msg.error;
+
+    // a stopped run has no summary from the core
+    run.summary = if msg.cancelled {
+        RunSummary::from_results(&run.results)
+    } else {
+        msg.summary
+    };
+
+    let link = global_state.bctx.link.as_ref().unwrap();
+    link.send_message(Msg::Update);
+}
+
+fn handle_collection_imported_msg(txt: String) {
+    let msg: CollectionImportedMsg = serde_json::from_str(&txt).unwrap();
+
+    let mut global_state = GLOBAL_STATE.lock().unwrap();
+    let bctx = &mut global_state.bctx;
+
+    if msg.error.is_empty() {
+        let mut collections = msg.collections;
+
+        // responses recorded in a HAR are shown like received ones
+        for request in collections.iter_mut().flat_map(|c| c.requests.iter_mut()) {
+            let response = &mut request.response;
+
+            if response.response_type == HttpResponseType::JSON && !response.body.is_empty() {
+                if serde_json::from_str::<serde_json::Value>(&response.body).is_ok() {
+                    response.body = format_json(&response.body);
+                }
+
+                response.body_highlight = highlight_body(&response.body);
+            }
+        }
+
+        let changes = add_imported(
+            &mut bctx.main_state,
+            collections,
+            msg.environments,
+            msg.ws_connections,
+            msg.update,
+        );
+
+        bctx.main_state.transfer = TransferStatus {
+            message: changes.join(". "),
+            error: String::new(),
+            warnings: msg.warnings,
+        };
+    } else {
+        bctx.main_state.transfer = TransferStatus {
+            message: String::new(),
+            error: format!("Could not import: {}", msg.error),
+            warnings: msg.warnings,
+        };
+    }
+
+    let link = global_state.bctx.link.as_ref().unwrap();
+    link.send_message(Msg::Update);
+}
+
+fn handle_collection_exported_msg(txt: String) {
+    let msg: CollectionExportedMsg = serde_json::from_str(&txt).unwrap();
+
+    let mut global_state = GLOBAL_STATE.lock().unwrap();
+    let bctx = &mut global_state.bctx;
+
+    let transfer = &mut bctx.main_state.transfer;
+    transfer.warnings = msg.warnings;
+
+    if msg.error.is_empty() {
+        transfer.error = String::new();
+        transfer.message = format!("Exported to {}", msg.path);
+    } else {
+        transfer.message = String::new();
+        transfer.error = format!("Could not export: {}", msg.error);
+    }
+
+    let link = global_state.bctx.link.as_ref().unwrap();
+    link.send_message(Msg::Update);
+}
+
+fn handle_graphql_schema_msg(txt: String) {
+    let msg: GraphqlSchemaMsg = serde_json::from_str(&txt).unwrap();
+
+    let mut global_state = GLOBAL_STATE.lock().unwrap();
+    let bctx = &mut global_state.bctx;
+
+    let status = &mut bctx.main_state.graphql_status;
+
+    if status.endpoint == msg.endpoint {
+        status.loading = false;
+        status.error = msg.error;
+    }
+
+    // a refetch replaces the cached schema of the endpoint
+    if let Some(schema) = msg.schema {
+        let schemas = &mut bctx.main_state.graphql_schemas;
+
+        match schemas.iter_mut().find(|s| s.endpoint == schema.endpoint) {
+            Some(cached) => *cached = schema,
+            None => schemas.push(schema),
+        }
+    }
+
+    let link = global_state.bctx.link.as_ref().unwrap();
+    link.send_message(Msg::Update);
+}
+
 fn handle_ping_msg(_txt: String) {}
 
 fn handle_invalid_msg(txt: String) {
@@ -690,7 +1401,57 @@ pub fn invoke_send(request: &mut HttpRequest) {
         method: request.method,
         body: request.body.clone(),
         headers: request.headers.clone(),
+        body_type: request.body_type,
+        form: request.form.clone(),
+        multipart: request.multipart.clone(),
+        body_file: request.body_file.clone(),
+        graphql: request.graphql.clone(),
+        auth: request.auth.clone(),
+        settings: request.settings.clone(),
+        assertions: request.assertions.clone(),
+        extractions: request.extractions.clone(),
         index: request.response.request_index,
+        request_id: request.request_id.clone(),
+    };
+
+    let msg = serde_json::to_string(&msg).unwrap();
+
+    ws_write(msg);
+
+    send_ping();
+}
+
+pub fn invoke_fetch_graphql_schema(endpoint: String, request: &HttpRequest) {
+    let msg = FetchGraphqlSchemaMsg {
+        msg_type: MsgType::FETCH_GRAPHQL_SCHEMA,
+        endpoint,
+        request: request.to_send_request(request.response.request_index),
+    };
+
+    let msg = serde_json::to_string(&msg).unwrap();
+
+    ws_write(msg);
+
+    send_ping();
+}
+
+// the jar is owned by the core, local edits are mirrored there
+pub fn invoke_edit_cookies(edit: CookieEdit) {
+    let msg = EditCookiesMsg {
+        msg_type: MsgType::EDIT_COOKIES,
+        edit,
+    };
+
+    let msg = serde_json::to_string(&msg).unwrap();
+
+    ws_write(msg);
+}
+
+pub fn invoke_load_grpc_methods(connection: &GrpcConnection) {
+    let msg = LoadGrpcMethodsMsg {
+        msg_type: MsgType::LOAD_GRPC_METHODS,
+        connection_id: connection.connection_id.clone(),
+        source: connection.services_source(),
     };
 
     let msg = serde_json::to_string(&msg).unwrap();
@@ -699,3 +1460,80 @@ pub fn invoke_send(request: &mut HttpRequest) {
 
     send_ping();
 }
+
+pub fn invoke_run_collection(collection: &Collection, config: &RunnerConfig, run_id: String) {
+    let mut collection = collection.clone();
+
+    // the core does not need the previous responses
+    for request in &mut collection.requests {
+        request.response = HttpResponse::new();
+    }
+
+    let msg = RunCollectionMsg {
+        msg_type: MsgType::RUN_COLLECTION,
+        run_id,
+        collection,
+        config: config.clone(),
+    };
+
+    let msg = serde_json::to_string(&msg).unwrap();
+
+    ws_write(msg);
+
+    send_ping();
+}
+
+pub fn invoke_import_collection(path: String, format: Option<CollectionFormat>) {
+    let msg = ImportCollectionMsg {
+        msg_type: MsgType::IMPORT_COLLECTION,
+        path,
+        format,
+    };
+
+    let msg = serde_json::to_string(&msg).unwrap();
+
+    ws_write(msg);
+}
+
+pub fn invoke_export_collection(collection: &Collection, format: CollectionFormat) {
+    let mut collection = collection.clone();
+
+    // only a HAR keeps the responses
+    for request in &mut collection.requests {
+        if format != CollectionFormat::HAR {
+            request.response = HttpResponse::new();
+        }
+    }
+
+    let msg = ExportCollectionMsg {
+        msg_type: MsgType::EXPORT_COLLECTION,
+        collection,
+        format,
+    };
+
+    let msg = serde_json::to_string(&msg).unwrap();
+
+    ws_write(msg);
+}
+
+pub fn invoke_cancel_run(run_id: String) {
+    let msg = CancelHttpMsg {
+        msg_type: MsgType::CANCEL_HTTP,
+        request_id: run_id,
+    };
+
+    let msg = serde_json::to_string(&msg).unwrap();
+
+    ws_write(msg);
+}
+
+pub fn invoke_cancel(request: &HttpRequest) {
+    let msg = CancelHttpMsg {
+        msg_type: MsgType::CANCEL_HTTP,
+        request_id: request.request_id.clone(),
+    };
+
+    let msg = serde_json::to_string(&msg).unwrap();
+
+    ws_write(msg);
+}
@@ -1,13 +1,30 @@
+use crate::connect_grpc;
+use crate::connect_sse;
 use crate::connect_tcp;
 use crate::connect_udp;
 use crate::connect_ws;
+use crate::disconnect_grpc;
+use crate::disconnect_sse;
 use crate::disconnect_tcp;
 use crate::disconnect_udp;
 use crate::disconnect_ws;
+use crate::helpers::enums::GrpcOutTabs;
+use crate::helpers::enums::WsOutTabs;
+use crate::invoke_cancel;
+use crate::invoke_cancel_run;
+use crate::invoke_edit_cookies;
+use crate::invoke_export_collection;
+use crate::invoke_fetch_graphql_schema;
+use crate::invoke_import_collection;
+use crate::invoke_load_grpc_methods;
+use crate::invoke_run_collection;
+use crate::send_grpc;
 use crate::send_http_request;
 use crate::send_tcp;
 use crate::send_udp;
 use crate::send_ws;
+use crate::stop_ws_subscription;
+use crate::subscribe_ws;
 use crate::utils::*;
 use crate::BoltContext;
 use crate::Collection;
@@ -28,9 +45,23 @@ pub fn process(bctx: &mut BoltContext, msg: Msg) -> bool {
         Msg::SendHttpPressed => {
             let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
 
-            send_http_request(current);
+            // a running send has to be cancelled before it can be sent again
+            if current.loading {
+                false
+            } else {
+                send_http_request(current);
 
-            true
+                true
+            }
+        }
+        Msg::CancelHttpPressed => {
+            let current = &bctx.main_state.http_requests[bctx.main_state.http_current];
+
+            if current.loading {
+                invoke_cancel(current);
+            }
+
+            false
         }
         Msg::HttpReqBodyPressed => {
             let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
@@ -50,6 +81,57 @@ pub fn process(bctx: &mut BoltContext, msg: Msg) -> bool {
 
             true
         }
+        Msg::HttpReqAuthPressed => {
+            let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
+            current.req_tab = 4;
+
+            true
+        }
+        Msg::HttpReqAuthTypeChanged => {
+            let auth_type = get_auth_type();
+
+            let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
+            current.auth.auth_type = auth_type;
+
+            true
+        }
+        Msg::HttpReqAuthChanged => {
+            let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
+
+            update_auth(&mut current.auth);
+
+            true
+        }
+        Msg::HttpReqSettingsPressed => {
+            let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
+            current.req_tab = 5;
+
+            true
+        }
+        Msg::HttpReqSettingsScopeChanged => {
+            let defaults = bctx.main_state.http_settings.clone();
+
+            let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
+
+            // a request switching to its own settings starts from the workspace defaults
+            current.settings = if get_settings_custom() {
+                Some(current.settings.clone().unwrap_or(defaults))
+            } else {
+                None
+            };
+
+            true
+        }
+        Msg::HttpReqSettingsChanged => {
+            let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
+
+            match current.settings.as_mut() {
+                Some(settings) => update_settings(settings),
+                None => update_settings(&mut bctx.main_state.http_settings),
+            }
+
+            true
+        }
         Msg::HttpRespBodyPressed => {
             let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
             current.resp_tab = 1;
@@ -62,6 +144,30 @@ pub fn process(bctx: &mut BoltContext, msg: Msg) -> bool {
 
             true
         }
+        Msg::HttpRespConsolePressed => {
+            let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
+            current.resp_tab = 3;
+
+            true
+        }
+        Msg::HttpRespRawPressed => {
+            let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
+            current.resp_tab = 4;
+
+            true
+        }
+        Msg::HttpRespTimingPressed => {
+            let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
+            current.resp_tab = 5;
+
+            true
+        }
+        Msg::HttpRespTestsPressed => {
+            let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
+            current.resp_tab = 6;
+
+            true
+        }
         Msg::HttpReceivedResponse => true,
         Msg::HttpReqAddHeader => {
             let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
@@ -104,6 +210,197 @@ pub fn process(bctx: &mut BoltContext, msg: Msg) -> bool {
 
             true
         }
+        Msg::HttpReqBodyTypeChanged => {
+            let body_type = get_body_type();
+
+            let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
+            current.body_type = body_type;
+
+            // GraphQL servers take queries as POST
+            if body_type == HttpBodyType::GRAPHQL && current.method == HttpMethod::GET {
+                current.method = HttpMethod::POST;
+            }
+
+            true
+        }
+        Msg::HttpReqBodyFileChanged => {
+            let body_file = get_body_file();
+
+            let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
+            current.body_file = body_file;
+
+            true
+        }
+        Msg::HttpReqGraphqlChanged => {
+            let graphql = get_graphql_body();
+            let cursor = get_graphql_cursor();
+
+            let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
+            current.graphql = graphql;
+            current.graphql_cursor = cursor;
+
+            true
+        }
+        Msg::HttpReqGraphqlCompletionPicked(index) => {
+            let current = &bctx.main_state.http_requests[bctx.main_state.http_current];
+
+            let Some(schema) = bctx.main_state.graphql_schema(&current.url) else {
+                return false;
+            };
+
+            let cursor = current.graphql_cursor;
+            let completions = complete_graphql(&current.graphql.query, cursor, schema);
+
+            let Some(item) = completions.items.get(index) else {
+                return false;
+            };
+
+            let mut query = current.graphql.query.clone();
+            query.replace_range(completions.start..cursor, &item.label);
+
+            let cursor = completions.start + item.label.len();
+
+            set_graphql_query(&query, cursor);
+
+            let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
+            current.graphql.query = query;
+            current.graphql_cursor = cursor;
+
+            true
+        }
+        Msg::FetchGraphqlSchemaPressed => {
+            let current = &bctx.main_state.http_requests[bctx.main_state.http_current];
+
+            if current.url.trim().is_empty() {
+                return false;
+            }
+
+            let endpoint = graphql_endpoint(&bctx.main_state.resolve_variables(&current.url));
+
+            invoke_fetch_graphql_schema(endpoint.clone(), current);
+
+            bctx.main_state.graphql_status = GraphqlSchemaStatus {
+                endpoint,
+                loading: true,
+                error: String::new(),
+            };
+
+            true
+        }
+        Msg::HttpReqFormChanged(index) => {
+            let row = get_form_row(index);
+
+            let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
+            current.form[index] = row;
+
+            true
+        }
+        Msg::HttpReqAddFormField => {
+            let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
+
+            current.form.push(vec!["".to_string(), "".to_string()]);
+            true
+        }
+        Msg::HttpReqRemoveFormField(index) => {
+            let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
+
+            current.form.remove(index);
+            true
+        }
+        Msg::HttpReqMultipartChanged(index) => {
+            let field = get_multipart_field(index);
+
+            let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
+            current.multipart[index] = field;
+
+            true
+        }
+        Msg::HttpReqAddMultipartField => {
+            let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
+
+            current.multipart.push(MultipartField::new());
+            true
+        }
+        Msg::HttpReqRemoveMultipartField(index) => {
+            let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
+
+            current.multipart.remove(index);
+            true
+        }
+        Msg::HttpReqTestsPressed => {
+            let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
+            current.req_tab = 6;
+
+            true
+        }
+        Msg::HttpReqAssertionChanged(index) => {
+            let assertion = get_assertion(index);
+
+            let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
+            current.assertions[index] = assertion;
+
+            true
+        }
+        Msg::HttpReqAddAssertion => {
+            let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
+
+            current.assertions.push(Assertion::new());
+            true
+        }
+        Msg::HttpReqRemoveAssertion(index) => {
+            let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
+
+            current.assertions.remove(index);
+            true
+        }
+        Msg::HttpReqExtractPressed => {
+            let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
+            current.req_tab = 7;
+
+            true
+        }
+        Msg::HttpReqExtractionChanged(index) => {
+            let rule = get_extraction(index);
+
+            let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
+            current.extractions[index] = rule;
+
+            true
+        }
+        Msg::HttpReqAddExtraction => {
+            let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
+
+            current.extractions.push(ExtractionRule::new());
+            true
+        }
+        Msg::HttpReqRemoveExtraction(index) => {
+            let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
+
+            current.extractions.remove(index);
+            true
+        }
+        Msg::HttpReqCodePressed => {
+            let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
+            current.req_tab = 8;
+
+            true
+        }
+        Msg::SnippetLanguageChanged => {
+            bctx.main_state.snippet_language = get_snippet_language();
+
+            true
+        }
+        Msg::CopySnippetPressed => {
+            let mut request = bctx.main_state.http_requests[bctx.main_state.http_current].clone();
+
+            if let Some(env) = bctx.main_state.active_environment() {
+                request.resolve_variables(env);
+            }
+
+            copy_string_to_clipboard(generate_snippet(&request, bctx.main_state.snippet_language));
+
+            false
+        }
         Msg::HttpReqHeaderChanged(index) => {
             let header = get_header(index);
 
@@ -174,6 +471,29 @@ pub fn process(bctx: &mut BoltContext, msg: Msg) -> bool {
 
             true
         }
+        Msg::CopyHttpCurlPressed => {
+            let mut request = bctx.main_state.http_requests[bctx.main_state.http_current].clone();
+
+            // what the core would send, with the workspace settings and the active environment
+            if request.settings.is_none() {
+                request.settings = Some(bctx.main_state.http_settings.clone());
+            }
+
+            if let Some(env) = bctx.main_state.active_environment() {
+                request.resolve_variables(env);
+            }
+
+            copy_string_to_clipboard(to_curl(&request.to_send_request(0)));
+
+            false
+        }
+        Msg::SaveHttpResponsePressed => {
+            let current = &bctx.main_state.http_requests[bctx.main_state.http_current];
+
+            crate::utils::save_response_to_file(current);
+
+            false
+        }
 
         // WEBSOCKETS-------------------------------------------------------------
         Msg::WsOutMessageChanged => {
@@ -248,9 +568,25 @@ pub fn process(bctx: &mut BoltContext, msg: Msg) -> bool {
             true
         }
         Msg::SendWsPressed => {
-            let current = &mut bctx.main_state.ws_connections[bctx.main_state.ws_current];
+            let current = &bctx.main_state.ws_connections[bctx.main_state.ws_current];
+
+            if current.mode == WsMode::GRAPHQL {
+                let state = &bctx.main_state;
+
+                let graphql = GraphqlBody {
+                    query: state.resolve_variables(&current.graphql.query),
+                    variables: state.resolve_variables(&current.graphql.variables),
+                    operation_name: state.resolve_variables(&current.graphql.operation_name),
+                };
+
+                let current = &mut bctx.main_state.ws_connections[bctx.main_state.ws_current];
+
+                subscribe_ws(current, &graphql);
+            } else {
+                let current = &mut bctx.main_state.ws_connections[bctx.main_state.ws_current];
 
-            send_ws(current);
+                send_ws(current);
+            }
 
             true
         }
@@ -263,6 +599,108 @@ pub fn process(bctx: &mut BoltContext, msg: Msg) -> bool {
 
             true
         }
+        Msg::WsModeChanged => {
+            let mode = get_ws_mode();
+
+            let current = &mut bctx.main_state.ws_connections[bctx.main_state.ws_current];
+            current.mode = mode;
+
+            // the init payload only exists in GraphQL mode
+            if mode == WsMode::RAW && current.out_tab == u8::from(WsOutTabs::Init) {
+                current.out_tab = 1;
+            }
+
+            true
+        }
+        Msg::WsGraphqlProtocolChanged => {
+            let protocol = get_ws_graphql_protocol();
+
+            let current = &mut bctx.main_state.ws_connections[bctx.main_state.ws_current];
+            current.graphql_protocol = protocol;
+
+            true
+        }
+        Msg::WsGraphqlChanged => {
+            let graphql = get_graphql_body();
+            let cursor = get_graphql_cursor();
+
+            let current = &mut bctx.main_state.ws_connections[bctx.main_state.ws_current];
+            current.graphql = graphql;
+            current.graphql_cursor = cursor;
+
+            true
+        }
+        Msg::WsGraphqlCompletionPicked(index) => {
+            let current = &bctx.main_state.ws_connections[bctx.main_state.ws_current];
+
+            let Some(schema) = bctx.main_state.graphql_schema(&current.url) else {
+                return false;
+            };
+
+            let cursor = current.graphql_cursor;
+            let completions = complete_graphql(&current.graphql.query, cursor, schema);
+
+            let Some(item) = completions.items.get(index) else {
+                return false;
+            };
+
+            let mut query = current.graphql.query.clone();
+            query.replace_range(completions.start..cursor, &item.label);
+
+            let cursor = completions.start + item.label.len();
+
+            set_graphql_query(&query, cursor);
+
+            let current = &mut bctx.main_state.ws_connections[bctx.main_state.ws_current];
+            current.graphql.query = query;
+            current.graphql_cursor = cursor;
+
+            true
+        }
+        Msg::FetchWsGraphqlSchemaPressed => {
+            let current = &bctx.main_state.ws_connections[bctx.main_state.ws_current];
+
+            if current.url.trim().is_empty() {
+                return false;
+            }
+
+            // the schema is introspected over plain http on the same address
+            let endpoint = graphql_endpoint(&bctx.main_state.resolve_variables(&current.url));
+
+            let mut request = HttpRequest::new();
+            request.url = endpoint.clone();
+
+            invoke_fetch_graphql_schema(endpoint.clone(), &request);
+
+            bctx.main_state.graphql_status = GraphqlSchemaStatus {
+                endpoint,
+                loading: true,
+                error: String::new(),
+            };
+
+            true
+        }
+        Msg::WsOutInitPressed => {
+            let current = &mut bctx.main_state.ws_connections[bctx.main_state.ws_current];
+            current.out_tab = u8::from(WsOutTabs::Init);
+
+            true
+        }
+        Msg::WsGraphqlInitChanged => {
+            let init = get_ws_graphql_init();
+
+            let current = &mut bctx.main_state.ws_connections[bctx.main_state.ws_current];
+            current.graphql_init = init;
+
+            true
+        }
+        Msg::StopWsSubscription(id) => {
+            let current = &mut bctx.main_state.ws_connections[bctx.main_state.ws_current];
+
+            stop_ws_subscription(current, &id);
+
+            true
+        }
 
         // TCP-------------------------------------------------------------
         Msg::TcpOutMessagePressed => {
@@ -458,27 +896,360 @@ pub fn process(bctx: &mut BoltContext, msg: Msg) -> bool {
             true
         }
 
-        // COLLECTIONS-------------------------------------------------------------
-        Msg::AddCollection => {
-            let mut new_collection = Collection::new();
+        // SSE-------------------------------------------------------------
+        Msg::SseOutHeadersPressed => {
+            let current = &mut bctx.main_state.sse_connections[bctx.main_state.sse_current];
+            current.out_tab = 1;
 
-            new_collection.name =
-                new_collection.name + &(bctx.main_state.collections.len() + 1).to_string();
-            bctx.main_state.collections.push(new_collection);
+            true
+        }
+        Msg::SseOutReconnectPressed => {
+            let current = &mut bctx.main_state.sse_connections[bctx.main_state.sse_current];
+            current.out_tab = 2;
 
             true
         }
-        Msg::RemoveCollection(index) => {
-            bctx.main_state.collections.remove(index);
+        Msg::SseHeaderChanged(index) => {
+            let header = get_header(index);
 
-            bctx.main_state.col_current = vec![0, 0];
+            let current = &mut bctx.main_state.sse_connections[bctx.main_state.sse_current];
+
+            current.out_headers[index] = header;
 
             true
         }
-        Msg::AddToCollection(index) => {
-            let collection = &mut bctx.main_state.collections[index];
+        Msg::SseAddHeader => {
+            let current = &mut bctx.main_state.sse_connections[bctx.main_state.sse_current];
 
-            let mut new_request = HttpRequest::new();
+            current
+                .out_headers
+                .push(vec!["".to_string(), "".to_string()]);
+
+            true
+        }
+        Msg::SseRemoveHeader(index) => {
+            let current = &mut bctx.main_state.sse_connections[bctx.main_state.sse_current];
+
+            current.out_headers.remove(index);
+
+            true
+        }
+        Msg::SseReconnectChanged => {
+            let last_event_id = get_sse_last_event_id();
+            let retry = get_sse_retry();
+
+            let current = &mut bctx.main_state.sse_connections[bctx.main_state.sse_current];
+
+            current.last_event_id = last_event_id;
+            current.retry = retry;
+
+            true
+        }
+        Msg::RemoveSseConnection(index) => {
+            bctx.main_state.sse_connections.remove(index);
+            if !bctx.main_state.sse_connections.is_empty()
+                && bctx.main_state.sse_current > bctx.main_state.sse_connections.len() - 1
+            {
+                bctx.main_state.sse_current = bctx.main_state.sse_connections.len() - 1;
+            }
+
+            true
+        }
+        Msg::SelectSseConnection(index) => {
+            let mut new_index = index;
+
+            if bctx.main_state.sse_connections.len() == 0 {
+                bctx.main_state.sse_current = new_index;
+            } else {
+                if index >= bctx.main_state.sse_connections.len() {
+                    new_index = bctx.main_state.sse_connections.len() - 1;
+                    bctx.main_state.sse_current = new_index;
+                } else {
+                    bctx.main_state.sse_current = new_index;
+                }
+            }
+
+            true
+        }
+        Msg::ConnectSsePressed => {
+            let current = &mut bctx.main_state.sse_connections[bctx.main_state.sse_current];
+
+            connect_sse(current);
+
+            true
+        }
+        Msg::DisconnectSsePressed => {
+            let current = &mut bctx.main_state.sse_connections[bctx.main_state.sse_current];
+
+            disconnect_sse(current);
+
+            true
+        }
+        Msg::AddSseConnection => {
+            let mut new_connection = SseConnection::new();
+
+            new_connection.name =
+                new_connection.name + &(bctx.main_state.sse_connections.len() + 1).to_string();
+
+            let msg = AddSseConnectionMsg {
+                msg_type: MsgType::ADD_SSE_CONNECTION,
+                connection_id: new_connection.connection_id.clone(),
+            };
+
+            let msg = serde_json::to_string(&msg).unwrap();
+
+            ws_write(msg);
+
+            bctx.main_state.sse_connections.push(new_connection);
+
+            true
+        }
+        Msg::CopySseMsgClicked(index) => {
+            let current = &mut bctx.main_state.sse_connections[bctx.main_state.sse_current];
+
+            let current_msg = current.msg_history[index].clone();
+
+            crate::utils::copy_string_to_clipboard(current_msg.data);
+
+            true
+        }
+        Msg::ClearSseHistory => {
+            let current = &mut bctx.main_state.sse_connections[bctx.main_state.sse_current];
+
+            current.msg_history.clear();
+
+            true
+        }
+
+        // GRPC-------------------------------------------------------------
+        Msg::GrpcOutMessagePressed => {
+            let current = &mut bctx.main_state.grpc_connections[bctx.main_state.grpc_current];
+            current.out_tab = GrpcOutTabs::Message.into();
+
+            true
+        }
+        Msg::GrpcOutMetadataPressed => {
+            let current = &mut bctx.main_state.grpc_connections[bctx.main_state.grpc_current];
+            current.out_tab = GrpcOutTabs::Metadata.into();
+
+            true
+        }
+        Msg::GrpcOutServicePressed => {
+            let current = &mut bctx.main_state.grpc_connections[bctx.main_state.grpc_current];
+            current.out_tab = GrpcOutTabs::Service.into();
+
+            true
+        }
+        Msg::GrpcInMessagesPressed => {
+            let current = &mut bctx.main_state.grpc_connections[bctx.main_state.grpc_current];
+            current.in_tab = 1;
+
+            true
+        }
+        Msg::GrpcInHeadersPressed => {
+            let current = &mut bctx.main_state.grpc_connections[bctx.main_state.grpc_current];
+            current.in_tab = 2;
+
+            true
+        }
+        Msg::GrpcInTrailersPressed => {
+            let current = &mut bctx.main_state.grpc_connections[bctx.main_state.grpc_current];
+            current.in_tab = 3;
+
+            true
+        }
+        Msg::GrpcOutMessageChanged => {
+            let message = get_body();
+            let current = &mut bctx.main_state.grpc_connections[bctx.main_state.grpc_current];
+            current.out_buffer = message;
+
+            true
+        }
+        Msg::GrpcMethodChanged => {
+            let method = get_grpc_method();
+
+            let current = &mut bctx.main_state.grpc_connections[bctx.main_state.grpc_current];
+
+            // an untouched template follows the method, an edited message is kept
+            let untouched = current.out_buffer.trim().is_empty()
+                || current
+                    .current_method()
+                    .is_some_and(|previous| previous.template == current.out_buffer);
+
+            if method < current.methods.len() {
+                current.method = method;
+            }
+
+            if untouched {
+                if let Some(method) = current.current_method() {
+                    current.out_buffer = method.template.clone();
+                }
+            }
+
+            true
+        }
+        Msg::GrpcTemplatePressed => {
+            let current = &mut bctx.main_state.grpc_connections[bctx.main_state.grpc_current];
+
+            if let Some(method) = current.current_method() {
+                current.out_buffer = method.template.clone();
+            }
+
+            true
+        }
+        Msg::GrpcHeaderChanged(index) => {
+            let header = get_header(index);
+
+            let current = &mut bctx.main_state.grpc_connections[bctx.main_state.grpc_current];
+
+            current.out_headers[index] = header;
+
+            true
+        }
+        Msg::GrpcAddHeader => {
+            let current = &mut bctx.main_state.grpc_connections[bctx.main_state.grpc_current];
+
+            current
+                .out_headers
+                .push(vec!["".to_string(), "".to_string()]);
+
+            true
+        }
+        Msg::GrpcRemoveHeader(index) => {
+            let current = &mut bctx.main_state.grpc_connections[bctx.main_state.grpc_current];
+
+            current.out_headers.remove(index);
+
+            true
+        }
+        Msg::GrpcServiceChanged => {
+            let current = &mut bctx.main_state.grpc_connections[bctx.main_state.grpc_current];
+
+            update_grpc_service(current);
+
+            true
+        }
+        Msg::LoadGrpcMethodsPressed => {
+            let current = &mut bctx.main_state.grpc_connections[bctx.main_state.grpc_current];
+
+            current.loading_methods = true;
+            current.methods_error = String::new();
+
+            invoke_load_grpc_methods(current);
+
+            true
+        }
+        Msg::RemoveGrpcConnection(index) => {
+            bctx.main_state.grpc_connections.remove(index);
+            if !bctx.main_state.grpc_connections.is_empty()
+                && bctx.main_state.grpc_current > bctx.main_state.grpc_connections.len() - 1
+            {
+                bctx.main_state.grpc_current = bctx.main_state.grpc_connections.len() - 1;
+            }
+
+            true
+        }
+        Msg::SelectGrpcConnection(index) => {
+            let mut new_index = index;
+
+            if bctx.main_state.grpc_connections.len() == 0 {
+                bctx.main_state.grpc_current = new_index;
+            } else {
+                if index >= bctx.main_state.grpc_connections.len() {
+                    new_index = bctx.main_state.grpc_connections.len() - 1;
+                    bctx.main_state.grpc_current = new_index;
+                } else {
+                    bctx.main_state.grpc_current = new_index;
+                }
+            }
+
+            true
+        }
+        Msg::InvokeGrpcPressed => {
+            let current = &mut bctx.main_state.grpc_connections[bctx.main_state.grpc_current];
+
+            connect_grpc(current);
+
+            true
+        }
+        Msg::CancelGrpcPressed => {
+            let current = &mut bctx.main_state.grpc_connections[bctx.main_state.grpc_current];
+
+            disconnect_grpc(current);
+
+            true
+        }
+        Msg::SendGrpcPressed => {
+            let current = &mut bctx.main_state.grpc_connections[bctx.main_state.grpc_current];
+
+            send_grpc(current);
+
+            true
+        }
+        Msg::EndGrpcPressed => {
+            let current = &mut bctx.main_state.grpc_connections[bctx.main_state.grpc_current];
+
+            current.closing = true;
+
+            true
+        }
+        Msg::AddGrpcConnection => {
+            let mut new_connection = GrpcConnection::new();
+
+            new_connection.name =
+                new_connection.name + &(bctx.main_state.grpc_connections.len() + 1).to_string();
+
+            let msg = AddGrpcConnectionMsg {
+                msg_type: MsgType::ADD_GRPC_CONNECTION,
+                connection_id: new_connection.connection_id.clone(),
+            };
+
+            let msg = serde_json::to_string(&msg).unwrap();
+
+            ws_write(msg);
+
+            bctx.main_state.grpc_connections.push(new_connection);
+
+            true
+        }
+        Msg::CopyGrpcMsgClicked(index) => {
+            let current = &mut bctx.main_state.grpc_connections[bctx.main_state.grpc_current];
+
+            let current_msg = current.msg_history[index].clone();
+
+            crate::utils::copy_string_to_clipboard(current_msg.txt);
+
+            true
+        }
+        Msg::ClearGrpcHistory => {
+            let current = &mut bctx.main_state.grpc_connections[bctx.main_state.grpc_current];
+
+            current.msg_history.clear();
+
+            true
+        }
+
+        // COLLECTIONS-------------------------------------------------------------
+        Msg::AddCollection => {
+            let mut new_collection = Collection::new();
+
+            new_collection.name =
+                new_collection.name + &(bctx.main_state.collections.len() + 1).to_string();
+            bctx.main_state.collections.push(new_collection);
+
+            true
+        }
+        Msg::RemoveCollection(index) => {
+            bctx.main_state.collections.remove(index);
+
+            bctx.main_state.col_current = vec![0, 0];
+
+            true
+        }
+        Msg::AddToCollection(index) => {
+            let collection = &mut bctx.main_state.collections[index];
+
+            let mut new_request = HttpRequest::new();
             new_request.name = new_request.name + &(collection.requests.len() + 1).to_string();
 
             collection.requests.push(new_request);
@@ -503,6 +1274,237 @@ pub fn process(bctx: &mut BoltContext, msg: Msg) -> bool {
             true
         }
 
+        Msg::RunCollection(index) => {
+            if bctx.main_state.collection_run.running {
+                return false;
+            }
+
+            let collection = &bctx.main_state.collections[index];
+
+            let run_id = new_request_id();
+
+            bctx.main_state.collection_run = CollectionRun {
+                run_id: run_id.clone(),
+                collection_index: index,
+                collection_name: collection.name.clone(),
+                running: true,
+                total: collection.requests.len(),
+                ..CollectionRun::default()
+            };
+
+            invoke_run_collection(collection, &bctx.main_state.runner_config, run_id);
+
+            bctx.main_state.page = Page::Collections;
+
+            true
+        }
+        Msg::StopCollectionRun => {
+            let run = &bctx.main_state.collection_run;
+
+            if run.running {
+                invoke_cancel_run(run.run_id.clone());
+            }
+
+            false
+        }
+        Msg::RunnerConfigChanged => {
+            update_runner_config(&mut bctx.main_state.runner_config);
+
+            true
+        }
+        Msg::ImportCollectionPressed => {
+            let path = get_import_path();
+
+            if path.trim().is_empty() {
+                return false;
+            }
+
+            bctx.main_state.transfer = TransferStatus {
+                message: format!("Importing {}...", path.trim()),
+                ..TransferStatus::default()
+            };
+
+            invoke_import_collection(path.trim().to_string(), get_import_format());
+
+            true
+        }
+        Msg::ExportCollectionPressed => {
+            let mut collection = match get_export_collection() {
+                Some(index) => match bctx.main_state.collections.get(index) {
+                    Some(collection) => collection.clone(),
+                    None => return false,
+                },
+                None => open_requests(&bctx.main_state),
+            };
+
+            let format = get_export_format();
+
+            // a HAR records what was sent, so it gets the values of the variables
+            if format == CollectionFormat::HAR {
+                if let Some(env) = bctx.main_state.active_environment() {
+                    for request in &mut collection.requests {
+                        request.resolve_variables(env);
+                    }
+                }
+            }
+
+            bctx.main_state.transfer = TransferStatus {
+                message: format!("Exporting {}...", collection.name),
+                ..TransferStatus::default()
+            };
+
+            invoke_export_collection(&collection, format);
+
+            true
+        }
+
+        // ENVIRONMENTS-------------------------------------------------------------
+        Msg::AddEnvironment => {
+            let mut new_env = Environment::new();
+
+            new_env.name = new_env.name + &(bctx.main_state.environments.len() + 1).to_string();
+            bctx.main_state.environments.push(new_env);
+
+            bctx.main_state.env_current = bctx.main_state.environments.len() - 1;
+
+            true
+        }
+        Msg::RemoveEnvironment(index) => {
+            bctx.main_state.environments.remove(index);
+
+            bctx.main_state.env_active = match bctx.main_state.env_active {
+                Some(active) if active == index => None,
+                Some(active) if active > index => Some(active - 1),
+                active => active,
+            };
+
+            if !bctx.main_state.environments.is_empty()
+                && bctx.main_state.env_current > bctx.main_state.environments.len() - 1
+            {
+                bctx.main_state.env_current = bctx.main_state.environments.len() - 1;
+            }
+
+            true
+        }
+        Msg::SelectEnvironment(index) => {
+            if index < bctx.main_state.environments.len() {
+                bctx.main_state.env_current = index;
+            }
+
+            true
+        }
+        Msg::EnvNameChanged => {
+            let name = get_env_name();
+
+            let current = &mut bctx.main_state.environments[bctx.main_state.env_current];
+            current.name = name;
+
+            true
+        }
+        Msg::EnvVariableChanged(index) => {
+            let variable = get_env_variable(index);
+
+            let current = &mut bctx.main_state.environments[bctx.main_state.env_current];
+            current.variables[index] = variable;
+
+            true
+        }
+        Msg::EnvAddVariable => {
+            let current = &mut bctx.main_state.environments[bctx.main_state.env_current];
+
+            current.variables.push(vec!["".to_string(), "".to_string()]);
+
+            true
+        }
+        Msg::EnvRemoveVariable(index) => {
+            let current = &mut bctx.main_state.environments[bctx.main_state.env_current];
+
+            current.variables.remove(index);
+
+            true
+        }
+        Msg::ActivateEnvironment => {
+            bctx.main_state.env_active = Some(bctx.main_state.env_current);
+
+            true
+        }
+        Msg::DeactivateEnvironment => {
+            bctx.main_state.env_active = None;
+
+            true
+        }
+        Msg::EnvSelectChanged => {
+            bctx.main_state.env_active = get_env_select();
+
+            true
+        }
+
+        // COOKIES-------------------------------------------------------------
+        Msg::AddCookie => {
+            let mut new_cookie = Cookie::new();
+
+            new_cookie.domain = bctx.main_state.cookie_domain.clone();
+            new_cookie.host_only = true;
+
+            invoke_edit_cookies(CookieEdit::Set {
+                old: None,
+                cookie: new_cookie.clone(),
+            });
+
+            bctx.main_state.cookies.push(new_cookie);
+
+            true
+        }
+        Msg::RemoveCookie(index) => {
+            let removed = bctx.main_state.cookies.remove(index);
+
+            invoke_edit_cookies(CookieEdit::Remove(removed));
+
+            true
+        }
+        Msg::CookieChanged(index) => {
+            let changed = get_cookie(index);
+
+            let cookie = &mut bctx.main_state.cookies[index];
+            let old = cookie.clone();
+
+            // the cookie moves to another domain, follow it
+            if cookie.domain != changed.domain {
+                bctx.main_state.cookie_domain = changed.domain.clone();
+            }
+
+            cookie.name = changed.name;
+            cookie.value = changed.value;
+            cookie.domain = changed.domain;
+            cookie.path = changed.path;
+
+            invoke_edit_cookies(CookieEdit::Set {
+                old: Some(old),
+                cookie: cookie.clone(),
+            });
+
+            true
+        }
+        Msg::SelectCookieDomain(domain) => {
+            bctx.main_state.cookie_domain = domain;
+
+            true
+        }
+        Msg::ClearCookieDomain(domain) => {
+            bctx.main_state.cookies.retain(|c| c.domain != domain);
+
+            invoke_edit_cookies(CookieEdit::ClearDomain(domain));
+
+            true
+        }
+        Msg::ClearCookies => {
+            bctx.main_state.cookies.clear();
+
+            invoke_edit_cookies(CookieEdit::Clear);
+
+            true
+        }
+
         // OTHER-------------------------------------------------------------
         Msg::UrlChanged => {
             let url = get_url();
@@ -521,10 +1523,44 @@ pub fn process(bctx: &mut BoltContext, msg: Msg) -> bool {
                 let current = &mut bctx.main_state.udp_connections[bctx.main_state.udp_current];
 
                 current.host_address = url.clone();
+            } else if bctx.main_state.page == Page::Sse {
+                let current = &mut bctx.main_state.sse_connections[bctx.main_state.sse_current];
+
+                current.url = url.clone();
+                current.name = url;
+            } else if bctx.main_state.page == Page::Grpc {
+                let current = &mut bctx.main_state.grpc_connections[bctx.main_state.grpc_current];
+
+                current.url = url.clone();
+                current.name = url;
             }
 
             true
         }
+        // a curl command pasted in the url bar replaces the request
+        Msg::UrlPasted => {
+            let pasted = get_url();
+
+            let imported = if bctx.main_state.page == Page::HttpPage
+                && pasted.trim_start().starts_with("curl ")
+            {
+                parse_curl(&pasted).ok()
+            } else {
+                None
+            };
+
+            match imported {
+                Some(imported) => {
+                    let current = &mut bctx.main_state.http_requests[bctx.main_state.http_current];
+
+                    import_curl_request(current, imported);
+
+                    true
+                }
+
+                None => process(bctx, Msg::UrlChanged),
+            }
+        }
         Msg::ToggleCollapsed(index) => {
             let collection = &mut bctx.main_state.collections[index];
 
@@ -552,3 +1588,13 @@ pub fn process(bctx: &mut BoltContext, msg: Msg) -> bool {
 
     should_render
 }
+
+fn import_curl_request(current: &mut HttpRequest, imported: HttpRequest) {
+    let previous = std::mem::replace(current, imported);
+
+    current.response = previous.response;
+    current.req_tab = previous.req_tab;
+    current.resp_tab = previous.resp_tab;
+    current.loading = previous.loading;
+    current.request_id = previous.request_id;
+}
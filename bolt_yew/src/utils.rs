@@ -169,6 +169,155 @@ pub fn get_method() -> HttpMethod {
     }
 }
 
+pub fn get_auth_type() -> HttpAuthType {
+    let window = web_sys::window().unwrap();
+    let doc = web_sys::Window::document(&window).unwrap();
+    let div = web_sys::Document::get_element_by_id(&doc, "authtypeselect").unwrap();
+
+    let select = div.dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+
+    match select.value().parse::<usize>() {
+        Ok(index) if index < HttpAuthType::count() => HttpAuthType::from(index),
+        _ => HttpAuthType::NONE,
+    }
+}
+
+fn get_input_value(id: &str) -> Option<String> {
+    let window = web_sys::window().unwrap();
+    let doc = web_sys::Window::document(&window).unwrap();
+    let div = web_sys::Document::get_element_by_id(&doc, id)?;
+
+    Some(div.dyn_into::<web_sys::HtmlInputElement>().unwrap().value())
+}
+
+// only the inputs of the selected auth type are rendered, the rest keep their value
+pub fn update_auth(auth: &mut HttpAuth) {
+    if let Some(username) = get_input_value("authusername") {
+        auth.username = username;
+    }
+    if let Some(password) = get_input_value("authpassword") {
+        auth.password = password;
+    }
+    if let Some(token) = get_input_value("authtoken") {
+        auth.token = token;
+    }
+    if let Some(key) = get_input_value("authkey") {
+        auth.key = key;
+    }
+    if let Some(value) = get_input_value("authvalue") {
+        auth.value = value;
+    }
+    if let Some(token_url) = get_input_value("oauth2tokenurl") {
+        auth.oauth2.token_url = token_url;
+    }
+    if let Some(auth_url) = get_input_value("oauth2authurl") {
+        auth.oauth2.auth_url = auth_url;
+    }
+    if let Some(redirect_url) = get_input_value("oauth2redirecturl") {
+        auth.oauth2.redirect_url = redirect_url;
+    }
+    if let Some(client_id) = get_input_value("oauth2clientid") {
+        auth.oauth2.client_id = client_id;
+    }
+    if let Some(client_secret) = get_input_value("oauth2clientsecret") {
+        auth.oauth2.client_secret = client_secret;
+    }
+    if let Some(scope) = get_input_value("oauth2scope") {
+        auth.oauth2.scope = scope;
+    }
+
+    let window = web_sys::window().unwrap();
+    let doc = web_sys::Window::document(&window).unwrap();
+
+    if let Some(div) = web_sys::Document::get_element_by_id(&doc, "authlocationselect") {
+        let select = div.dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+
+        auth.location = match select.value().as_str() {
+            "query" => ApiKeyLocation::QUERY,
+            _ => ApiKeyLocation::HEADER,
+        };
+    }
+
+    if let Some(div) = web_sys::Document::get_element_by_id(&doc, "oauth2grantselect") {
+        let select = div.dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+
+        if let Ok(index) = select.value().parse::<usize>() {
+            if index < OAuth2GrantType::count() {
+                auth.oauth2.grant_type = OAuth2GrantType::from(index);
+            }
+        }
+    }
+
+    if let Some(div) = web_sys::Document::get_element_by_id(&doc, "oauth2clientauthselect") {
+        let select = div.dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+
+        auth.oauth2.client_auth_header = select.value() == "header";
+    }
+}
+
+pub fn get_settings_custom() -> bool {
+    let window = web_sys::window().unwrap();
+    let doc = web_sys::Window::document(&window).unwrap();
+    let div = web_sys::Document::get_element_by_id(&doc, "settingsscopeselect").unwrap();
+
+    let select = div.dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+
+    select.value() == "request"
+}
+
+fn get_checkbox_value(id: &str) -> Option<bool> {
+    let window = web_sys::window().unwrap();
+    let doc = web_sys::Window::document(&window).unwrap();
+    let div = web_sys::Document::get_element_by_id(&doc, id)?;
+
+    Some(div.dyn_into::<web_sys::HtmlInputElement>().unwrap().checked())
+}
+
+pub fn update_settings(settings: &mut HttpSettings) {
+    if let Some(connect_timeout) = get_input_value("settingsconnecttimeout") {
+        settings.connect_timeout = connect_timeout.trim().parse().unwrap_or(0);
+    }
+    if let Some(timeout) = get_input_value("settingstimeout") {
+        settings.timeout = timeout.trim().parse().unwrap_or(0);
+    }
+    if let Some(follow_redirects) = get_checkbox_value("settingsfollowredirects") {
+        settings.follow_redirects = follow_redirects;
+    }
+    if let Some(max_redirects) = get_input_value("settingsmaxredirects") {
+        settings.max_redirects = max_redirects.trim().parse().unwrap_or(10);
+    }
+    if let Some(proxy) = get_input_value("settingsproxy") {
+        settings.proxy = proxy.trim().to_string();
+    }
+    if let Some(verify_tls) = get_checkbox_value("settingsverifytls") {
+        settings.verify_tls = verify_tls;
+    }
+    if let Some(ca_cert) = get_input_value("settingscacert") {
+        settings.ca_cert = ca_cert.trim().to_string();
+    }
+    if let Some(client_cert) = get_input_value("settingsclientcert") {
+        settings.client_cert = client_cert.trim().to_string();
+    }
+    if let Some(client_key) = get_input_value("settingsclientkey") {
+        settings.client_key = client_key.trim().to_string();
+    }
+}
+
+pub fn update_runner_config(config: &mut RunnerConfig) {
+    if let Some(delay) = get_input_value("runnerdelay") {
+        config.delay = delay.trim().parse().unwrap_or(0);
+    }
+    if let Some(iterations) = get_input_value("runneriterations") {
+        config.iterations = iterations.trim().parse().unwrap_or(1).max(1);
+    }
+    if let Some(stop_on_failure) = get_checkbox_value("runnerstoponfailure") {
+        config.stop_on_failure = stop_on_failure;
+    }
+    if let Some(data_file) = get_input_value("runnerdatafile") {
+        config.data_file = data_file.trim().to_string();
+    }
+}
+
 pub fn get_url() -> String {
     let window = web_sys::window().unwrap();
     let doc = web_sys::Window::document(&window).unwrap();
@@ -203,6 +352,287 @@ pub fn get_body() -> String {
         .value()
 }
 
+pub fn get_body_type() -> HttpBodyType {
+    let window = web_sys::window().unwrap();
+    let doc = web_sys::Window::document(&window).unwrap();
+    let div = web_sys::Document::get_element_by_id(&doc, "bodytypeselect").unwrap();
+
+    let select = div.dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+
+    match select.value().parse::<usize>() {
+        Ok(index) if index < HttpBodyType::count() => HttpBodyType::from(index),
+        _ => HttpBodyType::RAW,
+    }
+}
+
+pub fn get_body_file() -> String {
+    get_input_value("bodyfileinput").unwrap().trim().to_string()
+}
+
+fn get_textarea(id: &str) -> Option<web_sys::HtmlTextAreaElement> {
+    let window = web_sys::window().unwrap();
+    let doc = web_sys::Window::document(&window).unwrap();
+    let div = web_sys::Document::get_element_by_id(&doc, id)?;
+
+    Some(div.dyn_into::<web_sys::HtmlTextAreaElement>().unwrap())
+}
+
+pub fn get_graphql_body() -> GraphqlBody {
+    GraphqlBody {
+        query: get_textarea("graphqlquery").unwrap().value(),
+        variables: get_textarea("graphqlvariables").unwrap().value(),
+        operation_name: get_input_value("graphqloperation").unwrap(),
+    }
+}
+
+pub fn get_ws_mode() -> WsMode {
+    let window = web_sys::window().unwrap();
+    let doc = web_sys::Window::document(&window).unwrap();
+    let div = web_sys::Document::get_element_by_id(&doc, "wsmodeselect").unwrap();
+
+    let select = div.dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+
+    match select.value().parse::<usize>() {
+        Ok(index) if index < WsMode::count() => WsMode::from(index),
+        _ => WsMode::RAW,
+    }
+}
+
+pub fn get_ws_graphql_protocol() -> GraphqlWsProtocol {
+    let window = web_sys::window().unwrap();
+    let doc = web_sys::Window::document(&window).unwrap();
+    let div = web_sys::Document::get_element_by_id(&doc, "wsgraphqlprotocol").unwrap();
+
+    let select = div.dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+
+    match select.value().parse::<usize>() {
+        Ok(index) if index < GraphqlWsProtocol::count() => GraphqlWsProtocol::from(index),
+        _ => GraphqlWsProtocol::GRAPHQL_TRANSPORT_WS,
+    }
+}
+
+pub fn get_ws_graphql_init() -> String {
+    get_textarea("wsgraphqlinit").unwrap().value()
+}
+
+// the browser counts the caret in UTF-16 units, the completions want a byte offset
+pub fn get_graphql_cursor() -> usize {
+    let textarea = get_textarea("graphqlquery").unwrap();
+    let query = textarea.value();
+
+    let units = textarea.selection_start().ok().flatten().unwrap_or(0) as usize;
+
+    let mut counted = 0;
+
+    for (index, c) in query.char_indices() {
+        if counted >= units {
+            return index;
+        }
+
+        counted += c.len_utf16();
+    }
+
+    query.len()
+}
+
+// writes a picked completion into the editor and puts the caret after it
+pub fn set_graphql_query(query: &str, cursor: usize) {
+    let Some(textarea) = get_textarea("graphqlquery") else {
+        return;
+    };
+
+    textarea.set_value(query);
+
+    let caret = query[..cursor].encode_utf16().count() as u32;
+
+    let _ = textarea.set_selection_range(caret, caret);
+    let _ = textarea.focus();
+}
+
+pub fn get_form_row(index: usize) -> Vec<String> {
+    let key = get_input_value(&("formkey".to_string() + &index.to_string())).unwrap();
+    let value = get_input_value(&("formvalue".to_string() + &index.to_string())).unwrap();
+
+    vec![key, value]
+}
+
+pub fn get_multipart_field(index: usize) -> MultipartField {
+    let window = web_sys::window().unwrap();
+    let doc = web_sys::Window::document(&window).unwrap();
+
+    let kind = web_sys::Document::get_element_by_id(
+        &doc,
+        &("multiparttype".to_string() + &index.to_string()),
+    )
+    .unwrap();
+
+    let kind = kind.dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+
+    MultipartField {
+        name: get_input_value(&("multipartname".to_string() + &index.to_string())).unwrap(),
+        value: get_input_value(&("multipartvalue".to_string() + &index.to_string())).unwrap(),
+        is_file: kind.value() == "file",
+    }
+}
+
+pub fn get_assertion(index: usize) -> Assertion {
+    let window = web_sys::window().unwrap();
+    let doc = web_sys::Window::document(&window).unwrap();
+
+    let kind = web_sys::Document::get_element_by_id(
+        &doc,
+        &("assertionkind".to_string() + &index.to_string()),
+    )
+    .unwrap();
+
+    let kind = kind.dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+
+    let kind = match kind.value().parse::<usize>() {
+        Ok(kind_index) if kind_index < AssertionKind::count() => AssertionKind::from(kind_index),
+        _ => AssertionKind::STATUS_EQUALS,
+    };
+
+    Assertion {
+        kind,
+        target: get_input_value(&("assertiontarget".to_string() + &index.to_string())).unwrap(),
+        expected: get_input_value(&("assertionexpected".to_string() + &index.to_string())).unwrap(),
+        enabled: get_checkbox_value(&("assertionenabled".to_string() + &index.to_string())).unwrap(),
+    }
+}
+
+pub fn get_snippet_language() -> SnippetLanguage {
+    let window = web_sys::window().unwrap();
+    let doc = web_sys::Window::document(&window).unwrap();
+    let div = web_sys::Document::get_element_by_id(&doc, "snippetlanguageselect").unwrap();
+
+    let select = div.dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+
+    match select.value().parse::<usize>() {
+        Ok(index) if index < SnippetLanguage::count() => SnippetLanguage::from(index),
+        _ => SnippetLanguage::REQWEST,
+    }
+}
+
+// None is the Detect option
+pub fn get_import_format() -> Option<CollectionFormat> {
+    let window = web_sys::window().unwrap();
+    let doc = web_sys::Window::document(&window).unwrap();
+    let div = web_sys::Document::get_element_by_id(&doc, "importformat").unwrap();
+
+    let select = div.dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+
+    match select.value().parse::<usize>() {
+        Ok(index) if index < CollectionFormat::count() => Some(CollectionFormat::from(index)),
+        _ => None,
+    }
+}
+
+pub fn get_import_path() -> String {
+    get_input_value("importpath").unwrap_or_default()
+}
+
+// None is the HTTP tabs option
+pub fn get_export_collection() -> Option<usize> {
+    let window = web_sys::window().unwrap();
+    let doc = web_sys::Window::document(&window).unwrap();
+    let div = web_sys::Document::get_element_by_id(&doc, "exportcollection").unwrap();
+
+    let select = div.dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+
+    select.value().parse::<usize>().ok()
+}
+
+pub fn get_export_format() -> CollectionFormat {
+    let window = web_sys::window().unwrap();
+    let doc = web_sys::Window::document(&window).unwrap();
+    let div = web_sys::Document::get_element_by_id(&doc, "exportformat").unwrap();
+
+    let select = div.dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+
+    match select.value().parse::<usize>() {
+        Ok(index) if index < CollectionFormat::count() => CollectionFormat::from(index),
+        _ => CollectionFormat::BOLT,
+    }
+}
+
+pub fn get_extraction(index: usize) -> ExtractionRule {
+    let window = web_sys::window().unwrap();
+    let doc = web_sys::Window::document(&window).unwrap();
+
+    let source = web_sys::Document::get_element_by_id(
+        &doc,
+        &("extractionsource".to_string() + &index.to_string()),
+    )
+    .unwrap();
+
+    let source = source.dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+
+    let source = match source.value().parse::<usize>() {
+        Ok(source_index) if source_index < ExtractionSource::count() => {
+            ExtractionSource::from(source_index)
+        }
+        _ => ExtractionSource::JSON_PATH,
+    };
+
+    ExtractionRule {
+        source,
+        expression: get_input_value(&("extractionexpression".to_string() + &index.to_string()))
+            .unwrap(),
+        variable: get_input_value(&("extractionvariable".to_string() + &index.to_string()))
+            .unwrap(),
+        enabled: get_checkbox_value(&("extractionenabled".to_string() + &index.to_string()))
+            .unwrap(),
+    }
+}
+
+pub fn get_sse_last_event_id() -> String {
+    get_input_value("sselasteventid").unwrap()
+}
+
+pub fn get_sse_retry() -> u64 {
+    get_input_value("sseretry")
+        .unwrap()
+        .trim()
+        .parse()
+        .unwrap_or(SSE_DEFAULT_RETRY)
+}
+
+pub fn get_grpc_method() -> usize {
+    let window = web_sys::window().unwrap();
+    let doc = web_sys::Window::document(&window).unwrap();
+    let div = web_sys::Document::get_element_by_id(&doc, "grpcmethodselect").unwrap();
+
+    let select = div.dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+
+    select.value().parse::<usize>().unwrap_or(0)
+}
+
+// the path inputs are only there for .proto files
+pub fn update_grpc_service(connection: &mut GrpcConnection) {
+    let window = web_sys::window().unwrap();
+    let doc = web_sys::Window::document(&window).unwrap();
+
+    if let Some(div) = web_sys::Document::get_element_by_id(&doc, "grpcsourceselect") {
+        let select = div.dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+
+        if let Ok(index) = select.value().parse::<usize>() {
+            if index < GrpcSource::count() {
+                connection.source = GrpcSource::from(index);
+            }
+        }
+    }
+
+    if let Some(proto_files) = get_textarea("grpcprotofiles") {
+        connection.proto_files = proto_files.value();
+    }
+    if let Some(import_paths) = get_textarea("grpcimportpaths") {
+        connection.import_paths = import_paths.value();
+    }
+    if let Some(verify_tls) = get_checkbox_value("grpcverifytls") {
+        connection.verify_tls = verify_tls;
+    }
+}
+
 pub fn get_tcp_out_txt() -> String {
     let window = web_sys::window().unwrap();
     let doc = web_sys::Window::document(&window).unwrap();
@@ -281,12 +711,71 @@ pub fn get_param(index: usize) -> Vec<String> {
     vec![key.value(), value.value()]
 }
 
+pub fn get_env_name() -> String {
+    let window = web_sys::window().unwrap();
+    let doc = web_sys::Window::document(&window).unwrap();
+    let div = web_sys::Document::get_element_by_id(&doc, "envnameinput").unwrap();
+
+    div.dyn_into::<web_sys::HtmlInputElement>().unwrap().value()
+}
+
+pub fn get_env_variable(index: usize) -> Vec<String> {
+    let window = web_sys::window().unwrap();
+    let doc = web_sys::Window::document(&window).unwrap();
+
+    let key =
+        web_sys::Document::get_element_by_id(&doc, &("envkey".to_string() + &index.to_string()))
+            .unwrap();
+    let value =
+        web_sys::Document::get_element_by_id(&doc, &("envvalue".to_string() + &index.to_string()))
+            .unwrap();
+
+    let key = key.dyn_into::<web_sys::HtmlInputElement>().unwrap();
+    let value = value.dyn_into::<web_sys::HtmlInputElement>().unwrap();
+
+    vec![key.value(), value.value()]
+}
+
+pub fn get_cookie(index: usize) -> Cookie {
+    let mut cookie = Cookie::new();
+
+    cookie.name = get_input_value(&("cookiename".to_string() + &index.to_string())).unwrap();
+    cookie.value = get_input_value(&("cookievalue".to_string() + &index.to_string())).unwrap();
+    cookie.domain = get_input_value(&("cookiedomain".to_string() + &index.to_string()))
+        .unwrap()
+        .trim()
+        .trim_start_matches('.')
+        .to_lowercase();
+    cookie.path = get_input_value(&("cookiepath".to_string() + &index.to_string())).unwrap();
+
+    cookie
+}
+
+pub fn get_env_select() -> Option<usize> {
+    let window = web_sys::window().unwrap();
+    let doc = web_sys::Window::document(&window).unwrap();
+    let div = web_sys::Document::get_element_by_id(&doc, "envselect").unwrap();
+
+    let select = div.dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+
+    select.value().parse::<usize>().ok()
+}
+
 pub fn format_json(data: &str) -> String {
     let value: serde_json::Value = serde_json::from_str(data).unwrap();
 
     serde_json::to_string_pretty(&value).unwrap()
 }
 
+// atob gives one char per byte
+pub fn decode_base64(data: &str) -> Option<Vec<u8>> {
+    let window = web_sys::window().unwrap();
+
+    let decoded = window.atob(data).ok()?;
+
+    Some(decoded.chars().map(|c| c as u32 as u8).collect())
+}
+
 fn create_custom_theme() -> Theme {
     let mut theme = ThemeSet::load_defaults().themes["base16-eighties.dark"].clone();
 
@@ -308,28 +797,57 @@ pub fn highlight_body(body: &str) -> String {
     highlighted_html_for_string(body, &syntax_set, syntax, &theme).unwrap()
 }
 
-pub fn parse_url(url: String, params: Vec<Vec<String>>) -> String {
-    let mut new_url = url;
+// bolt_core writes the file into the downloads folder and answers with RESPONSE_SAVED
+pub fn save_response_to_file(request: &HttpRequest) {
+    let msg = SaveResponseMsg {
+        msg_type: MsgType::SAVE_RESPONSE,
+        file_name: response_file_name(&request.url, &request.response.content_type),
+        body_base64: request.response.body_base64.clone(),
+        // the received text, a JSON body is shown formatted
+        body: match &request.response.raw_body {
+            Some(raw) if request.response.body_base64.is_empty() => {
+                String::from_utf8_lossy(raw).to_string()
+            }
+            _ => request.response.body.clone(),
+        },
+    };
 
-    if !params.is_empty() && !params[0][0].is_empty() {
-        new_url.push('?');
-    }
+    let msg = serde_json::to_string(&msg).unwrap();
 
-    for (i, param) in params.iter().enumerate() {
-        if param[0].is_empty() || param[1].is_empty() {
-            continue;
-        }
+    ws_write(msg);
+}
 
-        new_url.push_str(&param[0]);
-        new_url.push('=');
-        new_url.push_str(&param[1]);
+// the last url segment when it looks like a file name, otherwise response.<ext>
+fn response_file_name(url: &str, content_type: &str) -> String {
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    let path = path.split("://").last().unwrap_or_default();
 
-        if i != params.len() - 1 {
-            new_url.push('&');
+    if let Some((_, segment)) = path.rsplit_once('/') {
+        if segment.contains('.') {
+            return segment.to_string();
         }
     }
 
-    new_url
+    let mime = content_type.split(';').next().unwrap_or_default().trim();
+
+    let extension = match mime {
+        "application/json" => "json",
+        "text/html" => "html",
+        "text/plain" => "txt",
+        "text/csv" => "csv",
+        "application/xml" | "text/xml" => "xml",
+        "application/pdf" => "pdf",
+        "image/png" => "png",
+        "image/jpeg" => "jpg",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        "image/svg+xml" => "svg",
+        _ if mime.contains("json") => "json",
+        _ if mime.starts_with("text/") => "txt",
+        _ => "bin",
+    };
+
+    format!("response.{extension}")
 }
 
 pub fn copy_string_to_clipboard(value: String) {
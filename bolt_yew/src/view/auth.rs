@@ -0,0 +1,126 @@
+use crate::BoltContext;
+use crate::Msg;
+use bolt_common::prelude::*;
+use yew::{html, Html};
+
+pub fn render_http_req_auth(bctx: &mut BoltContext, auth: &HttpAuth) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+
+    html! {
+        <div class="reqauth">
+            <div class="authrow">
+                <div class="authlabel">{"Type"}</div>
+                <select id="authtypeselect" class="authselect pointer" onchange={link.callback(|_| Msg::HttpReqAuthTypeChanged)}>
+                    { for (0..HttpAuthType::count()).map(|index| {
+                        let auth_type = HttpAuthType::from(index);
+                        html! {
+                            <option value={index.to_string()} selected={auth_type == auth.auth_type}>{auth_type.to_string()}</option>
+                        }
+                    })}
+                </select>
+            </div>
+
+            if auth.auth_type == HttpAuthType::BASIC || auth.auth_type == HttpAuthType::DIGEST {
+                <div class="authrow">
+                    <div class="authlabel">{"Username"}</div>
+                    <input id="authusername" type="text" class="authinput" autocomplete="off" spellcheck="false" value={auth.username.clone()} onchange={link.callback(|_| Msg::HttpReqAuthChanged)}/>
+                </div>
+                <div class="authrow">
+                    <div class="authlabel">{"Password"}</div>
+                    <input id="authpassword" type="password" class="authinput" autocomplete="off" value={auth.password.clone()} onchange={link.callback(|_| Msg::HttpReqAuthChanged)}/>
+                </div>
+            } else if auth.auth_type == HttpAuthType::BEARER {
+                <div class="authrow">
+                    <div class="authlabel">{"Token"}</div>
+                    <input id="authtoken" type="text" class="authinput" autocomplete="off" spellcheck="false" value={auth.token.clone()} onchange={link.callback(|_| Msg::HttpReqAuthChanged)}/>
+                </div>
+            } else if auth.auth_type == HttpAuthType::API_KEY {
+                <div class="authrow">
+                    <div class="authlabel">{"Key"}</div>
+                    <input id="authkey" type="text" class="authinput" autocomplete="off" spellcheck="false" value={auth.key.clone()} onchange={link.callback(|_| Msg::HttpReqAuthChanged)}/>
+                </div>
+                <div class="authrow">
+                    <div class="authlabel">{"Value"}</div>
+                    <input id="authvalue" type="text" class="authinput" autocomplete="off" spellcheck="false" value={auth.value.clone()} onchange={link.callback(|_| Msg::HttpReqAuthChanged)}/>
+                </div>
+                <div class="authrow">
+                    <div class="authlabel">{"Add to"}</div>
+                    <select id="authlocationselect" class="authselect pointer" onchange={link.callback(|_| Msg::HttpReqAuthChanged)}>
+                        <option value="header" selected={auth.location == ApiKeyLocation::HEADER}>{"Header"}</option>
+                        <option value="query" selected={auth.location == ApiKeyLocation::QUERY}>{"Query Params"}</option>
+                    </select>
+                </div>
+            } else if auth.auth_type == HttpAuthType::OAUTH2 {
+                {render_oauth2(bctx, auth)}
+            }
+        </div>
+    }
+}
+
+fn render_oauth2(bctx: &mut BoltContext, auth: &HttpAuth) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+    let config = &auth.oauth2;
+
+    html! {
+        <>
+            <div class="authrow">
+                <div class="authlabel">{"Grant Type"}</div>
+                <select id="oauth2grantselect" class="authselect pointer" onchange={link.callback(|_| Msg::HttpReqAuthChanged)}>
+                    { for (0..OAuth2GrantType::count()).map(|index| {
+                        let grant_type = OAuth2GrantType::from(index);
+                        html! {
+                            <option value={index.to_string()} selected={grant_type == config.grant_type}>{grant_type.to_string()}</option>
+                        }
+                    })}
+                </select>
+            </div>
+
+            if config.grant_type == OAuth2GrantType::AUTHORIZATION_CODE {
+                <div class="authrow">
+                    <div class="authlabel">{"Auth URL"}</div>
+                    <input id="oauth2authurl" type="text" class="authinput" autocomplete="off" spellcheck="false" value={config.auth_url.clone()} onchange={link.callback(|_| Msg::HttpReqAuthChanged)}/>
+                </div>
+                <div class="authrow">
+                    <div class="authlabel">{"Redirect URL"}</div>
+                    <input id="oauth2redirecturl" type="text" class="authinput" autocomplete="off" spellcheck="false" placeholder="http://localhost:7878/callback" value={config.redirect_url.clone()} onchange={link.callback(|_| Msg::HttpReqAuthChanged)}/>
+                </div>
+            }
+
+            <div class="authrow">
+                <div class="authlabel">{"Token URL"}</div>
+                <input id="oauth2tokenurl" type="text" class="authinput" autocomplete="off" spellcheck="false" value={config.token_url.clone()} onchange={link.callback(|_| Msg::HttpReqAuthChanged)}/>
+            </div>
+            <div class="authrow">
+                <div class="authlabel">{"Client ID"}</div>
+                <input id="oauth2clientid" type="text" class="authinput" autocomplete="off" spellcheck="false" value={config.client_id.clone()} onchange={link.callback(|_| Msg::HttpReqAuthChanged)}/>
+            </div>
+            <div class="authrow">
+                <div class="authlabel">{"Client Secret"}</div>
+                <input id="oauth2clientsecret" type="password" class="authinput" autocomplete="off" value={config.client_secret.clone()} onchange={link.callback(|_| Msg::HttpReqAuthChanged)}/>
+            </div>
+
+            if config.grant_type == OAuth2GrantType::PASSWORD {
+                <div class="authrow">
+                    <div class="authlabel">{"Username"}</div>
+                    <input id="authusername" type="text" class="authinput" autocomplete="off" spellcheck="false" value={auth.username.clone()} onchange={link.callback(|_| Msg::HttpReqAuthChanged)}/>
+                </div>
+                <div class="authrow">
+                    <div class="authlabel">{"Password"}</div>
+                    <input id="authpassword" type="password" class="authinput" autocomplete="off" value={auth.password.clone()} onchange={link.callback(|_| Msg::HttpReqAuthChanged)}/>
+                </div>
+            }
+
+            <div class="authrow">
+                <div class="authlabel">{"Scope"}</div>
+                <input id="oauth2scope" type="text" class="authinput" autocomplete="off" spellcheck="false" value={config.scope.clone()} onchange={link.callback(|_| Msg::HttpReqAuthChanged)}/>
+            </div>
+            <div class="authrow">
+                <div class="authlabel">{"Client Auth"}</div>
+                <select id="oauth2clientauthselect" class="authselect pointer" onchange={link.callback(|_| Msg::HttpReqAuthChanged)}>
+                    <option value="body" selected={!config.client_auth_header}>{"Send in body"}</option>
+                    <option value="header" selected={config.client_auth_header}>{"Send as Basic Auth header"}</option>
+                </select>
+            </div>
+        </>
+    }
+}
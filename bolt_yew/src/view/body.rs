@@ -0,0 +1,227 @@
+use crate::BoltContext;
+use crate::Msg;
+use bolt_common::prelude::*;
+use yew::{html, Html};
+
+pub fn render_http_req_body(bctx: &mut BoltContext, request: &HttpRequest) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+
+    html! {
+        <div class="reqbodycontainer">
+            <div class="bodytypebar">
+                <select id="bodytypeselect" class="authselect pointer" onchange={link.callback(|_| Msg::HttpReqBodyTypeChanged)}>
+                    { for (0..HttpBodyType::count()).map(|index| {
+                        let body_type = HttpBodyType::from(index);
+                        html! {
+                            <option value={index.to_string()} selected={body_type == request.body_type}>{body_type.to_string()}</option>
+                        }
+                    })}
+                </select>
+            </div>
+
+            if request.body_type == HttpBodyType::RAW || request.body_type == HttpBodyType::JSON {
+                <textarea autocomplete="off" spellcheck="false" id="reqbody" class="reqbody" value={request.body.clone()} placeholder="Request body" oninput={link.callback(|_| Msg::HttpReqBodyChanged)}>
+
+                </textarea>
+            } else if request.body_type == HttpBodyType::FORM_URLENCODED {
+                <div class="reqheaders">
+                    <table>
+                        <tr>
+                            <th>{"Key"}</th>
+                            <th>{"Value"}</th>
+                        </tr>
+                        { for request.form.iter().enumerate().map(|(index, row)| render_form_row(bctx, index, request.form.len(), &row[0], &row[1])) }
+                    </table>
+                </div>
+            } else if request.body_type == HttpBodyType::MULTIPART {
+                <div class="reqheaders">
+                    <table>
+                        <tr>
+                            <th>{"Name"}</th>
+                            <th>{"Type"}</th>
+                            <th>{"Value"}</th>
+                        </tr>
+                        { for request.multipart.iter().enumerate().map(|(index, field)| render_multipart_field(bctx, index, request.multipart.len(), field)) }
+                    </table>
+                </div>
+            } else if request.body_type == HttpBodyType::BINARY {
+                <div class="reqauth">
+                    <div class="authrow">
+                        <div class="authlabel">{"File"}</div>
+                        <input id="bodyfileinput" type="text" class="authinput" autocomplete="off" spellcheck="false" placeholder="path to the file to send" value={request.body_file.clone()} onchange={link.callback(|_| Msg::HttpReqBodyFileChanged)}/>
+                    </div>
+                </div>
+            } else if request.body_type == HttpBodyType::GRAPHQL {
+                {render_graphql_editor(bctx, &request.url, &request.graphql, request.graphql_cursor, GraphqlEditorMsgs::http())}
+            }
+        </div>
+    }
+}
+
+// the messages an editor sends, so HTTP bodies and WS subscriptions can share it
+pub struct GraphqlEditorMsgs {
+    pub changed: fn() -> Msg,
+    pub picked: fn(usize) -> Msg,
+    pub fetch: fn() -> Msg,
+}
+
+impl GraphqlEditorMsgs {
+    pub fn http() -> Self {
+        Self {
+            changed: || Msg::HttpReqGraphqlChanged,
+            picked: Msg::HttpReqGraphqlCompletionPicked,
+            fetch: || Msg::FetchGraphqlSchemaPressed,
+        }
+    }
+
+    pub fn ws() -> Self {
+        Self {
+            changed: || Msg::WsGraphqlChanged,
+            picked: Msg::WsGraphqlCompletionPicked,
+            fetch: || Msg::FetchWsGraphqlSchemaPressed,
+        }
+    }
+}
+
+pub fn render_graphql_editor(
+    bctx: &BoltContext,
+    url: &str,
+    graphql: &GraphqlBody,
+    cursor: usize,
+    msgs: GraphqlEditorMsgs,
+) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+
+    let endpoint = graphql_endpoint(&bctx.main_state.resolve_variables(url));
+    let schema = bctx.main_state.graphql_schema(url);
+
+    let status = &bctx.main_state.graphql_status;
+    let is_current = status.endpoint == endpoint;
+
+    let schema_line = if is_current && status.loading {
+        "Fetching the schema...".to_string()
+    } else if is_current && !status.error.is_empty() {
+        status.error.clone()
+    } else if let Some(schema) = schema {
+        format!(
+            "{} types in the schema of {}",
+            schema.types.len(),
+            schema.endpoint
+        )
+    } else {
+        "No schema yet, fetch it to get completions and checks".to_string()
+    };
+
+    let completions = schema
+        .map(|schema| complete_graphql(&graphql.query, cursor, schema))
+        .unwrap_or_default();
+
+    let diagnostics = check_graphql(graphql, schema);
+
+    let GraphqlEditorMsgs {
+        changed,
+        picked,
+        fetch,
+    } = msgs;
+
+    html! {
+        <div class="graphqlbody">
+            <div class="graphqlschemabar">
+                <button class="ws-connect-btn pointer" onclick={link.callback(move |_| fetch())}>{if schema.is_some() { "Refresh schema" } else { "Fetch schema" }}</button>
+                <div class={if is_current && !status.error.is_empty() { "graphqlschemastatus transfererror" } else { "graphqlschemastatus" }}>{schema_line}</div>
+            </div>
+
+            <textarea autocomplete="off" spellcheck="false" id="graphqlquery" class="reqbody graphqlquery" value={graphql.query.clone()} placeholder="query { ... }" oninput={link.callback(move |_| changed())} onkeyup={link.callback(move |_| changed())} onclick={link.callback(move |_| changed())}>
+
+            </textarea>
+
+            if !completions.items.is_empty() {
+                <div class="graphqlcompletions">
+                    { for completions.items.iter().enumerate().take(30).map(|(index, item)| html! {
+                        <div class="graphqlcompletion pointer" title={item.detail.clone()} onclick={link.callback(move |_| picked(index))}>
+                            {item.label.clone()}
+                            <span class="graphqlcompletiondetail">{item.detail.clone()}</span>
+                        </div>
+                    })}
+                </div>
+            }
+
+            if !diagnostics.is_empty() {
+                <div class="graphqldiagnostics">
+                    { for diagnostics.iter().map(|diagnostic| html! { <div>{diagnostic.to_string()}</div> }) }
+                </div>
+            }
+
+            <div class="graphqllabel">{"Variables"}</div>
+            <textarea autocomplete="off" spellcheck="false" id="graphqlvariables" class="reqbody graphqlvariables" value={graphql.variables.clone()} placeholder="{\"id\": 1}" oninput={link.callback(move |_| changed())}>
+
+            </textarea>
+
+            <div class="authrow">
+                <div class="authlabel">{"Operation"}</div>
+                <input id="graphqloperation" type="text" class="authinput" autocomplete="off" spellcheck="false" placeholder="the operation to run when the query has several" value={graphql.operation_name.clone()} oninput={link.callback(move |_| changed())}/>
+            </div>
+        </div>
+    }
+}
+
+fn render_form_row(
+    bctx: &mut BoltContext,
+    index: usize,
+    length: usize,
+    key: &String,
+    value: &String,
+) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+
+    html! {
+        <tr>
+            <td><input id={"formkey".to_string() + &index.to_string()} type="text" class="tableinput" value={key.to_string()} onchange={link.callback(move |_| Msg::HttpReqFormChanged(index))}/></td>
+            <td class="tableline">
+                <input id={"formvalue".to_string() + &index.to_string()} type="text" class="tableinput" value={value.to_string()} onchange={link.callback(move |_| Msg::HttpReqFormChanged(index))}/>
+                if index == length - 1 {
+                    <div class="pointer" onclick={link.callback(|_| Msg::HttpReqAddFormField)}>
+                        <svg viewBox="0 0 1024 1024" fill="currentColor" height="20px" width="20px" ><defs><style /></defs><path d="M482 152h60q8 0 8 8v704q0 8-8 8h-60q-8 0-8-8V160q0-8 8-8z" /><path d="M176 474h672q8 0 8 8v60q0 8-8 8H176q-8 0-8-8v-60q0-8 8-8z" /></svg>
+                    </div>
+                }else {
+                    <div class="pointer" onclick={link.callback(move |_| Msg::HttpReqRemoveFormField(index))}>
+                        <svg viewBox="0 0 1024 1024" fill="currentColor" height="1em" width="1em"> <path d="M864 256H736v-80c0-35.3-28.7-64-64-64H352c-35.3 0-64 28.7-64 64v80H160c-17.7 0-32 14.3-32 32v32c0 4.4 3.6 8 8 8h60.4l24.7 523c1.6 34.1 29.8 61 63.9 61h454c34.2 0 62.3-26.8 63.9-61l24.7-523H888c4.4 0 8-3.6 8-8v-32c0-17.7-14.3-32-32-32zm-200 0H360v-72h304v72z" /> </svg>
+                    </div>
+                }
+            </td>
+        </tr>
+    }
+}
+
+fn render_multipart_field(
+    bctx: &mut BoltContext,
+    index: usize,
+    length: usize,
+    field: &MultipartField,
+) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+
+    html! {
+        <tr>
+            <td><input id={"multipartname".to_string() + &index.to_string()} type="text" class="tableinput" value={field.name.clone()} onchange={link.callback(move |_| Msg::HttpReqMultipartChanged(index))}/></td>
+            <td>
+                <select id={"multiparttype".to_string() + &index.to_string()} class="tableselect pointer" onchange={link.callback(move |_| Msg::HttpReqMultipartChanged(index))}>
+                    <option value="text" selected={!field.is_file}>{"Text"}</option>
+                    <option value="file" selected={field.is_file}>{"File"}</option>
+                </select>
+            </td>
+            <td class="tableline">
+                <input id={"multipartvalue".to_string() + &index.to_string()} type="text" class="tableinput" placeholder={if field.is_file { "path to file" } else { "" }} value={field.value.clone()} onchange={link.callback(move |_| Msg::HttpReqMultipartChanged(index))}/>
+                if index == length - 1 {
+                    <div class="pointer" onclick={link.callback(|_| Msg::HttpReqAddMultipartField)}>
+                        <svg viewBox="0 0 1024 1024" fill="currentColor" height="20px" width="20px" ><defs><style /></defs><path d="M482 152h60q8 0 8 8v704q0 8-8 8h-60q-8 0-8-8V160q0-8 8-8z" /><path d="M176 474h672q8 0 8 8v60q0 8-8 8H176q-8 0-8-8v-60q0-8 8-8z" /></svg>
+                    </div>
+                }else {
+                    <div class="pointer" onclick={link.callback(move |_| Msg::HttpReqRemoveMultipartField(index))}>
+                        <svg viewBox="0 0 1024 1024" fill="currentColor" height="1em" width="1em"> <path d="M864 256H736v-80c0-35.3-28.7-64-64-64H352c-35.3 0-64 28.7-64 64v80H160c-17.7 0-32 14.3-32 32v32c0 4.4 3.6 8 8 8h60.4l24.7 523c1.6 34.1 29.8 61 63.9 61h454c34.2 0 62.3-26.8 63.9-61l24.7-523H888c4.4 0 8-3.6 8-8v-32c0-17.7-14.3-32-32-32zm-200 0H360v-72h304v72z" /> </svg>
+                    </div>
+                }
+            </td>
+        </tr>
+    }
+}
@@ -0,0 +1,40 @@
+use crate::BoltContext;
+use crate::Msg;
+use bolt_common::prelude::*;
+use yew::{html, Html};
+
+pub fn render_http_req_code(bctx: &mut BoltContext, request: &HttpRequest) -> Html {
+    let language = bctx.main_state.snippet_language;
+
+    // the code gets the values of the active environment, like a send would
+    let mut request = request.clone();
+
+    if let Some(env) = bctx.main_state.active_environment() {
+        request.resolve_variables(env);
+    }
+
+    let code = generate_snippet(&request, language);
+
+    let copy_icon = crate::view::icons::copy_icon(20, 20);
+
+    let link = bctx.link.as_ref().unwrap();
+
+    html! {
+        <div class="reqbodycontainer">
+            <div class="bodytypebar codebar">
+                <select id="snippetlanguageselect" class="authselect pointer" onchange={link.callback(|_| Msg::SnippetLanguageChanged)}>
+                    { for (0..SnippetLanguage::count()).map(|index| {
+                        let option = SnippetLanguage::from(index);
+                        html! {
+                            <option value={index.to_string()} selected={option == language}>{option.to_string()}</option>
+                        }
+                    })}
+                </select>
+
+                <div class="pointer copy-msg-icon" title="copy code" onclick={link.callback(|_| Msg::CopySnippetPressed)}>{copy_icon}</div>
+            </div>
+
+            <pre class="snippet">{code}</pre>
+        </div>
+    }
+}
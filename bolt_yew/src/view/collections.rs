@@ -16,11 +16,8 @@ pub fn collections_view(bctx: &mut BoltContext) -> Html {
                 <div class="resizer"></div>
 
                 <div class="content">
-                    // {view::request::collection_request(bctx)}
-
-                    <div class="resizer2"></div>
-
-                    // {view::response::collection_response(bctx)}
+                    {view::transfer::render_transfer(bctx)}
+                    {view::runner::render_runner(bctx)}
                 </div>
             </div>
 
@@ -0,0 +1,105 @@
+use crate::view;
+use crate::BoltContext;
+use crate::Msg;
+use yew::{html, Html};
+
+use bolt_common::prelude::*;
+
+pub fn cookies_view(bctx: &mut BoltContext) -> Html {
+    html! {
+       <body>
+            {view::navbar::get_navbar(bctx)}
+
+            <div class="main">
+                <div class="sidebars">
+                    {view::sidebar1::sidebar(bctx, bctx.main_state.page)}
+                    {view::sidebar2::sidebar_cookies(bctx)}
+                </div>
+
+                <div class="resizer"></div>
+
+                <div class="content">
+                    {cookie_editor(bctx)}
+                </div>
+            </div>
+        </body>
+    }
+}
+
+fn cookie_editor(bctx: &mut BoltContext) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+
+    let domain = bctx.main_state.cookie_domain.clone();
+
+    let can_display = bctx.main_state.cookies.iter().any(|c| c.domain == domain);
+
+    // indexes point into main_state.cookies so edits do not need to know the domain
+    let cookies: Vec<(usize, Cookie)> = bctx
+        .main_state
+        .cookies
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| c.domain == domain)
+        .map(|(index, c)| (index, c.clone()))
+        .collect();
+
+    let clear_domain = domain.clone();
+
+    html! {
+        <div class="req">
+        if can_display {
+            <div class="requestbar">
+                <div class="cookie-domain">{if domain.is_empty() { "(no domain)".to_string() } else { domain.clone() }}</div>
+
+                <button class="ws-disconnect-btn pointer" type="button" onclick={link.callback(move |_| Msg::ClearCookieDomain(clear_domain.clone()))}>{"Clear"}</button>
+            </div>
+
+            <div class="reqtabs">
+                <div class="tab tabSelected">{"Cookies"}</div>
+            </div>
+
+            <div class="tabcontent">
+                <div class="reqheaders">
+                    <table>
+                        <tr>
+                            <th>{"Name"}</th>
+                            <th>{"Value"}</th>
+                            <th>{"Domain"}</th>
+                            <th>{"Path"}</th>
+                            <th>{"Expires"}</th>
+                        </tr>
+                        { for cookies.iter().map(|(index, cookie)| render_cookie(bctx, *index, cookie)) }
+                    </table>
+                </div>
+            </div>
+        }
+        </div>
+    }
+}
+
+fn render_cookie(bctx: &BoltContext, index: usize, cookie: &Cookie) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+
+    let expires = if cookie.expires == 0 {
+        "Session".to_string()
+    } else {
+        let date = js_sys::Date::new(&wasm_bindgen::JsValue::from_f64(cookie.expires as f64));
+
+        String::from(date.to_utc_string())
+    };
+
+    html! {
+        <tr>
+            <td><input id={"cookiename".to_string() + &index.to_string()} type="text" class="tableinput" value={cookie.name.clone()} onchange={link.callback(move |_| Msg::CookieChanged(index))}/></td>
+            <td><input id={"cookievalue".to_string() + &index.to_string()} type="text" class="tableinput" value={cookie.value.clone()} onchange={link.callback(move |_| Msg::CookieChanged(index))}/></td>
+            <td><input id={"cookiedomain".to_string() + &index.to_string()} type="text" class="tableinput" value={cookie.domain.clone()} onchange={link.callback(move |_| Msg::CookieChanged(index))}/></td>
+            <td><input id={"cookiepath".to_string() + &index.to_string()} type="text" class="tableinput" value={cookie.path.clone()} onchange={link.callback(move |_| Msg::CookieChanged(index))}/></td>
+            <td class="tableline">
+                <div class="cookie-expires">{expires}</div>
+                <div class="pointer" onclick={link.callback(move |_| Msg::RemoveCookie(index))}>
+                    <svg viewBox="0 0 1024 1024" fill="currentColor" height="1em" width="1em"> <path d="M864 256H736v-80c0-35.3-28.7-64-64-64H352c-35.3 0-64 28.7-64 64v80H160c-17.7 0-32 14.3-32 32v32c0 4.4 3.6 8 8 8h60.4l24.7 523c1.6 34.1 29.8 61 63.9 61h454c34.2 0 62.3-26.8 63.9-61l24.7-523H888c4.4 0 8-3.6 8-8v-32c0-17.7-14.3-32-32-32zm-200 0H360v-72h304v72z" /> </svg>
+                </div>
+            </td>
+        </tr>
+    }
+}
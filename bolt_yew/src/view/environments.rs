@@ -0,0 +1,101 @@
+use crate::view;
+use crate::BoltContext;
+use crate::Msg;
+use yew::{html, Html};
+
+use bolt_common::prelude::*;
+
+pub fn environments_view(bctx: &mut BoltContext) -> Html {
+    html! {
+       <body>
+            {view::navbar::get_navbar(bctx)}
+
+            <div class="main">
+                <div class="sidebars">
+                    {view::sidebar1::sidebar(bctx, bctx.main_state.page)}
+                    {view::sidebar2::sidebar_environments(bctx)}
+                </div>
+
+                <div class="resizer"></div>
+
+                <div class="content">
+                    {environment_editor(bctx)}
+                </div>
+            </div>
+        </body>
+    }
+}
+
+fn environment_editor(bctx: &mut BoltContext) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+
+    let can_display = !bctx.main_state.environments.is_empty();
+
+    let mut env = Environment::new();
+
+    if can_display {
+        env = bctx.main_state.environments[bctx.main_state.env_current].clone();
+    }
+
+    let is_active = bctx.main_state.env_active == Some(bctx.main_state.env_current);
+
+    html! {
+        <div class="req">
+        if can_display {
+            <div class="requestbar">
+                <input id="envnameinput" class="urlinput" type="text" autocomplete="off" spellcheck="false" value={env.name.clone()} placeholder="environment name" oninput={link.callback(|_| Msg::EnvNameChanged)} />
+
+                if is_active {
+                    <button class="ws-disconnect-btn pointer" type="button" onclick={link.callback(|_| Msg::DeactivateEnvironment)}>{"Deactivate"}</button>
+                } else {
+                    <button class="ws-connect-btn pointer" type="button" onclick={link.callback(|_| Msg::ActivateEnvironment)}>{"Activate"}</button>
+                }
+            </div>
+
+            <div class="reqtabs">
+                <div class="tab tabSelected">{"Variables"}</div>
+            </div>
+
+            <div class="tabcontent">
+                <div class="reqheaders">
+                    <table>
+                        <tr>
+                            <th>{"Variable"}</th>
+                            <th>{"Value"}</th>
+                        </tr>
+                        { for env.variables.iter().enumerate().map(|(index, var)| render_env_variable(bctx, index, env.variables.len(), &var[0], &var[1])) }
+                    </table>
+                </div>
+            </div>
+        }
+        </div>
+    }
+}
+
+fn render_env_variable(
+    bctx: &BoltContext,
+    index: usize,
+    length: usize,
+    key: &String,
+    value: &String,
+) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+
+    html! {
+        <tr>
+            <td><input id={"envkey".to_string() + &index.to_string()} type="text" class="tableinput" value={key.to_string()} onchange={link.callback(move |_| Msg::EnvVariableChanged(index))}/></td>
+            <td class="tableline">
+                <input id={"envvalue".to_string() + &index.to_string()} type="text" class="tableinput" value={value.to_string()} onchange={link.callback(move |_| Msg::EnvVariableChanged(index))}/>
+                if index == length - 1 {
+                    <div class="pointer" onclick={link.callback(|_| Msg::EnvAddVariable)}>
+                        <svg viewBox="0 0 1024 1024" fill="currentColor" height="20px" width="20px" ><defs><style /></defs><path d="M482 152h60q8 0 8 8v704q0 8-8 8h-60q-8 0-8-8V160q0-8 8-8z" /><path d="M176 474h672q8 0 8 8v60q0 8-8 8H176q-8 0-8-8v-60q0-8 8-8z" /></svg>
+                    </div>
+                }else {
+                    <div class="pointer" onclick={link.callback(move |_| Msg::EnvRemoveVariable(index))}>
+                        <svg viewBox="0 0 1024 1024" fill="currentColor" height="1em" width="1em"> <path d="M864 256H736v-80c0-35.3-28.7-64-64-64H352c-35.3 0-64 28.7-64 64v80H160c-17.7 0-32 14.3-32 32v32c0 4.4 3.6 8 8 8h60.4l24.7 523c1.6 34.1 29.8 61 63.9 61h454c34.2 0 62.3-26.8 63.9-61l24.7-523H888c4.4 0 8-3.6 8-8v-32c0-17.7-14.3-32-32-32zm-200 0H360v-72h304v72z" /> </svg>
+                    </div>
+                }
+            </td>
+        </tr>
+    }
+}
@@ -0,0 +1,60 @@
+use crate::BoltContext;
+use crate::Msg;
+use bolt_common::prelude::*;
+use yew::{html, Html};
+
+pub fn render_http_req_extractions(bctx: &mut BoltContext, request: &HttpRequest) -> Html {
+    let rows: Vec<Html> = request
+        .extractions
+        .iter()
+        .enumerate()
+        .map(|(index, rule)| render_extraction(bctx, index, rule))
+        .collect();
+
+    let link = bctx.link.as_ref().unwrap();
+
+    html! {
+        <div class="reqheaders reqtests">
+            <table>
+                <tr>
+                    <th></th>
+                    <th>{"Source"}</th>
+                    <th>{"Expression"}</th>
+                    <th>{"Variable"}</th>
+                </tr>
+                { for rows }
+            </table>
+
+            <button class="addtestbtn pointer" type="button" onclick={link.callback(|_| Msg::HttpReqAddExtraction)}>{"Add rule"}</button>
+        </div>
+    }
+}
+
+fn render_extraction(bctx: &mut BoltContext, index: usize, rule: &ExtractionRule) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+
+    html! {
+        <tr>
+            <td><input id={"extractionenabled".to_string() + &index.to_string()} type="checkbox" class="pointer" checked={rule.enabled} onchange={link.callback(move |_| Msg::HttpReqExtractionChanged(index))}/></td>
+            <td>
+                <select id={"extractionsource".to_string() + &index.to_string()} class="tableselect pointer" onchange={link.callback(move |_| Msg::HttpReqExtractionChanged(index))}>
+                    { for (0..ExtractionSource::count()).map(|source_index| {
+                        let source = ExtractionSource::from(source_index);
+                        html! {
+                            <option value={source_index.to_string()} selected={source == rule.source}>{source.to_string()}</option>
+                        }
+                    })}
+                </select>
+            </td>
+            <td>
+                <input id={"extractionexpression".to_string() + &index.to_string()} type="text" class="tableinput" autocomplete="off" spellcheck="false" placeholder={rule.source.hint()} value={rule.expression.clone()} onchange={link.callback(move |_| Msg::HttpReqExtractionChanged(index))}/>
+            </td>
+            <td class="tableline">
+                <input id={"extractionvariable".to_string() + &index.to_string()} type="text" class="tableinput" autocomplete="off" spellcheck="false" placeholder="variable name" value={rule.variable.clone()} onchange={link.callback(move |_| Msg::HttpReqExtractionChanged(index))}/>
+                <div class="pointer" onclick={link.callback(move |_| Msg::HttpReqRemoveExtraction(index))}>
+                    <svg viewBox="0 0 1024 1024" fill="currentColor" height="1em" width="1em"> <path d="M864 256H736v-80c0-35.3-28.7-64-64-64H352c-35.3 0-64 28.7-64 64v80H160c-17.7 0-32 14.3-32 32v32c0 4.4 3.6 8 8 8h60.4l24.7 523c1.6 34.1 29.8 61 63.9 61h454c34.2 0 62.3-26.8 63.9-61l24.7-523H888c4.4 0 8-3.6 8-8v-32c0-17.7-14.3-32-32-32zm-200 0H360v-72h304v72z" /> </svg>
+                </div>
+            </td>
+        </tr>
+    }
+}
@@ -20,6 +20,16 @@ pub fn http_icon(height: u32, width: u32) -> Html {
     }
 }
 
+pub fn save_icon(height: u32, width: u32) -> Html {
+    html! {
+    <svg viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2" height={height.to_string() + "px"} width={width.to_string() + "px"}>
+      <path d="M21 15v4a2 2 0 01-2 2H5a2 2 0 01-2-2v-4" />
+      <path d="M7 10l5 5 5-5" />
+      <path d="M12 15V3" />
+    </svg>
+    }
+}
+
 pub fn copy_icon(height: u32, width: u32) -> Html {
     html! {
     <svg fill="none" viewBox="0 0 15 15" height={height.to_string() + "px"} width={width.to_string() + "px"}>
@@ -49,6 +59,51 @@ pub fn tcp_icon(height: u32, width: u32) -> Html {
     }
 }
 
+pub fn sse_icon(height: u32, width: u32) -> Html {
+    html! {
+        <svg viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2" height={height.to_string() + "px"} width={width.to_string() + "px"}>
+          <path d="M4 11a9 9 0 0 1 9 9" />
+          <path d="M4 4a16 16 0 0 1 16 16" />
+          <circle cx="5" cy="19" r="1" />
+        </svg>
+    }
+}
+
+pub fn grpc_icon(height: u32, width: u32) -> Html {
+    html! {
+        <svg viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2" height={height.to_string() + "px"} width={width.to_string() + "px"}>
+          <circle cx="5" cy="12" r="2" />
+          <circle cx="19" cy="5" r="2" />
+          <circle cx="19" cy="19" r="2" />
+          <path d="M7 11l10-5" />
+          <path d="M7 13l10 5" />
+        </svg>
+    }
+}
+
+pub fn cookie_icon(height: u32, width: u32) -> Html {
+    html! {
+        <svg viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2" height={height.to_string() + "px"} width={width.to_string() + "px"}>
+          <path d="M12 2a10 10 0 1 0 10 10 4 4 0 0 1-5-5 4 4 0 0 1-5-5z" />
+          <path d="M8.5 8.5v.01" />
+          <path d="M16 15.5v.01" />
+          <path d="M12 12v.01" />
+          <path d="M11 17v.01" />
+          <path d="M7 14v.01" />
+        </svg>
+    }
+}
+
+pub fn environment_icon(height: u32, width: u32) -> Html {
+    html! {
+        <svg viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2" height={height.to_string() + "px"} width={width.to_string() + "px"}>
+          <path d="M12 2L2 7l10 5 10-5-10-5z" />
+          <path d="M2 17l10 5 10-5" />
+          <path d="M2 12l10 5 10-5" />
+        </svg>
+    }
+}
+
 // pub fn udp_icon(height: u32, width: u32) -> Html {
 //     html! {
 //         <svg viewBox="0 0 24 24" fill="currentColor" height={height.to_string() + "px"} width={width.to_string() + "px"}>
@@ -68,8 +123,8 @@ pub fn tcp_icon(height: u32, width: u32) -> Html {
 //     }
 // }
 
-// pub fn collections_icon(height: u32, width: u32) -> Html {
-//     html! {
-//         <svg stroke="currentColor" fill="currentColor" stroke-width="0" viewBox="0 0 16 16" height={height.to_string() + "px"} width={width.to_string() + "px"} xmlns="http://www.w3.org/2000/svg"><path d="M0 13a1.5 1.5 0 0 0 1.5 1.5h13A1.5 1.5 0 0 0 16 13V6a1.5 1.5 0 0 0-1.5-1.5h-13A1.5 1.5 0 0 0 0 6v7zM2 3a.5.5 0 0 0 .5.5h11a.5.5 0 0 0 0-1h-11A.5.5 0 0 0 2 3zm2-2a.5.5 0 0 0 .5.5h7a.5.5 0 0 0 0-1h-7A.5.5 0 0 0 4 1z"></path></svg>
-//     }
-// }
+pub fn collections_icon(height: u32, width: u32) -> Html {
+    html! {
+        <svg stroke="currentColor" fill="currentColor" stroke-width="0" viewBox="0 0 16 16" height={height.to_string() + "px"} width={width.to_string() + "px"} xmlns="http://www.w3.org/2000/svg"><path d="M0 13a1.5 1.5 0 0 0 1.5 1.5h13A1.5 1.5 0 0 0 16 13V6a1.5 1.5 0 0 0-1.5-1.5h-13A1.5 1.5 0 0 0 0 6v7zM2 3a.5.5 0 0 0 .5.5h11a.5.5 0 0 0 0-1h-11A.5.5 0 0 0 2 3zm2-2a.5.5 0 0 0 .5.5h7a.5.5 0 0 0 0-1h-7A.5.5 0 0 0 4 1z"></path></svg>
+    }
+}
@@ -1,15 +1,27 @@
+pub mod auth;
+pub mod body;
+pub mod code;
 pub mod collections;
 mod console;
+pub mod cookies;
+pub mod environments;
+pub mod extract;
 pub mod header;
 pub mod http;
 pub mod tcp;
 pub mod udp;
+pub mod sse;
+pub mod grpc;
 pub mod servers;
+pub mod settings;
+pub mod tests;
+pub mod transfer;
 pub mod navbar;
 pub mod param;
 pub mod msg;
 mod request;
 mod response;
+pub mod runner;
 pub mod sidebar1;
 mod sidebar2;
 pub mod websockets;
@@ -4,11 +4,12 @@ use bolt_common::prelude::*;
 use yew::html::Scope;
 use yew::{html, Html};
 
-pub fn render_ws_msg(msg: &WsMessage, link: &Scope<BoltApp>, index: usize) -> Html {
+// shared by the WebSocket and gRPC pages, `copy` is what the copy icon sends
+pub fn render_ws_msg(msg: &WsMessage, link: &Scope<BoltApp>, copy: Msg) -> Html {
     let txt = msg.txt.clone();
 
-    let txt = if txt.len() > 60 {
-        format!("{}...", &txt[0..60])
+    let txt = if txt.chars().count() > 60 {
+        format!("{}...", txt.chars().take(60).collect::<String>())
     } else {
         txt
     };
@@ -25,11 +26,14 @@ pub fn render_ws_msg(msg: &WsMessage, link: &Scope<BoltApp>, index: usize) -> Ht
                 <label class="atab-label" for={msg.msg_id.clone()}>
                      <div class="ws-msg-left">
                         <div class="ws-in-arrow">{"↓"}</div>
+                        if !msg.label.is_empty() {
+                            <div class="ws-msg-label">{msg.label.clone()}</div>
+                        }
                         <div class="ws-msg-txt">{txt}</div>
                      </div>
 
                     <div class="ws-msg-right">
-                        <div class="copy-msg-icon" title="copy message" onclick={link.callback(move |_| Msg::CopyWsMsgClicked(index))} >{copy_icon}</div>
+                        <div class="copy-msg-icon" title="copy message" onclick={link.callback(move |_| copy.clone())} >{copy_icon}</div>
                         {time}
                         <div class="ws-open-arrow">{"❯"}</div>
                     </div>
@@ -49,11 +53,14 @@ pub fn render_ws_msg(msg: &WsMessage, link: &Scope<BoltApp>, index: usize) -> Ht
                 <label class="atab-label" for={msg.msg_id.clone()}>
                      <div class="ws-msg-left">
                         <div class="ws-out-arrow">{"↑"}</div>
+                        if !msg.label.is_empty() {
+                            <div class="ws-msg-label">{msg.label.clone()}</div>
+                        }
                         <div class="ws-msg-txt">{txt}</div>
                      </div>
 
                     <div class="ws-msg-right">
-                        <div class="copy-msg-icon" title="copy message" onclick={link.callback(move |_| Msg::CopyWsMsgClicked(index))} >{copy_icon}</div>
+                        <div class="copy-msg-icon" title="copy message" onclick={link.callback(move |_| copy.clone())} >{copy_icon}</div>
                         {time}
                         <div class="ws-open-arrow">{"❯"}</div>
                     </div>
@@ -200,6 +207,50 @@ pub fn render_udp_msg(msg: &UdpMessage, link: &Scope<BoltApp>, index: usize) ->
     }
 }
 
+pub fn render_sse_event(msg: &SseEvent, link: &Scope<BoltApp>, index: usize) -> Html {
+    let txt = msg.data.clone();
+
+    // event data is text, cut on a char boundary
+    let txt = if txt.chars().count() > 60 {
+        format!("{}...", txt.chars().take(60).collect::<String>())
+    } else {
+        txt
+    };
+
+    let time = format_time(msg.timestamp);
+
+    let copy_icon = crate::view::icons::copy_icon(20, 20);
+
+    html! {
+      <div class="atab">
+        <input type="checkbox" id={msg.msg_id.clone()} />
+        <label class="atab-label" for={msg.msg_id.clone()}>
+             <div class="ws-msg-left">
+                <div class="ws-in-arrow">{"↓"}</div>
+                <div class="ws-msg-label">{msg.event.clone()}</div>
+                <div class="ws-msg-txt">{txt}</div>
+             </div>
+
+            <div class="ws-msg-right">
+                <div class="copy-msg-icon" title="copy data" onclick={link.callback(move |_| Msg::CopySseMsgClicked(index))} >{copy_icon}</div>
+                {time}
+                <div class="ws-open-arrow">{"❯"}</div>
+            </div>
+        </label>
+
+        <div class="atab-content">
+          if !msg.id.is_empty() {
+            <div class="sse-field">{format!("id: {}", msg.id)}</div>
+          }
+          if let Some(retry) = msg.retry {
+            <div class="sse-field">{format!("retry: {}", retry)}</div>
+          }
+          {msg.data.clone()}
+        </div>
+      </div>
+    }
+}
+
 pub fn format_time(timestamp_ms: u64) -> String {
     let js = js_sys::Date::new(&wasm_bindgen::JsValue::from_f64(timestamp_ms as f64));
     let hour = js.get_hours() as u8;
@@ -15,6 +15,15 @@ pub fn get_navbar(bctx: &mut BoltContext) -> Html {
             </div>
 
             <div class="nav-links">
+                <select id="envselect" class="envselect pointer" onchange={link.callback(|_| Msg::EnvSelectChanged)}>
+                    <option value="none" selected={bctx.main_state.env_active.is_none()}>{"No Environment"}</option>
+                    { for bctx.main_state.environments.iter().enumerate().map(|(index, env)| {
+                        html! {
+                            <option value={index.to_string()} selected={bctx.main_state.env_active == Some(index)}>{env.name.clone()}</option>
+                        }
+                    })}
+                </select>
+
                 <div class="githubicon pointer" onclick={link.callback(|_| Msg::GithubPressed)}>
                     {github_icon}
                 </div>
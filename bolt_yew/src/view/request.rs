@@ -1,9 +1,11 @@
+use crate::helpers::enums::GrpcOutTabs;
 use crate::helpers::enums::HttpReqTabs;
+use crate::helpers::enums::SseOutTabs;
 use crate::helpers::enums::WsOutTabs;
 use crate::view;
 use crate::BoltContext;
 use crate::Msg;
-use yew::KeyboardEvent;
+use yew::{InputEvent, KeyboardEvent};
 use yew::{html, Html};
 
 use bolt_common::prelude::*;
@@ -21,6 +23,8 @@ pub fn http_request(bctx: &mut BoltContext) -> Html {
 
     let selected_method = request.method.to_string();
 
+    let copy_icon = crate::view::icons::copy_icon(20, 20);
+
     html! {
         <div class="req">
         if can_display {
@@ -37,22 +41,31 @@ pub fn http_request(bctx: &mut BoltContext) -> Html {
                     </select>
                 </div>
 
-                <input id="urlinput" class="urlinput" type="text" autocomplete="off" spellcheck="false" value={request.url.clone()} placeholder="http://" onkeydown={link.callback(|e: KeyboardEvent| { if e.key() == "Enter" { Msg::SendHttpPressed } else { Msg::Nothing } })}  oninput={link.callback(|_|{ Msg::UrlChanged })} />
+                <input id="urlinput" class="urlinput" type="text" autocomplete="off" spellcheck="false" value={request.url.clone()} placeholder="http://" onkeydown={link.callback(|e: KeyboardEvent| { if e.key() == "Enter" { Msg::SendHttpPressed } else { Msg::Nothing } })}  oninput={link.callback(|e: InputEvent| { if e.input_type() == "insertFromPaste" { Msg::UrlPasted } else { Msg::UrlChanged } })} />
 
-                <button class="sendbtn pointer" type="button" onclick={link.callback(|_| Msg::SendHttpPressed)}>{"Send"}</button>
+                if request.loading {
+                    <button class="ws-disconnect-btn pointer" type="button" onclick={link.callback(|_| Msg::CancelHttpPressed)}>{"Cancel"}</button>
+                } else {
+                    <button class="sendbtn pointer" type="button" onclick={link.callback(|_| Msg::SendHttpPressed)}>{"Send"}</button>
+                }
+
+                <div class="pointer copy-msg-icon" title="copy as cURL" onclick={link.callback(|_| Msg::CopyHttpCurlPressed)}>{copy_icon}</div>
             </div>
 
             <div class="reqtabs">
                 <div id="req_body_tab" class={if is_tab_selected(&request.req_tab, HttpReqTabs::Body) {"tab pointer tabSelected"} else {"tab pointer"}} onclick={link.callback(|_| Msg::HttpReqBodyPressed)}>{"Body"}</div>
                 <div id="req_params_tab" class={if is_tab_selected(&request.req_tab, HttpReqTabs::Params) {"tab pointer tabSelected"} else {"tab pointer"}} onclick={link.callback(|_| Msg::HttpReqParamsPressed)}>{"Params"}</div>
                 <div id="req_headers_tab" class={if is_tab_selected(&request.req_tab, HttpReqTabs::Headers) {"tab pointer tabSelected"} else {"tab pointer"}} onclick={link.callback(|_| Msg::HttpReqHeadersPressed)}>{"Headers"}</div>
+                <div id="req_auth_tab" class={if is_tab_selected(&request.req_tab, HttpReqTabs::Auth) {"tab pointer tabSelected"} else {"tab pointer"}} onclick={link.callback(|_| Msg::HttpReqAuthPressed)}>{"Auth"}</div>
+                <div id="req_settings_tab" class={if is_tab_selected(&request.req_tab, HttpReqTabs::Settings) {"tab pointer tabSelected"} else {"tab pointer"}} onclick={link.callback(|_| Msg::HttpReqSettingsPressed)}>{"Settings"}</div>
+                <div id="req_tests_tab" class={if is_tab_selected(&request.req_tab, HttpReqTabs::Tests) {"tab pointer tabSelected"} else {"tab pointer"}} onclick={link.callback(|_| Msg::HttpReqTestsPressed)}>{"Tests"}</div>
+                <div id="req_extract_tab" class={if is_tab_selected(&request.req_tab, HttpReqTabs::Extract) {"tab pointer tabSelected"} else {"tab pointer"}} onclick={link.callback(|_| Msg::HttpReqExtractPressed)}>{"Extract"}</div>
+                <div id="req_code_tab" class={if is_tab_selected(&request.req_tab, HttpReqTabs::Code) {"tab pointer tabSelected"} else {"tab pointer"}} onclick={link.callback(|_| Msg::HttpReqCodePressed)}>{"Code"}</div>
             </div>
 
             <div class="tabcontent">
                 if is_tab_selected(&request.req_tab, HttpReqTabs::Body) {
-                    <textarea autocomplete="off" spellcheck="false" id="reqbody" class="reqbody" value={request.body.clone()} placeholder="Request body" oninput={link.callback(|_| Msg::HttpReqBodyChanged)}>
-
-                    </textarea>
+                    {view::body::render_http_req_body(bctx, &request)}
                 } else if is_tab_selected(&request.req_tab, HttpReqTabs::Params) {
                     <div class="reqheaders">
                         <table>
@@ -74,6 +87,16 @@ pub fn http_request(bctx: &mut BoltContext) -> Html {
                             { for request.headers.iter().enumerate().map(|(index, header)| view::header::render_http_req_header(bctx, index, request.headers.len(), &header[0], &header[1])) }
                         </table>
                     </div>
+                } else if is_tab_selected(&request.req_tab, HttpReqTabs::Auth) {
+                    {view::auth::render_http_req_auth(bctx, &request.auth)}
+                } else if is_tab_selected(&request.req_tab, HttpReqTabs::Settings) {
+                    {view::settings::render_http_req_settings(bctx, &request)}
+                } else if is_tab_selected(&request.req_tab, HttpReqTabs::Tests) {
+                    {view::tests::render_http_req_tests(bctx, &request)}
+                } else if is_tab_selected(&request.req_tab, HttpReqTabs::Extract) {
+                    {view::extract::render_http_req_extractions(bctx, &request)}
+                } else if is_tab_selected(&request.req_tab, HttpReqTabs::Code) {
+                    {view::code::render_http_req_code(bctx, &request)}
                 }
             </div>
         }
@@ -209,10 +232,24 @@ pub fn ws_out(bctx: &mut BoltContext) -> Html {
         connection = bctx.main_state.ws_connections[bctx.main_state.ws_current].clone();
     }
 
+    let is_graphql = connection.mode == WsMode::GRAPHQL;
+    let send_label = if is_graphql { "Subscribe" } else { "Send" };
+
     html! {
         <div class="req">
         if can_display {
             <div class="requestbar">
+                <div class="">
+                    <select id="wsmodeselect" class="methodselect pointer" disabled={connection.connected || connection.connecting} onchange={link.callback(|_| Msg::WsModeChanged)}>
+                        { for (0..WsMode::count()).map(|index| {
+                            let mode = WsMode::from(index);
+                            html! {
+                                <option value={index.to_string()} selected={mode == connection.mode}>{mode.to_string()}</option>
+                            }
+                        })}
+                    </select>
+                </div>
+
                 <input id="urlinput" class="urlinput" type="text" autocomplete="off" spellcheck="false" value={connection.url.clone()} placeholder="ws://" onkeydown={link.callback(|e: KeyboardEvent| { if e.key() == "Enter" { Msg::ConnectWsPressed } else { Msg::Nothing } })}  oninput={link.callback(|_|{ Msg::UrlChanged })} />
 
                 if connection.connecting {
@@ -226,22 +263,58 @@ pub fn ws_out(bctx: &mut BoltContext) -> Html {
 
             <div class="reqline">
                 <div class="reqtabs">
-                    <div id="req_body_tab" class={if is_ws_tab_selected(&connection.out_tab, WsOutTabs::Message) {"tab pointer tabSelected"} else {"tab pointer"}} onclick={link.callback(|_| Msg::WsOutMessagePressed)}>{"Message"}</div>
+                    <div id="req_body_tab" class={if is_ws_tab_selected(&connection.out_tab, WsOutTabs::Message) {"tab pointer tabSelected"} else {"tab pointer"}} onclick={link.callback(|_| Msg::WsOutMessagePressed)}>{if is_graphql { "Subscription" } else { "Message" }}</div>
+                    if is_graphql {
+                        <div id="req_init_tab" class={if is_ws_tab_selected(&connection.out_tab, WsOutTabs::Init) {"tab pointer tabSelected"} else {"tab pointer"}} onclick={link.callback(|_| Msg::WsOutInitPressed)}>{"Init"}</div>
+                    }
                     // <div id="req_params_tab" class={if is_ws_tab_selected(&connection.out_tab, WsOutTabs::Params) {"tab pointer tabSelected"} else {"tab pointer"}} onclick={link.callback(|_| Msg::WsOutParamsPressed)}>{"Params"}</div>
                     // <div id="req_headers_tab" class={if is_ws_tab_selected(&connection.out_tab, WsOutTabs::Headers) {"tab pointer tabSelected"} else {"tab pointer"}} onclick={link.callback(|_| Msg::WsOutHeadersPressed)}>{"Headers"}</div>
                 </div>
                 if connection.connected {
-                    <button class="ws-send-btn pointer" type="button" onclick={link.callback(|_| Msg::SendWsPressed)}>{"Send"}</button>
+                    <button class="ws-send-btn pointer" type="button" onclick={link.callback(|_| Msg::SendWsPressed)}>{send_label}</button>
                 } else {
-                    <button class="ws-send-btn disabled-cursor" type="button">{"Send"}</button>
+                    <button class="ws-send-btn disabled-cursor" type="button">{send_label}</button>
                 }
             </div>
 
+            if is_graphql && !connection.subscriptions.is_empty() {
+                <div class="graphqlsubscriptions">
+                    {"Active"}
+                    { for connection.subscriptions.iter().map(|id| {
+                        let stop_id = id.clone();
+                        html! {
+                            <div class="graphqlsubscription">
+                                {id.clone()}
+                                <div class="pointer" title="stop the subscription" onclick={link.callback(move |_| Msg::StopWsSubscription(stop_id.clone()))}>{"Stop"}</div>
+                            </div>
+                        }
+                    })}
+                </div>
+            }
+
              <div class="tabcontent">
-                if is_ws_tab_selected(&connection.out_tab, WsOutTabs::Message) {
+                if is_graphql && is_ws_tab_selected(&connection.out_tab, WsOutTabs::Message) {
+                    {view::body::render_graphql_editor(bctx, &connection.url, &connection.graphql, connection.graphql_cursor, view::body::GraphqlEditorMsgs::ws())}
+                } else if is_ws_tab_selected(&connection.out_tab, WsOutTabs::Message) {
                     <textarea autocomplete="off" spellcheck="false" id="reqbody" class="reqbody" value={connection.out_buffer.clone()} placeholder="Compose Message" oninput={link.callback(|_| Msg::WsOutMessageChanged)}>
 
                     </textarea>
+                } else if is_ws_tab_selected(&connection.out_tab, WsOutTabs::Init) {
+                    <div class="graphqlbody">
+                        <select id="wsgraphqlprotocol" class="authselect pointer" disabled={connection.connected || connection.connecting} onchange={link.callback(|_| Msg::WsGraphqlProtocolChanged)}>
+                            { for (0..GraphqlWsProtocol::count()).map(|index| {
+                                let protocol = GraphqlWsProtocol::from(index);
+                                html! {
+                                    <option value={index.to_string()} selected={protocol == connection.graphql_protocol}>{protocol.to_string()}</option>
+                                }
+                            })}
+                        </select>
+
+                        <div class="graphqllabel">{"connection_init payload"}</div>
+                        <textarea autocomplete="off" spellcheck="false" id="wsgraphqlinit" class="reqbody" value={connection.graphql_init.clone()} placeholder="{\"Authorization\": \"Bearer ...\"}" oninput={link.callback(|_| Msg::WsGraphqlInitChanged)}>
+
+                        </textarea>
+                    </div>
                 } else if is_ws_tab_selected(&connection.out_tab, WsOutTabs::Params) {
                     // <div class="reqheaders">
                     //     <table>
@@ -271,6 +344,223 @@ pub fn ws_out(bctx: &mut BoltContext) -> Html {
     }
 }
 
+pub fn sse_out(bctx: &mut BoltContext) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+
+    let can_display = !bctx.main_state.sse_connections.is_empty();
+
+    let mut connection = SseConnection::new();
+
+    if can_display {
+        connection = bctx.main_state.sse_connections[bctx.main_state.sse_current].clone();
+    }
+
+    html! {
+        <div class="req">
+        if can_display {
+            <div class="requestbar">
+                <input id="urlinput" class="urlinput" type="text" autocomplete="off" spellcheck="false" value={connection.url.clone()} placeholder="http://" onkeydown={link.callback(|e: KeyboardEvent| { if e.key() == "Enter" { Msg::ConnectSsePressed } else { Msg::Nothing } })}  oninput={link.callback(|_|{ Msg::UrlChanged })} />
+
+                if connection.connecting {
+                    <button class="ws-connecting-btn disabled-cursor" type="button">{"..."}</button>
+                } else if connection.connected {
+                    <button class="ws-disconnect-btn pointer" type="button" onclick={link.callback(|_| Msg::DisconnectSsePressed)}>{"Disconnect"}</button>
+                } else {
+                    <button class="ws-connect-btn pointer" type="button" onclick={link.callback(|_| Msg::ConnectSsePressed)}>{"Connect"}</button>
+                }
+            </div>
+
+            <div class="reqline">
+                <div class="reqtabs">
+                    <div id="req_headers_tab" class={if is_sse_tab_selected(&connection.out_tab, SseOutTabs::Headers) {"tab pointer tabSelected"} else {"tab pointer"}} onclick={link.callback(|_| Msg::SseOutHeadersPressed)}>{"Headers"}</div>
+                    <div id="req_reconnect_tab" class={if is_sse_tab_selected(&connection.out_tab, SseOutTabs::Reconnect) {"tab pointer tabSelected"} else {"tab pointer"}} onclick={link.callback(|_| Msg::SseOutReconnectPressed)}>{"Reconnect"}</div>
+                </div>
+            </div>
+
+             <div class="tabcontent">
+                if is_sse_tab_selected(&connection.out_tab, SseOutTabs::Headers) {
+                    <div class="reqheaders">
+                        <table>
+                            <tr>
+                                <th>{"Header"}</th>
+                                <th>{"Value"}</th>
+                            </tr>
+                            { for connection.out_headers.iter().enumerate().map(|(index, header)| view::header::render_sse_header(bctx, index, connection.out_headers.len(), &header[0], &header[1])) }
+                        </table>
+                    </div>
+                } else if is_sse_tab_selected(&connection.out_tab, SseOutTabs::Reconnect) {
+                    <div class="reqauth">
+                        <div class="authrow">
+                            <div class="authlabel">{"Last-Event-ID"}</div>
+                            <input id="sselasteventid" type="text" class="authinput" autocomplete="off" spellcheck="false" value={connection.last_event_id.clone()} onchange={link.callback(|_| Msg::SseReconnectChanged)}/>
+                        </div>
+                        <div class="authrow">
+                            <div class="authlabel">{"Retry (ms)"}</div>
+                            <input id="sseretry" type="number" min="0" class="authinput" autocomplete="off" value={connection.retry.to_string()} onchange={link.callback(|_| Msg::SseReconnectChanged)}/>
+                        </div>
+                    </div>
+                }
+            </div>
+        }
+        </div>
+
+    }
+}
+
+fn is_sse_tab_selected(request_tab: &u8, tab: SseOutTabs) -> bool {
+    *request_tab == u8::from(tab)
+}
+
+pub fn grpc_out(bctx: &mut BoltContext) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+
+    let can_display = !bctx.main_state.grpc_connections.is_empty();
+
+    let mut connection = GrpcConnection::new();
+
+    if can_display {
+        connection = bctx.main_state.grpc_connections[bctx.main_state.grpc_current].clone();
+    }
+
+    let method = connection.current_method().cloned();
+    let busy = connection.connected || connection.connecting;
+
+    // a streaming client sends from the editor while the call is open
+    let client_streaming = method
+        .as_ref()
+        .is_some_and(|method| method.kind.is_client_streaming());
+
+    html! {
+        <div class="req">
+        if can_display {
+            <div class="requestbar">
+                <div class="">
+                    <select id="grpcmethodselect" class="methodselect grpcmethodselect pointer" disabled={busy} onchange={link.callback(|_| Msg::GrpcMethodChanged)}>
+                        if connection.methods.is_empty() {
+                            <option value="0" selected=true>{"No methods"}</option>
+                        }
+                        { for connection.methods.iter().enumerate().map(|(index, method)| {
+                            html! {
+                                <option value={index.to_string()} selected={index == connection.method} title={method.path()}>{grpc_method_name(method)}</option>
+                            }
+                        })}
+                    </select>
+                </div>
+
+                <input id="urlinput" class="urlinput" type="text" autocomplete="off" spellcheck="false" value={connection.url.clone()} placeholder="http://localhost:50051" onkeydown={link.callback(|e: KeyboardEvent| { if e.key() == "Enter" { Msg::InvokeGrpcPressed } else { Msg::Nothing } })}  oninput={link.callback(|_|{ Msg::UrlChanged })} />
+
+                if connection.connecting {
+                    <button class="ws-connecting-btn disabled-cursor" type="button">{"..."}</button>
+                } else if connection.connected {
+                    <button class="ws-disconnect-btn pointer" type="button" onclick={link.callback(|_| Msg::CancelGrpcPressed)}>{"Cancel"}</button>
+                } else {
+                    <button class="ws-connect-btn pointer" type="button" onclick={link.callback(|_| Msg::InvokeGrpcPressed)}>{"Invoke"}</button>
+                }
+            </div>
+
+            <div class="reqline">
+                <div class="reqtabs">
+                    <div id="req_body_tab" class={if is_grpc_tab_selected(&connection.out_tab, GrpcOutTabs::Message) {"tab pointer tabSelected"} else {"tab pointer"}} onclick={link.callback(|_| Msg::GrpcOutMessagePressed)}>{"Message"}</div>
+                    <div id="req_headers_tab" class={if is_grpc_tab_selected(&connection.out_tab, GrpcOutTabs::Metadata) {"tab pointer tabSelected"} else {"tab pointer"}} onclick={link.callback(|_| Msg::GrpcOutMetadataPressed)}>{"Metadata"}</div>
+                    <div id="req_service_tab" class={if is_grpc_tab_selected(&connection.out_tab, GrpcOutTabs::Service) {"tab pointer tabSelected"} else {"tab pointer"}} onclick={link.callback(|_| Msg::GrpcOutServicePressed)}>{"Service"}</div>
+                </div>
+
+                if let Some(method) = &method {
+                    <div class="grpc-kind" title={format!("{} -> {}", method.input_type, method.output_type)}>{method.kind.to_string()}</div>
+                }
+
+                if is_grpc_tab_selected(&connection.out_tab, GrpcOutTabs::Message) {
+                    if client_streaming && connection.connected {
+                        <button class="ws-send-btn pointer" type="button" onclick={link.callback(|_| Msg::SendGrpcPressed)}>{"Send"}</button>
+                        if connection.closing {
+                            <button class="ws-send-btn disabled-cursor" type="button">{"End"}</button>
+                        } else {
+                            <button class="ws-send-btn pointer" type="button" title="close the sending side of the call" onclick={link.callback(|_| Msg::EndGrpcPressed)}>{"End"}</button>
+                        }
+                    } else if method.is_some() {
+                        <button class="ws-send-btn pointer" type="button" title="fill the editor with every field of the request message" onclick={link.callback(|_| Msg::GrpcTemplatePressed)}>{"Template"}</button>
+                    }
+                }
+            </div>
+
+             <div class="tabcontent">
+                if is_grpc_tab_selected(&connection.out_tab, GrpcOutTabs::Message) {
+                    <textarea autocomplete="off" spellcheck="false" id="reqbody" class="reqbody" value={connection.out_buffer.clone()} placeholder="{}" oninput={link.callback(|_| Msg::GrpcOutMessageChanged)}>
+
+                    </textarea>
+                } else if is_grpc_tab_selected(&connection.out_tab, GrpcOutTabs::Metadata) {
+                    <div class="reqheaders">
+                        <table>
+                            <tr>
+                                <th>{"Key"}</th>
+                                <th>{"Value"}</th>
+                            </tr>
+                            { for connection.out_headers.iter().enumerate().map(|(index, header)| view::header::render_grpc_header(bctx, index, connection.out_headers.len(), &header[0], &header[1])) }
+                        </table>
+                    </div>
+                } else if is_grpc_tab_selected(&connection.out_tab, GrpcOutTabs::Service) {
+                    <div class="reqauth reqsettings">
+                        <div class="authrow">
+                            <div class="authlabel">{"Services from"}</div>
+                            <select id="grpcsourceselect" class="authselect pointer" onchange={link.callback(|_| Msg::GrpcServiceChanged)}>
+                                { for (0..GrpcSource::count()).map(|index| {
+                                    let source = GrpcSource::from(index);
+                                    html! {
+                                        <option value={index.to_string()} selected={source == connection.source}>{source.to_string()}</option>
+                                    }
+                                })}
+                            </select>
+                        </div>
+
+                        if connection.source == GrpcSource::PROTO_FILES {
+                            <div class="authrow">
+                                <div class="authlabel">{"Proto files"}</div>
+                                <textarea id="grpcprotofiles" class="authinput grpcpaths" autocomplete="off" spellcheck="false" placeholder="one .proto path per line" value={connection.proto_files.clone()} onchange={link.callback(|_| Msg::GrpcServiceChanged)}></textarea>
+                            </div>
+                            <div class="authrow">
+                                <div class="authlabel">{"Import paths"}</div>
+                                <textarea id="grpcimportpaths" class="authinput grpcpaths" autocomplete="off" spellcheck="false" placeholder="folders the imports are resolved from, one per line" value={connection.import_paths.clone()} onchange={link.callback(|_| Msg::GrpcServiceChanged)}></textarea>
+                            </div>
+                        }
+
+                        <div class="authrow">
+                            <div class="authlabel">{"Verify TLS"}</div>
+                            <input id="grpcverifytls" type="checkbox" class="pointer" checked={connection.verify_tls} onchange={link.callback(|_| Msg::GrpcServiceChanged)}/>
+                        </div>
+
+                        <div class="authrow">
+                            if connection.loading_methods {
+                                <button class="ws-send-btn disabled-cursor" type="button">{"Loading"}</button>
+                            } else {
+                                <button class="ws-send-btn pointer" type="button" onclick={link.callback(|_| Msg::LoadGrpcMethodsPressed)}>{"Load"}</button>
+                            }
+
+                            if !connection.methods_error.is_empty() {
+                                <div class="grpc-status">{connection.methods_error.clone()}</div>
+                            } else if !connection.methods.is_empty() {
+                                <div class="grpc-info">{format!("{} methods", connection.methods.len())}</div>
+                            }
+                        </div>
+                    </div>
+                }
+            </div>
+        }
+        </div>
+
+    }
+}
+
+// the package is left out, the full path is in the option title
+fn grpc_method_name(method: &GrpcMethod) -> String {
+    let service = method.service.rsplit('.').next().unwrap_or_default();
+
+    format!("{}/{}", service, method.name)
+}
+
+fn is_grpc_tab_selected(request_tab: &u8, tab: GrpcOutTabs) -> bool {
+    *request_tab == u8::from(tab)
+}
+
 // pub fn collection_request(bctx: &mut BoltContext) -> Html {
 //     let link = bctx.main_state.link.as_ref().unwrap();
 
@@ -23,31 +23,49 @@ pub fn http_response(bctx: &mut BoltContext) -> Html {
     }
 
     let copy_icon = crate::view::icons::copy_icon(20, 20);
+    let save_icon = crate::view::icons::save_icon(20, 20);
 
     html! {
     <div class="resp">
-        if can_display && !request.response.failed && !request.loading {
+        if can_display && request.response.cancelled && !request.loading {
+            <div class="respcancelled">{request.response.body.clone()}</div>
+        } else if can_display && !request.response.failed && !request.loading {
             <div class="respline">
                 <div class="resptabs">
                     <div id="resp_body_tab" class={if request.resp_tab == 1  {"tab pointer tabSelected"} else {"tab pointer"}} onclick={link.callback(|_| Msg::HttpRespBodyPressed)}>{"Body"}</div>
+                    <div id="resp_raw_tab" class={if request.resp_tab == 4  {"tab pointer tabSelected"} else {"tab pointer"}} onclick={link.callback(|_| Msg::HttpRespRawPressed)}>{"Raw"}</div>
                     <div id="resp_headers_tab" class={if request.resp_tab == 2  {"tab pointer tabSelected"} else {"tab pointer"}} onclick={link.callback(|_| Msg::HttpRespHeadersPressed)}>{"Headers"}</div>
+                    <div id="resp_console_tab" class={if request.resp_tab == 3  {"tab pointer tabSelected"} else {"tab pointer"}} onclick={link.callback(|_| Msg::HttpRespConsolePressed)}>{"Console"}</div>
+                    <div id="resp_timing_tab" class={if request.resp_tab == 5  {"tab pointer tabSelected"} else {"tab pointer"}} onclick={link.callback(|_| Msg::HttpRespTimingPressed)}>{"Timing"}</div>
+                    <div id="resp_tests_tab" class={if request.resp_tab == 6  {"tab pointer tabSelected"} else {"tab pointer"}} onclick={link.callback(|_| Msg::HttpRespTestsPressed)}>{view::tests::tests_tab_label(&request.response)}</div>
                 </div>
 
                 <div class="respstats">
                     <div class="pointer copy-msg-icon" title="copy response body" onclick={link.callback(move |_| Msg::CopyHttpResponsePressed)} >{copy_icon}</div>
+                    <div class="pointer copy-msg-icon" title={if request.response.saved_to.is_empty() { "save response to file".to_string() } else { format!("saved to {}", request.response.saved_to) }} onclick={link.callback(move |_| Msg::SaveHttpResponsePressed)} >{save_icon}</div>
                     <div id="status" class="respstat">{"Status: "} {request.response.status}</div>
                     <div id="time" class="respstat">{"Time: "} {request.response.time} {" ms"}</div>
                     <div id="size" class="respstat">{"Size: "} {request.response.size} {" B"}</div>
                 </div>
             </div>
 
+            // the HTTP request went through, the query did not
+            if !request.response.graphql_errors.is_empty() {
+                <div class="graphqlerrors">
+                    <div class="graphqlerrorstitle">{"GraphQL errors"}</div>
+                    { for request.response.graphql_errors.iter().map(|error| html! { <div class="graphqlerror">{error}</div> }) }
+                </div>
+            }
+
             <div class="tabcontent">
                 if request.resp_tab == 1 {
+                    {render_body_preview(&request.response)}
+                } else if request.resp_tab == 4 {
                     <div id="respbody" class="respbody" >
-                        if request.response.response_type == HttpResponseType::JSON {
-                            {Html::from_html_unchecked(AttrValue::from(request.response.body_highlight.clone()))}
-                        } else {
+                        if is_text_response(&request.response) {
                             {request.response.body.clone()}
+                        } else {
+                            <pre class="hexdump">{hex_dump(&request.response)}</pre>
                         }
                     </div>
                 } else if request.resp_tab == 2 {
@@ -60,18 +78,222 @@ pub fn http_response(bctx: &mut BoltContext) -> Html {
                             { for request.response.headers.iter().map(|header| view::header::render_http_resp_header(&header[0], &header[1])) }
                         </table>
                     </div>
+                } else if request.resp_tab == 3 {
+                    <div class="respconsole">
+                        { for request.response.console.iter().map(|line| html! { <div class="consoleline">{line}</div> }) }
+                    </div>
+                } else if request.resp_tab == 5 {
+                    {render_timing(&request.response)}
+                } else if request.resp_tab == 6 {
+                    {view::tests::render_test_results(&request.response)}
                 }
             </div>
         } else if can_display && request.loading {
             <div class="resploading"><img src="/icon/icon.png" /></div>
         } else if request.response.failed {
             <div class="resperror">{request.response.body.clone()}</div>
+
+            if !request.response.console.is_empty() {
+                <div class="respconsole">
+                    { for request.response.console.iter().map(|line| html! { <div class="consoleline">{line}</div> }) }
+                </div>
+            }
         }
 
     </div>
     }
 }
 
+fn render_timing(response: &HttpResponse) -> Html {
+    let timing = &response.timing;
+    let connection = &response.connection;
+
+    // sent requests do not measure connect and the TLS handshake, they are part of the
+    // time to first byte
+    let phases: Vec<(&str, Option<u64>)> = vec![
+        ("DNS lookup", Some(timing.dns)),
+        ("TCP connect", timing.connect),
+        ("TLS handshake", timing.tls),
+        ("Time to first byte", Some(timing.ttfb)),
+        ("Content download", Some(timing.download)),
+    ];
+
+    let total = timing.total.max(1) as f64;
+
+    let mut offset = 0;
+
+    let rows = phases.iter().map(|(name, duration)| {
+        let Some(duration) = duration else {
+            return html! {
+                <tr>
+                    <td>{name}</td>
+                    <td class="timingtrack"></td>
+                    <td class="timingvalue">{"not measured"}</td>
+                </tr>
+            };
+        };
+
+        // each bar starts where the previous phase ended
+        let left = offset as f64 / total * 100.0;
+        let width = *duration as f64 / total * 100.0;
+
+        offset += duration;
+
+        html! {
+            <tr>
+                <td>{name}</td>
+                <td class="timingtrack">
+                    <div class="timingbar" style={format!("margin-left: {:.2}%; width: {:.2}%;", left, width)}></div>
+                </td>
+                <td class="timingvalue">{format_duration(*duration)}</td>
+            </tr>
+        }
+    });
+
+    let mut details = vec![
+        ("HTTP version", connection.http_version.clone()),
+        ("Remote address", connection.remote_addr.clone()),
+    ];
+
+    if !connection.tls_subject.is_empty() {
+        details.push(("Certificate subject", connection.tls_subject.clone()));
+        details.push(("Certificate issuer", connection.tls_issuer.clone()));
+        details.push(("Valid from", connection.tls_valid_from.clone()));
+        details.push(("Valid until", connection.tls_valid_until.clone()));
+    }
+
+    html! {
+        <div class="resptiming">
+            <table class="timingtable">
+                { for rows }
+                <tr class="timingtotal">
+                    <td>{"Total"}</td>
+                    <td></td>
+                    <td class="timingvalue">{format_duration(timing.total)}</td>
+                </tr>
+            </table>
+
+            <table class="timingtable">
+                { for details.into_iter().map(|(name, value)| html! {
+                    <tr>
+                        <td>{name}</td>
+                        <td>{value}</td>
+                    </tr>
+                }) }
+            </table>
+        </div>
+    }
+}
+
+// timings are kept in microseconds
+fn format_duration(micros: u64) -> String {
+    format!("{:.2} ms", micros as f64 / 1000.0)
+}
+
+fn is_text_response(response: &HttpResponse) -> bool {
+    matches!(
+        response.response_type,
+        HttpResponseType::TEXT | HttpResponseType::JSON | HttpResponseType::HTML
+    )
+}
+
+fn render_body_preview(response: &HttpResponse) -> Html {
+    let data_url = format!(
+        "data:{};base64,{}",
+        response.content_type.split(';').next().unwrap_or_default(),
+        response.body_base64
+    );
+
+    match response.response_type {
+        // imported responses are not highlighted until they are received again
+        HttpResponseType::JSON if response.body_highlight.is_empty() => html! {
+            <div id="respbody" class="respbody" >{response.body.clone()}</div>
+        },
+
+        HttpResponseType::JSON => html! {
+            <div id="respbody" class="respbody" >
+                {Html::from_html_unchecked(AttrValue::from(response.body_highlight.clone()))}
+            </div>
+        },
+
+        // an empty sandbox keeps scripts, forms and navigation of the page disabled
+        HttpResponseType::HTML => html! {
+            <iframe class="resppreview" sandbox="" srcdoc={response.body.clone()}></iframe>
+        },
+
+        HttpResponseType::IMAGE => html! {
+            <div class="respbody respimage">
+                <img src={data_url} />
+            </div>
+        },
+
+        HttpResponseType::PDF => html! {
+            <embed class="resppreview" type="application/pdf" src={data_url} />
+        },
+
+        HttpResponseType::BINARY => html! {
+            <div id="respbody" class="respbody" >
+                <pre class="hexdump">{hex_dump(response)}</pre>
+            </div>
+        },
+
+        HttpResponseType::TEXT => html! {
+            <div id="respbody" class="respbody" >
+                {response.body.clone()}
+            </div>
+        },
+    }
+}
+
+// a whole number of base64 groups and of dump lines
+const HEX_DUMP_LIMIT: usize = 48 * 1024;
+
+// classic offset / hex / ascii dump, long bodies are cut to keep the DOM small
+fn hex_dump(response: &HttpResponse) -> String {
+    let decoded;
+
+    let (bytes, total): (&[u8], usize) = match &response.raw_body {
+        Some(raw) => (raw, raw.len()),
+
+        // restored responses have no decoded bytes, only the part that is shown is decoded
+        None => {
+            let base64 = &response.body_base64;
+            let shown = &base64[..base64.len().min(HEX_DUMP_LIMIT / 3 * 4)];
+
+            decoded = match crate::utils::decode_base64(shown) {
+                Some(bytes) => bytes,
+                None => return "could not decode the response body".to_string(),
+            };
+
+            let padding = base64.bytes().rev().take_while(|b| *b == b'=').count();
+
+            (&decoded, base64.len() / 4 * 3 - padding)
+        }
+    };
+
+    let mut dump = String::new();
+
+    for (line, chunk) in bytes.chunks(16).take(HEX_DUMP_LIMIT / 16).enumerate() {
+        let hex: Vec<String> = chunk.iter().map(|b| format!("{:02x}", b)).collect();
+
+        let ascii: String = chunk
+            .iter()
+            .map(|b| if b.is_ascii_graphic() || *b == b' ' { *b as char } else { '.' })
+            .collect();
+
+        dump.push_str(&format!("{:08x}  {:<48}  |{}|\n", line * 16, hex.join(" "), ascii));
+    }
+
+    if total > HEX_DUMP_LIMIT {
+        dump.push_str(&format!(
+            "... {} more bytes, save the response to see all of it",
+            total - HEX_DUMP_LIMIT
+        ));
+    }
+
+    dump
+}
+
 pub fn tcp_history(bctx: &mut BoltContext) -> Html {
     let link = bctx.link.as_ref().unwrap();
 
@@ -194,7 +416,7 @@ pub fn ws_history(bctx: &mut BoltContext) -> Html {
 
                 <div class="tabcontent">
                     <div class="atabs">
-                        { for connection.msg_history.iter().enumerate().rev().map(|(index, msg)| view::msg::render_ws_msg(&msg, link, index)) }
+                        { for connection.msg_history.iter().enumerate().rev().map(|(index, msg)| view::msg::render_ws_msg(&msg, link, Msg::CopyWsMsgClicked(index))) }
                     </div>
                 </div>
             } else if can_display && connection.connecting {
@@ -206,3 +428,132 @@ pub fn ws_history(bctx: &mut BoltContext) -> Html {
         </div>
     }
 }
+
+pub fn sse_history(bctx: &mut BoltContext) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+
+    let can_display = !bctx.main_state.sse_connections.is_empty();
+
+    let mut connection = SseConnection::new();
+
+    if can_display {
+        connection = bctx.main_state.sse_connections[bctx.main_state.sse_current].clone();
+    }
+
+    html! {
+        <div class="resp">
+            if can_display && !connection.connecting {
+                <div class="respline">
+                    <div class="resptabs">
+                        <div id="resp_body_tab" class={if connection.in_tab == 1  {"tab tabSelected"} else {"tab pointer"}}>{"Events"}</div>
+                    </div>
+
+                    <div class="respstats">
+                        if connection.reconnecting {
+                            <div id="status" class="respstat">{"Reconnecting"}</div>
+                        } else if connection.connected {
+                            <div id="status" class="respstat">{"Connected"}</div>
+                        } else {
+                            <div id="status" class="respstat">{"Disconnected"}</div>
+                        }
+                        <div class="respstat pointer" title="clear the events" onclick={link.callback(|_| Msg::ClearSseHistory)}>{"Clear"}</div>
+                    </div>
+                 </div>
+
+                // the events received before a failed reconnect stay visible under the reason
+                if connection.failed || connection.reconnecting {
+                    <div class="sse-status">{connection.failed_reason.clone()}</div>
+                }
+
+                <div class="tabcontent">
+                    <div class="atabs">
+                        { for connection.msg_history.iter().enumerate().rev().map(|(index, msg)| view::msg::render_sse_event(&msg, link, index)) }
+                    </div>
+                </div>
+            } else if can_display && connection.connecting {
+                <div class="resploading"><img src="/icon/icon.png" /></div>
+            }
+
+        </div>
+    }
+}
+
+pub fn grpc_history(bctx: &mut BoltContext) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+
+    let can_display = !bctx.main_state.grpc_connections.is_empty();
+
+    let mut connection = GrpcConnection::new();
+
+    if can_display {
+        connection = bctx.main_state.grpc_connections[bctx.main_state.grpc_current].clone();
+    }
+
+    // a failed call, a refused message or an error status from the server
+    let error = if !connection.failed_reason.is_empty() {
+        connection.failed_reason.clone()
+    } else {
+        match &connection.status {
+            Some(status) if status.code != 0 => status.message.clone(),
+            _ => String::new(),
+        }
+    };
+
+    html! {
+        <div class="resp">
+            if can_display && !connection.connecting {
+                <div class="respline">
+                    <div class="resptabs">
+                        <div id="resp_body_tab" class={if connection.in_tab == 1  {"tab pointer tabSelected"} else {"tab pointer"}} onclick={link.callback(|_| Msg::GrpcInMessagesPressed)}>{"Messages"}</div>
+                        <div id="resp_headers_tab" class={if connection.in_tab == 2  {"tab pointer tabSelected"} else {"tab pointer"}} onclick={link.callback(|_| Msg::GrpcInHeadersPressed)}>{"Headers"}</div>
+                        <div id="resp_trailers_tab" class={if connection.in_tab == 3  {"tab pointer tabSelected"} else {"tab pointer"}} onclick={link.callback(|_| Msg::GrpcInTrailersPressed)}>{"Trailers"}</div>
+                    </div>
+
+                    <div class="respstats">
+                        if connection.connected {
+                            <div id="status" class="respstat">{"Open"}</div>
+                        } else if let Some(status) = &connection.status {
+                            <div id="status" class="respstat" title={status.message.clone()}>{format!("Status: {} ({})", status.code_name(), status.code)}</div>
+                        }
+                        <div class="respstat pointer" title="clear the messages" onclick={link.callback(|_| Msg::ClearGrpcHistory)}>{"Clear"}</div>
+                    </div>
+                 </div>
+
+                if !error.is_empty() {
+                    <div class="grpc-status">{error}</div>
+                }
+
+                <div class="tabcontent">
+                    if connection.in_tab == 1 {
+                        <div class="atabs">
+                            { for connection.msg_history.iter().enumerate().rev().map(|(index, msg)| view::msg::render_ws_msg(&msg, link, Msg::CopyGrpcMsgClicked(index))) }
+                        </div>
+                    } else if connection.in_tab == 2 {
+                        <div class="respheaders">
+                            <table>
+                                <tr>
+                                    <th>{"Header"}</th>
+                                    <th>{"Value"}</th>
+                                </tr>
+                                { for connection.response_headers.iter().map(|header| view::header::render_http_resp_header(&header[0], &header[1])) }
+                            </table>
+                        </div>
+                    } else if connection.in_tab == 3 {
+                        <div class="respheaders">
+                            <table>
+                                <tr>
+                                    <th>{"Trailer"}</th>
+                                    <th>{"Value"}</th>
+                                </tr>
+                                { for connection.trailers.iter().map(|trailer| view::header::render_http_resp_header(&trailer[0], &trailer[1])) }
+                            </table>
+                        </div>
+                    }
+                </div>
+            } else if can_display && connection.connecting {
+                <div class="resploading"><img src="/icon/icon.png" /></div>
+            }
+
+        </div>
+    }
+}
@@ -0,0 +1,139 @@
+use crate::BoltContext;
+use crate::Msg;
+use bolt_common::prelude::*;
+use yew::{html, Html};
+
+pub fn render_runner(bctx: &mut BoltContext) -> Html {
+    let config = bctx.main_state.runner_config.clone();
+    let run = &bctx.main_state.collection_run;
+
+    let done = run.results.len();
+
+    let percent = if run.total == 0 {
+        0
+    } else {
+        done * 100 / run.total
+    };
+
+    let status = if run.running {
+        format!("Running {}... {}/{}", run.collection_name, done, run.total)
+    } else if !run.error.is_empty() {
+        format!("{} could not run: {}", run.collection_name, run.error)
+    } else if run.cancelled {
+        format!("{} stopped after {}/{}", run.collection_name, done, run.total)
+    } else if run.run_id.is_empty() {
+        "Press the run icon of a collection to run its requests in order".to_string()
+    } else {
+        format!("{} finished", run.collection_name)
+    };
+
+    let rows: Vec<Html> = run.results.iter().map(render_run_result).collect();
+
+    let summary = if !run.running && !run.run_id.is_empty() && run.error.is_empty() {
+        render_run_summary(&run.summary)
+    } else {
+        html! {}
+    };
+
+    let running = run.running;
+
+    let link = bctx.link.as_ref().unwrap();
+
+    html! {
+        <div class="runner">
+            <div class="runnerconfig">
+                <div class="authrow">
+                    <div class="authlabel">{"Delay (ms)"}</div>
+                    <input id="runnerdelay" type="number" min="0" class="authinput" value={config.delay.to_string()} onchange={link.callback(|_| Msg::RunnerConfigChanged)}/>
+                </div>
+                <div class="authrow">
+                    <div class="authlabel">{"Iterations"}</div>
+                    <input id="runneriterations" type="number" min="1" class="authinput" disabled={!config.data_file.trim().is_empty()} value={config.iterations.to_string()} onchange={link.callback(|_| Msg::RunnerConfigChanged)}/>
+                </div>
+                <div class="authrow">
+                    <div class="authlabel">{"Stop on failure"}</div>
+                    <input id="runnerstoponfailure" type="checkbox" class="pointer" checked={config.stop_on_failure} onchange={link.callback(|_| Msg::RunnerConfigChanged)}/>
+                </div>
+                <div class="authrow">
+                    <div class="authlabel">{"Data file"}</div>
+                    <input id="runnerdatafile" type="text" class="authinput" autocomplete="off" spellcheck="false" placeholder="path to a CSV or JSON file, one iteration per row" value={config.data_file.clone()} onchange={link.callback(|_| Msg::RunnerConfigChanged)}/>
+                </div>
+            </div>
+
+            <div class="runnerstatus">
+                <div class="runnerstatustext">{status}</div>
+                if running {
+                    <button class="ws-disconnect-btn pointer" onclick={link.callback(|_| Msg::StopCollectionRun)}>{"Stop"}</button>
+                }
+            </div>
+
+            <div class="runnerprogress">
+                <div class="runnerprogressbar" style={format!("width: {percent}%")}></div>
+            </div>
+
+            {summary}
+
+            <div class="runnerresults">
+                <table class="timingtable">
+                    <tr class="runnerheader">
+                        <td>{"#"}</td>
+                        <td>{"Request"}</td>
+                        <td>{"Status"}</td>
+                        <td>{"Time"}</td>
+                        <td>{"Tests"}</td>
+                        <td>{"Details"}</td>
+                    </tr>
+                    { for rows }
+                </table>
+            </div>
+        </div>
+    }
+}
+
+fn render_run_result(result: &RunRequestResult) -> Html {
+    let passed_tests = result.tests.iter().filter(|test| test.passed).count();
+
+    let details = if result.failed {
+        result.error.clone()
+    } else {
+        result
+            .tests
+            .iter()
+            .filter(|test| !test.passed)
+            .map(|test| format!("{}: {}", test.name, test.message))
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+
+    html! {
+        <tr>
+            <td>{result.iteration + 1}</td>
+            <td>
+                <div class="testbadge runnerbadge">{result.method.to_string()}</div>
+                {result.name.clone()}
+            </td>
+            <td>{if result.failed { "-".to_string() } else { result.status.to_string() }}</td>
+            <td>{format!("{} ms", result.time)}</td>
+            <td>
+                <div class={if result.passed() { "testbadge testpassed" } else { "testbadge testfailed" }}>
+                    {if result.tests.is_empty() { if result.failed { "FAIL".to_string() } else { "PASS".to_string() } } else { format!("{}/{}", passed_tests, result.tests.len()) }}
+                </div>
+            </td>
+            <td class="testmessage">{details}</td>
+        </tr>
+    }
+}
+
+fn render_run_summary(summary: &RunSummary) -> Html {
+    html! {
+        <div class="runnersummary">
+            <div>{format!("{} iteration(s)", summary.iterations)}</div>
+            <div>{format!("{}/{} requests passed", summary.requests - summary.failed_requests, summary.requests)}</div>
+            <div>{format!("{}/{} assertions passed", summary.assertions - summary.failed_assertions, summary.assertions)}</div>
+            <div>{format!("avg {} ms, max {} ms, total {} ms", summary.average_time(), summary.max_time, summary.total_time)}</div>
+            if summary.stopped {
+                <div class="runnerstopped">{"stopped on failure"}</div>
+            }
+        </div>
+    }
+}
@@ -0,0 +1,69 @@
+use crate::BoltContext;
+use crate::Msg;
+use bolt_common::prelude::*;
+use yew::{html, Html};
+
+pub fn render_http_req_settings(bctx: &mut BoltContext, request: &HttpRequest) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+
+    let custom = request.settings.is_some();
+
+    let settings = match &request.settings {
+        Some(settings) => settings.clone(),
+        None => bctx.main_state.http_settings.clone(),
+    };
+
+    html! {
+        <div class="reqauth reqsettings">
+            <div class="authrow">
+                <div class="authlabel">{"Apply to"}</div>
+                <select id="settingsscopeselect" class="authselect pointer" onchange={link.callback(|_| Msg::HttpReqSettingsScopeChanged)}>
+                    <option value="workspace" selected={!custom}>{"Workspace defaults"}</option>
+                    <option value="request" selected={custom}>{"This request only"}</option>
+                </select>
+            </div>
+
+            <div class="authrow">
+                <div class="authlabel">{"Connect timeout (ms)"}</div>
+                <input id="settingsconnecttimeout" type="number" min="0" class="authinput" placeholder="0 = none" value={settings.connect_timeout.to_string()} onchange={link.callback(|_| Msg::HttpReqSettingsChanged)}/>
+            </div>
+            <div class="authrow">
+                <div class="authlabel">{"Total timeout (ms)"}</div>
+                <input id="settingstimeout" type="number" min="0" class="authinput" placeholder="0 = none" value={settings.timeout.to_string()} onchange={link.callback(|_| Msg::HttpReqSettingsChanged)}/>
+            </div>
+
+            <div class="authrow">
+                <div class="authlabel">{"Follow redirects"}</div>
+                <input id="settingsfollowredirects" type="checkbox" class="pointer" checked={settings.follow_redirects} onchange={link.callback(|_| Msg::HttpReqSettingsChanged)}/>
+            </div>
+            if settings.follow_redirects {
+                <div class="authrow">
+                    <div class="authlabel">{"Max redirects"}</div>
+                    <input id="settingsmaxredirects" type="number" min="0" class="authinput" value={settings.max_redirects.to_string()} onchange={link.callback(|_| Msg::HttpReqSettingsChanged)}/>
+                </div>
+            }
+
+            <div class="authrow">
+                <div class="authlabel">{"Proxy"}</div>
+                <input id="settingsproxy" type="text" class="authinput" autocomplete="off" spellcheck="false" placeholder="http://, https:// or socks5://host:port" value={settings.proxy.clone()} onchange={link.callback(|_| Msg::HttpReqSettingsChanged)}/>
+            </div>
+
+            <div class="authrow">
+                <div class="authlabel">{"Verify TLS"}</div>
+                <input id="settingsverifytls" type="checkbox" class="pointer" checked={settings.verify_tls} onchange={link.callback(|_| Msg::HttpReqSettingsChanged)}/>
+            </div>
+            <div class="authrow">
+                <div class="authlabel">{"CA bundle"}</div>
+                <input id="settingscacert" type="text" class="authinput" autocomplete="off" spellcheck="false" placeholder="path to PEM file" value={settings.ca_cert.clone()} onchange={link.callback(|_| Msg::HttpReqSettingsChanged)}/>
+            </div>
+            <div class="authrow">
+                <div class="authlabel">{"Client certificate"}</div>
+                <input id="settingsclientcert" type="text" class="authinput" autocomplete="off" spellcheck="false" placeholder="path to PEM file" value={settings.client_cert.clone()} onchange={link.callback(|_| Msg::HttpReqSettingsChanged)}/>
+            </div>
+            <div class="authrow">
+                <div class="authlabel">{"Client key"}</div>
+                <input id="settingsclientkey" type="text" class="authinput" autocomplete="off" spellcheck="false" placeholder="path to PKCS#8 PEM file, empty if in the certificate" value={settings.client_key.clone()} onchange={link.callback(|_| Msg::HttpReqSettingsChanged)}/>
+            </div>
+        </div>
+    }
+}
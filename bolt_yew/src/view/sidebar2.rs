@@ -75,6 +75,40 @@ pub fn sidebar_tcp(bctx: &mut BoltContext) -> Html {
     }
 }
 
+pub fn sidebar_sse(bctx: &mut BoltContext) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+
+    html! {
+        <div class="sidebar2">
+            <div>
+                <div class="pointer" onclick={link.callback(|_| Msg::AddSseConnection)}>
+                    <svg viewBox="0 0 1024 1024" fill="currentColor" height="20px" width="20px" ><defs><style /></defs><path d="M482 152h60q8 0 8 8v704q0 8-8 8h-60q-8 0-8-8V160q0-8 8-8z" /><path d="M176 474h672q8 0 8 8v60q0 8-8 8H176q-8 0-8-8v-60q0-8 8-8z" /></svg>
+                </div>
+            </div>
+
+            { for bctx.main_state.sse_connections.iter().enumerate().map(|(index, req)| render_sse_connection(bctx.link.as_ref().unwrap(), bctx.main_state.sse_current, index, req))}
+
+        </div>
+    }
+}
+
+pub fn sidebar_grpc(bctx: &mut BoltContext) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+
+    html! {
+        <div class="sidebar2">
+            <div>
+                <div class="pointer" onclick={link.callback(|_| Msg::AddGrpcConnection)}>
+                    <svg viewBox="0 0 1024 1024" fill="currentColor" height="20px" width="20px" ><defs><style /></defs><path d="M482 152h60q8 0 8 8v704q0 8-8 8h-60q-8 0-8-8V160q0-8 8-8z" /><path d="M176 474h672q8 0 8 8v60q0 8-8 8H176q-8 0-8-8v-60q0-8 8-8z" /></svg>
+                </div>
+            </div>
+
+            { for bctx.main_state.grpc_connections.iter().enumerate().map(|(index, req)| render_grpc_connection(bctx.link.as_ref().unwrap(), bctx.main_state.grpc_current, index, req))}
+
+        </div>
+    }
+}
+
 pub fn sidebar_udp(bctx: &mut BoltContext) -> Html {
     let link = bctx.link.as_ref().unwrap();
 
@@ -92,6 +126,45 @@ pub fn sidebar_udp(bctx: &mut BoltContext) -> Html {
     }
 }
 
+pub fn sidebar_environments(bctx: &mut BoltContext) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+
+    html! {
+        <div class="sidebar2">
+            <div>
+                <div class="pointer" onclick={link.callback(|_| Msg::AddEnvironment)}>
+                    <svg viewBox="0 0 1024 1024" fill="currentColor" height="20px" width="20px" ><defs><style /></defs><path d="M482 152h60q8 0 8 8v704q0 8-8 8h-60q-8 0-8-8V160q0-8 8-8z" /><path d="M176 474h672q8 0 8 8v60q0 8-8 8H176q-8 0-8-8v-60q0-8 8-8z" /></svg>
+                </div>
+            </div>
+
+            { for bctx.main_state.environments.iter().enumerate().map(|(index, env)| render_environment(bctx.link.as_ref().unwrap(), bctx.main_state.env_current, bctx.main_state.env_active, index, env))}
+
+        </div>
+    }
+}
+
+pub fn sidebar_cookies(bctx: &mut BoltContext) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+
+    let domains = cookie_domains(&bctx.main_state.cookies);
+
+    html! {
+        <div class="sidebar2">
+            <div class="cookie-sidebar-actions">
+                <div class="pointer" title="add cookie" onclick={link.callback(|_| Msg::AddCookie)}>
+                    <svg viewBox="0 0 1024 1024" fill="currentColor" height="20px" width="20px" ><defs><style /></defs><path d="M482 152h60q8 0 8 8v704q0 8-8 8h-60q-8 0-8-8V160q0-8 8-8z" /><path d="M176 474h672q8 0 8 8v60q0 8-8 8H176q-8 0-8-8v-60q0-8 8-8z" /></svg>
+                </div>
+                <div class="pointer" title="clear all cookies" onclick={link.callback(|_| Msg::ClearCookies)}>
+                    <svg viewBox="0 0 1024 1024" fill="currentColor" height="1em" width="1em"> <path d="M864 256H736v-80c0-35.3-28.7-64-64-64H352c-35.3 0-64 28.7-64 64v80H160c-17.7 0-32 14.3-32 32v32c0 4.4 3.6 8 8 8h60.4l24.7 523c1.6 34.1 29.8 61 63.9 61h454c34.2 0 62.3-26.8 63.9-61l24.7-523H888c4.4 0 8-3.6 8-8v-32c0-17.7-14.3-32-32-32zm-200 0H360v-72h304v72z" /> </svg>
+                </div>
+            </div>
+
+            { for domains.iter().map(|domain| render_cookie_domain(bctx.link.as_ref().unwrap(), &bctx.main_state.cookie_domain, domain, &bctx.main_state.cookies))}
+
+        </div>
+    }
+}
+
 pub fn sidebar_collections(bctx: &mut BoltContext) -> Html {
     let link = bctx.link.as_ref().unwrap();
 
@@ -128,6 +201,10 @@ fn render_collection(
             <div>{col.name.clone()}</div>
 
             <div class="col-icons">
+            <div class="pointer run-col" title="Run collection" onclick={link.callback(move |_| Msg::RunCollection(index))}>
+                <svg viewBox="0 0 24 24" fill="currentColor" height="15px" width="15px"><path d="M8 5.14v13.72a1 1 0 001.5.86l11.04-6.86a1 1 0 000-1.72L9.5 4.28A1 1 0 008 5.14z" /></svg>
+            </div>
+
             <div class="pointer add-col" onclick={link.callback(move |_| Msg::AddToCollection(index))}>
                 <svg viewBox="0 0 1024 1024" fill="currentColor" height="15px" width="15px" ><defs><style /></defs><path d="M482 152h60q8 0 8 8v704q0 8-8 8h-60q-8 0-8-8V160q0-8 8-8z" /><path d="M176 474h672q8 0 8 8v60q0 8-8 8H176q-8 0-8-8v-60q0-8 8-8z" /></svg>
             </div>
@@ -169,6 +246,64 @@ fn render_http_request(
     }
 }
 
+fn render_environment(
+    link: &Scope<BoltApp>,
+    current: usize,
+    active: Option<usize>,
+    index: usize,
+    env: &Environment,
+) -> Html {
+    let env_name = env.name.clone();
+
+    let env_name = if env_name.len() > 20 {
+        format!("{}...", &env_name[0..20])
+    } else {
+        env_name
+    };
+
+    html! {
+        <div onclick={link.callback(move |_| Msg::SelectEnvironment(index))} id={"environment".to_string() + &index.to_string()} class={if index == current { "pointer sidebar2item sidebar2item-selected" } else { "pointer sidebar2item" }} >
+            <div class="requestname">{env_name}</div>
+            if active == Some(index) {
+                <div class="env-active-badge">{"active"}</div>
+            }
+            <div class="pointer bin-req" title="delete" onclick={link.callback(move |_| Msg::RemoveEnvironment(index))}>
+                <svg viewBox="0 0 1024 1024" fill="currentColor" height="1em" width="1em"> <path d="M864 256H736v-80c0-35.3-28.7-64-64-64H352c-35.3 0-64 28.7-64 64v80H160c-17.7 0-32 14.3-32 32v32c0 4.4 3.6 8 8 8h60.4l24.7 523c1.6 34.1 29.8 61 63.9 61h454c34.2 0 62.3-26.8 63.9-61l24.7-523H888c4.4 0 8-3.6 8-8v-32c0-17.7-14.3-32-32-32zm-200 0H360v-72h304v72z" /> </svg>
+            </div>
+        </div>
+    }
+}
+
+fn render_cookie_domain(
+    link: &Scope<BoltApp>,
+    current: &String,
+    domain: &String,
+    cookies: &[Cookie],
+) -> Html {
+    let count = cookies.iter().filter(|c| &c.domain == domain).count();
+
+    let domain_name = if domain.is_empty() {
+        "(no domain)".to_string()
+    } else if domain.len() > 20 {
+        format!("{}...", &domain[0..20])
+    } else {
+        domain.clone()
+    };
+
+    let select_domain = domain.clone();
+    let clear_domain = domain.clone();
+
+    html! {
+        <div onclick={link.callback(move |_| Msg::SelectCookieDomain(select_domain.clone()))} class={if domain == current { "pointer sidebar2item sidebar2item-selected" } else { "pointer sidebar2item" }} >
+            <div class="requestname">{domain_name}</div>
+            <div class="env-active-badge">{count}</div>
+            <div class="pointer bin-req" title="clear domain" onclick={link.callback(move |_| Msg::ClearCookieDomain(clear_domain.clone()))}>
+                <svg viewBox="0 0 1024 1024" fill="currentColor" height="1em" width="1em"> <path d="M864 256H736v-80c0-35.3-28.7-64-64-64H352c-35.3 0-64 28.7-64 64v80H160c-17.7 0-32 14.3-32 32v32c0 4.4 3.6 8 8 8h60.4l24.7 523c1.6 34.1 29.8 61 63.9 61h454c34.2 0 62.3-26.8 63.9-61l24.7-523H888c4.4 0 8-3.6 8-8v-32c0-17.7-14.3-32-32-32zm-200 0H360v-72h304v72z" /> </svg>
+            </div>
+        </div>
+    }
+}
+
 fn render_tcp_connection(
     link: &Scope<BoltApp>,
     current: usize,
@@ -193,6 +328,54 @@ fn render_tcp_connection(
     }
 }
 
+fn render_sse_connection(
+    link: &Scope<BoltApp>,
+    current: usize,
+    index: usize,
+    req: &SseConnection,
+) -> Html {
+    let request_name = req.name.clone();
+
+    let request_name = if request_name.len() > 20 {
+        format!("{}...", &request_name[0..20])
+    } else {
+        request_name
+    };
+
+    html! {
+        <div onclick={link.callback(move |_| Msg::SelectSseConnection(index))} id={"request".to_string() + &index.to_string()} class={if index == current { "pointer sidebar2item sidebar2item-selected" } else { "pointer sidebar2item" }} >
+            <div class="requestname">{request_name}</div>
+            <div class="pointer bin-req" title="delete" onclick={link.callback(move |_| Msg::RemoveSseConnection(index))}>
+                <svg viewBox="0 0 1024 1024" fill="currentColor" height="1em" width="1em"> <path d="M864 256H736v-80c0-35.3-28.7-64-64-64H352c-35.3 0-64 28.7-64 64v80H160c-17.7 0-32 14.3-32 32v32c0 4.4 3.6 8 8 8h60.4l24.7 523c1.6 34.1 29.8 61 63.9 61h454c34.2 0 62.3-26.8 63.9-61l24.7-523H888c4.4 0 8-3.6 8-8v-32c0-17.7-14.3-32-32-32zm-200 0H360v-72h304v72z" /> </svg>
+            </div>
+        </div>
+    }
+}
+
+fn render_grpc_connection(
+    link: &Scope<BoltApp>,
+    current: usize,
+    index: usize,
+    req: &GrpcConnection,
+) -> Html {
+    let request_name = req.name.clone();
+
+    let request_name = if request_name.len() > 20 {
+        format!("{}...", &request_name[0..20])
+    } else {
+        request_name
+    };
+
+    html! {
+        <div onclick={link.callback(move |_| Msg::SelectGrpcConnection(index))} id={"request".to_string() + &index.to_string()} class={if index == current { "pointer sidebar2item sidebar2item-selected" } else { "pointer sidebar2item" }} >
+            <div class="requestname">{request_name}</div>
+            <div class="pointer bin-req" title="delete" onclick={link.callback(move |_| Msg::RemoveGrpcConnection(index))}>
+                <svg viewBox="0 0 1024 1024" fill="currentColor" height="1em" width="1em"> <path d="M864 256H736v-80c0-35.3-28.7-64-64-64H352c-35.3 0-64 28.7-64 64v80H160c-17.7 0-32 14.3-32 32v32c0 4.4 3.6 8 8 8h60.4l24.7 523c1.6 34.1 29.8 61 63.9 61h454c34.2 0 62.3-26.8 63.9-61l24.7-523H888c4.4 0 8-3.6 8-8v-32c0-17.7-14.3-32-32-32zm-200 0H360v-72h304v72z" /> </svg>
+            </div>
+        </div>
+    }
+}
+
 fn render_udp_connection(
     link: &Scope<BoltApp>,
     current: usize,
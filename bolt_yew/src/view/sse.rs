@@ -0,0 +1,30 @@
+use crate::BoltContext;
+use crate::view;
+use yew::{html, Html};
+
+pub fn sse_view(bctx: &mut BoltContext) -> Html {
+    html! {
+       <body>
+            {view::navbar::get_navbar(bctx)}
+
+            <div class="main">
+                <div class="sidebars">
+                    {view::sidebar1::sidebar(bctx, bctx.main_state.page)}
+                    {view::sidebar2::sidebar_sse(bctx)}
+                </div>
+
+                <div class="resizer"></div>
+        
+                <div class="content">
+                    {view::request::sse_out(bctx)}
+                                
+                    <div class="resizer2"></div>     
+        
+                    {view::response::sse_history(bctx)}
+                </div>
+            </div>
+
+            // {view::console::console()}
+        </body>
+    }
+}
@@ -0,0 +1,96 @@
+use crate::BoltContext;
+use crate::Msg;
+use bolt_common::prelude::*;
+use yew::{html, Html};
+
+pub fn render_http_req_tests(bctx: &mut BoltContext, request: &HttpRequest) -> Html {
+    let rows: Vec<Html> = request
+        .assertions
+        .iter()
+        .enumerate()
+        .map(|(index, assertion)| render_assertion(bctx, index, assertion))
+        .collect();
+
+    let link = bctx.link.as_ref().unwrap();
+
+    html! {
+        <div class="reqheaders reqtests">
+            <table>
+                <tr>
+                    <th></th>
+                    <th>{"Assertion"}</th>
+                    <th>{"Target"}</th>
+                    <th>{"Expected"}</th>
+                </tr>
+                { for rows }
+            </table>
+
+            <button class="addtestbtn pointer" type="button" onclick={link.callback(|_| Msg::HttpReqAddAssertion)}>{"Add assertion"}</button>
+        </div>
+    }
+}
+
+fn render_assertion(bctx: &mut BoltContext, index: usize, assertion: &Assertion) -> Html {
+    let link = bctx.link.as_ref().unwrap();
+
+    let target_hint = assertion.kind.target_hint();
+    let value_hint = assertion.kind.value_hint();
+
+    html! {
+        <tr>
+            <td><input id={"assertionenabled".to_string() + &index.to_string()} type="checkbox" class="pointer" checked={assertion.enabled} onchange={link.callback(move |_| Msg::HttpReqAssertionChanged(index))}/></td>
+            <td>
+                <select id={"assertionkind".to_string() + &index.to_string()} class="tableselect pointer" onchange={link.callback(move |_| Msg::HttpReqAssertionChanged(index))}>
+                    { for (0..AssertionKind::count()).map(|kind_index| {
+                        let kind = AssertionKind::from(kind_index);
+                        html! {
+                            <option value={kind_index.to_string()} selected={kind == assertion.kind}>{kind.to_string()}</option>
+                        }
+                    })}
+                </select>
+            </td>
+            <td>
+                <input id={"assertiontarget".to_string() + &index.to_string()} type="text" class="tableinput" autocomplete="off" spellcheck="false" disabled={target_hint.is_empty()} placeholder={target_hint} value={assertion.target.clone()} onchange={link.callback(move |_| Msg::HttpReqAssertionChanged(index))}/>
+            </td>
+            <td class="tableline">
+                <input id={"assertionexpected".to_string() + &index.to_string()} type="text" class="tableinput" autocomplete="off" spellcheck="false" disabled={value_hint.is_empty()} placeholder={value_hint} value={assertion.expected.clone()} onchange={link.callback(move |_| Msg::HttpReqAssertionChanged(index))}/>
+                <div class="pointer" onclick={link.callback(move |_| Msg::HttpReqRemoveAssertion(index))}>
+                    <svg viewBox="0 0 1024 1024" fill="currentColor" height="1em" width="1em"> <path d="M864 256H736v-80c0-35.3-28.7-64-64-64H352c-35.3 0-64 28.7-64 64v80H160c-17.7 0-32 14.3-32 32v32c0 4.4 3.6 8 8 8h60.4l24.7 523c1.6 34.1 29.8 61 63.9 61h454c34.2 0 62.3-26.8 63.9-61l24.7-523H888c4.4 0 8-3.6 8-8v-32c0-17.7-14.3-32-32-32zm-200 0H360v-72h304v72z" /> </svg>
+                </div>
+            </td>
+        </tr>
+    }
+}
+
+pub fn render_test_results(response: &HttpResponse) -> Html {
+    if response.tests.is_empty() {
+        return html! {
+            <div class="resptests">
+                <div class="testempty">{"No assertions, add them in the Tests tab of the request"}</div>
+            </div>
+        };
+    }
+
+    html! {
+        <div class="resptests">
+            { for response.tests.iter().map(|result| html! {
+                <div class="testresult">
+                    <div class={if result.passed { "testbadge testpassed" } else { "testbadge testfailed" }}>{if result.passed { "PASS" } else { "FAIL" }}</div>
+                    <div class="testname">{result.name.clone()}</div>
+                    <div class="testmessage">{result.message.clone()}</div>
+                </div>
+            }) }
+        </div>
+    }
+}
+
+// e.g. "Tests 3/4", plain "Tests" when nothing ran
+pub fn tests_tab_label(response: &HttpResponse) -> String {
+    if response.tests.is_empty() {
+        return "Tests".to_string();
+    }
+
+    let passed = response.tests.iter().filter(|result| result.passed).count();
+
+    format!("Tests {}/{}", passed, response.tests.len())
+}
@@ -0,0 +1,61 @@
+use crate::BoltContext;
+use crate::Msg;
+use bolt_common::prelude::*;
+use yew::{html, Html};
+
+pub fn render_transfer(bctx: &mut BoltContext) -> Html {
+    let transfer = &bctx.main_state.transfer;
+
+    let status = if !transfer.error.is_empty() {
+        html! { <div class="transfererror">{transfer.error.clone()}</div> }
+    } else {
+        html! { <div>{transfer.message.clone()}</div> }
+    };
+
+    let warnings: Vec<Html> = transfer
+        .warnings
+        .iter()
+        .map(|warning| html! { <div class="transferwarning">{warning.clone()}</div> })
+        .collect();
+
+    let collections = bctx.main_state.collections.clone();
+
+    let link = bctx.link.as_ref().unwrap();
+
+    html! {
+        <div class="transfer">
+            <div class="authrow">
+                <div class="authlabel">{"Import"}</div>
+                <input id="importpath" type="text" class="authinput" autocomplete="off" spellcheck="false" placeholder="path to an exported file or a Bruno folder"/>
+                <select id="importformat" class="authselect pointer">
+                    <option value="">{"Detect"}</option>
+                    { for (0..CollectionFormat::count()).map(|index| {
+                        html! { <option value={index.to_string()}>{CollectionFormat::from(index).to_string()}</option> }
+                    })}
+                </select>
+                <button class="ws-connect-btn pointer" onclick={link.callback(|_| Msg::ImportCollectionPressed)}>{"Import"}</button>
+            </div>
+
+            <div class="authrow">
+                <div class="authlabel">{"Export"}</div>
+                <select id="exportcollection" class="authselect pointer">
+                    <option value="tabs">{"HTTP tabs"}</option>
+                    { for collections.iter().enumerate().map(|(index, collection)| {
+                        html! { <option value={index.to_string()}>{collection.name.clone()}</option> }
+                    })}
+                </select>
+                <select id="exportformat" class="authselect pointer">
+                    { for (0..CollectionFormat::count()).filter(|index| CollectionFormat::from(*index).can_export()).map(|index| {
+                        html! { <option value={index.to_string()}>{CollectionFormat::from(index).to_string()}</option> }
+                    })}
+                </select>
+                <button class="ws-connect-btn pointer" onclick={link.callback(|_| Msg::ExportCollectionPressed)}>{"Export"}</button>
+            </div>
+
+            <div class="transferstatus">
+                {status}
+                { for warnings }
+            </div>
+        </div>
+    }
+}
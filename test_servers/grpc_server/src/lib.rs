@@ -0,0 +1,224 @@
+// A stand-in gRPC server for testing Bolt's calls and server reflection.
+//
+// echo.Echo is described by proto/echo/echo.proto, which imports proto/common/mood.proto.
+// the messages are written by hand so the server builds without protoc
+
+// tonic's Status is the error of every call
+#![allow(clippy::result_large_err)]
+
+use std::net::TcpListener;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio_stream::{Stream, StreamExt};
+use tonic::codegen::{empty_body, BoxFuture, Service, StdError};
+use tonic::{Request, Response, Status, Streaming};
+
+pub const PROTO_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/proto");
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct EchoRequest {
+    #[prost(string, tag = "1")]
+    pub message: String,
+    #[prost(int32, tag = "2")]
+    pub count: i32,
+    #[prost(int32, tag = "3")]
+    pub mood: i32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct EchoReply {
+    #[prost(string, tag = "1")]
+    pub message: String,
+    #[prost(int32, tag = "2")]
+    pub index: i32,
+    #[prost(int32, tag = "3")]
+    pub mood: i32,
+}
+
+type ReplyStream = Pin<Box<dyn Stream<Item = Result<EchoReply, Status>> + Send>>;
+
+async fn unary(request: Request<EchoRequest>) -> Result<Response<EchoReply>, Status> {
+    let token = request.metadata().get("x-token").cloned();
+    let request = request.into_inner();
+
+    if request.message == "fail" {
+        return Err(Status::invalid_argument("fail is not a message"));
+    }
+
+    let mut response = Response::new(EchoReply {
+        message: request.message,
+        index: 0,
+        mood: request.mood,
+    });
+
+    // metadata comes back as a header
+    if let Some(token) = token {
+        response.metadata_mut().insert("x-token", token);
+    }
+
+    Ok(response)
+}
+
+async fn server_stream(request: Request<EchoRequest>) -> Result<Response<ReplyStream>, Status> {
+    let request = request.into_inner();
+
+    let replies = (0..request.count).map(move |index| {
+        Ok(EchoReply {
+            message: request.message.clone(),
+            index,
+            mood: request.mood,
+        })
+    });
+
+    let stream = tokio_stream::iter(replies).throttle(Duration::from_millis(20));
+
+    Ok(Response::new(Box::pin(stream)))
+}
+
+async fn chat(request: Request<Streaming<EchoRequest>>) -> Result<Response<ReplyStream>, Status> {
+    let mut index = 0;
+
+    let stream = request.into_inner().map(move |request| {
+        let request = request?;
+
+        index += 1;
+
+        Ok(EchoReply {
+            message: request.message.to_uppercase(),
+            index: index - 1,
+            mood: request.mood,
+        })
+    });
+
+    Ok(Response::new(Box::pin(stream)))
+}
+
+struct UnarySvc;
+
+impl tonic::server::UnaryService<EchoRequest> for UnarySvc {
+    type Response = EchoReply;
+    type Future = BoxFuture<Response<EchoReply>, Status>;
+
+    fn call(&mut self, request: Request<EchoRequest>) -> Self::Future {
+        Box::pin(unary(request))
+    }
+}
+
+struct ServerStreamSvc;
+
+impl tonic::server::ServerStreamingService<EchoRequest> for ServerStreamSvc {
+    type Response = EchoReply;
+    type ResponseStream = ReplyStream;
+    type Future = BoxFuture<Response<ReplyStream>, Status>;
+
+    fn call(&mut self, request: Request<EchoRequest>) -> Self::Future {
+        Box::pin(server_stream(request))
+    }
+}
+
+struct ChatSvc;
+
+impl tonic::server::StreamingService<EchoRequest> for ChatSvc {
+    type Response = EchoReply;
+    type ResponseStream = ReplyStream;
+    type Future = BoxFuture<Response<ReplyStream>, Status>;
+
+    fn call(&mut self, request: Request<Streaming<EchoRequest>>) -> Self::Future {
+        Box::pin(chat(request))
+    }
+}
+
+#[derive(Clone, Default)]
+struct EchoServer;
+
+impl tonic::server::NamedService for EchoServer {
+    const NAME: &'static str = "echo.Echo";
+}
+
+impl<B> Service<http::Request<B>> for EchoServer
+where
+    B: tonic::codegen::Body + Send + 'static,
+    B::Error: Into<StdError> + Send + 'static,
+{
+    type Response = http::Response<tonic::body::BoxBody>;
+    type Error = std::convert::Infallible;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        let mut grpc = tonic::server::Grpc::new(tonic::codec::ProstCodec::default());
+
+        match request.uri().path() {
+            "/echo.Echo/Unary" => Box::pin(async move { Ok(grpc.unary(UnarySvc, request).await) }),
+
+            "/echo.Echo/ServerStream" => {
+                Box::pin(async move { Ok(grpc.server_streaming(ServerStreamSvc, request).await) })
+            }
+
+            "/echo.Echo/Chat" => {
+                Box::pin(async move { Ok(grpc.streaming(ChatSvc, request).await) })
+            }
+
+            // UNIMPLEMENTED as trailers only
+            _ => Box::pin(async move {
+                Ok(http::Response::builder()
+                    .status(200)
+                    .header("grpc-status", "12")
+                    .header("content-type", "application/grpc")
+                    .body(empty_body())
+                    .unwrap())
+            }),
+        }
+    }
+}
+
+// what reflection serves, parsed from the same files a client would load
+fn descriptor_set() -> Vec<u8> {
+    let parsed = protobuf_parse::Parser::new()
+        .pure()
+        .include(PROTO_DIR)
+        .input(format!("{PROTO_DIR}/echo/echo.proto"))
+        .parse_and_typecheck()
+        .unwrap();
+
+    let mut set = protobuf::descriptor::FileDescriptorSet::new();
+    set.file = parsed.file_descriptors;
+
+    protobuf::Message::write_to_bytes(&set).unwrap()
+}
+
+pub async fn serve(listener: TcpListener) -> Result<(), tonic::transport::Error> {
+    let reflection = tonic_reflection::server::Builder::configure()
+        .register_encoded_file_descriptor_set(&descriptor_set())
+        .build()
+        .unwrap();
+
+    listener.set_nonblocking(true).unwrap();
+    let listener = tokio::net::TcpListener::from_std(listener).unwrap();
+
+    tonic::transport::Server::builder()
+        .add_service(EchoServer)
+        .add_service(reflection)
+        .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener))
+        .await
+}
+
+// starts the server on a free port in the background and returns that port, used by tests
+pub fn spawn() -> u16 {
+    let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    std::thread::spawn(move || {
+        tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(serve(listener))
+    });
+
+    port
+}
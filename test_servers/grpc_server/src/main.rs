@@ -0,0 +1,12 @@
+use std::net::TcpListener;
+
+#[tokio::main]
+async fn main() {
+    let address = "127.0.0.1";
+    let port = 50051;
+
+    println!("Starting grpc server on http://{}:{}", address, port);
+
+    let listener = TcpListener::bind((address, port)).unwrap();
+    grpc_server::serve(listener).await.unwrap();
+}
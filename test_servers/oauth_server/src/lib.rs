@@ -0,0 +1,233 @@
+// A stand-in OAuth 2.0 identity provider for testing Bolt's token flows.
+//
+// client_id: bolt, client_secret: secret, user: user / pass
+// tokens expire after 30 seconds so refreshing can be tested quickly
+
+use actix_web::{body, http, web, App, HttpRequest, HttpResponse, HttpServer};
+use base64::{engine::general_purpose, Engine};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::net::TcpListener;
+use std::sync::Mutex;
+
+const CLIENT_ID: &str = "bolt";
+const CLIENT_SECRET: &str = "secret";
+const USERNAME: &str = "user";
+const PASSWORD: &str = "pass";
+const EXPIRES_IN: u64 = 30;
+
+#[derive(Default)]
+struct IdpState {
+    counter: u64,
+    // code -> (code_challenge, redirect_uri)
+    codes: HashMap<String, (String, String)>,
+    access_tokens: Vec<String>,
+    refresh_tokens: Vec<String>,
+}
+
+pub async fn e404(_req: HttpRequest) -> HttpResponse {
+    let body = body::BoxBody::new("Not Found");
+    let response: HttpResponse = HttpResponse::new(http::StatusCode::NOT_FOUND).set_body(body);
+
+    return response;
+}
+
+fn json_response(status: http::StatusCode, value: serde_json::Value) -> HttpResponse {
+    HttpResponse::build(status)
+        .content_type("application/json")
+        .body(value.to_string())
+}
+
+fn oauth_error(error: &str) -> HttpResponse {
+    println!("token request rejected: {}", error);
+
+    json_response(
+        http::StatusCode::BAD_REQUEST,
+        serde_json::json!({ "error": error }),
+    )
+}
+
+fn issue_token(state: &mut IdpState) -> HttpResponse {
+    state.counter += 1;
+
+    let access_token = format!("access-{}", state.counter);
+    let refresh_token = format!("refresh-{}", state.counter);
+
+    state.access_tokens.push(access_token.clone());
+    state.refresh_tokens.push(refresh_token.clone());
+
+    println!("issued {}", access_token);
+
+    json_response(
+        http::StatusCode::OK,
+        serde_json::json!({
+            "access_token": access_token,
+            "token_type": "Bearer",
+            "expires_in": EXPIRES_IN,
+            "refresh_token": refresh_token,
+        }),
+    )
+}
+
+fn client_authenticated(req: &HttpRequest, form: &HashMap<String, String>) -> bool {
+    if let Some(header) = req.headers().get(http::header::AUTHORIZATION) {
+        let header = header.to_str().unwrap_or_default();
+
+        if let Some(encoded) = header.strip_prefix("Basic ") {
+            let decoded = general_purpose::STANDARD.decode(encoded).unwrap_or_default();
+
+            return String::from_utf8_lossy(&decoded) == format!("{}:{}", CLIENT_ID, CLIENT_SECRET);
+        }
+    }
+
+    form.get("client_id").map(|s| s.as_str()) == Some(CLIENT_ID)
+        && form.get("client_secret").map(|s| s.as_str()) == Some(CLIENT_SECRET)
+}
+
+#[actix_web::get("/authorize")]
+async fn authorize(
+    query: web::Query<HashMap<String, String>>,
+    state: web::Data<Mutex<IdpState>>,
+) -> HttpResponse {
+    let redirect_uri = query.get("redirect_uri").cloned().unwrap_or_default();
+    let challenge = query.get("code_challenge").cloned().unwrap_or_default();
+    let client_state = query.get("state").cloned().unwrap_or_default();
+
+    if query.get("client_id").map(|s| s.as_str()) != Some(CLIENT_ID) {
+        return HttpResponse::BadRequest().body("unknown client_id");
+    }
+
+    let mut state = state.lock().unwrap();
+    state.counter += 1;
+
+    let code = format!("code-{}", state.counter);
+    state.codes.insert(code.clone(), (challenge, redirect_uri.clone()));
+
+    let location = format!("{}?code={}&state={}", redirect_uri, code, client_state);
+
+    HttpResponse::Found()
+        .append_header((http::header::LOCATION, location))
+        .finish()
+}
+
+#[actix_web::post("/token")]
+async fn token(
+    req: HttpRequest,
+    form: web::Form<HashMap<String, String>>,
+    state: web::Data<Mutex<IdpState>>,
+) -> HttpResponse {
+    let mut state = state.lock().unwrap();
+
+    let grant_type = form.get("grant_type").cloned().unwrap_or_default();
+
+    println!("token request: grant_type={}", grant_type);
+
+    match grant_type.as_str() {
+        "client_credentials" => {
+            if !client_authenticated(&req, &form) {
+                return oauth_error("invalid_client");
+            }
+
+            issue_token(&mut state)
+        }
+
+        "password" => {
+            if !client_authenticated(&req, &form) {
+                return oauth_error("invalid_client");
+            }
+
+            if form.get("username").map(|s| s.as_str()) != Some(USERNAME)
+                || form.get("password").map(|s| s.as_str()) != Some(PASSWORD)
+            {
+                return oauth_error("invalid_grant");
+            }
+
+            issue_token(&mut state)
+        }
+
+        "authorization_code" => {
+            let code = form.get("code").cloned().unwrap_or_default();
+            let verifier = form.get("code_verifier").cloned().unwrap_or_default();
+
+            let (challenge, redirect_uri) = match state.codes.remove(&code) {
+                Some(entry) => entry,
+                None => return oauth_error("invalid_grant"),
+            };
+
+            if form.get("redirect_uri") != Some(&redirect_uri) {
+                return oauth_error("invalid_grant");
+            }
+
+            let computed = general_purpose::URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()));
+
+            if computed != challenge {
+                return oauth_error("invalid_grant");
+            }
+
+            issue_token(&mut state)
+        }
+
+        "refresh_token" => {
+            let refresh_token = form.get("refresh_token").cloned().unwrap_or_default();
+
+            if !state.refresh_tokens.contains(&refresh_token) {
+                return oauth_error("invalid_grant");
+            }
+
+            state.refresh_tokens.retain(|t| t != &refresh_token);
+
+            issue_token(&mut state)
+        }
+
+        _ => oauth_error("unsupported_grant_type"),
+    }
+}
+
+#[actix_web::get("/protected")]
+async fn protected(req: HttpRequest, state: web::Data<Mutex<IdpState>>) -> HttpResponse {
+    let state = state.lock().unwrap();
+
+    let bearer = req
+        .headers()
+        .get(http::header::AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .unwrap_or_default();
+
+    if !state.access_tokens.iter().any(|t| t == bearer) {
+        return json_response(
+            http::StatusCode::UNAUTHORIZED,
+            serde_json::json!({ "error": "invalid_token" }),
+        );
+    }
+
+    json_response(
+        http::StatusCode::OK,
+        serde_json::json!({ "message": "hello from the protected resource", "token": bearer }),
+    )
+}
+
+pub async fn serve(listener: TcpListener) -> std::io::Result<()> {
+    let state = web::Data::new(Mutex::new(IdpState::default()));
+
+    let server = HttpServer::new(move || {
+        App::new()
+            .app_data(state.clone())
+            .service(authorize)
+            .service(token)
+            .service(protected)
+            .default_service(web::post().to(e404))
+    });
+
+    server.listen(listener)?.run().await
+}
+
+// starts the provider on a free port in the background and returns that port, used by tests
+pub fn spawn() -> u16 {
+    let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    std::thread::spawn(move || actix_web::rt::System::new().block_on(serve(listener)));
+
+    port
+}
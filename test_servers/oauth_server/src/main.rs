@@ -0,0 +1,12 @@
+use std::net::TcpListener;
+
+#[actix_web::main]
+pub async fn main() {
+    let address = "127.0.0.1";
+    let port = 8484;
+
+    println!("Starting oauth server on http://{}:{}", address, port);
+
+    let listener = TcpListener::bind((address, port)).unwrap();
+    oauth_server::serve(listener).await.unwrap();
+}
@@ -0,0 +1,107 @@
+// A stand-in Server-Sent Events server for testing Bolt's event streams.
+//
+// GET /events sends events 1 to 6, two per connection, then closes the stream so the
+// client reconnects. a Last-Event-ID header resumes after that event and once all six
+// were sent the server answers 204 to stop the client
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::Duration;
+
+pub const LAST_EVENT: u64 = 6;
+const EVENTS_PER_CONNECTION: u64 = 2;
+// short, so reconnects can be tested quickly
+pub const RETRY: u64 = 100;
+
+fn handle_client(mut stream: TcpStream) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    let mut last_event_id = None;
+
+    loop {
+        let mut line = String::new();
+
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("last-event-id") {
+                last_event_id = Some(value.trim().to_string());
+            }
+        }
+    }
+
+    println!(
+        "{} (Last-Event-ID: {:?})",
+        request_line.trim(),
+        last_event_id
+    );
+
+    let path = request_line.split(' ').nth(1).unwrap_or_default();
+
+    if path != "/events" {
+        return stream.write_all(
+            b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        );
+    }
+
+    let after: u64 = last_event_id
+        .as_deref()
+        .and_then(|id| id.parse().ok())
+        .unwrap_or(0);
+
+    if after >= LAST_EVENT {
+        return stream.write_all(b"HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n");
+    }
+
+    stream.write_all(
+        b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: close\r\n\r\n",
+    )?;
+
+    // a comment first, like servers that keep proxies from closing the stream
+    write!(stream, ": resuming after {after}\nretry: {RETRY}\n\n")?;
+
+    let resumed = match &last_event_id {
+        Some(id) => format!("after {id}"),
+        None => "start".to_string(),
+    };
+
+    for id in after + 1..=(after + EVENTS_PER_CONNECTION).min(LAST_EVENT) {
+        // every other event uses CRLF line endings
+        let end = if id % 2 == 0 { "\r\n" } else { "\n" };
+
+        write!(
+            stream,
+            "event: tick{end}id: {id}{end}data: event {id}{end}data: {resumed}{end}{end}"
+        )?;
+        stream.flush()?;
+
+        std::thread::sleep(Duration::from_millis(20));
+    }
+
+    Ok(())
+}
+
+pub fn serve(listener: TcpListener) {
+    for stream in listener.incoming() {
+        std::thread::spawn(|| {
+            if let Err(err) = handle_client(stream.unwrap()) {
+                println!("client failed: {}", err);
+            }
+        });
+    }
+}
+
+// starts the server on a free port in the background and returns that port, used by tests
+pub fn spawn() -> u16 {
+    let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    std::thread::spawn(move || serve(listener));
+
+    port
+}
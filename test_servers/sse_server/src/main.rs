@@ -0,0 +1,11 @@
+use std::net::TcpListener;
+
+fn main() {
+    let address = "127.0.0.1";
+    let port = 8585;
+
+    println!("Starting sse server on http://{}:{}/events", address, port);
+
+    let listener = TcpListener::bind((address, port)).unwrap();
+    sse_server::serve(listener);
+}